@@ -0,0 +1,42 @@
+// Copyright 2025 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The error type for fallible operations in Vello API.
+
+use thiserror::Error;
+
+use crate::texture::TextureId;
+
+/// Errors that can occur when painting with Vello API, or reading back textures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum PaintError {
+    /// A hinted [`Scene`](crate::Scene) was appended to a target which cannot guarantee that
+    /// its hinting properties would be maintained, such as an unhinted `Scene`.
+    #[error("Hinted scene appended to a target which does not maintain hinting")]
+    HintingNotSupported,
+    /// A hinted [`Scene`](crate::Scene) was appended with a transform other than an integer translation.
+    #[error("Hinted scene appended with a transform which is not an integer translation")]
+    HintingIncompatibleTransform,
+    /// A [`Scene`](crate::Scene) was appended to a target for a different renderer.
+    ///
+    /// This is not currently detected, as `Scene`s are not yet associated with a renderer.
+    #[error("Scene was created for a different renderer")]
+    RendererMismatch,
+    /// A [`Scene`](crate::Scene) with unbalanced layers was appended.
+    ///
+    /// That is, a layer was popped without being pushed, or a pushed layer was never popped.
+    #[error("Scene has unbalanced layers")]
+    UnbalancedLayers,
+    /// A [`Scene`](crate::Scene) with unbalanced clip paths was appended.
+    ///
+    /// That is, a clip path was popped without being pushed, or a pushed clip path was never popped.
+    #[error("Scene has unbalanced clip paths")]
+    UnbalancedClipPaths,
+    /// The texture is not valid for this renderer.
+    #[error("Texture {0:?} is not valid for this renderer")]
+    InvalidTexture(TextureId),
+    /// The renderer failed to read back the contents of a texture.
+    #[error("Failed to read back texture")]
+    ReadbackFailed,
+}
@@ -69,7 +69,7 @@
 //! Text can be rendered to Vello API scenes using the "Parley Draw" crate.
 //! Note that this crate is not currently implemented; design work is ongoing.
 //! We also support rendering using using traditional glyph atlases, which may be preferred by some consumers.
-//! This is especially useful to achieve subpixel rendering, such as ClearType, which Vello doesn't currently support directly.
+//! This is especially useful to achieve subpixel rendering, such as ClearType, which Vello only supports directly in Vello CPU (for glyphs drawn onto opaque backgrounds).
 //!
 //! # Unimplemented Features
 //!
@@ -92,13 +92,8 @@
 //!
 //! - Renderer specific painting commands (i.e. using downcasting).
 //!   This is intended to be an immediate follow-up to the MVP landing.
-//! - Pushing/popping clip paths (i.e. non-isolated clipping).
-//!   This feature should be easy to restore, although it isn't clear how it will work with "Vello GPU", i.e. Hybrid with GPU sparse strip rendering.
-//! - Downloading rendered textures back to the CPU/host.
-//!   This is currently supported through individual methods on the renderers, but we hope to have a portable API for coordinating this.
 //! - Anti-aliasing threshold.
 //! - Fill rules for clip paths.
-//! - Proper error types; we currently return `()`. This is to ensure that these error types are explicitly interim.
 //! - Texture resizing; this might not be viably possible
 //! - More explicit texture atlas support
 //! - Proper handling of where `TextureHandle` and `TextureId` should be passed.
@@ -115,15 +110,11 @@
 //! - Masks
 //! - Filter effects
 //! - Non-isolated blending (this is "supported" by Vello Hybrid, but currently silently ignored)
-//! - Blurred rounded rectangles (note that currently this is actually included in the abstraction, despite this status)
 //!
 //! There are currently no such features the other way around (i.e. which only Vello Hybrid supports).
 //!
 //! ## Not implemented
 //!
-//! - Path caching. This feature is intended to allow re-using paths efficiently, primarily for glyphs.
-//! - Blurred rounded rectangle paints in custom shapes (e.g. to exclude the unblurred parts).
-//!   (TODO: This actually does exist as a method, but no renderer implements it; we should maybe remove that method?)
 //! - Mipmaps
 //!
 //! For even more detail on some of these, see the `design.md` file.
@@ -131,18 +122,21 @@
 
 #![forbid(unsafe_code)]
 #![no_std]
-#![expect(clippy::result_unit_err, reason = "This code is very experimental.")]
 
 extern crate alloc;
 
 mod painter;
 
+pub mod error;
 pub mod exact;
 pub mod paths;
 pub mod scene;
 pub mod texture;
 
+pub use self::error::PaintError;
 pub use self::painter::{PaintScene, StandardBrush};
 pub use self::scene::Scene;
+pub use self::texture::TextureReadback;
 
 pub use ::peniko;
+pub use vello_common::pixmap::Pixmap;
@@ -6,7 +6,9 @@ use core::any::Any;
 use peniko::kurbo::{Affine, BezPath, Rect, Stroke};
 use peniko::{BlendMode, Brush, Color, Fill, ImageBrush};
 
+use crate::PaintError;
 use crate::exact::ExactPathElements;
+use crate::paths::{PathId, PathSet};
 use crate::scene::Scene;
 use crate::texture::TextureId;
 
@@ -27,7 +29,8 @@ pub type StandardBrush = Brush<ImageBrush<TextureId>>;
 ///   The brush's coordinate system is currently relative to the drawn path's coordinate system.
 ///   <!-- We might also want to allow this to be explicitly unset, in which case drawing will fail. -->
 /// - The area over which this brush is to be drawn.
-///   This can either be a filled path ([`fill_path`])
+///   This can either be a filled path ([`fill_path`]), a stroked path ([`stroke_path`]),
+///   or a path which has been prepared ahead of time ([`draw_prepared_path`]).
 /// - The layer stack, which allows for multiple 2d contexts to be blended together, with clipping.
 ///
 /// This separation of brush and drawing area is an experimental aspect of this API.
@@ -55,6 +58,8 @@ pub type StandardBrush = Brush<ImageBrush<TextureId>>;
 /// [`set_brush`]: PaintScene::set_brush
 /// [`set_solid_brush`]: PaintScene::set_solid_brush
 /// [`fill_path`]: PaintScene::fill_path
+/// [`stroke_path`]: PaintScene::stroke_path
+/// [`draw_prepared_path`]: PaintScene::draw_prepared_path
 /// [`append`]: PaintScene::append
 /// [`Renderer`]: todo
 pub trait PaintScene: Any {
@@ -66,17 +71,21 @@ pub trait PaintScene: Any {
     ///
     /// # Errors
     ///
-    /// This returns an error if:
-    /// - `scene` is hinted, but `self` cannot guarantee that the hinting properties would be maintained.
+    /// This returns an error, without changing `self`, if:
+    /// - `scene` is hinted, but `self` cannot guarantee that the hinting properties would be maintained
+    ///   ([`PaintError::HintingNotSupported`]).
     ///   Hinted scenes can be appended to the final, renderer-specific, implementations of `PaintScene`
     ///   or to other hinted `Scene`s.
-    /// - `scene` is hinted, but the transform would remove the hinting property.
+    /// - `scene` is hinted, but the transform would remove the hinting property
+    ///   ([`PaintError::HintingIncompatibleTransform`]).
     ///   For hinted scenes, the only valid transforms are integer translations.
-    /// - `scene` does not apply to the same renderer as `self`.
-    /// - `scene` has unbalanced layers (TODO: This isn't implemented yet).
+    /// - `scene` does not apply to the same renderer as `self` ([`PaintError::RendererMismatch`]).
+    /// - `scene` has unbalanced layers or clip paths
+    ///   ([`PaintError::UnbalancedLayers`] and [`PaintError::UnbalancedClipPaths`]).
+    /// - `scene` uses a texture which isn't valid for `self` ([`PaintError::InvalidTexture`]).
     // TODO: The reason this method doesn't have a default impl is because of future support for custom paint commands.
     // However, it might be possible to also implement those directly in a generic impl.
-    fn append(&mut self, transform: Affine, scene: &Scene) -> Result<(), ()>;
+    fn append(&mut self, transform: Affine, scene: &Scene) -> Result<(), PaintError>;
 
     /// Fill the interior of `shape` with the current brush.
     ///
@@ -100,12 +109,35 @@ pub trait PaintScene: Any {
         path: &impl ExactPathElements,
     );
 
+    /// Fill or stroke the path `path` from `paths` with the current brush, as described by
+    /// the style it was prepared with.
+    ///
+    /// Both the path and the current brush will be transformed using the 2d affine `transform`.
+    ///
+    /// Renderers can cache the rasterised form of paths drawn using this method, so this is the
+    /// most efficient way to draw the same path many times (such as the glyphs of a font),
+    /// with different brushes and transforms.
+    /// In Vello CPU and Vello Hybrid, drawing a path again with a transform which only differs by
+    /// a translation by whole multiples of the tile size (4 pixels) reuses the previous
+    /// rasterisation, as long as the path is fully within the viewport.
+    /// Other translations are cached separately by their offset within a tile, so a path drawn at
+    /// arbitrary integer offsets can have up to 16 cached rasterisations.
+    /// See the [`paths`](crate::paths#path-caching) module for details.
+    fn draw_prepared_path(&mut self, transform: Affine, paths: &PathSet, path: PathId);
+
     /// Set the current brush to `brush`.
     ///
     /// This method is used to set the brush for images and gradients.
     /// The `paint_transform` will be applied to only the brush contents, in
     /// addition to the transform applied to the object.
     /// For solid colors, this has no effect, and so you may prefer [`set_solid_brush`](PaintScene::set_solid_brush).
+    ///
+    /// # Errors
+    ///
+    /// This returns [`PaintError::InvalidTexture`], without changing the current brush, if `brush`
+    /// is an image whose texture is not valid for this painter.
+    /// A [`Scene`] does not know which renderer it will be used with, so it instead reports
+    /// invalid textures when it is [`append`](PaintScene::append)ed.
     fn set_brush(
         &mut self,
         brush: impl Into<StandardBrush>,
@@ -116,12 +148,10 @@ pub trait PaintScene: Any {
         // However, this isn't exposed in the current Vellos, so we're choosing to defer this.
         // transform: Affine,
         paint_transform: Affine,
-    );
+    ) -> Result<(), PaintError>;
 
     /// Set the current brush to represent a rounded rectangle blurred with an approximate gaussian filter.
     ///
-    /// **This method is currently unimplemented on all backends, so should not be used.**
-    ///
     /// For performance reasons, shapes drawn with this brush should not extend more than approximately
     /// 2.5 times `std_dev` away from the edges of `rect` (as any such points will not be perceptably
     /// painted to, but calculations will still be performed for them).
@@ -149,14 +179,16 @@ pub trait PaintScene: Any {
     /// Set the current brush to a solid `color`.
     fn set_solid_brush(&mut self, color: Color) {
         // The transform doesn't matter for a solid color brush.
-        self.set_brush(Brush::Solid(color), Affine::IDENTITY);
+        let result = self.set_brush(Brush::Solid(color), Affine::IDENTITY);
+        debug_assert!(
+            result.is_ok(),
+            "Solid color brushes don't reference a texture, so cannot be invalid."
+        );
     }
 
     /// Draw a rounded rectangle blurred with an approximate gaussian filter.
     /// This method resets the current brush.
     ///
-    /// **This method is currently unimplemented on some backends, so should not be used.**
-    ///
     /// If the rounded rectangle needs to be clipped, you can instead use
     /// [`set_blurred_rounded_rect_brush`](PaintScene::set_blurred_rounded_rect_brush).
     ///
@@ -253,4 +285,32 @@ pub trait PaintScene: Any {
     /// All open layers in a [`Scene`] must be popped before [appending](PaintScene::append)
     /// it to another scene.
     fn pop_layer(&mut self);
+
+    /// Pushes a new clip path, which restricts all following drawing commands to the interior
+    /// of `path` until it is [popped](PaintScene::pop_clip_path).
+    ///
+    /// The `fill_rule` determines which areas of `path` are considered interior.
+    /// If a clip path is already active, the new clip path is intersected with it.
+    ///
+    /// Unlike [`push_clip_layer`](PaintScene::push_clip_layer), this doesn't create an isolated layer.
+    /// Instead, each following shape is intersected with the clip path directly.
+    /// This is typically much cheaper when only a few shapes are drawn inside the clip, but overlapping
+    /// anti-aliased edges of the clipped shapes are blended individually, which can cause conflation artifacts.
+    ///
+    /// The clip path stack is independent of the layer stack, so pushed clip paths are not popped by
+    /// [`pop_layer`](PaintScene::pop_layer).
+    /// All pushed clip paths in a [`Scene`] must be popped before [appending](PaintScene::append)
+    /// it to another scene.
+    ///
+    /// **However, the transforms are *not* saved or modified by the clip path stack.**
+    /// That is, the `transform` argument to this function only applies a transform to the `path`.
+    fn push_clip_path(
+        &mut self,
+        clip_transform: Affine,
+        fill_rule: Fill,
+        path: &impl ExactPathElements,
+    );
+
+    /// Pop the most recently pushed clip path.
+    fn pop_clip_path(&mut self);
 }
@@ -7,12 +7,23 @@
 //! If you're a consumer of Vello API writing an application, you do not need to use this API.
 //! Instead, this is exposed for use by renderers.
 //!
-//! This design has been made with "path caching" in mind, which will allow using rasterised forms of
+//! This design has been made with "path caching" in mind, which allows using rasterised forms of
 //! paths (such as glyphs) directly in the renderer, instead of rasterising from scratch each frame.
 //! This can massively improve performance and efficiency on subsequent frames.
-//! This is however not implemented by any backend, so isn't provided for in Vello API.
 //! It also ensures that there are very few per-frame allocations (i.e. avoids allocating
 //! for each path).
+//!
+//! # Path caching
+//!
+//! Consumers who want to draw the same path many times (such as the glyphs of a font) can keep
+//! their own long-lived `PathSet`, and draw its paths using [`PaintScene::draw_prepared_path`].
+//! Each `PathSet` has a unique [`PathSetId`], so a [`PathKey`] identifies a single path for as long
+//! as the set isn't [cleared](PathSet::clear).
+//! Renderers use this key to cache the rasterised form of the path.
+//!
+//! [`PaintScene::draw_prepared_path`]: crate::PaintScene::draw_prepared_path
+
+use core::sync::atomic::{AtomicU64, Ordering};
 
 use alloc::vec::Vec;
 use peniko::{Style, kurbo::PathEl};
@@ -21,11 +32,34 @@ use crate::exact::ExactPathElements;
 
 /// The id for a single path within a given [`PathSet`].
 /// This is an index into the [`meta`](PathSet::meta) field.
-#[derive(Debug, Clone, Copy)]
-// In a future world with path caching, this would be paired with a path group id.
-// For "scene-local" paths, you would then use a marker "local" path group id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PathId(pub u32);
 
+/// A unique identifier for the contents of a [`PathSet`].
+///
+/// A new id is assigned whenever a path set is created or [cleared](PathSet::clear).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathSetId(u64);
+
+impl PathSetId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Identifies a single path in a specific [`PathSet`].
+///
+/// Renderers can use this as the key for caching the rasterised form of the path.
+/// See the [module level documentation](crate::paths#path-caching) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathKey {
+    /// The id of the path set containing the path.
+    pub set: PathSetId,
+    /// The id of the path within that set.
+    pub path: PathId,
+}
+
 /// A collection of filled or stroked paths, each associated with an id.
 ///
 /// As noted in the [module level documentation](crate::paths), this type is an implementation
@@ -39,9 +73,12 @@ pub struct PathId(pub u32);
 /// without any brush information.
 /// Each `Scene` stores a sequence of these, with the associated brush, to create a 2d scene.
 ///
-/// This separation is designed for a future path caching mechanism, where the rasterised geometry
-/// of a path can be computed once, then re-used with multiple brushed, to increase efficiency.
-/// Note however that this plan is not proven in the current version of Vello API.
+/// This separation allows path caching, where the rasterised geometry of a path can be computed once,
+/// then re-used with multiple brushes, to increase efficiency.
+/// See the [module level documentation](crate::paths#path-caching) for details.
+///
+/// Paths should only be added to the public fields; modifying the existing paths would invalidate
+/// any rasterised forms which renderers have cached for them.
 #[derive(Debug)]
 // The same "reason about visibility" comment applies as in `Scene`
 pub struct PathSet {
@@ -51,6 +88,7 @@ pub struct PathSet {
     pub elements: Vec<PathEl>,
     /// The metadata about each path.
     pub meta: Vec<PathMeta>,
+    id: PathSetId,
 }
 
 impl PathSet {
@@ -61,15 +99,43 @@ impl PathSet {
         Self {
             elements: Vec::new(),
             meta: Vec::new(),
+            id: PathSetId::next(),
         }
     }
 
     /// Clears the path set, removing all values.
     ///
     /// This does not free the underlying allocations.
+    /// The path set is given a new [`id`](PathSet::id), as the existing [`PathId`]s are no longer valid.
     pub fn clear(&mut self) {
         self.elements.clear();
         self.meta.clear();
+        self.id = PathSetId::next();
+    }
+
+    /// The unique id of the current contents of this path set.
+    pub fn id(&self) -> PathSetId {
+        self.id
+    }
+
+    /// The key which identifies the path `path` in this path set, for caching.
+    pub fn key(&self, path: PathId) -> PathKey {
+        PathKey { set: self.id, path }
+    }
+
+    /// The elements of the path with the given id.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is not a valid id in this path set.
+    pub fn elements(&self, path: PathId) -> &[PathEl] {
+        let index = usize::try_from(path.0).unwrap();
+        let start = self.meta[index].start_index;
+        let end = self
+            .meta
+            .get(index + 1)
+            .map_or(self.elements.len(), |it| it.start_index);
+        &self.elements[start..end]
     }
 }
 
@@ -103,6 +169,25 @@ impl PathSet {
         PathId(meta_index.try_into().unwrap())
     }
 
+    /// Copy the path with id `path` from `other` into this pathset.
+    ///
+    /// This returns the id of the copied path in this `PathSet`.
+    ///
+    /// This method is expected to be used to implement [`PaintScene::draw_prepared_path`](crate::PaintScene::draw_prepared_path).
+    pub fn copy_path(&mut self, other: &Self, path: PathId) -> PathId {
+        let start_index = self.elements.len();
+        self.elements.extend_from_slice(other.elements(path));
+        let meta_index = self.meta.len();
+        self.meta.push(PathMeta {
+            start_index,
+            operation: other.meta[usize::try_from(path.0).unwrap()]
+                .operation
+                .clone(),
+        });
+
+        PathId(meta_index.try_into().unwrap())
+    }
+
     /// Append the shapes in `other` to this pathset.
     ///
     /// The return value should be added to the field of [`PathId`]s from `other`
@@ -16,9 +16,9 @@ use peniko::{
 };
 
 use crate::{
-    PaintScene, StandardBrush,
+    PaintError, PaintScene, StandardBrush,
     exact::ExactPathElements,
-    paths::{PathId, PathSet},
+    paths::{PathId, PathKey, PathSet},
 };
 
 #[cfg(not(feature = "std"))]
@@ -28,17 +28,27 @@ use peniko::kurbo::common::FloatFuncs as _;
 ///
 /// The [`PathId`]s contained within are the index into the pathset associated with this `Scene`.
 /// As such, when moving these commands between scenes, the path id must be updated.
-/// (N.B. this will be less true when we get path caching, if it follows the expected design).
+/// Paths drawn using [`PaintScene::draw_prepared_path`] are also copied into this pathset, but
+/// additionally keep the [`PathKey`] of the original path, which renderers can use for caching.
 ///
 /// The abstract renderer these commands operate on has the state described in the [`PaintScene`] trait; that is, the current brush and a layer stack.
 #[derive(Debug)]
 pub enum RenderCommand {
     /// Draw a path with the current brush.
     DrawPath(Affine, PathId),
+    /// Draw a path from an external [`PathSet`] with the current brush.
+    ///
+    /// The [`PathId`] is the copy of the path in this scene's pathset, and the [`PathKey`]
+    /// identifies the original path, for use in caching.
+    DrawPreparedPath(Affine, PathId, PathKey),
     /// Push a new layer with optional clipping and effects.
     PushLayer(PushLayerCommand),
     /// Pop the current layer.
     PopLayer,
+    /// Push a new clip path, prepared with the fill rule to use.
+    PushClipPath(Affine, PathId),
+    /// Pop the current clip path.
+    PopClipPath,
     /// Set the current paint.
     ///
     /// The affine is currently path local for future drawing operations.
@@ -170,6 +180,38 @@ pub fn extract_integer_translation(transform: Affine) -> Option<(f64, f64)> {
     }
 }
 
+/// Check that every layer and clip path pushed in `commands` is later popped, and that nothing
+/// is popped which wasn't pushed.
+///
+/// This is expected to be used to implement [`PaintScene::append`].
+pub fn check_balanced(commands: &[RenderCommand]) -> Result<(), PaintError> {
+    let mut layers = 0_usize;
+    let mut clip_paths = 0_usize;
+    for command in commands {
+        match command {
+            RenderCommand::PushLayer(_) => layers += 1,
+            RenderCommand::PopLayer => {
+                layers = layers.checked_sub(1).ok_or(PaintError::UnbalancedLayers)?;
+            }
+            RenderCommand::PushClipPath(..) => clip_paths += 1,
+            RenderCommand::PopClipPath => {
+                clip_paths = clip_paths
+                    .checked_sub(1)
+                    .ok_or(PaintError::UnbalancedClipPaths)?;
+            }
+            _ => {}
+        }
+    }
+    if layers != 0 {
+        return Err(PaintError::UnbalancedLayers);
+    }
+    if clip_paths != 0 {
+        return Err(PaintError::UnbalancedClipPaths);
+    }
+
+    Ok(())
+}
+
 impl PaintScene for Scene {
     fn append(
         &mut self,
@@ -180,16 +222,16 @@ impl PaintScene for Scene {
             commands: other_commands,
             hinted: other_hinted,
         }: &Scene,
-    ) -> Result<(), ()> {
+    ) -> Result<(), PaintError> {
         // if !Arc::ptr_eq(&self.renderer, other_renderer) {
-        //     // Mismatched Renderers
-        //     return Err(());
+        //     return Err(PaintError::RendererMismatch);
         // }
 
+        check_balanced(other_commands)?;
         if *other_hinted {
             if !self.hinted {
                 // Trying to bring a "hinted" scene into an unhinted context.
-                return Err(());
+                return Err(PaintError::HintingNotSupported);
             }
             if let Some((dx, dy)) = extract_integer_translation(scene_transform) {
                 // Update the transform to be a pure integer translation.
@@ -198,8 +240,7 @@ impl PaintScene for Scene {
                 // makes our reasoning about this easier.
                 scene_transform = Affine::translate((dx, dy));
             } else {
-                // Translation not hinting compatible.
-                return Err(());
+                return Err(PaintError::HintingIncompatibleTransform);
             }
         }
         let path_correction_factor = self.paths.append(other_paths);
@@ -211,6 +252,13 @@ impl PaintScene for Scene {
                 RenderCommand::DrawPath(transform, path) => {
                     RenderCommand::DrawPath(correct_transform(*transform), correct_path(*path))
                 }
+                RenderCommand::DrawPreparedPath(transform, path, key) => {
+                    RenderCommand::DrawPreparedPath(
+                        correct_transform(*transform),
+                        correct_path(*path),
+                        *key,
+                    )
+                }
                 RenderCommand::PushLayer(command) => RenderCommand::PushLayer(PushLayerCommand {
                     clip_transform: correct_transform(command.clip_transform),
                     clip_path: command.clip_path.map(correct_path),
@@ -218,6 +266,10 @@ impl PaintScene for Scene {
                     opacity: command.opacity,
                 }),
                 RenderCommand::PopLayer => RenderCommand::PopLayer,
+                RenderCommand::PushClipPath(transform, path) => {
+                    RenderCommand::PushClipPath(correct_transform(*transform), correct_path(*path))
+                }
+                RenderCommand::PopClipPath => RenderCommand::PopClipPath,
                 RenderCommand::SetPaint(affine, brush) => {
                     // Don't update the paint_transform, as it's already path local.
                     RenderCommand::SetPaint(*affine, brush.clone())
@@ -251,10 +303,24 @@ impl PaintScene for Scene {
         self.commands.push(RenderCommand::DrawPath(transform, idx));
     }
 
-    fn set_brush(&mut self, brush: impl Into<StandardBrush>, paint_transform: Affine) {
+    fn draw_prepared_path(&mut self, transform: Affine, paths: &PathSet, path: PathId) {
+        let idx = self.paths.copy_path(paths, path);
+        self.commands.push(RenderCommand::DrawPreparedPath(
+            transform,
+            idx,
+            paths.key(path),
+        ));
+    }
+
+    fn set_brush(
+        &mut self,
+        brush: impl Into<StandardBrush>,
+        paint_transform: Affine,
+    ) -> Result<(), PaintError> {
         let brush = brush.into();
         self.commands
             .push(RenderCommand::SetPaint(paint_transform, brush));
+        Ok(())
     }
 
     fn set_blurred_rounded_rect_brush(
@@ -305,15 +371,32 @@ impl PaintScene for Scene {
     fn pop_layer(&mut self) {
         self.commands.push(RenderCommand::PopLayer);
     }
+
+    fn push_clip_path(
+        &mut self,
+        clip_transform: Affine,
+        fill_rule: peniko::Fill,
+        path: &impl ExactPathElements,
+    ) {
+        let idx = self.paths.prepare_shape(&path, fill_rule);
+        self.commands
+            .push(RenderCommand::PushClipPath(clip_transform, idx));
+    }
+
+    fn pop_clip_path(&mut self) {
+        self.commands.push(RenderCommand::PopClipPath);
+    }
 }
 
 #[cfg(test)]
 mod test {
     use core::f64::consts::{FRAC_PI_2, FRAC_PI_3, PI};
 
-    use peniko::kurbo::Affine;
+    use peniko::Fill;
+    use peniko::kurbo::{Affine, Rect};
 
     use crate::scene::extract_integer_translation;
+    use crate::{PaintError, PaintScene, Scene};
 
     #[test]
     fn integer_translations() {
@@ -352,4 +435,44 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn append_errors() {
+        let rect = Rect::new(0., 0., 10., 10.);
+        let mut unbalanced_layers = Scene::new(false);
+        unbalanced_layers.push_opacity_layer(0.5);
+        let mut unbalanced_clip_paths = Scene::new(false);
+        unbalanced_clip_paths.pop_clip_path();
+        unbalanced_clip_paths.push_clip_path(Affine::IDENTITY, Fill::NonZero, &rect);
+        let mut hinted = Scene::new(true);
+        hinted.fill_path(Affine::IDENTITY, Fill::NonZero, &rect);
+
+        let mut target = Scene::new(false);
+        assert_eq!(
+            target.append(Affine::IDENTITY, &unbalanced_layers),
+            Err(PaintError::UnbalancedLayers)
+        );
+        assert_eq!(
+            target.append(Affine::IDENTITY, &unbalanced_clip_paths),
+            Err(PaintError::UnbalancedClipPaths)
+        );
+        assert_eq!(
+            target.append(Affine::IDENTITY, &hinted),
+            Err(PaintError::HintingNotSupported)
+        );
+        assert!(
+            target.commands.is_empty(),
+            "failed appends shouldn't change the scene"
+        );
+
+        let mut hinted_target = Scene::new(true);
+        assert_eq!(
+            hinted_target.append(Affine::translate((0.5, 0.)), &hinted),
+            Err(PaintError::HintingIncompatibleTransform)
+        );
+        assert_eq!(
+            hinted_target.append(Affine::translate((1., 0.)), &hinted),
+            Ok(())
+        );
+    }
 }
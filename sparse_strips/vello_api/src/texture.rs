@@ -3,6 +3,10 @@
 
 //! Types and utilities for textures, which are 2d images owned by a renderer.
 
+use vello_common::pixmap::Pixmap;
+
+use crate::PaintError;
+
 /// A renderer-specific ID for a texture.
 ///
 /// Note that we don't currently expect these to be constructed,
@@ -29,3 +33,29 @@ impl TextureId {
         Self(raw)
     }
 }
+
+/// Reading the contents of textures back to the CPU/host.
+///
+/// This allows downloading textures (for example, for screenshots or thumbnails) without
+/// special-casing each renderer.
+pub trait TextureReadback {
+    /// Read the contents of `texture` back to the CPU.
+    ///
+    /// The readback is started when this method is called, and the returned future resolves
+    /// once the texture's contents are available.
+    /// Some renderers need to be driven for this to happen (for example, by polling the GPU device);
+    /// see the documentation of their implementations for details.
+    ///
+    /// # Errors
+    ///
+    /// - `texture` is not valid for this renderer ([`PaintError::InvalidTexture`]).
+    /// - The renderer failed to read back the texture's contents ([`PaintError::ReadbackFailed`]).
+    fn read_texture(&self, texture: TextureId) -> impl Future<Output = Result<Pixmap, PaintError>>;
+
+    /// Read the contents of `texture` back to the CPU, blocking until they are available.
+    ///
+    /// # Errors
+    ///
+    /// See [`read_texture`](Self::read_texture).
+    fn read_texture_blocking(&self, texture: TextureId) -> Result<Pixmap, PaintError>;
+}
@@ -6,7 +6,7 @@ use crate::color::{AlphaColor, Srgb};
 use crate::kurbo::Rect;
 
 /// A blurred, rounded rectangle.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BlurredRoundedRectangle {
     /// The base rectangle to use for the blur effect.
     pub rect: Rect,
@@ -11,7 +11,7 @@ use crate::mask::Mask;
 use crate::paint::{Paint, PremulColor};
 use crate::peniko::{BlendMode, Compose, Mix};
 use crate::render_graph::{DependencyKind, LayerId, RenderGraph, RenderNodeKind};
-use crate::{strip::Strip, tile::Tile};
+use crate::{strip::Strip, subpixel, tile::Tile};
 use alloc::vec;
 use alloc::{boxed::Box, vec::Vec};
 #[cfg(debug_assertions)]
@@ -495,12 +495,62 @@ impl<const MODE: u8> Wide<MODE> {
         thread_idx: u8,
         mask: Option<Mask>,
         encoded_paints: &[EncodedPaint],
+    ) {
+        self.generate_inner(
+            strip_buf,
+            paint,
+            blend_mode,
+            thread_idx,
+            mask,
+            false,
+            encoded_paints,
+        );
+    }
+
+    /// Generate wide tile commands from a strip buffer with subpixel coverage.
+    ///
+    /// This works like [`Wide::generate`], except that the alpha buffer stores a separate
+    /// coverage value for each color component of a pixel (see [`crate::subpixel`]). Such
+    /// strips are always drawn with the default blend mode and without a mask.
+    pub fn generate_subpixel(
+        &mut self,
+        strip_buf: &[Strip],
+        paint: Paint,
+        thread_idx: u8,
+        encoded_paints: &[EncodedPaint],
+    ) {
+        self.generate_inner(
+            strip_buf,
+            paint,
+            BlendMode::default(),
+            thread_idx,
+            None,
+            true,
+            encoded_paints,
+        );
+    }
+
+    fn generate_inner(
+        &mut self,
+        strip_buf: &[Strip],
+        paint: Paint,
+        blend_mode: BlendMode,
+        thread_idx: u8,
+        mask: Option<Mask>,
+        subpixel: bool,
+        encoded_paints: &[EncodedPaint],
     ) {
         if strip_buf.is_empty() {
             return;
         }
 
         let alpha_base_idx = strip_buf[0].alpha_idx();
+        // The number of alpha values per column of a strip.
+        let column_size = if subpixel {
+            u32::from(Tile::HEIGHT) * subpixel::COMPONENTS as u32
+        } else {
+            u32::from(Tile::HEIGHT)
+        };
 
         // Create shared attributes for all commands from this path
         let attrs_idx = self.attrs.fill.len() as u32;
@@ -509,6 +559,7 @@ impl<const MODE: u8> Wide<MODE> {
             paint,
             blend_mode,
             mask,
+            subpixel,
             alpha_base_idx,
         });
 
@@ -545,8 +596,8 @@ impl<const MODE: u8> Wide<MODE> {
             }
 
             // Calculate the width of the strip in columns
-            let mut col = strip.alpha_idx() / u32::from(Tile::HEIGHT);
-            let next_col = next_strip.alpha_idx() / u32::from(Tile::HEIGHT);
+            let mut col = (strip.alpha_idx() - alpha_base_idx) / column_size;
+            let next_col = (next_strip.alpha_idx() - alpha_base_idx) / column_size;
             // Can potentially be 0 if strip only changes winding without covering pixels
             let strip_width = next_col.saturating_sub(col) as u16;
             let x1 = x0.saturating_add(strip_width);
@@ -577,7 +628,7 @@ impl<const MODE: u8> Wide<MODE> {
                 let cmd = CmdAlphaFill {
                     x: x_wtile_rel,
                     width,
-                    alpha_offset: col * u32::from(Tile::HEIGHT) - alpha_base_idx,
+                    alpha_offset: col * column_size,
                     attrs_idx,
                 };
                 x += width;
@@ -1946,6 +1997,9 @@ pub struct FillAttrs {
     pub blend_mode: BlendMode,
     /// A mask to apply to the command.
     pub mask: Option<Mask>,
+    /// Whether the alpha buffer stores a separate coverage value for each color component
+    /// of a pixel, which is used for subpixel rendering.
+    pub subpixel: bool,
     /// Base index into the alpha buffer for this path's commands.
     /// Commands store a relative offset that is added to this base.
     alpha_base_idx: u32,
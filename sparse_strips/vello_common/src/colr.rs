@@ -37,6 +37,73 @@ pub trait ColrRenderer {
     fn pop_layer(&mut self);
 }
 
+/// A [`ColrRenderer`] that records the painting commands of a COLR glyph, so that they can
+/// be replayed into another [`ColrRenderer`] later on.
+///
+/// This is useful for renderers that can't draw the glyph while it is being painted, since
+/// a [`ColorGlyph`] borrows from the font data.
+#[derive(Debug, Clone, Default)]
+pub struct ColrRecording {
+    commands: Vec<ColrCommand>,
+}
+
+#[derive(Debug, Clone)]
+enum ColrCommand {
+    PushClipLayer(BezPath),
+    PushBlendLayer(BlendMode),
+    FillSolid(AlphaColor<Srgb>),
+    FillGradient(Gradient),
+    SetPaintTransform(Affine),
+    PopLayer,
+}
+
+impl ColrRecording {
+    /// Create a new, empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replay the recorded commands into `renderer`.
+    pub fn replay(&self, renderer: &mut impl ColrRenderer) {
+        for command in &self.commands {
+            match command {
+                ColrCommand::PushClipLayer(clip) => renderer.push_clip_layer(clip),
+                ColrCommand::PushBlendLayer(blend_mode) => renderer.push_blend_layer(*blend_mode),
+                ColrCommand::FillSolid(color) => renderer.fill_solid(*color),
+                ColrCommand::FillGradient(gradient) => renderer.fill_gradient(gradient.clone()),
+                ColrCommand::SetPaintTransform(affine) => renderer.set_paint_transform(*affine),
+                ColrCommand::PopLayer => renderer.pop_layer(),
+            }
+        }
+    }
+}
+
+impl ColrRenderer for ColrRecording {
+    fn push_clip_layer(&mut self, clip: &BezPath) {
+        self.commands.push(ColrCommand::PushClipLayer(clip.clone()));
+    }
+
+    fn push_blend_layer(&mut self, blend_mode: BlendMode) {
+        self.commands.push(ColrCommand::PushBlendLayer(blend_mode));
+    }
+
+    fn fill_solid(&mut self, color: AlphaColor<Srgb>) {
+        self.commands.push(ColrCommand::FillSolid(color));
+    }
+
+    fn fill_gradient(&mut self, gradient: Gradient) {
+        self.commands.push(ColrCommand::FillGradient(gradient));
+    }
+
+    fn set_paint_transform(&mut self, affine: Affine) {
+        self.commands.push(ColrCommand::SetPaintTransform(affine));
+    }
+
+    fn pop_layer(&mut self) {
+        self.commands.push(ColrCommand::PopLayer);
+    }
+}
+
 /// An abstraction for painting COLR glyphs.
 pub struct ColrPainter<'a> {
     transforms: Vec<Affine>,
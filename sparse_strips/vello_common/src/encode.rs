@@ -222,6 +222,7 @@ impl EncodeExt for Gradient {
             stops: self.stops.clone(),
             interpolation_cs: self.interpolation_cs,
             hue_direction: self.hue_direction,
+            has_undefined: kind.has_undefined(),
         });
 
         let encoded = EncodedGradient {
@@ -486,11 +487,12 @@ impl EncodeExt for Image {
         let idx = paints.len();
 
         let mut sampler = self.sampler;
-
-        if sampler.alpha != 1.0 {
-            // If the sampler alpha is not 1.0, we need to force alpha compositing.
-            unimplemented!("Applying opacity to image commands");
-        }
+        // A non-finite alpha would otherwise bypass the opacity checks below.
+        sampler.alpha = if sampler.alpha.is_finite() {
+            sampler.alpha.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
 
         let c = transform.as_coeffs();
 
@@ -513,12 +515,14 @@ impl EncodeExt for Image {
 
         let (x_advance, y_advance) = x_y_advances(&transform);
 
-        // If the tint color has alpha < 1.0, the image will have opacities
+        // If the sampler alpha or the tint color has alpha < 1.0, the image will have opacities
         // even if the source pixels are all opaque.
         let tint_has_opacity = tint.as_ref().is_some_and(|t| t.color.components[3] < 1.0);
 
         let encoded = EncodedImage {
-            may_have_opacities: self.image.may_have_opacities() || tint_has_opacity,
+            may_have_opacities: self.image.may_have_opacities()
+                || sampler.alpha < 1.0
+                || tint_has_opacity,
             source: self.image.clone(),
             sampler,
             transform,
@@ -773,6 +777,8 @@ pub struct GradientCacheKey {
     pub interpolation_cs: ColorSpaceTag,
     /// Hue direction used for interpolation.
     pub hue_direction: HueDirection,
+    /// Whether the lookup table has a trailing transparent entry for undefined positions.
+    pub has_undefined: bool,
 }
 
 impl BitHash for GradientCacheKey {
@@ -780,6 +786,7 @@ impl BitHash for GradientCacheKey {
         self.stops.bit_hash(state);
         core::mem::discriminant(&self.interpolation_cs).hash(state);
         core::mem::discriminant(&self.hue_direction).hash(state);
+        self.has_undefined.hash(state);
     }
 }
 
@@ -788,6 +795,7 @@ impl BitEq for GradientCacheKey {
         self.stops.bit_eq(&other.stops)
             && self.interpolation_cs == other.interpolation_cs
             && self.hue_direction == other.hue_direction
+            && self.has_undefined == other.has_undefined
     }
 }
 
@@ -1149,11 +1157,12 @@ mod private {
 
 #[cfg(test)]
 mod tests {
-    use super::{EncodeExt, Gradient};
+    use super::{EncodeExt, EncodedPaint, Gradient};
     use crate::color::DynamicColor;
     use crate::color::palette::css::{BLACK, BLUE, GREEN};
     use crate::kurbo::{Affine, Point};
-    use crate::peniko::{ColorStop, ColorStops};
+    use crate::paint::{Image, ImageId, ImageSource};
+    use crate::peniko::{ColorStop, ColorStops, ImageSampler};
     use alloc::vec;
     use peniko::{LinearGradientPosition, RadialGradientPosition};
     use smallvec::smallvec;
@@ -1289,4 +1298,28 @@ mod tests {
             GREEN.into()
         );
     }
+
+    #[test]
+    fn image_non_finite_alpha_is_opaque() {
+        let mut buf = vec![];
+
+        // `ImageSampler::with_alpha` rejects NaN, so set the field directly.
+        let image = Image {
+            image: ImageSource::OpaqueId {
+                id: ImageId::new(0),
+                may_have_opacities: false,
+            },
+            sampler: ImageSampler {
+                alpha: f32::NAN,
+                ..Default::default()
+            },
+        };
+        image.encode_into(&mut buf, Affine::IDENTITY, None);
+
+        let EncodedPaint::Image(encoded) = &buf[0] else {
+            panic!("expected an encoded image");
+        };
+        assert_eq!(encoded.sampler.alpha, 1.0);
+        assert!(!encoded.may_have_opacities);
+    }
 }
@@ -0,0 +1,30 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The color matrix filter.
+
+/// A 4x5 color matrix filter.
+///
+/// The matrix operates on unpremultiplied color values in the range [0, 1]. Each
+/// output channel is a linear combination of the input R, G, B and A channels plus
+/// a constant offset, clamped back into [0, 1].
+#[derive(Clone, Copy, Debug)]
+pub struct ColorMatrix {
+    /// The matrix in row-major order: 4 rows (R, G, B, A) × 5 columns (R, G, B, A, offset).
+    pub matrix: [f32; 20],
+}
+
+impl ColorMatrix {
+    /// Create a new color matrix filter.
+    pub fn new(matrix: [f32; 20]) -> Self {
+        Self { matrix }
+    }
+
+    /// Whether the matrix leaves the alpha channel unchanged.
+    ///
+    /// If this is the case, fully transparent pixels stay transparent and
+    /// can be skipped.
+    pub fn preserves_alpha(&self) -> bool {
+        self.matrix[15..20] == [0.0, 0.0, 0.0, 1.0, 0.0]
+    }
+}
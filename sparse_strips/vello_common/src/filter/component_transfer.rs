@@ -0,0 +1,42 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The component transfer filter.
+
+use crate::filter_effects::TransferFunction;
+
+/// A component transfer filter.
+///
+/// Each channel of the unpremultiplied color is mapped through its own transfer function.
+#[derive(Debug)]
+pub struct ComponentTransfer {
+    /// The transfer functions for the R, G, B and A channels, in that order.
+    pub functions: [TransferFunction; 4],
+}
+
+impl ComponentTransfer {
+    /// Create a new component transfer filter.
+    ///
+    /// Missing transfer functions are replaced with the identity function.
+    pub fn new(
+        red: Option<&TransferFunction>,
+        green: Option<&TransferFunction>,
+        blue: Option<&TransferFunction>,
+        alpha: Option<&TransferFunction>,
+    ) -> Self {
+        let resolve =
+            |f: Option<&TransferFunction>| f.cloned().unwrap_or(TransferFunction::Identity);
+
+        Self {
+            functions: [resolve(red), resolve(green), resolve(blue), resolve(alpha)],
+        }
+    }
+
+    /// Whether the filter leaves the alpha channel unchanged.
+    ///
+    /// If this is the case, fully transparent pixels stay transparent and
+    /// can be skipped.
+    pub fn preserves_alpha(&self) -> bool {
+        matches!(self.functions[3], TransferFunction::Identity)
+    }
+}
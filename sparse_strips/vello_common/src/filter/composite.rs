@@ -0,0 +1,20 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The composite filter.
+
+use crate::filter_effects::CompositeOperator;
+
+/// A composite filter, combining two inputs with a Porter-Duff operator.
+#[derive(Clone, Copy, Debug)]
+pub struct Composite {
+    /// The compositing operator.
+    pub operator: CompositeOperator,
+}
+
+impl Composite {
+    /// Create a new composite filter.
+    pub fn new(operator: CompositeOperator) -> Self {
+        Self { operator }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The convolve matrix filter.
+
+use crate::filter_effects::{ConvolutionKernel, EdgeMode};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A convolve matrix filter.
+#[derive(Debug)]
+pub struct ConvolveMatrix {
+    /// The width and height of the kernel.
+    pub size: u16,
+    /// The kernel weights divided by the divisor, in row-major order.
+    ///
+    /// Unlike in [`ConvolutionKernel`], the weights are rotated by 180°, such that
+    /// `weights[i * size + j]` is applied to the input pixel at offset `(j, i)` from
+    /// the top-left corner of the kernel.
+    pub weights: Vec<f32>,
+    /// The position of the target pixel within the kernel along each axis.
+    pub target: u16,
+    /// The bias added to the result.
+    pub bias: f32,
+    /// Whether only the color channels of the unpremultiplied input are convolved.
+    pub preserve_alpha: bool,
+    /// Edge mode for handling out-of-bounds sampling.
+    pub edge_mode: EdgeMode,
+}
+
+impl ConvolveMatrix {
+    /// Create a new convolve matrix filter.
+    ///
+    /// Invalid kernels are replaced with a kernel that leaves the input unchanged, as
+    /// required by the specification. A divisor of zero is treated as one.
+    pub fn new(kernel: &ConvolutionKernel) -> Self {
+        let size = u16::try_from(kernel.size)
+            .ok()
+            .filter(|_| kernel.is_valid());
+
+        let Some(size) = size else {
+            return Self {
+                size: 1,
+                weights: vec![1.0],
+                target: 0,
+                bias: 0.0,
+                preserve_alpha: false,
+                edge_mode: EdgeMode::None,
+            };
+        };
+
+        let divisor = if kernel.divisor == 0.0 {
+            1.0
+        } else {
+            kernel.divisor
+        };
+
+        Self {
+            size,
+            weights: kernel.values.iter().rev().map(|w| w / divisor).collect(),
+            target: size / 2,
+            bias: kernel.bias,
+            preserve_alpha: kernel.preserve_alpha,
+            edge_mode: kernel.edge_mode,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The displacement map filter.
+
+use crate::filter_effects::ColorChannel;
+use crate::kurbo::Affine;
+use crate::util::extract_scales;
+
+/// A displacement map filter.
+#[derive(Debug)]
+pub struct DisplacementMap {
+    /// The horizontal displacement scale in pixels.
+    pub scale_x: f32,
+    /// The vertical displacement scale in pixels.
+    pub scale_y: f32,
+    /// The channel of the displacement map used for horizontal displacement.
+    pub x_channel: ColorChannel,
+    /// The channel of the displacement map used for vertical displacement.
+    pub y_channel: ColorChannel,
+}
+
+impl DisplacementMap {
+    /// Create a new displacement map filter with the scale in user space.
+    pub fn new(
+        scale: f32,
+        x_channel: ColorChannel,
+        y_channel: ColorChannel,
+        transform: &Affine,
+    ) -> Self {
+        let (scale_x, scale_y) = extract_scales(transform);
+
+        Self {
+            scale_x: scale * scale_x,
+            scale_y: scale * scale_y,
+            x_channel,
+            y_channel,
+        }
+    }
+
+    /// Compute the displacement in pixels for a pixel of the displacement map, given as
+    /// unpremultiplied RGBA channels.
+    pub fn displacement(&self, map: [u8; 4]) -> (f32, f32) {
+        let channel = |c: ColorChannel| {
+            let index = match c {
+                ColorChannel::Red => 0,
+                ColorChannel::Green => 1,
+                ColorChannel::Blue => 2,
+                ColorChannel::Alpha => 3,
+            };
+            f32::from(map[index]) / 255.0 - 0.5
+        };
+
+        (
+            self.scale_x * channel(self.x_channel),
+            self.scale_y * channel(self.y_channel),
+        )
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The diffuse and specular lighting filters.
+
+use crate::color::{AlphaColor, Srgb};
+use crate::filter_effects::LightSource;
+use crate::kurbo::{Affine, Point, Vec2};
+#[cfg(not(feature = "std"))]
+use peniko::kurbo::common::FloatFuncs as _;
+
+/// The reflection model of a lighting filter.
+#[derive(Debug, Clone, Copy)]
+pub enum LightingModel {
+    /// Diffuse (matte) reflection.
+    Diffuse {
+        /// The diffuse reflection constant (kd).
+        diffuse_constant: f32,
+    },
+    /// Specular (shiny) reflection.
+    Specular {
+        /// The specular reflection constant (ks).
+        specular_constant: f32,
+        /// The specular reflection exponent, in the range [1, 128].
+        specular_exponent: f32,
+    },
+}
+
+/// A light source in the pixel coordinates of the filtered layer.
+#[derive(Debug, Clone, Copy)]
+pub enum Light {
+    /// A light infinitely far away.
+    Distant {
+        /// The unit vector pointing from the surface towards the light.
+        direction: [f32; 3],
+    },
+    /// A light at a specific position, radiating in all directions.
+    Point {
+        /// The position of the light.
+        position: [f32; 3],
+    },
+    /// A light at a specific position, radiating in a cone.
+    Spot {
+        /// The position of the light.
+        position: [f32; 3],
+        /// The unit vector pointing from the light towards the point it is aimed at.
+        direction: [f32; 3],
+        /// The exponent controlling the focus of the beam.
+        specular_exponent: f32,
+        /// The cosine of the limiting cone angle, if any.
+        cos_cone_angle: Option<f32>,
+    },
+}
+
+/// A lighting filter.
+#[derive(Debug)]
+pub struct Lighting {
+    /// The reflection model.
+    pub model: LightingModel,
+    /// The factor converting alpha values to surface heights in pixels.
+    pub surface_scale: f32,
+    /// The unpremultiplied RGB color of the light.
+    pub color: [f32; 3],
+    /// The light source.
+    pub light: Light,
+}
+
+impl Lighting {
+    /// Create a new lighting filter.
+    ///
+    /// `transform` maps user space, in which the light source is defined, to the pixel
+    /// coordinates of the filtered layer. Heights are scaled by the average scale factor
+    /// of the transform.
+    pub fn new(
+        model: LightingModel,
+        surface_scale: f32,
+        light_source: &LightSource,
+        lighting_color: AlphaColor<Srgb>,
+        transform: &Affine,
+    ) -> Self {
+        // Negative constants are an error, which disables the light.
+        let model = match model {
+            LightingModel::Diffuse { diffuse_constant } => LightingModel::Diffuse {
+                diffuse_constant: diffuse_constant.max(0.0),
+            },
+            LightingModel::Specular {
+                specular_constant,
+                specular_exponent,
+            } => LightingModel::Specular {
+                specular_constant: specular_constant.max(0.0),
+                specular_exponent: specular_exponent.clamp(1.0, 128.0),
+            },
+        };
+
+        let z_scale = transform.determinant().abs().sqrt() as f32;
+        let to_pixels = |x: f32, y: f32, z: f32| {
+            let p = *transform * Point::new(f64::from(x), f64::from(y));
+            [p.x as f32, p.y as f32, z * z_scale]
+        };
+
+        let light = match *light_source {
+            LightSource::Distant { azimuth, elevation } => {
+                let (azimuth, elevation) = (azimuth.to_radians(), elevation.to_radians());
+                // Only the direction within the surface plane is affected by the transform.
+                let planar = transform_direction(
+                    transform,
+                    Vec2::new(f64::from(azimuth.cos()), f64::from(azimuth.sin())),
+                );
+                Light::Distant {
+                    direction: [
+                        planar.x as f32 * elevation.cos(),
+                        planar.y as f32 * elevation.cos(),
+                        elevation.sin(),
+                    ],
+                }
+            }
+            LightSource::Point { x, y, z } => Light::Point {
+                position: to_pixels(x, y, z),
+            },
+            LightSource::Spot {
+                x,
+                y,
+                z,
+                points_at_x,
+                points_at_y,
+                points_at_z,
+                specular_exponent,
+                limiting_cone_angle,
+            } => {
+                let position = to_pixels(x, y, z);
+                let points_at = to_pixels(points_at_x, points_at_y, points_at_z);
+                Light::Spot {
+                    position,
+                    direction: normalize(core::array::from_fn(|i| points_at[i] - position[i])),
+                    specular_exponent,
+                    cos_cone_angle: limiting_cone_angle.map(|angle| angle.to_radians().cos()),
+                }
+            }
+        };
+
+        let [r, g, b, _] = lighting_color.components;
+
+        Self {
+            model,
+            surface_scale: surface_scale * z_scale,
+            color: [r, g, b],
+            light,
+        }
+    }
+
+    /// Compute the unit vector from a point on the surface towards the light, and the
+    /// color of the light arriving at that point.
+    pub fn light_at(&self, x: f32, y: f32, z: f32) -> ([f32; 3], [f32; 3]) {
+        match self.light {
+            Light::Distant { direction } => (direction, self.color),
+            Light::Point { position } => (
+                normalize([position[0] - x, position[1] - y, position[2] - z]),
+                self.color,
+            ),
+            Light::Spot {
+                position,
+                direction,
+                specular_exponent,
+                cos_cone_angle,
+            } => {
+                let to_light = normalize([position[0] - x, position[1] - y, position[2] - z]);
+                // The cosine of the angle between the beam and the ray towards the point.
+                let cos_angle = -dot(to_light, direction);
+
+                let inside_cone = cos_cone_angle.is_none_or(|cos_cone| cos_angle >= cos_cone);
+                let intensity = if cos_angle > 0.0 && inside_cone {
+                    cos_angle.powf(specular_exponent)
+                } else {
+                    0.0
+                };
+
+                (to_light, self.color.map(|c| c * intensity))
+            }
+        }
+    }
+
+    /// Compute the unpremultiplied RGBA result for a surface normal, the unit vector
+    /// towards the light and the color of the light.
+    pub fn shade(&self, normal: [f32; 3], to_light: [f32; 3], light_color: [f32; 3]) -> [f32; 4] {
+        match self.model {
+            LightingModel::Diffuse { diffuse_constant } => {
+                let factor = diffuse_constant * dot(normal, to_light).max(0.0);
+                let [r, g, b] = light_color.map(|c| (c * factor).clamp(0.0, 1.0));
+                [r, g, b, 1.0]
+            }
+            LightingModel::Specular {
+                specular_constant,
+                specular_exponent,
+            } => {
+                // The halfway vector between the light and the eye, which is looking
+                // straight down at the surface.
+                let halfway = normalize([to_light[0], to_light[1], to_light[2] + 1.0]);
+                let factor =
+                    specular_constant * dot(normal, halfway).max(0.0).powf(specular_exponent);
+                let [r, g, b] = light_color.map(|c| (c * factor).clamp(0.0, 1.0));
+                [r, g, b, r.max(g).max(b)]
+            }
+        }
+    }
+}
+
+/// Transform a direction with the linear part of `transform`, keeping its length.
+fn transform_direction(transform: &Affine, direction: Vec2) -> Vec2 {
+    let [a, b, c, d, _, _] = transform.as_coeffs();
+    let transformed = Vec2::new(
+        a * direction.x + c * direction.y,
+        b * direction.x + d * direction.y,
+    );
+    let length = transformed.hypot();
+
+    if length > 0.0 {
+        transformed * (direction.hypot() / length)
+    } else {
+        direction
+    }
+}
+
+#[inline(always)]
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline(always)]
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = dot(v, v).sqrt();
+
+    if length > 0.0 {
+        v.map(|c| c / length)
+    } else {
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::palette::css::WHITE;
+
+    fn diffuse(light_source: &LightSource, transform: &Affine) -> Lighting {
+        Lighting::new(
+            LightingModel::Diffuse {
+                diffuse_constant: 1.0,
+            },
+            1.0,
+            light_source,
+            WHITE,
+            transform,
+        )
+    }
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-5, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn distant_light_direction() {
+        let overhead = LightSource::Distant {
+            azimuth: 0.0,
+            elevation: 90.0,
+        };
+        let (to_light, _) = diffuse(&overhead, &Affine::IDENTITY).light_at(0.0, 0.0, 0.0);
+        assert_close(to_light, [0.0, 0.0, 1.0]);
+
+        // Rotating the content rotates the light within the surface plane.
+        let right = LightSource::Distant {
+            azimuth: 0.0,
+            elevation: 0.0,
+        };
+        let rotated = diffuse(&right, &Affine::rotate(core::f64::consts::FRAC_PI_2));
+        assert_close(rotated.light_at(0.0, 0.0, 0.0).0, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn point_light_is_transformed() {
+        let point = LightSource::Point {
+            x: 10.0,
+            y: 0.0,
+            z: 5.0,
+        };
+        let lighting = diffuse(&point, &Affine::scale(2.0));
+        // The light is at (20, 0, 10) in pixels.
+        let (to_light, _) = lighting.light_at(20.0, 0.0, 0.0);
+        assert_close(to_light, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn spot_light_cone() {
+        let spot = LightSource::Spot {
+            x: 0.0,
+            y: 0.0,
+            z: 10.0,
+            points_at_x: 0.0,
+            points_at_y: 0.0,
+            points_at_z: 0.0,
+            specular_exponent: 1.0,
+            limiting_cone_angle: Some(30.0),
+        };
+        let lighting = diffuse(&spot, &Affine::IDENTITY);
+
+        assert_close(lighting.light_at(0.0, 0.0, 0.0).1, [1.0; 3]);
+        // 45 degrees away from the beam, outside of the cone.
+        assert_close(lighting.light_at(10.0, 0.0, 0.0).1, [0.0; 3]);
+    }
+
+    #[test]
+    fn specular_alpha_is_max_channel() {
+        let lighting = Lighting::new(
+            LightingModel::Specular {
+                specular_constant: 1.0,
+                specular_exponent: 1.0,
+            },
+            1.0,
+            &LightSource::Distant {
+                azimuth: 0.0,
+                elevation: 90.0,
+            },
+            AlphaColor::new([0.5, 0.25, 0.0, 1.0]),
+            &Affine::IDENTITY,
+        );
+        let (to_light, color) = lighting.light_at(0.0, 0.0, 0.0);
+
+        assert_eq!(
+            lighting.shade([0.0, 0.0, 1.0], to_light, color),
+            [0.5, 0.25, 0.0, 0.5]
+        );
+    }
+}
@@ -7,17 +7,33 @@
 //! represent a special representation of each filter to be used as the basis for rendering in
 //! `vello_hybrid` and `vello_cpu`.
 
+use crate::filter::color_matrix::ColorMatrix;
+use crate::filter::component_transfer::ComponentTransfer;
+use crate::filter::composite::Composite;
+use crate::filter::convolve_matrix::ConvolveMatrix;
+use crate::filter::displacement_map::DisplacementMap;
 use crate::filter::drop_shadow::{DropShadow, transform_shadow_params};
 use crate::filter::flood::Flood;
 use crate::filter::gaussian_blur::{GaussianBlur, transform_blur_params};
+use crate::filter::lighting::{Lighting, LightingModel};
+use crate::filter::morphology::{Morphology, transform_morphology_params};
 use crate::filter::offset::Offset;
-use crate::filter_effects::{Filter, FilterPrimitive};
+use crate::filter::turbulence::Turbulence;
+use crate::filter_effects::FilterPrimitive;
 use crate::kurbo::{Affine, Vec2};
 
+pub mod color_matrix;
+pub mod component_transfer;
+pub mod composite;
+pub mod convolve_matrix;
+pub mod displacement_map;
 pub mod drop_shadow;
 pub mod flood;
 pub mod gaussian_blur;
+pub mod lighting;
+pub mod morphology;
 pub mod offset;
+pub mod turbulence;
 
 /// A filter that has been prepared for rendering.
 #[derive(Debug)]
@@ -30,17 +46,29 @@ pub enum PreparedFilter {
     Offset(Offset),
     /// A drop shadow filter.
     DropShadow(DropShadow),
+    /// A color matrix filter.
+    ColorMatrix(ColorMatrix),
+    /// A component transfer filter.
+    ComponentTransfer(ComponentTransfer),
+    /// A morphology filter.
+    Morphology(Morphology),
+    /// A convolve matrix filter.
+    ConvolveMatrix(ConvolveMatrix),
+    /// A turbulence filter.
+    Turbulence(Turbulence),
+    /// A displacement map filter.
+    DisplacementMap(DisplacementMap),
+    /// A diffuse or specular lighting filter.
+    Lighting(Lighting),
+    /// A composite filter.
+    Composite(Composite),
 }
 
 impl PreparedFilter {
-    /// Build a new prepared filter for the given transform.
-    pub fn new(filter: &Filter, transform: &Affine) -> Self {
-        // Multi-primitive filter graphs are not yet implemented.
-        if filter.graph.primitives.len() != 1 {
-            unimplemented!("Multi-primitive filter graphs are not yet supported");
-        }
-
-        match &filter.graph.primitives[0] {
+    /// Build a new prepared filter from a single primitive of a filter graph for
+    /// the given transform.
+    pub fn new(primitive: &FilterPrimitive, transform: &Affine) -> Self {
+        match primitive {
             FilterPrimitive::Flood { color } => {
                 let flood = Flood::new(*color);
                 Self::Flood(flood)
@@ -73,8 +101,79 @@ impl PreparedFilter {
 
                 Self::Offset(offset)
             }
+            FilterPrimitive::ColorMatrix { matrix } => Self::ColorMatrix(ColorMatrix::new(*matrix)),
+            FilterPrimitive::ComponentTransfer {
+                red_function,
+                green_function,
+                blue_function,
+                alpha_function,
+            } => Self::ComponentTransfer(ComponentTransfer::new(
+                red_function.as_ref(),
+                green_function.as_ref(),
+                blue_function.as_ref(),
+                alpha_function.as_ref(),
+            )),
+            FilterPrimitive::Morphology { operator, radius } => {
+                let (radius_x, radius_y) = transform_morphology_params(*radius, transform);
+                Self::Morphology(Morphology::new(*operator, radius_x, radius_y))
+            }
+            FilterPrimitive::ConvolveMatrix { kernel } => {
+                Self::ConvolveMatrix(ConvolveMatrix::new(kernel))
+            }
+            FilterPrimitive::Turbulence {
+                base_frequency,
+                num_octaves,
+                seed,
+                turbulence_type,
+            } => Self::Turbulence(Turbulence::new(
+                *base_frequency,
+                *num_octaves,
+                *seed,
+                *turbulence_type,
+                transform,
+            )),
+            FilterPrimitive::DisplacementMap {
+                scale,
+                x_channel,
+                y_channel,
+            } => Self::DisplacementMap(DisplacementMap::new(
+                *scale, *x_channel, *y_channel, transform,
+            )),
+            FilterPrimitive::DiffuseLighting {
+                surface_scale,
+                diffuse_constant,
+                kernel_unit_length: _,
+                light_source,
+                lighting_color,
+            } => Self::Lighting(Lighting::new(
+                LightingModel::Diffuse {
+                    diffuse_constant: *diffuse_constant,
+                },
+                *surface_scale,
+                light_source,
+                *lighting_color,
+                transform,
+            )),
+            FilterPrimitive::SpecularLighting {
+                surface_scale,
+                specular_constant,
+                specular_exponent,
+                kernel_unit_length: _,
+                light_source,
+                lighting_color,
+            } => Self::Lighting(Lighting::new(
+                LightingModel::Specular {
+                    specular_constant: *specular_constant,
+                    specular_exponent: *specular_exponent,
+                },
+                *surface_scale,
+                light_source,
+                *lighting_color,
+                transform,
+            )),
+            FilterPrimitive::Composite { operator } => Self::Composite(Composite::new(*operator)),
             _ => {
-                // Other primitives like Blend, ColorMatrix, ComponentTransfer, etc.
+                // Other primitives like Blend, Image, etc.
                 // are not yet implemented
                 unimplemented!("Other filter primitives not yet implemented");
             }
@@ -0,0 +1,53 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The morphology filter.
+
+use crate::filter_effects::MorphologyOperator;
+use crate::kurbo::Affine;
+use crate::util::extract_scales;
+#[cfg(not(feature = "std"))]
+use peniko::kurbo::common::FloatFuncs as _;
+
+/// Scale a morphology radius based on the transformation.
+///
+/// # Returns
+/// A tuple of (`radius_x`, `radius_y`) in device space.
+pub(crate) fn transform_morphology_params(radius: f32, transform: &Affine) -> (f32, f32) {
+    let (scale_x, scale_y) = extract_scales(transform);
+    (radius * scale_x, radius * scale_y)
+}
+
+/// A morphology filter.
+#[derive(Debug)]
+pub struct Morphology {
+    /// Whether to erode or dilate the input.
+    pub operator: MorphologyOperator,
+    /// The horizontal radius in pixels.
+    pub radius_x: u16,
+    /// The vertical radius in pixels.
+    pub radius_y: u16,
+}
+
+impl Morphology {
+    /// Create a new morphology filter with the radii in device space.
+    ///
+    /// The radii are rounded to whole pixels.
+    pub fn new(operator: MorphologyOperator, radius_x: f32, radius_y: f32) -> Self {
+        let to_pixels = |radius: f32| radius.max(0.0).round().min(f32::from(u16::MAX)) as u16;
+
+        Self {
+            operator,
+            radius_x: to_pixels(radius_x),
+            radius_y: to_pixels(radius_y),
+        }
+    }
+
+    /// Whether the filter leaves its input unchanged.
+    ///
+    /// This is the case if both radii round to zero pixels, which includes the case
+    /// of a radius of zero or less disabling the filter, as required by the specification.
+    pub fn is_noop(&self) -> bool {
+        self.radius_x == 0 && self.radius_y == 0
+    }
+}
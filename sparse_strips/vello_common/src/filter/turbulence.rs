@@ -0,0 +1,235 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The turbulence filter.
+//!
+//! This is a direct port of the reference implementation of Perlin noise from the
+//! specification, including its pseudo-random number generator, so that the generated
+//! noise matches other implementations exactly.
+
+use crate::filter_effects::TurbulenceType;
+use crate::kurbo::{Affine, Point};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use peniko::kurbo::common::FloatFuncs as _;
+
+const B_SIZE: usize = 0x100;
+const BM: i32 = 0xff;
+const PERLIN_N: f64 = 4096.0;
+
+const RAND_M: i64 = 2147483647; // 2**31 - 1
+const RAND_A: i64 = 16807; // 7**5; primitive root of m
+const RAND_Q: i64 = 127773; // m / a
+const RAND_R: i64 = 2836; // m % a
+
+/// A turbulence filter.
+#[derive(Debug)]
+pub struct Turbulence {
+    /// The base frequency of the noise, in user space.
+    pub base_frequency: f64,
+    /// The number of octaves of noise to add up.
+    pub num_octaves: u32,
+    /// Whether to compute fractal noise (or turbulence).
+    pub fractal_sum: bool,
+    /// The transform from pixel coordinates of the filtered layer to user space, in which
+    /// the noise is defined.
+    pub pixel_to_user: Affine,
+    /// The permutation of lattice points.
+    lattice: Vec<usize>,
+    /// The gradients of the lattice points for each color channel.
+    gradients: [Vec<[f64; 2]>; 4],
+}
+
+impl Turbulence {
+    /// Create a new turbulence filter.
+    ///
+    /// `transform` maps user space to the pixel coordinates of the filtered layer.
+    pub fn new(
+        base_frequency: f32,
+        num_octaves: u32,
+        seed: u32,
+        turbulence_type: TurbulenceType,
+        transform: &Affine,
+    ) -> Self {
+        let (lattice, gradients) = init(i64::from(seed));
+        let pixel_to_user = if transform.determinant().abs() > f64::EPSILON {
+            transform.inverse()
+        } else {
+            Affine::IDENTITY
+        };
+
+        Self {
+            // Negative base frequencies are an error, which disables the noise.
+            base_frequency: f64::from(base_frequency.max(0.0)),
+            num_octaves,
+            fractal_sum: turbulence_type == TurbulenceType::FractalNoise,
+            pixel_to_user,
+            lattice,
+            gradients,
+        }
+    }
+
+    /// Evaluate the noise at the given pixel of the filtered layer.
+    ///
+    /// Returns the unpremultiplied RGBA color with each channel in [0, 1].
+    pub fn sample(&self, x: u16, y: u16) -> [f32; 4] {
+        let point = self.pixel_to_user * Point::new(f64::from(x), f64::from(y));
+
+        core::array::from_fn(|channel| {
+            let sum = self.turbulence(channel, point);
+            let value = if self.fractal_sum {
+                (sum + 1.0) / 2.0
+            } else {
+                sum
+            };
+
+            value.clamp(0.0, 1.0) as f32
+        })
+    }
+
+    /// Sum the noise of all octaves for a single color channel.
+    fn turbulence(&self, channel: usize, point: Point) -> f64 {
+        let mut sum = 0.0;
+        let mut vec = [point.x * self.base_frequency, point.y * self.base_frequency];
+        let mut ratio = 1.0;
+
+        for _ in 0..self.num_octaves {
+            let noise = self.noise2(channel, vec);
+            if self.fractal_sum {
+                sum += noise / ratio;
+            } else {
+                sum += noise.abs() / ratio;
+            }
+            vec[0] *= 2.0;
+            vec[1] *= 2.0;
+            ratio *= 2.0;
+        }
+
+        sum
+    }
+
+    /// Evaluate 2D Perlin noise at the given point.
+    fn noise2(&self, channel: usize, vec: [f64; 2]) -> f64 {
+        let lattice_coords = |v: f64| {
+            let t = v + PERLIN_N;
+            let b0 = (t as i32) & BM;
+            let b1 = (b0 + 1) & BM;
+            let r0 = t - f64::from(t as i32);
+            (b0 as usize, b1 as usize, r0, r0 - 1.0)
+        };
+
+        let (bx0, bx1, rx0, rx1) = lattice_coords(vec[0]);
+        let (by0, by1, ry0, ry1) = lattice_coords(vec[1]);
+
+        let i = self.lattice[bx0];
+        let j = self.lattice[bx1];
+        let b00 = self.lattice[i + by0];
+        let b10 = self.lattice[j + by0];
+        let b01 = self.lattice[i + by1];
+        let b11 = self.lattice[j + by1];
+
+        let sx = s_curve(rx0);
+        let sy = s_curve(ry0);
+
+        let gradients = &self.gradients[channel];
+        let dot = |b: usize, rx: f64, ry: f64| rx * gradients[b][0] + ry * gradients[b][1];
+
+        let a = lerp(sx, dot(b00, rx0, ry0), dot(b10, rx1, ry0));
+        let b = lerp(sx, dot(b01, rx0, ry1), dot(b11, rx1, ry1));
+
+        lerp(sy, a, b)
+    }
+}
+
+#[inline(always)]
+fn s_curve(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[inline(always)]
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn setup_seed(mut seed: i64) -> i64 {
+    if seed <= 0 {
+        seed = -(seed % (RAND_M - 1)) + 1;
+    }
+    if seed > RAND_M - 1 {
+        seed = RAND_M - 1;
+    }
+    seed
+}
+
+fn random(seed: i64) -> i64 {
+    let mut result = RAND_A * (seed % RAND_Q) - RAND_R * (seed / RAND_Q);
+    if result <= 0 {
+        result += RAND_M;
+    }
+    result
+}
+
+/// Initialize the lattice permutation and the gradients for the given seed.
+fn init(seed: i64) -> (Vec<usize>, [Vec<[f64; 2]>; 4]) {
+    let mut lattice = vec![0_usize; B_SIZE + B_SIZE + 2];
+    let mut gradients: [Vec<[f64; 2]>; 4] =
+        core::array::from_fn(|_| vec![[0.0; 2]; B_SIZE + B_SIZE + 2]);
+    let mut seed = setup_seed(seed);
+
+    for channel in gradients.iter_mut() {
+        for (i, gradient) in channel.iter_mut().take(B_SIZE).enumerate() {
+            lattice[i] = i;
+            for component in gradient.iter_mut() {
+                seed = random(seed);
+                *component =
+                    ((seed % (B_SIZE + B_SIZE) as i64) - B_SIZE as i64) as f64 / B_SIZE as f64;
+            }
+            let s = (gradient[0] * gradient[0] + gradient[1] * gradient[1]).sqrt();
+            gradient[0] /= s;
+            gradient[1] /= s;
+        }
+    }
+
+    for i in (1..B_SIZE).rev() {
+        seed = random(seed);
+        let j = (seed % B_SIZE as i64) as usize;
+        lattice.swap(i, j);
+    }
+
+    for i in 0..B_SIZE + 2 {
+        lattice[B_SIZE + i] = lattice[i];
+        for channel in gradients.iter_mut() {
+            channel[B_SIZE + i] = channel[i];
+        }
+    }
+
+    (lattice, gradients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The pseudo-random number generator must match the reference implementation.
+    #[test]
+    fn random_matches_reference() {
+        assert_eq!(setup_seed(0), 1);
+        assert_eq!(setup_seed(-5), 6);
+        assert_eq!(random(1), 16807);
+        assert_eq!(random(16807), 282475249);
+        assert_eq!(random(282475249), 1622650073);
+    }
+
+    /// Noise is zero at lattice points.
+    #[test]
+    fn noise_vanishes_on_lattice() {
+        let turbulence =
+            Turbulence::new(1.0, 1, 0, TurbulenceType::FractalNoise, &Affine::IDENTITY);
+
+        for channel in 0..4 {
+            assert_eq!(turbulence.noise2(channel, [3.0, 7.0]), 0.0);
+            assert_ne!(turbulence.noise2(channel, [3.5, 7.25]), 0.0);
+        }
+    }
+}
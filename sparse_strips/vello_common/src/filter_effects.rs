@@ -15,43 +15,43 @@
 //!
 //! **Filter Functions:**
 //! - `Blur` - Gaussian blur effect
+//! - `Brightness`, `Contrast`, `Grayscale`, `HueRotate`, `Invert`,
+//!   `Opacity`, `Saturate`, `Sepia` - Color adjustments (lowered to `ColorMatrix`)
+//!
+//! **Core Features:**
+//! - `FilterGraph` execution - Chaining multiple filter primitives together (Vello CPU only)
+//! - `FilterInputs` - Connecting primitives to `SourceGraphic`, `SourceAlpha` or earlier results
 //!
-//! **Filter Primitives (Single Use Only):**
+//! **Filter Primitives:**
 //! - `Flood` - Solid color fill
 //! - `GaussianBlur` - Gaussian blur filter
 //! - `DropShadow` - Drop shadow effect (compound primitive)
-//! - `Offset` - Translation/shift (single primitive)
+//! - `Offset` - Translation/shift
+//! - `ColorMatrix` - Matrix-based color transformation
+//! - `ComponentTransfer` - Per-channel transfer functions
+//! - `Morphology` - Dilate/erode operations
+//! - `ConvolveMatrix` - Custom convolution kernels
+//! - `Turbulence` - Perlin noise generation
+//! - `DisplacementMap` - Pixel displacement
+//! - `DiffuseLighting`, `SpecularLighting` - Lighting effects
+//! - `Composite` - Porter-Duff compositing operations
 //!
-//! **Note:** Currently only single primitive filters are supported. Filter graphs with
-//! multiple connected primitives are not yet implemented.
+//! **Note:** `BackgroundImage`, `BackgroundAlpha`, `FillPaint` and `StrokePaint` inputs
+//! are not available and evaluate to transparent black.
 //!
 //! ### 🚧 Not Yet Implemented
 //!
-//! **Core Features:**
-//! - `FilterGraph` execution - Chaining multiple filter primitives together
-//! - `FilterInputs` - Connecting primitives to create complex effects
-//!
-//! **Filter Functions:**
-//! - `Brightness`, `Contrast`, `Grayscale`, `HueRotate`, `Invert`,
-//!   `Opacity`, `Saturate`, `Sepia`
-//!
 //! **Filter Primitives:**
-//! - `ColorMatrix` - Matrix-based color transformation
-//! - `Composite` - Porter-Duff compositing operations
 //! - `Blend` - Blend mode operations
-//! - `Morphology` - Dilate/erode operations
-//! - `ConvolveMatrix` - Custom convolution kernels
-//! - `Turbulence` - Perlin noise generation
-//! - `DisplacementMap` - Pixel displacement
-//! - `ComponentTransfer` - Per-channel transfer functions
 //! - `Image` - External image reference
 //! - `Tile` - Tiling operation
-//! - `DiffuseLighting`, `SpecularLighting` - Lighting effects
 
 use crate::color::{AlphaColor, Srgb};
 use crate::kurbo::{Affine, Rect};
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use peniko::kurbo::common::FloatFuncs as _;
 use smallvec::SmallVec;
 
 /// The main filter system.
@@ -80,7 +80,30 @@ impl Filter {
                 std_deviation: radius,
                 edge_mode: EdgeMode::default(),
             },
-            _ => unimplemented!("Filter function {:?} not supported", function),
+            FilterFunction::Brightness { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::brightness(amount),
+            },
+            FilterFunction::Contrast { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::contrast(amount),
+            },
+            FilterFunction::Grayscale { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::grayscale(amount),
+            },
+            FilterFunction::HueRotate { angle } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::hue_rotate(angle),
+            },
+            FilterFunction::Invert { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::invert(amount),
+            },
+            FilterFunction::Opacity { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::opacity(amount),
+            },
+            FilterFunction::Saturate { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::saturate(amount),
+            },
+            FilterFunction::Sepia { amount } => FilterPrimitive::ColorMatrix {
+                matrix: matrices::sepia(amount),
+            },
         };
 
         Self::from_primitive(primitive)
@@ -100,6 +123,16 @@ impl Filter {
         }
     }
 
+    /// Create a filter system from a filter graph.
+    ///
+    /// Use this to combine multiple filter primitives, e.g. to build an SVG `<filter>`
+    /// element with several primitives.
+    pub fn from_graph(graph: FilterGraph) -> Self {
+        Self {
+            graph: Arc::new(graph),
+        }
+    }
+
     /// Calculate the bounds expansion for this filter in pixel/device space.
     ///
     /// Returns a `Rect` representing how many extra pixels are needed around the
@@ -129,18 +162,31 @@ impl Filter {
 ///
 /// The graph represents a pipeline of filter primitives where outputs of some
 /// primitives can be used as inputs to others. Each primitive has a unique `FilterId`.
+///
+/// Primitives can only reference the results of primitives that were added before them,
+/// so the insertion order is always a valid execution order.
 #[derive(Debug, Clone, PartialEq)]
 pub struct FilterGraph {
     /// All filter primitives in the graph, stored in insertion order.
     pub primitives: SmallVec<[FilterPrimitive; 1]>,
+    /// The resolved inputs of each primitive, parallel to `primitives`.
+    pub inputs: SmallVec<[FilterInputs; 1]>,
     /// The final output filter ID whose result is the output of this graph.
     pub output: FilterId,
     /// Next available filter ID (monotonically increasing counter).
     next_id: u16,
-    /// Accumulated bounds expansion from all primitives in the graph, cached in user space.
-    /// This is the axis-aligned bounding box of the expansion region (centered at origin),
-    /// which can be transformed to device space when needed.
-    expansion_rect: Rect,
+    /// Accumulated bounds expansion of each primitive's result, cached in user space.
+    ///
+    /// This is the expansion of the primitive itself combined with the expansion of
+    /// all the results it (transitively) reads from. Each entry is the axis-aligned
+    /// bounding box of the expansion region (centered at origin), which can be
+    /// transformed to device space when needed.
+    expansion_rects: SmallVec<[Rect; 1]>,
+    /// Accumulated bounds expansion of each primitive's result in device pixels.
+    ///
+    /// This is the part of the expansion which doesn't scale with the transform, like the
+    /// kernel radius of `ConvolveMatrix`, which is applied to device pixels.
+    pixel_expansion_rects: SmallVec<[Rect; 1]>,
 }
 
 impl Default for FilterGraph {
@@ -154,25 +200,61 @@ impl FilterGraph {
     pub fn new() -> Self {
         Self {
             primitives: SmallVec::new(),
+            inputs: SmallVec::new(),
             output: FilterId(0),
             next_id: 0,
-            expansion_rect: Rect::ZERO,
+            expansion_rects: SmallVec::new(),
+            pixel_expansion_rects: SmallVec::new(),
         }
     }
 
     /// Add a filter primitive with optional inputs.
     ///
+    /// If `inputs` is `None`, the primitive reads from the result of the previously added
+    /// primitive, or from `SourceGraphic` if it is the first one, matching the default
+    /// of the `in` attribute in SVG. The same default applies to a missing secondary input
+    /// of primitives that combine two inputs.
+    ///
     /// Returns a `FilterId` that can be referenced by other primitives.
     /// Automatically updates the accumulated bounds expansion based on the primitive's requirements.
-    pub fn add(&mut self, primitive: FilterPrimitive, _inputs: Option<FilterInputs>) -> FilterId {
+    ///
+    /// # Panics
+    /// Panics if one of the inputs references a primitive that has not been added yet.
+    pub fn add(&mut self, primitive: FilterPrimitive, inputs: Option<FilterInputs>) -> FilterId {
         let id = FilterId(self.next_id);
         self.next_id += 1;
 
-        // Update accumulated expansion by taking the union of rects
-        let primitive_rect = primitive.expansion_rect();
-        self.expansion_rect = self.expansion_rect.union(primitive_rect);
+        let default_input = match id.0.checked_sub(1) {
+            Some(previous) => FilterInput::Result(FilterId(previous)),
+            None => FilterInput::Source(FilterSource::SourceGraphic),
+        };
+        let mut inputs = inputs.unwrap_or_else(|| FilterInputs::single(default_input.clone()));
+        if primitive.num_inputs() == 2 && inputs.secondary.is_none() {
+            inputs.secondary = Some(default_input);
+        }
+
+        // The expansion of the result is the expansion of the primitive applied on top of
+        // the (largest) expansion of its inputs.
+        let mut input_rect = Rect::ZERO;
+        let mut input_pixel_rect = Rect::ZERO;
+        for input in core::iter::once(&inputs.primary).chain(inputs.secondary.as_ref()) {
+            if let FilterInput::Result(input_id) = input {
+                assert!(
+                    input_id.0 < id.0,
+                    "filter primitives can only read from primitives that were added before them"
+                );
+                let input_id = usize::from(input_id.0);
+                input_rect = input_rect.union(self.expansion_rects[input_id]);
+                input_pixel_rect = input_pixel_rect.union(self.pixel_expansion_rects[input_id]);
+            }
+        }
+        self.expansion_rects
+            .push(expand(input_rect, primitive.expansion_rect()));
+        self.pixel_expansion_rects
+            .push(expand(input_pixel_rect, primitive.pixel_expansion_rect()));
 
         self.primitives.push(primitive);
+        self.inputs.push(inputs);
 
         id
     }
@@ -184,8 +266,9 @@ impl FilterGraph {
 
     /// Get the accumulated bounds expansion for all primitives in this graph.
     ///
-    /// This returns the expansion required by all primitives in the graph,
-    /// representing the padding needed to render all filter effects correctly.
+    /// This returns the expansion required by all primitives that contribute to the
+    /// output of the graph, representing the padding needed to render all filter
+    /// effects correctly.
     ///
     /// The expansion accounts for the transform (rotation, scale, and shear) to compute
     /// the correct axis-aligned bounding box expansion in device space.
@@ -193,12 +276,41 @@ impl FilterGraph {
     /// # Arguments
     /// * `transform` - The transform applied to this filter layer
     pub fn bounds_expansion(&self, transform: &Affine) -> Rect {
+        let output = usize::from(self.output.0);
+        let expansion_rect = self
+            .expansion_rects
+            .get(output)
+            .copied()
+            .unwrap_or(Rect::ZERO);
+        let pixel_expansion_rect = self
+            .pixel_expansion_rects
+            .get(output)
+            .copied()
+            .unwrap_or(Rect::ZERO);
+
         // Transform the cached expansion rect to device space
         // transform_rect_bbox computes the axis-aligned bounding box of the transformed rect
-        transform.transform_rect_bbox(self.expansion_rect)
+        //
+        // The pixel expansion is already in device space. Adding it after transforming is the
+        // same as dividing it by the scale of the transform first, but doesn't enlarge it for
+        // rotations or degenerate transforms.
+        expand(
+            transform.transform_rect_bbox(expansion_rect),
+            pixel_expansion_rect,
+        )
     }
 }
 
+/// Expand the expansion rect `rect` by the expansion rect `by`.
+fn expand(rect: Rect, by: Rect) -> Rect {
+    Rect::new(
+        rect.x0 + by.x0,
+        rect.y0 + by.y0,
+        rect.x1 + by.x1,
+        rect.y1 + by.y1,
+    )
+}
+
 /// All possible filter effects.
 ///
 /// This enum allows choosing between high-level filter functions (simple CSS-style effects)
@@ -217,6 +329,8 @@ pub enum FilterEffect {
 ///
 /// These match the CSS Filter Effects specification and provide simple,
 /// commonly-used visual effects without needing to construct a filter graph.
+/// Apart from `Blur`, all of them are color transformations which are lowered to a
+/// single `ColorMatrix` primitive, using the equivalent matrices from the specification.
 ///
 /// See: <https://drafts.fxtf.org/filter-effects/#filter-functions>
 #[derive(Debug, Clone)]
@@ -238,11 +352,6 @@ pub enum FilterFunction {
         /// approximately 3 times this value in each direction.
         radius: f32,
     },
-    //
-    // ============================================================
-    // TODO: The following filter functions are not yet implemented
-    // ============================================================
-    //
     /// Brightness adjustment.
     ///
     /// Adjusts the brightness of the input image using a linear multiplier.
@@ -400,20 +509,25 @@ pub enum FilterPrimitive {
         /// Default is `EdgeMode::None` per SVG spec.
         edge_mode: EdgeMode,
     },
-    //
-    // ============================================================
-    // TODO: The following filter primitives are not yet implemented
-    // ============================================================
-    //
     /// Matrix-based color transformation.
     ///
     /// Applies a 4x5 matrix transformation to colors, allowing arbitrary
     /// color space transformations, hue shifts, and color adjustments.
+    /// The matrix is applied to unpremultiplied colors.
     ColorMatrix {
         /// 4x5 color transformation matrix: 4 rows (R,G,B,A) × 5 columns (R,G,B,A,offset).
         /// Each output channel is computed as a linear combination of input channels plus offset.
         matrix: [f32; 20],
     },
+    /// Composite two inputs using Porter-Duff compositing operations.
+    ///
+    /// Combines two input images using standard compositing operators
+    /// (over, in, out, atop, xor) or custom arithmetic combination.
+    /// The primary input is the source and the secondary input is the destination.
+    Composite {
+        /// Porter-Duff compositing operator to apply.
+        operator: CompositeOperator,
+    },
     /// Geometric offset/translation.
     ///
     /// Shifts the input image by the specified offset. Useful for creating
@@ -424,22 +538,20 @@ pub enum FilterPrimitive {
         /// Vertical offset in pixels. Positive values shift down.
         dy: f32,
     },
-
-    /// Composite two inputs using Porter-Duff compositing operations.
-    ///
-    /// Combines two input images using standard compositing operators
-    /// (over, in, out, atop, xor) or custom arithmetic combination.
-    Composite {
-        /// Porter-Duff compositing operator to apply.
-        operator: CompositeOperator,
-    },
-    /// Blend two inputs using blend modes.
+    /// Per-channel component transfer using lookup tables or functions.
     ///
-    /// Combines two input images using Photoshop-style blend modes
-    /// (multiply, screen, overlay, etc.).
-    Blend {
-        /// Blend mode determining how colors are combined.
-        mode: BlendMode,
+    /// Applies independent transfer functions to each color channel,
+    /// enabling color corrections, gamma adjustments, and custom mappings.
+    /// The functions are applied to unpremultiplied colors.
+    ComponentTransfer {
+        /// Transfer function applied to the red channel (None = identity).
+        red_function: Option<TransferFunction>,
+        /// Transfer function applied to the green channel (None = identity).
+        green_function: Option<TransferFunction>,
+        /// Transfer function applied to the blue channel (None = identity).
+        blue_function: Option<TransferFunction>,
+        /// Transfer function applied to the alpha channel (None = identity).
+        alpha_function: Option<TransferFunction>,
     },
     /// Morphological operations (dilate/erode).
     ///
@@ -448,13 +560,15 @@ pub enum FilterPrimitive {
     Morphology {
         /// Morphological operator determining whether to erode or dilate.
         operator: MorphologyOperator,
-        /// Operation radius in pixels. Larger values create stronger effects.
+        /// Operation radius in user space. Larger values create stronger effects.
+        /// A radius of zero or less disables the operation.
         radius: f32,
     },
     /// Custom convolution kernel for image processing.
     ///
     /// Applies a custom convolution matrix to the input image, enabling
     /// effects like sharpening, edge detection, embossing, and custom filters.
+    /// The kernel is applied in device pixels.
     ConvolveMatrix {
         /// Convolution kernel specification including size, values, and normalization.
         kernel: ConvolutionKernel,
@@ -463,6 +577,7 @@ pub enum FilterPrimitive {
     ///
     /// Creates procedural noise patterns useful for textures, clouds,
     /// marble effects, and other organic-looking randomness.
+    /// The noise replaces the input and fills the whole filter region.
     Turbulence {
         /// Base frequency for noise generation. Higher values create finer detail.
         base_frequency: f32,
@@ -477,6 +592,7 @@ pub enum FilterPrimitive {
     ///
     /// Uses the color values from a second input to spatially displace pixels
     /// in the primary input, creating warping and distortion effects.
+    /// The secondary input is the displacement map.
     DisplacementMap {
         /// Scale factor controlling the displacement intensity.
         scale: f32,
@@ -485,54 +601,31 @@ pub enum FilterPrimitive {
         /// Color channel from the displacement map used for Y-axis displacement.
         y_channel: ColorChannel,
     },
-    /// Per-channel component transfer using lookup tables or functions.
-    ///
-    /// Applies independent transfer functions to each color channel,
-    /// enabling color corrections, gamma adjustments, and custom mappings.
-    ComponentTransfer {
-        /// Transfer function applied to the red channel (None = identity).
-        red_function: Option<TransferFunction>,
-        /// Transfer function applied to the green channel (None = identity).
-        green_function: Option<TransferFunction>,
-        /// Transfer function applied to the blue channel (None = identity).
-        blue_function: Option<TransferFunction>,
-        /// Transfer function applied to the alpha channel (None = identity).
-        alpha_function: Option<TransferFunction>,
-    },
-    /// Reference an external image as filter input.
-    ///
-    /// Allows using pre-existing images (from an atlas or resource) as
-    /// input to filter operations, useful for texturing and overlays.
-    Image {
-        /// Identifier referencing an image in the resource atlas.
-        image_id: u32,
-        /// Optional 2D affine transformation matrix [a, b, c, d, e, f].
-        /// Transforms the image before using it as filter input.
-        transform: Option<[f32; 6]>,
-    },
-    /// Tile the input to fill the filter region.
-    ///
-    /// Repeats the input image to fill the entire filter primitive subregion,
-    /// creating a tiling/repeating pattern.
-    Tile,
     /// Diffuse lighting simulation.
     ///
     /// Creates a lighting effect by treating the input's alpha channel as a height map
     /// and calculating diffuse (matte) reflection from a light source.
+    /// The result is opaque and fills the whole filter region.
     DiffuseLighting {
         /// Surface scale factor for converting alpha values to heights.
         surface_scale: f32,
         /// Diffuse reflection constant (kd). Controls lighting intensity.
         diffuse_constant: f32,
         /// Kernel unit length for gradient calculations in user space.
+        ///
+        /// This is currently ignored, and gradients are always computed between
+        /// neighboring pixels.
         kernel_unit_length: f32,
         /// Configuration of the light source (point, distant, or spot).
         light_source: LightSource,
+        /// Color of the light. The alpha channel is ignored.
+        lighting_color: AlphaColor<Srgb>,
     },
     /// Specular lighting simulation.
     ///
     /// Creates a lighting effect by treating the input's alpha channel as a height map
     /// and calculating specular (shiny) reflection highlights from a light source.
+    /// The alpha of the result is the maximum of its color channels.
     SpecularLighting {
         /// Surface scale factor for converting alpha values to heights.
         surface_scale: f32,
@@ -541,13 +634,57 @@ pub enum FilterPrimitive {
         /// Specular reflection exponent. Controls highlight sharpness (higher = sharper).
         specular_exponent: f32,
         /// Kernel unit length for gradient calculations in user space.
+        ///
+        /// This is currently ignored, and gradients are always computed between
+        /// neighboring pixels.
         kernel_unit_length: f32,
         /// Configuration of the light source (point, distant, or spot).
         light_source: LightSource,
+        /// Color of the light. The alpha channel is ignored.
+        lighting_color: AlphaColor<Srgb>,
+    },
+    //
+    // ============================================================
+    // TODO: The following filter primitives are not yet implemented
+    // ============================================================
+    //
+    /// Blend two inputs using blend modes.
+    ///
+    /// Combines two input images using Photoshop-style blend modes
+    /// (multiply, screen, overlay, etc.).
+    Blend {
+        /// Blend mode determining how colors are combined.
+        mode: BlendMode,
+    },
+    /// Reference an external image as filter input.
+    ///
+    /// Allows using pre-existing images (from an atlas or resource) as
+    /// input to filter operations, useful for texturing and overlays.
+    Image {
+        /// Identifier referencing an image in the resource atlas.
+        image_id: u32,
+        /// Optional 2D affine transformation matrix [a, b, c, d, e, f].
+        /// Transforms the image before using it as filter input.
+        transform: Option<[f32; 6]>,
     },
+    /// Tile the input to fill the filter region.
+    ///
+    /// Repeats the input image to fill the entire filter primitive subregion,
+    /// creating a tiling/repeating pattern.
+    Tile,
 }
 
 impl FilterPrimitive {
+    /// The number of inputs the primitive reads from.
+    ///
+    /// Generators like `Flood` ignore their input, but still have one for simplicity.
+    pub fn num_inputs(&self) -> usize {
+        match self {
+            Self::Composite { .. } | Self::Blend { .. } | Self::DisplacementMap { .. } => 2,
+            _ => 1,
+        }
+    }
+
     /// Calculate the bounds expansion as a `Rect` in user space.
     ///
     /// Returns a rectangle centered at the origin representing how much the filter
@@ -594,16 +731,49 @@ impl FilterPrimitive {
                     blur_radius + dy.max(0.0),
                 )
             }
+            Self::Morphology {
+                operator: MorphologyOperator::Dilate,
+                radius,
+            } => {
+                // Dilation grows shapes by the radius in every direction, while erosion
+                // only shrinks them.
+                let radius = radius.max(0.0) as f64;
+                Rect::new(-radius, -radius, radius, radius)
+            }
+            Self::DisplacementMap { scale, .. } => {
+                // Each pixel is displaced by at most half the scale in each direction.
+                let max_offset = (scale.abs() * 0.5) as f64;
+                Rect::new(-max_offset, -max_offset, max_offset, max_offset)
+            }
             // Most other filters don't expand bounds
             _ => Rect::ZERO,
         }
     }
+
+    /// Calculate the bounds expansion as a `Rect` in device pixels.
+    ///
+    /// This uses the same representation as [`expansion_rect`](Self::expansion_rect), but
+    /// is for primitives which operate on device pixels, so that their expansion doesn't
+    /// depend on the transform.
+    pub fn pixel_expansion_rect(&self) -> Rect {
+        match self {
+            Self::ConvolveMatrix { kernel } => {
+                // The result at a pixel depends on the input pixels covered by the kernel
+                // centered on it, so content spreads by the kernel radius.
+                let (before, after) = kernel.radii();
+                let (before, after) = (f64::from(before), f64::from(after));
+                Rect::new(-after, -after, before, before)
+            }
+            _ => Rect::ZERO,
+        }
+    }
 }
 
 #[cfg(test)]
-mod offset_expansion_tests {
-    use super::FilterPrimitive;
+mod expansion_tests {
+    use super::{ColorChannel, ConvolutionKernel, FilterPrimitive, MorphologyOperator, kernels};
     use crate::kurbo::Rect;
+    use alloc::vec;
 
     #[test]
     fn offset_expands_in_direction_of_shift() {
@@ -614,6 +784,150 @@ mod offset_expansion_tests {
             "Offset expansion should be asymmetric and include the shift vector"
         );
     }
+
+    #[test]
+    fn morphology_expands_only_when_dilating() {
+        let dilate = FilterPrimitive::Morphology {
+            operator: MorphologyOperator::Dilate,
+            radius: 3.0,
+        };
+        assert_eq!(dilate.expansion_rect(), Rect::new(-3.0, -3.0, 3.0, 3.0));
+
+        let erode = FilterPrimitive::Morphology {
+            operator: MorphologyOperator::Erode,
+            radius: 3.0,
+        };
+        assert_eq!(erode.expansion_rect(), Rect::ZERO);
+    }
+
+    #[test]
+    fn convolve_matrix_expands_by_kernel_radius() {
+        let odd = FilterPrimitive::ConvolveMatrix {
+            kernel: kernels::sharpen_3x3(),
+        };
+        assert_eq!(odd.expansion_rect(), Rect::ZERO);
+        assert_eq!(odd.pixel_expansion_rect(), Rect::new(-1.0, -1.0, 1.0, 1.0));
+
+        // For even sizes, the target pixel is `size / 2`, so the kernel reaches
+        // further to the left and top of each pixel than to the right and bottom.
+        let even = FilterPrimitive::ConvolveMatrix {
+            kernel: ConvolutionKernel {
+                size: 4,
+                values: vec![1.0; 16],
+                ..kernels::gaussian_3x3()
+            },
+        };
+        assert_eq!(even.pixel_expansion_rect(), Rect::new(-1.0, -1.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn displacement_map_expands_by_half_scale() {
+        let p = FilterPrimitive::DisplacementMap {
+            scale: -10.0,
+            x_channel: ColorChannel::Red,
+            y_channel: ColorChannel::Alpha,
+        };
+        assert_eq!(p.expansion_rect(), Rect::new(-5.0, -5.0, 5.0, 5.0));
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::{
+        CompositeOperator, FilterGraph, FilterId, FilterInput, FilterInputs, FilterPrimitive,
+        FilterSource, kernels,
+    };
+    use crate::kurbo::{Affine, Rect};
+
+    #[test]
+    fn default_inputs_chain_primitives() {
+        let mut graph = FilterGraph::new();
+        let first = graph.add(FilterPrimitive::Offset { dx: 1.0, dy: 0.0 }, None);
+        let second = graph.add(
+            FilterPrimitive::Composite {
+                operator: CompositeOperator::Over,
+            },
+            None,
+        );
+
+        assert_eq!(
+            graph.inputs[usize::from(first.0)],
+            FilterInputs::single(FilterInput::Source(FilterSource::SourceGraphic))
+        );
+        assert_eq!(
+            graph.inputs[usize::from(second.0)],
+            FilterInputs::dual(FilterInput::Result(first), FilterInput::Result(first))
+        );
+    }
+
+    #[test]
+    fn expansion_accumulates_along_used_inputs() {
+        let mut graph = FilterGraph::new();
+        let shifted = graph.add(FilterPrimitive::Offset { dx: 2.0, dy: 0.0 }, None);
+        let shifted_twice = graph.add(FilterPrimitive::Offset { dx: 3.0, dy: 0.0 }, None);
+        let unrelated = graph.add(
+            FilterPrimitive::Offset { dx: -4.0, dy: 0.0 },
+            Some(FilterInputs::single(FilterInput::Source(
+                FilterSource::SourceGraphic,
+            ))),
+        );
+
+        graph.set_output(shifted_twice);
+        assert_eq!(
+            graph.bounds_expansion(&Affine::IDENTITY),
+            Rect::new(0.0, 0.0, 5.0, 0.0)
+        );
+
+        graph.set_output(unrelated);
+        assert_eq!(
+            graph.bounds_expansion(&Affine::IDENTITY),
+            Rect::new(-4.0, 0.0, 0.0, 0.0)
+        );
+
+        let merged = graph.add(
+            FilterPrimitive::Composite {
+                operator: CompositeOperator::Over,
+            },
+            Some(FilterInputs::dual(
+                FilterInput::Result(shifted),
+                FilterInput::Result(unrelated),
+            )),
+        );
+        graph.set_output(merged);
+        assert_eq!(
+            graph.bounds_expansion(&Affine::IDENTITY),
+            Rect::new(-4.0, 0.0, 2.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn convolve_matrix_expansion_does_not_scale() {
+        let mut graph = FilterGraph::new();
+        graph.add(FilterPrimitive::Offset { dx: 2.0, dy: 0.0 }, None);
+        let convolved = graph.add(
+            FilterPrimitive::ConvolveMatrix {
+                kernel: kernels::sharpen_3x3(),
+            },
+            None,
+        );
+        graph.set_output(convolved);
+
+        // The offset is in user space, while the kernel is applied to device pixels.
+        assert_eq!(
+            graph.bounds_expansion(&Affine::scale(3.0)),
+            Rect::new(-1.0, -1.0, 7.0, 1.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "added before them")]
+    fn forward_references_are_rejected() {
+        let mut graph = FilterGraph::new();
+        graph.add(
+            FilterPrimitive::Offset { dx: 1.0, dy: 1.0 },
+            Some(FilterInputs::single(FilterInput::Result(FilterId(3)))),
+        );
+    }
 }
 
 /// Unique identifier for a filter primitive in the graph.
@@ -855,6 +1169,30 @@ pub struct ConvolutionKernel {
     /// Whether to preserve the alpha channel unchanged.
     /// If true, convolution only applies to RGB; if false, it applies to RGBA.
     pub preserve_alpha: bool,
+    /// Edge mode for handling pixels of the kernel that lie outside of the input.
+    /// The default of `feConvolveMatrix` in SVG is `EdgeMode::Duplicate`.
+    pub edge_mode: EdgeMode,
+}
+
+impl ConvolutionKernel {
+    /// Whether the kernel is valid, i.e. it is not empty and has `size × size` values.
+    ///
+    /// Invalid kernels leave the input unchanged.
+    pub fn is_valid(&self) -> bool {
+        self.size > 0 && self.values.len() as u64 == u64::from(self.size) * u64::from(self.size)
+    }
+
+    /// The number of kernel cells before and after the target pixel along each axis.
+    ///
+    /// The target pixel is the center of the kernel, i.e. `size / 2`.
+    pub fn radii(&self) -> (u32, u32) {
+        if !self.is_valid() {
+            return (0, 0);
+        }
+
+        let target = self.size / 2;
+        (target, self.size - 1 - target)
+    }
 }
 
 /// Types of turbulence noise generation.
@@ -940,6 +1278,44 @@ pub enum TransferFunction {
     },
 }
 
+impl TransferFunction {
+    /// Apply the transfer function to an unpremultiplied channel value in [0, 1].
+    ///
+    /// The result is clamped to [0, 1]. Tables without any values behave like the
+    /// identity function, as required by the specification.
+    ///
+    /// See: <https://drafts.fxtf.org/filter-effects/#feComponentTransferElement>
+    pub fn apply(&self, c: f32) -> f32 {
+        let c = c.clamp(0.0, 1.0);
+
+        let result = match self {
+            Self::Identity => c,
+            Self::Table { values } => match values.len() {
+                0 => c,
+                1 => values[0],
+                n => {
+                    let segments = (n - 1) as f32;
+                    let k = ((c * segments) as usize).min(n - 2);
+                    let t = c * segments - k as f32;
+                    values[k] + t * (values[k + 1] - values[k])
+                }
+            },
+            Self::Discrete { values } => match values.len() {
+                0 => c,
+                n => values[((c * n as f32) as usize).min(n - 1)],
+            },
+            Self::Linear { slope, intercept } => slope * c + intercept,
+            Self::Gamma {
+                amplitude,
+                exponent,
+                offset,
+            } => amplitude * c.powf(*exponent) + offset,
+        };
+
+        result.clamp(0.0, 1.0)
+    }
+}
+
 /// Light source configurations for lighting effects.
 ///
 /// Defines different types of light sources used in diffuse and specular lighting
@@ -1001,7 +1377,13 @@ pub enum LightSource {
 ///
 /// These 4x5 matrices are used with the `ColorMatrix` filter primitive.
 /// Each row transforms a color channel: [R, G, B, A, offset].
+///
+/// The functions in this module build the matrices equivalent to the CSS filter
+/// functions, as defined in <https://drafts.fxtf.org/filter-effects/#ShorthandEquivalents>.
 pub mod matrices {
+    #[cfg(not(feature = "std"))]
+    use peniko::kurbo::common::FloatFuncs as _;
+
     /// Identity matrix (no change).
     pub const IDENTITY: [f32; 20] = [
         1.0, 0.0, 0.0, 0.0, 0.0, // Red
@@ -1018,6 +1400,16 @@ pub mod matrices {
         0.0, 0.0, 0.0, 1.0, 0.0, // Alpha = Alpha
     ];
 
+    /// Luminance to alpha conversion, as used by `feColorMatrix type="luminanceToAlpha"`.
+    ///
+    /// The color channels are set to black and alpha is set to the luminance of the color.
+    pub const LUMINANCE_TO_ALPHA: [f32; 20] = [
+        0.0, 0.0, 0.0, 0.0, 0.0, // Red
+        0.0, 0.0, 0.0, 0.0, 0.0, // Green
+        0.0, 0.0, 0.0, 0.0, 0.0, // Blue
+        0.2125, 0.7154, 0.0721, 0.0, 0.0, // Alpha
+    ];
+
     /// Grayscale conversion matrix using luminosity weights.
     pub const GRAYSCALE: [f32; 20] = [
         0.2126, 0.7152, 0.0722, 0.0, 0.0, // Red
@@ -1033,6 +1425,137 @@ pub mod matrices {
         0.272, 0.534, 0.131, 0.0, 0.0, // Blue
         0.0, 0.0, 0.0, 1.0, 0.0, // Alpha
     ];
+
+    /// Matrix for `brightness(amount)`: scales the color channels by `amount`.
+    pub fn brightness(amount: f32) -> [f32; 20] {
+        let a = amount.max(0.0);
+
+        [
+            a, 0.0, 0.0, 0.0, 0.0, // Red
+            0.0, a, 0.0, 0.0, 0.0, // Green
+            0.0, 0.0, a, 0.0, 0.0, // Blue
+            0.0, 0.0, 0.0, 1.0, 0.0, // Alpha
+        ]
+    }
+
+    /// Matrix for `contrast(amount)`: scales the color channels around 0.5.
+    pub fn contrast(amount: f32) -> [f32; 20] {
+        let a = amount.max(0.0);
+        let o = 0.5 - 0.5 * a;
+
+        [
+            a, 0.0, 0.0, 0.0, o, // Red
+            0.0, a, 0.0, 0.0, o, // Green
+            0.0, 0.0, a, 0.0, o, // Blue
+            0.0, 0.0, 0.0, 1.0, 0.0, // Alpha
+        ]
+    }
+
+    /// Matrix for `grayscale(amount)`, where `amount` is clamped to [0, 1].
+    pub fn grayscale(amount: f32) -> [f32; 20] {
+        let s = 1.0 - amount.clamp(0.0, 1.0);
+
+        rgb_matrix([
+            [
+                0.2126 + 0.7874 * s,
+                0.7152 - 0.7152 * s,
+                0.0722 - 0.0722 * s,
+            ],
+            [
+                0.2126 - 0.2126 * s,
+                0.7152 + 0.2848 * s,
+                0.0722 - 0.0722 * s,
+            ],
+            [
+                0.2126 - 0.2126 * s,
+                0.7152 - 0.7152 * s,
+                0.0722 + 0.9278 * s,
+            ],
+        ])
+    }
+
+    /// Matrix for `hue-rotate(angle)`, with `angle` in degrees.
+    pub fn hue_rotate(angle: f32) -> [f32; 20] {
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        rgb_matrix([
+            [
+                0.213 + cos * 0.787 - sin * 0.213,
+                0.715 - cos * 0.715 - sin * 0.715,
+                0.072 - cos * 0.072 + sin * 0.928,
+            ],
+            [
+                0.213 - cos * 0.213 + sin * 0.143,
+                0.715 + cos * 0.285 + sin * 0.140,
+                0.072 - cos * 0.072 - sin * 0.283,
+            ],
+            [
+                0.213 - cos * 0.213 - sin * 0.787,
+                0.715 - cos * 0.715 + sin * 0.715,
+                0.072 + cos * 0.928 + sin * 0.072,
+            ],
+        ])
+    }
+
+    /// Matrix for `invert(amount)`, where `amount` is clamped to [0, 1].
+    ///
+    /// This is the matrix form of the `[amount, 1 - amount]` transfer table.
+    pub fn invert(amount: f32) -> [f32; 20] {
+        let a = amount.clamp(0.0, 1.0);
+        let s = 1.0 - 2.0 * a;
+
+        [
+            s, 0.0, 0.0, 0.0, a, // Red
+            0.0, s, 0.0, 0.0, a, // Green
+            0.0, 0.0, s, 0.0, a, // Blue
+            0.0, 0.0, 0.0, 1.0, 0.0, // Alpha
+        ]
+    }
+
+    /// Matrix for `opacity(amount)`, where `amount` is clamped to [0, 1].
+    pub fn opacity(amount: f32) -> [f32; 20] {
+        let a = amount.clamp(0.0, 1.0);
+
+        [
+            1.0, 0.0, 0.0, 0.0, 0.0, // Red
+            0.0, 1.0, 0.0, 0.0, 0.0, // Green
+            0.0, 0.0, 1.0, 0.0, 0.0, // Blue
+            0.0, 0.0, 0.0, a, 0.0, // Alpha
+        ]
+    }
+
+    /// Matrix for `saturate(amount)`.
+    pub fn saturate(amount: f32) -> [f32; 20] {
+        let s = amount.max(0.0);
+
+        rgb_matrix([
+            [0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s],
+        ])
+    }
+
+    /// Matrix for `sepia(amount)`, where `amount` is clamped to [0, 1].
+    pub fn sepia(amount: f32) -> [f32; 20] {
+        let s = 1.0 - amount.clamp(0.0, 1.0);
+
+        rgb_matrix([
+            [0.393 + 0.607 * s, 0.769 - 0.769 * s, 0.189 - 0.189 * s],
+            [0.349 - 0.349 * s, 0.686 + 0.314 * s, 0.168 - 0.168 * s],
+            [0.272 - 0.272 * s, 0.534 - 0.534 * s, 0.131 + 0.869 * s],
+        ])
+    }
+
+    /// Build a matrix that only mixes the color channels and leaves alpha unchanged.
+    fn rgb_matrix(rows: [[f32; 3]; 3]) -> [f32; 20] {
+        let mut matrix = IDENTITY;
+
+        for (row, coefficients) in rows.iter().enumerate() {
+            matrix[row * 5..row * 5 + 3].copy_from_slice(coefficients);
+        }
+
+        matrix
+    }
 }
 
 /// Common convolution kernels.
@@ -1040,7 +1563,7 @@ pub mod matrices {
 /// These kernels are used with the `ConvolveMatrix` filter primitive
 /// for various image processing effects. All provided kernels are 3x3.
 pub mod kernels {
-    use super::ConvolutionKernel;
+    use super::{ConvolutionKernel, EdgeMode};
     use alloc::vec;
 
     /// 3x3 Gaussian blur kernel for basic smoothing.
@@ -1051,6 +1574,7 @@ pub mod kernels {
             divisor: 16.0,
             bias: 0.0,
             preserve_alpha: false,
+            edge_mode: EdgeMode::Duplicate,
         }
     }
 
@@ -1062,6 +1586,7 @@ pub mod kernels {
             divisor: 1.0,
             bias: 0.0,
             preserve_alpha: true,
+            edge_mode: EdgeMode::Duplicate,
         }
     }
 
@@ -1073,6 +1598,7 @@ pub mod kernels {
             divisor: 1.0,
             bias: 0.0,
             preserve_alpha: true,
+            edge_mode: EdgeMode::Duplicate,
         }
     }
 
@@ -1084,6 +1610,7 @@ pub mod kernels {
             divisor: 1.0,
             bias: 0.5,
             preserve_alpha: true,
+            edge_mode: EdgeMode::Duplicate,
         }
     }
 }
@@ -3,10 +3,12 @@
 
 //! Processing and drawing glyphs.
 
-use crate::kurbo::{Affine, BezPath, Vec2};
+use crate::kurbo::{Affine, BezPath, Join, PathEl, Shape, Stroke, StrokeOpts, Vec2};
 use crate::peniko::FontData;
 use alloc::boxed::Box;
+use alloc::collections::VecDeque;
 use alloc::vec::Vec;
+use core::cmp::Reverse;
 use core::fmt::{Debug, Formatter};
 use hashbrown::hash_map::{Entry, RawEntryMut};
 use hashbrown::{Equivalent, HashMap};
@@ -76,6 +78,8 @@ pub struct BitmapGlyph {
     pub pixmap: Pixmap,
     /// The rectangular area that should be filled with the bitmap when painting.
     pub area: Rect,
+    /// A key identifying the pixmap of the glyph, for caching it.
+    pub cache_key: GlyphCacheKey,
 }
 
 /// A glyph defined by a COLR glyph description.
@@ -95,6 +99,10 @@ pub struct ColorGlyph<'a> {
     pub pix_width: u16,
     /// The height of the pixmap/texture in pixels to which the glyph should be rendered to.
     pub pix_height: u16,
+    /// A key identifying the rendered representation of the glyph, for caching it.
+    ///
+    /// The key doesn't account for the context color the glyph is painted with.
+    pub cache_key: GlyphCacheKey,
 }
 
 impl Debug for ColorGlyph<'_> {
@@ -103,6 +111,20 @@ impl Debug for ColorGlyph<'_> {
     }
 }
 
+/// A key identifying the image of a bitmap or COLR glyph.
+///
+/// Two glyphs with the same key are rendered into the same image, so renderers can use the
+/// key to cache these images instead of rendering them each time the glyph is drawn.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlyphCacheKey {
+    font_id: u64,
+    font_index: u32,
+    glyph_id: u32,
+    /// The bits of the horizontal and vertical scale the glyph is rendered at.
+    scale_bits: [u64; 2],
+    coords: VarKey,
+}
+
 /// Trait for types that can render glyphs.
 pub trait GlyphRenderer {
     /// Fill glyphs with the current paint and fill rule.
@@ -140,6 +162,8 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
                 transform,
                 glyph_transform: None,
                 hint: true,
+                hinting: HintingSettings::default(),
+                synthesis: Synthesis::default(),
                 normalized_coords: &[],
             },
             renderer,
@@ -168,6 +192,47 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
         self
     }
 
+    /// Set the engine used for hinting.
+    ///
+    /// This has no effect if hinting is disabled.
+    pub fn hinting_engine(mut self, engine: HintingEngine) -> Self {
+        self.run.hinting.engine = engine;
+        self
+    }
+
+    /// Set the intended target of hinted glyph outlines.
+    ///
+    /// This has no effect if hinting is disabled.
+    pub fn hinting_target(mut self, target: HintingTarget) -> Self {
+        self.run.hinting.target = target;
+        self
+    }
+
+    /// Set the strength of synthetic bold, to simulate a bold font face.
+    ///
+    /// The outline of each glyph is expanded by `strength` times the font size on each side.
+    /// Values between 0.01 and 0.03 work well for most fonts. The default is 0, which disables
+    /// synthetic bold.
+    ///
+    /// This only applies to outline glyphs.
+    pub fn embolden(mut self, strength: f32) -> Self {
+        self.run.synthesis.embolden = strength;
+        self
+    }
+
+    /// Set the angle in degrees by which glyphs are skewed horizontally, to simulate an oblique
+    /// font face.
+    ///
+    /// Positive angles slant the glyphs to the right. The default is 0, which disables synthetic
+    /// oblique. Unlike a skew in the [glyph transform](Self::glyph_transform), the skew is
+    /// applied to the cached outlines, which also works if hinting is enabled.
+    ///
+    /// This only applies to outline glyphs.
+    pub fn skew(mut self, angle: f32) -> Self {
+        self.run.synthesis.skew = angle;
+        self
+    }
+
     /// Set normalized variation coordinates for variable fonts.
     pub fn normalized_coords(mut self, coords: &'a [NormalizedCoord]) -> Self {
         self.run.normalized_coords = bytemuck::cast_slice(coords);
@@ -232,6 +297,8 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
                     prepare_colr_glyph(
                         &font_ref,
                         glyph,
+                        self.run.font.data.id(),
+                        self.run.font.index,
                         self.run.font_size,
                         upem,
                         initial_transform,
@@ -242,6 +309,8 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
                     prepare_bitmap_glyph(
                         &bitmaps,
                         glyph,
+                        self.run.font.data.id(),
+                        self.run.font.index,
                         pixmap,
                         self.run.font_size,
                         upem,
@@ -263,6 +332,8 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
                         self.run.transform,
                         &outline,
                         hinting_instance,
+                        self.run.hinting,
+                        self.run.synthesis,
                         normalized_coords,
                     )
                 };
@@ -275,6 +346,7 @@ impl<'a, T: GlyphRenderer + 'a> GlyphRunBuilder<'a, T> {
             render_glyph(self.renderer, prepared_glyph);
         }
 
+        outline_cache.enforce_budget();
         self.renderer.restore_glyph_caches(GlyphCaches {
             outline_cache,
             hinting_cache,
@@ -294,6 +366,8 @@ fn prepare_outline_glyph<'a>(
     run_transform: Affine,
     outline_glyph: &skrifa::outline::OutlineGlyph<'a>,
     hinting_instance: Option<&HintingInstance>,
+    hinting: HintingSettings,
+    synthesis: Synthesis,
     normalized_coords: &[skrifa::instance::NormalizedCoord],
 ) -> (GlyphType<'a>, Affine) {
     let path = outline_cache.get_or_insert(
@@ -304,6 +378,8 @@ fn prepare_outline_glyph<'a>(
         VarLookupKey(normalized_coords),
         outline_glyph,
         hinting_instance,
+        hinting,
+        synthesis,
     );
 
     // Calculate the global glyph translation based on the glyph's local position within
@@ -340,6 +416,8 @@ fn prepare_outline_glyph<'a>(
 fn prepare_bitmap_glyph<'a>(
     bitmaps: &BitmapStrikes<'_>,
     glyph: Glyph,
+    font_id: u64,
+    font_index: u32,
     pixmap: Pixmap,
     font_size: f32,
     upem: f32,
@@ -395,12 +473,30 @@ fn prepare_bitmap_glyph<'a>(
         f64::from(pixmap.height()),
     );
 
-    (GlyphType::Bitmap(BitmapGlyph { pixmap, area }), transform)
+    // The bitmap only depends on the strike, which is selected based on the font size.
+    let cache_key = GlyphCacheKey {
+        font_id,
+        font_index,
+        glyph_id: glyph.id,
+        scale_bits: [f64::from(font_size).to_bits(); 2],
+        coords: VarKey::new(),
+    };
+
+    (
+        GlyphType::Bitmap(BitmapGlyph {
+            pixmap,
+            area,
+            cache_key,
+        }),
+        transform,
+    )
 }
 
 fn prepare_colr_glyph<'a>(
     font_ref: &'a FontRef<'a>,
     glyph: Glyph,
+    font_id: u64,
+    font_index: u32,
     font_size: f32,
     upem: f32,
     run_transform: Affine,
@@ -501,6 +597,13 @@ fn prepare_colr_glyph<'a>(
             pix_width,
             pix_height,
             draw_transform,
+            cache_key: GlyphCacheKey {
+                font_id,
+                font_index,
+                glyph_id: glyph.id,
+                scale_bits: [scale_factor_x.to_bits(), scale_factor_y.to_bits()],
+                coords: normalized_coords.to_vec(),
+            },
         })),
         glyph_transform,
     )
@@ -531,6 +634,10 @@ struct GlyphRun<'a> {
     normalized_coords: &'a [skrifa::instance::NormalizedCoord],
     /// Controls whether font hinting is enabled.
     hint: bool,
+    /// The settings used for hinting.
+    hinting: HintingSettings,
+    /// The settings used for synthesizing bold and oblique glyphs.
+    synthesis: Synthesis,
 }
 
 struct PreparedGlyphRun<'a> {
@@ -587,6 +694,7 @@ fn prepare_glyph_run<'a>(
             outlines,
             size,
             coords: run.normalized_coords,
+            hinting: run.hinting,
         });
 
         PreparedGlyphRun {
@@ -605,16 +713,221 @@ fn prepare_glyph_run<'a>(
     }
 }
 
-// TODO: Although these are sane defaults, we might want to make them
-// configurable.
-const HINTING_OPTIONS: HintingOptions = HintingOptions {
-    engine: skrifa::outline::Engine::AutoFallback,
-    target: skrifa::outline::Target::Smooth {
-        mode: skrifa::outline::SmoothMode::Lcd,
-        symmetric_rendering: false,
-        preserve_linear_metrics: true,
+/// The engine used for hinting glyph outlines.
+///
+/// Equivalent to [`skrifa::outline::Engine`], but defined in Vello so that Skrifa is not part
+/// of Vello's public API.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub enum HintingEngine {
+    /// The TrueType or PostScript interpreter, which executes the hinting instructions
+    /// embedded in the font.
+    Interpreter,
+    /// The automatic hinter, which adjusts the outlines without using the hinting
+    /// instructions of the font.
+    Auto,
+    /// Use the interpreter for PostScript fonts and TrueType fonts that contain hinting
+    /// instructions, and the automatic hinter otherwise.
+    #[default]
+    AutoFallback,
+}
+
+impl HintingEngine {
+    fn to_skrifa(self) -> skrifa::outline::Engine {
+        match self {
+            Self::Interpreter => skrifa::outline::Engine::Interpreter,
+            Self::Auto => skrifa::outline::Engine::Auto(None),
+            Self::AutoFallback => skrifa::outline::Engine::AutoFallback,
+        }
+    }
+}
+
+/// The intended target of hinted glyph outlines.
+///
+/// Equivalent to [`skrifa::outline::Target`], but defined in Vello so that Skrifa is not part
+/// of Vello's public API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HintingTarget {
+    /// Strong hinting that should only be used for aliased, monochrome rendering.
+    Mono,
+    /// Hinting that is suitable for anti-aliased rendering.
+    Smooth {
+        /// The basic mode for smooth hinting.
+        mode: SmoothHintingMode,
+        /// Whether TrueType hinting instructions may assume that the outline will be
+        /// rasterized with supersampling in the vertical direction.
+        symmetric_rendering: bool,
+        /// Whether to prevent adjustments of the outline in the horizontal direction, which
+        /// preserves the spacing between glyphs.
+        preserve_linear_metrics: bool,
     },
-};
+}
+
+impl Default for HintingTarget {
+    /// Smooth hinting optimized for horizontal LCD layouts, preserving linear metrics.
+    fn default() -> Self {
+        Self::Smooth {
+            mode: SmoothHintingMode::Lcd,
+            symmetric_rendering: false,
+            preserve_linear_metrics: true,
+        }
+    }
+}
+
+impl HintingTarget {
+    fn to_skrifa(self) -> skrifa::outline::Target {
+        match self {
+            Self::Mono => skrifa::outline::Target::Mono,
+            Self::Smooth {
+                mode,
+                symmetric_rendering,
+                preserve_linear_metrics,
+            } => skrifa::outline::Target::Smooth {
+                mode: mode.to_skrifa(),
+                symmetric_rendering,
+                preserve_linear_metrics,
+            },
+        }
+    }
+}
+
+/// The mode for smooth hinting.
+///
+/// Equivalent to [`skrifa::outline::SmoothMode`], but defined in Vello so that Skrifa is not
+/// part of Vello's public API.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub enum SmoothHintingMode {
+    /// The standard smooth hinting mode.
+    #[default]
+    Normal,
+    /// Hinting with less aggressive adjustments in the horizontal direction.
+    Light,
+    /// Hinting optimized for subpixel rendering with horizontal LCD layouts.
+    Lcd,
+    /// Hinting optimized for subpixel rendering with vertical LCD layouts.
+    VerticalLcd,
+}
+
+impl SmoothHintingMode {
+    fn to_skrifa(self) -> skrifa::outline::SmoothMode {
+        match self {
+            Self::Normal => skrifa::outline::SmoothMode::Normal,
+            Self::Light => skrifa::outline::SmoothMode::Light,
+            Self::Lcd => skrifa::outline::SmoothMode::Lcd,
+            Self::VerticalLcd => skrifa::outline::SmoothMode::VerticalLcd,
+        }
+    }
+}
+
+/// The settings used for hinting a glyph run.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+struct HintingSettings {
+    engine: HintingEngine,
+    target: HintingTarget,
+}
+
+impl HintingSettings {
+    fn options(self) -> HintingOptions {
+        HintingOptions {
+            engine: self.engine.to_skrifa(),
+            target: self.target.to_skrifa(),
+        }
+    }
+}
+
+/// Settings for synthesizing bold and oblique glyphs from regular outlines.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+struct Synthesis {
+    /// The amount by which the outline is expanded, relative to the font size.
+    embolden: f32,
+    /// The horizontal skew angle in degrees.
+    skew: f32,
+}
+
+impl Synthesis {
+    fn to_bits(self) -> [u32; 2] {
+        [self.embolden.to_bits(), self.skew.to_bits()]
+    }
+
+    /// Applies the synthesis to an outline drawn at the given size.
+    fn apply(self, path: &mut BezPath, size: Size) {
+        if self.embolden != 0.0 {
+            let amount = self.embolden * size.ppem().unwrap_or(1.0);
+            *path = embolden_path(path, f64::from(amount));
+        }
+
+        if self.skew != 0.0 {
+            let skew = f64::from(self.skew).to_radians().tan();
+            // Font outlines are y-up, so a positive skew slants the glyph to the right.
+            path.apply_affine(Affine::skew(skew, 0.0));
+        }
+    }
+}
+
+/// Expands the area covered by a filled outline by `amount` in all directions.
+///
+/// Each contour is stroked with a width of twice the amount, and only the offset curve on the
+/// side facing away from the filled area is kept. This means outer contours grow while counters
+/// shrink, and the result can still be filled with the non-zero fill rule.
+fn embolden_path(path: &BezPath, amount: f64) -> BezPath {
+    // The outline is in pixels at the size it is drawn at, so this is precise enough even if
+    // the glyph is scaled up afterwards.
+    const TOLERANCE: f64 = 0.01;
+
+    let contours = subpaths(path);
+    let areas = contours.iter().map(Shape::area).collect::<Vec<_>>();
+    // The sign of the total area tells us the orientation of outer contours.
+    let total_area = areas.iter().sum::<f64>();
+
+    if total_area == 0.0 {
+        return path.clone();
+    }
+
+    let style = Stroke::new(2.0 * amount).with_join(Join::Miter);
+    let mut emboldened = BezPath::new();
+
+    for (contour, area) in contours.iter().zip(areas) {
+        let stroked = crate::kurbo::stroke(contour, &style, &StrokeOpts::default(), TOLERANCE);
+
+        // For closed contours, the stroke consists of one offset curve on each side.
+        let Ok([first, second]) = <[BezPath; 2]>::try_from(subpaths(&stroked)) else {
+            // Keep degenerate or open contours unchanged.
+            emboldened.extend(contour);
+            continue;
+        };
+
+        let is_outer = area.signum() == total_area.signum();
+        let first_is_larger = first.area().abs() > second.area().abs();
+        let offset = if first_is_larger == is_outer {
+            first
+        } else {
+            second
+        };
+
+        // Keep the orientation of the original contour, so that the winding is preserved.
+        if offset.area().signum() == area.signum() {
+            emboldened.extend(offset);
+        } else {
+            emboldened.extend(offset.reverse_subpaths());
+        }
+    }
+
+    emboldened
+}
+
+/// Splits a path into its subpaths.
+fn subpaths(path: &BezPath) -> Vec<BezPath> {
+    let mut subpaths = Vec::new();
+
+    for el in path.elements() {
+        if matches!(el, PathEl::MoveTo(_)) || subpaths.is_empty() {
+            subpaths.push(BezPath::new());
+        }
+
+        subpaths.last_mut().unwrap().push(*el);
+    }
+
+    subpaths
+}
 
 #[derive(Clone, Default)]
 pub(crate) struct OutlinePath(pub(crate) BezPath);
@@ -623,6 +936,11 @@ impl OutlinePath {
     pub(crate) fn new() -> Self {
         Self(BezPath::new())
     }
+
+    /// Returns the number of bytes used by the path elements.
+    fn byte_size(&self) -> usize {
+        size_of_val(self.0.elements())
+    }
 }
 
 // Note that we flip the y-axis to match our coordinate system.
@@ -673,7 +991,6 @@ mod tests {
 }
 
 /// Caches used for glyph rendering.
-// TODO: Consider capturing cache performance metrics like hit rate, etc.
 #[derive(Debug, Default)]
 pub struct GlyphCaches {
     outline_cache: OutlineCache,
@@ -686,7 +1003,16 @@ impl GlyphCaches {
         Self::default()
     }
 
+    /// Creates a new `GlyphCaches` instance with the given budget.
+    pub fn with_budget(budget: GlyphCacheBudget) -> Self {
+        let mut caches = Self::default();
+        caches.set_budget(budget);
+        caches
+    }
+
     /// Clears the glyph caches.
+    ///
+    /// This does not reset the statistics, see [`GlyphCaches::reset_stats`] for that.
     pub fn clear(&mut self) {
         self.outline_cache.clear();
         self.hinting_cache.clear();
@@ -698,6 +1024,123 @@ impl GlyphCaches {
     pub fn maintain(&mut self) {
         self.outline_cache.maintain();
     }
+
+    /// Returns the budget of the glyph caches.
+    pub fn budget(&self) -> GlyphCacheBudget {
+        GlyphCacheBudget {
+            max_outlines: self.outline_cache.max_entries,
+            max_outline_bytes: self.outline_cache.max_bytes,
+            max_hint_instances: self.hinting_cache.max_instances,
+        }
+    }
+
+    /// Sets the budget of the glyph caches.
+    ///
+    /// If the caches currently exceed the new budget, the least recently used entries
+    /// are evicted immediately.
+    pub fn set_budget(&mut self, budget: GlyphCacheBudget) {
+        self.outline_cache.max_entries = budget.max_outlines;
+        self.outline_cache.max_bytes = budget.max_outline_bytes;
+        self.outline_cache.enforce_budget();
+        self.hinting_cache
+            .set_max_instances(budget.max_hint_instances.max(1));
+    }
+
+    /// Returns statistics about the usage of the glyph caches.
+    pub fn stats(&self) -> GlyphCacheStats {
+        let outline_usage = &self.outline_cache.usage;
+        let hint_usage = &self.hinting_cache.usage;
+
+        GlyphCacheStats {
+            outline_hits: outline_usage.hits,
+            outline_misses: outline_usage.misses,
+            outline_evictions: outline_usage.evictions,
+            outline_entries: self.outline_cache.cached_count,
+            outline_bytes: outline_usage.resident_bytes,
+            hint_hits: hint_usage.hits,
+            hint_misses: hint_usage.misses,
+            hint_evictions: hint_usage.evictions,
+            hint_instances: self.hinting_cache.len(),
+        }
+    }
+
+    /// Resets the hit, miss and eviction counters of the glyph caches.
+    pub fn reset_stats(&mut self) {
+        self.outline_cache.usage.reset_counters();
+        self.hinting_cache.usage.reset_counters();
+    }
+}
+
+/// Limits for the size of [`GlyphCaches`].
+///
+/// Outlines that exceed the budget are evicted in least recently used order. The outline
+/// budget is enforced after each glyph run and in [`GlyphCaches::maintain`], so it can be
+/// exceeded temporarily while a single glyph run is being rendered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GlyphCacheBudget {
+    /// The maximum number of cached glyph outlines, or `None` for no limit.
+    pub max_outlines: Option<usize>,
+    /// The maximum number of bytes used by cached glyph outlines, or `None` for no limit.
+    pub max_outline_bytes: Option<usize>,
+    /// The maximum number of cached hinting instances per outline format.
+    ///
+    /// At least one hinting instance is always kept.
+    pub max_hint_instances: usize,
+}
+
+impl Default for GlyphCacheBudget {
+    fn default() -> Self {
+        Self {
+            max_outlines: None,
+            max_outline_bytes: None,
+            max_hint_instances: MAX_CACHED_HINT_INSTANCES,
+        }
+    }
+}
+
+/// Statistics about the usage of [`GlyphCaches`].
+///
+/// The hit, miss and eviction counters accumulate until [`GlyphCaches::reset_stats`] is called.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphCacheStats {
+    /// The number of glyph outlines that were found in the cache.
+    pub outline_hits: u64,
+    /// The number of glyph outlines that had to be generated.
+    pub outline_misses: u64,
+    /// The number of glyph outlines that were evicted from the cache.
+    pub outline_evictions: u64,
+    /// The number of glyph outlines currently in the cache.
+    pub outline_entries: usize,
+    /// The number of bytes used by the glyph outlines currently in the cache.
+    pub outline_bytes: usize,
+    /// The number of hinting instances that were found in the cache.
+    pub hint_hits: u64,
+    /// The number of hinting instances that had to be created or reconfigured.
+    pub hint_misses: u64,
+    /// The number of hinting instances that were replaced by another one.
+    pub hint_evictions: u64,
+    /// The number of hinting instances currently in the cache.
+    pub hint_instances: usize,
+}
+
+/// Usage counters of a glyph cache.
+#[derive(Debug, Default)]
+struct CacheUsage {
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    /// The number of bytes held by the cache entries.
+    resident_bytes: usize,
+    /// Incremented on each access, used to determine the least recently used entries.
+    tick: u64,
+}
+
+impl CacheUsage {
+    fn reset_counters(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+        self.evictions = 0;
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
@@ -706,17 +1149,52 @@ struct OutlineKey {
     font_index: u32,
     glyph_id: u32,
     size_bits: u32,
-    hint: bool,
+    /// The settings the outline was hinted with, if it was hinted.
+    hinting: Option<HintingSettings>,
+    /// The bits of the synthesis settings the outline was generated with.
+    synthesis_bits: [u32; 2],
 }
 
 struct OutlineEntry {
     path: OutlinePath,
     serial: u32,
+    /// The tick of the last access, see [`CacheUsage::tick`].
+    last_used: u64,
 }
 
 impl OutlineEntry {
-    const fn new(path: OutlinePath, serial: u32) -> Self {
-        Self { path, serial }
+    const fn new(path: OutlinePath, serial: u32, last_used: u64) -> Self {
+        Self {
+            path,
+            serial,
+            last_used,
+        }
+    }
+}
+
+/// The outlines cached for a single variable font instance.
+struct VariableOutlines {
+    /// Identifies the instance in [`LruRecord`]s, so that they don't need to store its coordinates.
+    id: u64,
+    entries: HashMap<OutlineKey, OutlineEntry>,
+}
+
+/// An access to an entry of the [`OutlineCache`], in the order of [`OutlineCache::lru`].
+#[derive(Debug)]
+struct LruRecord {
+    /// The tick of the access, see [`CacheUsage::tick`].
+    tick: u64,
+    /// The [`VariableOutlines::id`] of the entry, or `None` if it is in the static map.
+    var_id: Option<u64>,
+    key: OutlineKey,
+}
+
+impl LruRecord {
+    /// Whether this is the most recent access of its entry in `entries`.
+    fn is_current(&self, entries: &HashMap<OutlineKey, OutlineEntry>) -> bool {
+        entries
+            .get(&self.key)
+            .is_some_and(|entry| entry.last_used == self.tick)
     }
 }
 
@@ -726,10 +1204,21 @@ impl OutlineEntry {
 struct OutlineCache {
     free_list: Vec<OutlinePath>,
     static_map: HashMap<OutlineKey, OutlineEntry>,
-    variable_map: HashMap<VarKey, HashMap<OutlineKey, OutlineEntry>>,
+    variable_map: HashMap<VarKey, VariableOutlines>,
+    /// The coordinates of each variable font instance in `variable_map`, by its id.
+    var_keys: HashMap<u64, VarKey>,
+    next_var_id: u64,
+    /// The accesses of entries, from least to most recently used.
+    ///
+    /// An entry which was accessed again leaves a stale record behind, which is skipped
+    /// when evicting, and dropped once there are too many of them.
+    lru: VecDeque<LruRecord>,
     cached_count: usize,
     serial: u32,
     last_prune_serial: u32,
+    usage: CacheUsage,
+    max_entries: Option<usize>,
+    max_bytes: Option<usize>,
 }
 
 impl Debug for OutlineCache {
@@ -738,9 +1227,15 @@ impl Debug for OutlineCache {
             .field("free_list", &self.free_list.len())
             .field("static_map", &self.static_map.len())
             .field("variable_map", &self.variable_map.len())
+            .field("var_keys", &self.var_keys.len())
+            .field("next_var_id", &self.next_var_id)
+            .field("lru", &self.lru.len())
             .field("cached_count", &self.cached_count)
             .field("serial", &self.serial)
             .field("last_prune_serial", &self.last_prune_serial)
+            .field("usage", &self.usage)
+            .field("max_entries", &self.max_entries)
+            .field("max_bytes", &self.max_bytes)
             .finish()
     }
 }
@@ -753,43 +1248,108 @@ impl OutlineCache {
         const PRUNE_FREQUENCY: u32 = 64;
         // Always prune if the cached count is greater than this value
         const CACHED_COUNT_THRESHOLD: usize = 256;
-        // Number of encoding buffers we'll keep on the free list
-        const MAX_FREE_LIST_SIZE: usize = 128;
 
-        let free_list = &mut self.free_list;
         let serial = self.serial;
         self.serial += 1;
         // Don't iterate over the whole cache every frame
         if serial - self.last_prune_serial < PRUNE_FREQUENCY
             && self.cached_count < CACHED_COUNT_THRESHOLD
         {
+            self.enforce_budget();
             return;
         }
         self.last_prune_serial = serial;
-        self.static_map.retain(|_, entry| {
-            if serial - entry.serial > MAX_ENTRY_AGE {
-                if free_list.len() < MAX_FREE_LIST_SIZE {
-                    free_list.push(core::mem::take(&mut entry.path));
+        self.evict(|entry| serial - entry.serial > MAX_ENTRY_AGE);
+        self.enforce_budget();
+    }
+
+    /// Evicts the least recently used entries until the cache fits into its budget.
+    fn enforce_budget(&mut self) {
+        let max_entries = self.max_entries.unwrap_or(usize::MAX);
+        let max_bytes = self.max_bytes.unwrap_or(usize::MAX);
+
+        while self.cached_count > max_entries || self.usage.resident_bytes > max_bytes {
+            let Some(record) = self.lru.pop_front() else {
+                break;
+            };
+
+            let entry = match record.var_id {
+                None => take_current(&mut self.static_map, &record),
+                Some(id) => {
+                    let Some(var_key) = self.var_keys.get(&id) else {
+                        continue;
+                    };
+                    let outlines = self.variable_map.get_mut(var_key).unwrap();
+                    let entry = take_current(&mut outlines.entries, &record);
+                    if outlines.entries.is_empty() {
+                        self.variable_map.remove(var_key);
+                        self.var_keys.remove(&id);
+                    }
+                    entry
                 }
-                self.cached_count -= 1;
-                false
-            } else {
-                true
+            };
+
+            if let Some(entry) = entry {
+                release_entry(
+                    &mut self.free_list,
+                    &mut self.usage,
+                    &mut self.cached_count,
+                    entry.path,
+                );
             }
+        }
+
+        self.compact_lru();
+    }
+
+    /// Drops stale records from the LRU queue once they outnumber the cached entries, so that
+    /// the queue stays proportional to the size of the cache.
+    fn compact_lru(&mut self) {
+        // Avoid compacting small queues over and over again.
+        const MIN_STALE_RECORDS: usize = 256;
+
+        if self.lru.len() <= 2 * self.cached_count + MIN_STALE_RECORDS {
+            return;
+        }
+
+        let mut lru = core::mem::take(&mut self.lru);
+        lru.retain(|record| match record.var_id {
+            None => record.is_current(&self.static_map),
+            Some(id) => self
+                .var_keys
+                .get(&id)
+                .is_some_and(|var_key| record.is_current(&self.variable_map[var_key].entries)),
         });
-        self.variable_map.retain(|_, map| {
-            map.retain(|_, entry| {
-                if serial - entry.serial > MAX_ENTRY_AGE {
-                    if free_list.len() < MAX_FREE_LIST_SIZE {
-                        free_list.push(core::mem::take(&mut entry.path));
-                    }
-                    self.cached_count -= 1;
-                    false
-                } else {
-                    true
-                }
-            });
-            !map.is_empty()
+        self.lru = lru;
+    }
+
+    /// Evicts all entries for which `predicate` returns true.
+    ///
+    /// The records of the evicted entries in the LRU queue become stale.
+    fn evict(&mut self, predicate: impl Fn(&OutlineEntry) -> bool) {
+        let free_list = &mut self.free_list;
+        let usage = &mut self.usage;
+        let cached_count = &mut self.cached_count;
+        let var_keys = &mut self.var_keys;
+        let mut evict_entry = |entry: &mut OutlineEntry| {
+            if !predicate(entry) {
+                return false;
+            }
+
+            let path = core::mem::take(&mut entry.path);
+            release_entry(free_list, usage, cached_count, path);
+
+            true
+        };
+
+        self.static_map.retain(|_, entry| !evict_entry(entry));
+        self.variable_map.retain(|_, outlines| {
+            outlines.entries.retain(|_, entry| !evict_entry(entry));
+            if outlines.entries.is_empty() {
+                var_keys.remove(&outlines.id);
+                return false;
+            }
+            true
         });
     }
 
@@ -797,38 +1357,90 @@ impl OutlineCache {
         self.free_list.clear();
         self.static_map.clear();
         self.variable_map.clear();
+        self.var_keys.clear();
+        self.lru.clear();
         self.cached_count = 0;
         self.serial = 0;
         self.last_prune_serial = 0;
+        self.usage.resident_bytes = 0;
+        self.usage.tick = 0;
+    }
+}
+
+/// Removes the entry of `record` from `entries`, if `record` is its most recent access.
+fn take_current(
+    entries: &mut HashMap<OutlineKey, OutlineEntry>,
+    record: &LruRecord,
+) -> Option<OutlineEntry> {
+    match entries.entry(record.key) {
+        Entry::Occupied(entry) if entry.get().last_used == record.tick => Some(entry.remove()),
+        _ => None,
+    }
+}
+
+/// Updates the accounting of the outline cache for an evicted entry, and keeps its `path`
+/// for reuse.
+fn release_entry(
+    free_list: &mut Vec<OutlinePath>,
+    usage: &mut CacheUsage,
+    cached_count: &mut usize,
+    path: OutlinePath,
+) {
+    // Number of encoding buffers we'll keep on the free list
+    const MAX_FREE_LIST_SIZE: usize = 128;
+
+    usage.resident_bytes -= path.byte_size();
+    usage.evictions += 1;
+    *cached_count -= 1;
+
+    if free_list.len() < MAX_FREE_LIST_SIZE {
+        free_list.push(path);
     }
 }
 
 struct OutlineCacheSession<'a> {
     map: &'a mut HashMap<OutlineKey, OutlineEntry>,
+    /// The [`VariableOutlines::id`] of `map`, or `None` if it is the static map.
+    var_id: Option<u64>,
+    lru: &'a mut VecDeque<LruRecord>,
     free_list: &'a mut Vec<OutlinePath>,
     serial: u32,
     cached_count: &'a mut usize,
+    usage: &'a mut CacheUsage,
 }
 
 impl<'a> OutlineCacheSession<'a> {
     fn new(outline_cache: &'a mut OutlineCache, var_key: VarLookupKey<'_>) -> Self {
-        let map = if var_key.0.is_empty() {
-            &mut outline_cache.static_map
+        let (map, var_id) = if var_key.0.is_empty() {
+            (&mut outline_cache.static_map, None)
         } else {
-            match outline_cache
+            let outlines = match outline_cache
                 .variable_map
                 .raw_entry_mut()
                 .from_key(&var_key)
             {
                 RawEntryMut::Occupied(entry) => entry.into_mut(),
-                RawEntryMut::Vacant(entry) => entry.insert(var_key.into(), HashMap::new()).1,
-            }
+                RawEntryMut::Vacant(entry) => {
+                    let id = outline_cache.next_var_id;
+                    outline_cache.next_var_id += 1;
+                    outline_cache.var_keys.insert(id, var_key.into());
+                    let outlines = VariableOutlines {
+                        id,
+                        entries: HashMap::new(),
+                    };
+                    entry.insert(var_key.into(), outlines).1
+                }
+            };
+            (&mut outlines.entries, Some(outlines.id))
         };
         Self {
             map,
+            var_id,
+            lru: &mut outline_cache.lru,
             free_list: &mut outline_cache.free_list,
             serial: outline_cache.serial,
             cached_count: &mut outline_cache.cached_count,
+            usage: &mut outline_cache.usage,
         }
     }
 
@@ -841,18 +1453,30 @@ impl<'a> OutlineCacheSession<'a> {
         var_key: VarLookupKey<'_>,
         outline_glyph: &skrifa::outline::OutlineGlyph<'_>,
         hinting_instance: Option<&HintingInstance>,
+        hinting: HintingSettings,
+        synthesis: Synthesis,
     ) -> &OutlinePath {
         let key = OutlineKey {
             glyph_id,
             font_id,
             font_index,
             size_bits: size.ppem().unwrap().to_bits(),
-            hint: hinting_instance.is_some(),
+            hinting: hinting_instance.map(|_| hinting),
+            synthesis_bits: synthesis.to_bits(),
         };
 
+        self.usage.tick += 1;
+        self.lru.push_back(LruRecord {
+            tick: self.usage.tick,
+            var_id: self.var_id,
+            key,
+        });
+
         match self.map.entry(key) {
             Entry::Occupied(mut entry) => {
+                self.usage.hits += 1;
                 entry.get_mut().serial = self.serial;
+                entry.get_mut().last_used = self.usage.tick;
                 &entry.into_mut().path
             }
             Entry::Vacant(entry) => {
@@ -866,8 +1490,12 @@ impl<'a> OutlineCacheSession<'a> {
 
                 path.0.truncate(0);
                 outline_glyph.draw(draw_settings, &mut path).unwrap();
+                synthesis.apply(&mut path.0, size);
 
-                let entry = entry.insert(OutlineEntry::new(path, self.serial));
+                self.usage.misses += 1;
+                self.usage.resident_bytes += path.byte_size();
+
+                let entry = entry.insert(OutlineEntry::new(path, self.serial, self.usage.tick));
                 *self.cached_count += 1;
                 &entry.path
             }
@@ -894,6 +1522,8 @@ impl From<VarLookupKey<'_>> for VarKey {
     }
 }
 
+/// The default maximum number of cached hinting instances per outline format.
+///
 /// We keep this small to enable a simple LRU cache with a linear
 /// search. Regenerating hinting data is low to medium cost so it's fine
 /// to redo it occasionally.
@@ -905,24 +1535,44 @@ struct HintKey<'a> {
     outlines: &'a OutlineGlyphCollection<'a>,
     size: Size,
     coords: &'a [skrifa::instance::NormalizedCoord],
+    hinting: HintingSettings,
 }
 
 impl HintKey<'_> {
     fn instance(&self) -> Option<HintingInstance> {
-        HintingInstance::new(self.outlines, self.size, self.coords, HINTING_OPTIONS).ok()
+        HintingInstance::new(
+            self.outlines,
+            self.size,
+            self.coords,
+            self.hinting.options(),
+        )
+        .ok()
     }
 }
 
 /// LRU cache for hinting instances.
 ///
 /// Heavily inspired by `vello_encoding::glyph_cache`.
-#[derive(Default)]
 struct HintCache {
     // Split caches for glyf/cff because the instance type can reuse
     // internal memory when reconfigured for the same format.
     glyf_entries: Vec<HintEntry>,
     cff_entries: Vec<HintEntry>,
     serial: u64,
+    usage: CacheUsage,
+    max_instances: usize,
+}
+
+impl Default for HintCache {
+    fn default() -> Self {
+        Self {
+            glyf_entries: Vec::new(),
+            cff_entries: Vec::new(),
+            serial: 0,
+            usage: CacheUsage::default(),
+            max_instances: MAX_CACHED_HINT_INSTANCES,
+        }
+    }
 }
 
 impl Debug for HintCache {
@@ -931,6 +1581,8 @@ impl Debug for HintCache {
             .field("glyf_entries", &self.glyf_entries.len())
             .field("cff_entries", &self.cff_entries.len())
             .field("serial", &self.serial)
+            .field("usage", &self.usage)
+            .field("max_instances", &self.max_instances)
             .finish()
     }
 }
@@ -941,21 +1593,45 @@ impl HintCache {
             OutlineGlyphFormat::Glyf => &mut self.glyf_entries,
             OutlineGlyphFormat::Cff | OutlineGlyphFormat::Cff2 => &mut self.cff_entries,
         };
-        let (entry_ix, is_current) = find_hint_entry(entries, key)?;
+        let (entry_ix, is_current, is_new) = find_hint_entry(entries, key, self.max_instances)?;
         let entry = entries.get_mut(entry_ix)?;
         self.serial += 1;
         entry.serial = self.serial;
+        if is_current && !is_new {
+            self.usage.hits += 1;
+        } else {
+            self.usage.misses += 1;
+        }
         if !is_current {
+            self.usage.evictions += 1;
             entry.font_id = key.font_id;
             entry.font_index = key.font_index;
+            entry.hinting = key.hinting;
             entry
                 .instance
-                .reconfigure(key.outlines, key.size, key.coords, HINTING_OPTIONS)
+                .reconfigure(key.outlines, key.size, key.coords, key.hinting.options())
                 .ok()?;
         }
         Some(&entry.instance)
     }
 
+    fn set_max_instances(&mut self, max_instances: usize) {
+        self.max_instances = max_instances;
+
+        for entries in [&mut self.glyf_entries, &mut self.cff_entries] {
+            if entries.len() > max_instances {
+                // Keep the most recently used entries.
+                entries.sort_unstable_by_key(|entry| Reverse(entry.serial));
+                self.usage.evictions += (entries.len() - max_instances) as u64;
+                entries.truncate(max_instances);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.glyf_entries.len() + self.cff_entries.len()
+    }
+
     fn clear(&mut self) {
         self.glyf_entries.clear();
         self.cff_entries.clear();
@@ -966,11 +1642,18 @@ impl HintCache {
 struct HintEntry {
     font_id: u64,
     font_index: u32,
+    hinting: HintingSettings,
     instance: HintingInstance,
     serial: u64,
 }
 
-fn find_hint_entry(entries: &mut Vec<HintEntry>, key: &HintKey<'_>) -> Option<(usize, bool)> {
+/// Returns the index of the entry to use for `key`, whether it already matches the key and
+/// whether it was newly created.
+fn find_hint_entry(
+    entries: &mut Vec<HintEntry>,
+    key: &HintKey<'_>,
+    max_instances: usize,
+) -> Option<(usize, bool, bool)> {
     let mut found_serial = u64::MAX;
     let mut found_index = 0;
     for (ix, entry) in entries.iter().enumerate() {
@@ -978,26 +1661,28 @@ fn find_hint_entry(entries: &mut Vec<HintEntry>, key: &HintKey<'_>) -> Option<(u
             && entry.font_index == key.font_index
             && entry.instance.size() == key.size
             && entry.instance.location().coords() == key.coords
+            && entry.hinting == key.hinting
         {
-            return Some((ix, true));
+            return Some((ix, true, false));
         }
         if entry.serial < found_serial {
             found_serial = entry.serial;
             found_index = ix;
         }
     }
-    if entries.len() < MAX_CACHED_HINT_INSTANCES {
+    if entries.len() < max_instances {
         let instance = key.instance()?;
         let ix = entries.len();
         entries.push(HintEntry {
             font_id: key.font_id,
             font_index: key.font_index,
+            hinting: key.hinting,
             instance,
             // This should be updated by the caller.
             serial: 0,
         });
-        Some((ix, true))
+        Some((ix, true, true))
     } else {
-        Some((found_index, false))
+        Some((found_index, false, false))
     }
 }
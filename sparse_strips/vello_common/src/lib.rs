@@ -97,7 +97,9 @@ pub mod rect;
 pub mod render_graph;
 pub mod simd;
 pub mod strip;
+pub mod strip_cache;
 pub mod strip_generator;
+pub mod subpixel;
 pub mod tile;
 pub mod util;
 
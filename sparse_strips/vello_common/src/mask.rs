@@ -99,6 +99,15 @@ impl Mask {
         self.0.height
     }
 
+    /// Return whether both masks share the same data, i.e. one is a clone of the other.
+    ///
+    /// Unlike `==`, this doesn't compare the mask values, so it can be used to cheaply
+    /// identify masks.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
     /// Sample the value at a specific location.
     ///
     /// This function might panic or yield a wrong result if the location
@@ -3,12 +3,15 @@
 
 //! Types for paints.
 
+use crate::blurred_rounded_rect::BlurredRoundedRectangle;
 use crate::pixmap::Pixmap;
 use alloc::sync::Arc;
+use alloc::vec;
+use fearless_simd::{Level, Simd, dispatch, u8x16, u16x16};
 pub use peniko::Color;
 use peniko::{
-    Gradient,
-    color::{AlphaColor, PremulRgba8, Srgb},
+    Brush, Gradient,
+    color::{AlphaColor, ColorSpace, DynamicColor, OpaqueColor, PremulRgba8, Srgb},
 };
 
 /// A paint that needs to be resolved via its index.
@@ -117,16 +120,14 @@ impl ImageSource {
     /// Convert a [`peniko::ImageData`] to an [`ImageSource`].
     ///
     /// This is a somewhat lossy conversion, as the image data data is transformed to
-    /// [premultiplied RGBA8](`PremulRgba8`).
+    /// [premultiplied RGBA8](`PremulRgba8`). Both straight and premultiplied alpha
+    /// types are supported, as well as all formats of [`peniko::ImageFormat`].
     ///
     /// # Panics
     ///
-    /// This panics if `image` has a `width` or `height` greater than `u16::MAX`.
+    /// This panics if `image` has a `width` or `height` greater than `u16::MAX`, or
+    /// if it uses an image format added to peniko after this version.
     pub fn from_peniko_image_data(image: &peniko::ImageData) -> Self {
-        // TODO: how do we deal with `peniko::ImageFormat` growing? See also
-        // <https://github.com/linebender/vello/pull/996#discussion_r2080510863>.
-        let do_alpha_multiply = image.alpha_type != peniko::ImageAlphaType::AlphaPremultiplied;
-
         assert!(
             image.width <= u16::MAX as u32 && image.height <= u16::MAX as u32,
             "The image is too big. Its width and height can be no larger than {} pixels.",
@@ -135,43 +136,118 @@ impl ImageSource {
         let width = image.width.try_into().unwrap();
         let height = image.height.try_into().unwrap();
 
-        // TODO: SIMD
-        #[expect(clippy::cast_possible_truncation, reason = "This cannot overflow.")]
-        let pixels = image
-            .data
-            .data()
-            .chunks_exact(4)
-            .map(|pixel| {
-                let rgba: [u8; 4] = match image.format {
-                    peniko::ImageFormat::Rgba8 => pixel.try_into().unwrap(),
-                    peniko::ImageFormat::Bgra8 => [pixel[2], pixel[1], pixel[0], pixel[3]],
-                    format => unimplemented!("Unsupported image format: {format:?}"),
-                };
-                let alpha = u16::from(rgba[3]);
-                let multiply = |component| ((alpha * u16::from(component)) / 255) as u8;
-                if do_alpha_multiply {
-                    PremulRgba8 {
-                        r: multiply(rgba[0]),
-                        g: multiply(rgba[1]),
-                        b: multiply(rgba[2]),
-                        a: rgba[3],
-                    }
-                } else {
-                    PremulRgba8 {
-                        r: rgba[0],
-                        g: rgba[1],
-                        b: rgba[2],
-                        a: rgba[3],
-                    }
-                }
-            })
-            .collect();
+        let src = image.data.data();
+        let mut pixels = vec![
+            PremulRgba8 {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            };
+            src.len() / 4
+        ];
+        let level = Level::try_detect().unwrap_or(Level::baseline());
+        dispatch!(level, simd => convert_image_data(
+            simd,
+            image.format,
+            image.alpha_type,
+            src,
+            bytemuck::cast_slice_mut(&mut pixels),
+        ));
         let pixmap = Pixmap::from_parts(pixels, width, height);
 
         Self::Pixmap(Arc::new(pixmap))
     }
 }
 
+/// Convert image data in the given format and alpha type into premultiplied RGBA8.
+///
+/// `src` and `dst` must have the same length, which must be a multiple of 4.
+#[inline(always)]
+fn convert_image_data<S: Simd>(
+    simd: S,
+    format: peniko::ImageFormat,
+    alpha_type: peniko::ImageAlphaType,
+    src: &[u8],
+    dst: &mut [u8],
+) {
+    // Whether the red and blue channels need to be swapped to get RGBA order.
+    let swap_rb = match format {
+        peniko::ImageFormat::Rgba8 => false,
+        peniko::ImageFormat::Bgra8 => true,
+        // TODO: how do we deal with `peniko::ImageFormat` growing? See also
+        // <https://github.com/linebender/vello/pull/996#discussion_r2080510863>.
+        format => unimplemented!("Unsupported image format: {format:?}"),
+    };
+    let premultiply = alpha_type == peniko::ImageAlphaType::Alpha;
+
+    simd.vectorize(
+        #[inline(always)]
+        || {
+            let mut src_chunks = src.chunks_exact(64);
+            let mut dst_chunks = dst.chunks_exact_mut(64);
+
+            // Process 16 pixels at a time, with each channel deinterleaved into its own vector.
+            for (src, dst) in (&mut src_chunks).zip(&mut dst_chunks) {
+                let pixels = simd.load_interleaved_128_u8x64(src.try_into().unwrap());
+                let (c01, c23) = simd.split_u8x64(pixels);
+                let (c0, c1) = simd.split_u8x32(c01);
+                let (c2, a) = simd.split_u8x32(c23);
+                let (r, g, b) = if swap_rb { (c2, c1, c0) } else { (c0, c1, c2) };
+
+                let (r, g, b) = if premultiply {
+                    let alpha = simd.widen_u8x16(a);
+                    (
+                        multiply_alpha(simd, r, alpha),
+                        multiply_alpha(simd, g, alpha),
+                        multiply_alpha(simd, b, alpha),
+                    )
+                } else {
+                    (r, g, b)
+                };
+
+                let combined =
+                    simd.combine_u8x32(simd.combine_u8x16(r, g), simd.combine_u8x16(b, a));
+                simd.store_interleaved_128_u8x64(combined, dst.try_into().unwrap());
+            }
+
+            for (src, dst) in src_chunks
+                .remainder()
+                .chunks_exact(4)
+                .zip(dst_chunks.into_remainder().chunks_exact_mut(4))
+            {
+                let (r, g, b) = if swap_rb {
+                    (src[2], src[1], src[0])
+                } else {
+                    (src[0], src[1], src[2])
+                };
+                let a = src[3];
+
+                #[expect(clippy::cast_possible_truncation, reason = "This cannot overflow.")]
+                let multiply = |component| {
+                    if premultiply {
+                        ((u16::from(a) * u16::from(component)) / 255) as u8
+                    } else {
+                        component
+                    }
+                };
+
+                dst.copy_from_slice(&[multiply(r), multiply(g), multiply(b), a]);
+            }
+        },
+    );
+}
+
+/// Multiply a channel by alpha, computing `(component * alpha) / 255` rounded down.
+#[inline(always)]
+fn multiply_alpha<S: Simd>(simd: S, component: u8x16<S>, alpha: u16x16<S>) -> u8x16<S> {
+    let product = simd.widen_u8x16(component) * alpha;
+    // Exact for all products of two u8 values.
+    let divided = (product + (product >> 8) + 1) >> 8;
+
+    simd.narrow_u16x16(divided)
+}
+
 /// An image.
 pub type Image = peniko::ImageBrush<ImageSource>;
 
@@ -272,4 +348,142 @@ pub struct Tint {
 }
 
 /// A kind of paint that can be used for filling and stroking shapes.
-pub type PaintType = peniko::Brush<Image, Gradient>;
+///
+/// This has the same variants as [`peniko::Brush`], and can be created from a `Brush`
+/// using [`From`], with the addition of blurred rounded rectangles.
+#[derive(Debug, Clone)]
+pub enum PaintType {
+    /// A solid color.
+    Solid(AlphaColor<Srgb>),
+    /// A gradient.
+    Gradient(Gradient),
+    /// An image.
+    Image(Image),
+    /// A rounded rectangle blurred with an approximate gaussian filter.
+    ///
+    /// The rectangle is only painted inside the shapes which are drawn with this paint,
+    /// which can for example be used to draw a box shadow only outside of the box.
+    BlurredRoundedRect(BlurredRoundedRectangle),
+}
+
+impl Default for PaintType {
+    fn default() -> Self {
+        Brush::default().into()
+    }
+}
+
+impl From<Brush<Image, Gradient>> for PaintType {
+    fn from(brush: Brush<Image, Gradient>) -> Self {
+        match brush {
+            Brush::Solid(color) => Self::Solid(color),
+            Brush::Gradient(gradient) => Self::Gradient(gradient),
+            Brush::Image(image) => Self::Image(image),
+        }
+    }
+}
+
+impl<CS: ColorSpace> From<AlphaColor<CS>> for PaintType {
+    fn from(color: AlphaColor<CS>) -> Self {
+        Brush::from(color).into()
+    }
+}
+
+impl<CS: ColorSpace> From<&AlphaColor<CS>> for PaintType {
+    fn from(color: &AlphaColor<CS>) -> Self {
+        Brush::from(color).into()
+    }
+}
+
+impl<CS: ColorSpace> From<OpaqueColor<CS>> for PaintType {
+    fn from(color: OpaqueColor<CS>) -> Self {
+        Brush::from(color).into()
+    }
+}
+
+impl From<DynamicColor> for PaintType {
+    fn from(color: DynamicColor) -> Self {
+        Brush::from(color).into()
+    }
+}
+
+impl From<Gradient> for PaintType {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl From<Image> for PaintType {
+    fn from(image: Image) -> Self {
+        Self::Image(image)
+    }
+}
+
+impl From<BlurredRoundedRectangle> for PaintType {
+    fn from(rect: BlurredRoundedRectangle) -> Self {
+        Self::BlurredRoundedRect(rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use peniko::{Blob, ImageAlphaType, ImageData, ImageFormat};
+
+    fn image_data(format: ImageFormat, alpha_type: ImageAlphaType, data: Vec<u8>) -> ImageData {
+        ImageData {
+            data: Blob::new(Arc::new(data)),
+            format,
+            alpha_type,
+            width: 5,
+            height: 5,
+        }
+    }
+
+    fn pixels(source: &ImageSource) -> &[PremulRgba8] {
+        match source {
+            ImageSource::Pixmap(pixmap) => pixmap.data(),
+            ImageSource::OpaqueId { .. } => panic!("expected a pixmap"),
+        }
+    }
+
+    // 25 pixels, so that both the SIMD path and the scalar remainder are exercised.
+    fn test_data() -> Vec<u8> {
+        (0..100).map(|n| ((n * 37 + 11) % 256) as u8).collect()
+    }
+
+    #[test]
+    fn from_peniko_image_data_premultiplies() {
+        let data = test_data();
+        let source = ImageSource::from_peniko_image_data(&image_data(
+            ImageFormat::Rgba8,
+            ImageAlphaType::Alpha,
+            data.clone(),
+        ));
+
+        for (pixel, src) in pixels(&source).iter().zip(data.chunks_exact(4)) {
+            let multiply = |c: u8| (u16::from(c) * u16::from(src[3]) / 255) as u8;
+            assert_eq!(
+                [pixel.r, pixel.g, pixel.b, pixel.a],
+                [multiply(src[0]), multiply(src[1]), multiply(src[2]), src[3]]
+            );
+        }
+    }
+
+    #[test]
+    fn from_peniko_image_data_premultiplied_bgra() {
+        let data = test_data();
+        let source = ImageSource::from_peniko_image_data(&image_data(
+            ImageFormat::Bgra8,
+            ImageAlphaType::AlphaPremultiplied,
+            data.clone(),
+        ));
+
+        for (pixel, src) in pixels(&source).iter().zip(data.chunks_exact(4)) {
+            assert_eq!(
+                [pixel.r, pixel.g, pixel.b, pixel.a],
+                [src[2], src[1], src[0], src[3]]
+            );
+        }
+    }
+}
@@ -3,17 +3,27 @@
 
 //! Recording API for caching sparse strips
 
+#[cfg(feature = "text")]
+use crate::color::palette::css::BLACK;
+#[cfg(feature = "text")]
+use crate::color::{AlphaColor, Srgb};
+#[cfg(feature = "text")]
+use crate::colr::{ColrPainter, ColrRecording};
 use crate::filter_effects::Filter;
 #[cfg(feature = "text")]
-use crate::glyph::{GlyphRenderer, GlyphRunBuilder, GlyphType, PreparedGlyph};
+use crate::glyph::{GlyphCacheKey, GlyphRenderer, GlyphRunBuilder, GlyphType, PreparedGlyph};
 use crate::kurbo::{Affine, BezPath, Rect, Stroke};
 use crate::mask::Mask;
 use crate::paint::{PaintType, Tint};
 #[cfg(feature = "text")]
 use crate::peniko::FontData;
 use crate::peniko::{BlendMode, Fill};
+#[cfg(feature = "text")]
+use crate::pixmap::Pixmap;
 use crate::strip::Strip;
 use crate::strip_generator::StripStorage;
+#[cfg(feature = "text")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 
 /// Cached sparse strip data.
@@ -104,6 +114,46 @@ pub struct PushLayerCommand {
     pub filter: Option<Filter>,
 }
 
+/// Command for filling a bitmap glyph.
+#[cfg(feature = "text")]
+#[derive(Debug, Clone)]
+pub struct BitmapGlyphCommand {
+    /// The pixmap of the glyph.
+    pub pixmap: Arc<Pixmap>,
+    /// The rectangular area that should be filled with the pixmap.
+    pub area: Rect,
+    /// The transform of the glyph.
+    pub transform: Affine,
+    /// A key identifying the pixmap of the glyph, for caching it.
+    pub cache_key: GlyphCacheKey,
+}
+
+/// Command for filling a COLR glyph.
+///
+/// Renderers first draw the painting commands into a pixmap/texture of size
+/// `pix_width` x `pix_height`, and then fill the area of the glyph with it.
+#[cfg(feature = "text")]
+#[derive(Debug, Clone)]
+pub struct ColrGlyphCommand {
+    /// The painting commands of the glyph.
+    pub recording: Arc<ColrRecording>,
+    /// The context color the glyph was painted with.
+    ///
+    /// This is the solid color that was set as the paint of the recording before the glyph,
+    /// or black if there is none.
+    pub context_color: AlphaColor<Srgb>,
+    /// The rectangular area that should be filled with the rendered glyph.
+    pub area: Rect,
+    /// The width of the pixmap/texture in pixels to which the glyph should be rendered to.
+    pub pix_width: u16,
+    /// The height of the pixmap/texture in pixels to which the glyph should be rendered to.
+    pub pix_height: u16,
+    /// The transform of the glyph.
+    pub transform: Affine,
+    /// A key identifying the rendered glyph, for caching it.
+    pub cache_key: GlyphCacheKey,
+}
+
 /// Individual rendering commands that can be recorded.
 #[derive(Debug)]
 pub enum RenderCommand {
@@ -143,6 +193,12 @@ pub enum RenderCommand {
     /// Render a stroke outline glyph.
     #[cfg(feature = "text")]
     StrokeOutlineGlyph((BezPath, Affine)),
+    /// Render a bitmap glyph.
+    #[cfg(feature = "text")]
+    FillBitmapGlyph(BitmapGlyphCommand),
+    /// Render a COLR glyph.
+    #[cfg(feature = "text")]
+    FillColrGlyph(ColrGlyphCommand),
 }
 
 impl Recording {
@@ -334,6 +390,9 @@ pub struct Recorder<'a> {
 
     #[cfg(feature = "text")]
     glyph_caches: Option<crate::glyph::GlyphCaches>,
+    /// The context color that COLR glyphs are painted with.
+    #[cfg(feature = "text")]
+    context_color: AlphaColor<Srgb>,
 }
 
 impl<'a> Recorder<'a> {
@@ -347,6 +406,8 @@ impl<'a> Recorder<'a> {
             recording,
             #[cfg(feature = "text")]
             glyph_caches: Some(glyph_caches),
+            #[cfg(feature = "text")]
+            context_color: BLACK,
         };
         // Ensure that the initial transform is saved on the recording.
         s.set_transform(transform);
@@ -395,8 +456,15 @@ impl<'a> Recorder<'a> {
 
     /// Set the paint for subsequent rendering operations.
     pub fn set_paint(&mut self, paint: impl Into<PaintType>) {
-        self.recording
-            .add_command(RenderCommand::SetPaint(paint.into()));
+        let paint = paint.into();
+        #[cfg(feature = "text")]
+        {
+            self.context_color = match paint {
+                PaintType::Solid(color) => color,
+                _ => BLACK,
+            };
+        }
+        self.recording.add_command(RenderCommand::SetPaint(paint));
     }
 
     /// Set the current paint transform.
@@ -483,8 +551,33 @@ impl GlyphRenderer for Recorder<'_> {
                 }
             }
 
-            _ => {
-                unimplemented!("Recording glyphs of type {:?}", glyph.glyph_type);
+            GlyphType::Bitmap(bitmap_glyph) => {
+                self.recording
+                    .add_command(RenderCommand::FillBitmapGlyph(BitmapGlyphCommand {
+                        pixmap: Arc::new(bitmap_glyph.pixmap),
+                        area: bitmap_glyph.area,
+                        transform: glyph.transform,
+                        cache_key: bitmap_glyph.cache_key,
+                    }));
+            }
+            GlyphType::Colr(colr_glyph) => {
+                let area = colr_glyph.area;
+                let (pix_width, pix_height) = (colr_glyph.pix_width, colr_glyph.pix_height);
+                let cache_key = colr_glyph.cache_key.clone();
+
+                let mut recording = ColrRecording::new();
+                ColrPainter::new(colr_glyph, self.context_color, &mut recording).paint();
+
+                self.recording
+                    .add_command(RenderCommand::FillColrGlyph(ColrGlyphCommand {
+                        recording: Arc::new(recording),
+                        context_color: self.context_color,
+                        area,
+                        pix_width,
+                        pix_height,
+                        transform: glyph.transform,
+                        cache_key,
+                    }));
             }
         }
     }
@@ -500,8 +593,10 @@ impl GlyphRenderer for Recorder<'_> {
                         )));
                 }
             }
-            _ => {
-                unimplemented!("Recording glyphs of type {:?}", glyph.glyph_type);
+            GlyphType::Bitmap(_) | GlyphType::Colr(_) => {
+                // The definitions of COLR and bitmap glyphs can't meaningfully support being stroked.
+                // (COLR's imaging model only has fills)
+                self.fill_glyph(glyph);
             }
         }
     }
@@ -0,0 +1,380 @@
+// Copyright 2025 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Caching of the strips of paths which are drawn repeatedly.
+//!
+//! Strips are aligned to the tile grid, so the strips of a path can be reused for every placement
+//! of that path which only differs by a translation of a whole number of tiles. A [`StripCache`]
+//! stores the strips of a path generated once near the origin, and moves them into place for each
+//! later draw. This avoids flattening and tiling the path again, which is especially useful for
+//! glyphs, where the same outline is typically drawn many times at integer offsets.
+
+use crate::fearless_simd::Level;
+use crate::kurbo::{Affine, PathEl, Rect, Vec2};
+use crate::peniko::Style;
+use crate::strip::Strip;
+use crate::strip_generator::{GenerationMode, StripGenerator, StripStorage};
+use crate::tile::Tile;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "std"))]
+use peniko::kurbo::common::FloatFuncs as _;
+
+/// The maximum number of cached placements.
+///
+/// Each rotation, scale or sub-tile translation of a path is cached separately, so animating
+/// these adds a new entry every frame.
+const MAX_ENTRIES: usize = 1024;
+
+/// A cache for the strips of paths, keyed by a caller-provided path identity `K`.
+///
+/// The key must identify both the path elements and the [`Style`] they are drawn with; the
+/// cache doesn't check that the path passed to [`StripCache::generate`] matches the one which
+/// was cached for a key.
+///
+/// Once the cache holds 1024 placements, the entries which haven't been used since the
+/// previous call to [`StripCache::maintain`] are evicted before adding a new one, or all entries
+/// if every one of them has been used.
+#[derive(Debug)]
+pub struct StripCache<K> {
+    entries: HashMap<EntryKey<K>, CachedStrips>,
+    strip_generator: StripGenerator,
+    width: u16,
+    height: u16,
+}
+
+/// The strips of a path, generated for a given transform modulo whole tiles.
+#[derive(Debug)]
+struct CachedStrips {
+    storage: StripStorage,
+    /// A conservative bound of the area affected by the path, relative to the tile-aligned part
+    /// of the translation of the drawn transform.
+    bounds: Rect,
+    /// The translation (in whole tiles) applied to the path when generating `storage`.
+    offset: (f64, f64),
+    /// Whether the entry has been used since the last call to [`StripCache::maintain`].
+    used: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct EntryKey<K> {
+    path: K,
+    /// The bits of the linear part of the transform.
+    linear: [u64; 4],
+    /// The bits of the translation of the transform, modulo the tile size.
+    residue: [u64; 2],
+    aliasing_threshold: Option<u8>,
+}
+
+impl<K: Hash + Eq> StripCache<K> {
+    /// Create a new, empty cache for a viewport of the given width and height.
+    pub fn new(width: u16, height: u16, level: Level) -> Self {
+        Self {
+            entries: HashMap::new(),
+            strip_generator: StripGenerator::new(width, height, level),
+            width,
+            height,
+        }
+    }
+
+    /// Append the strips for `path` drawn with `style` and `transform` to `strip_storage`,
+    /// reusing previously generated strips for `key` where possible.
+    ///
+    /// The alpha indices of the appended strips refer to `strip_storage.alphas`. As with
+    /// [`StripGenerator`], the existing strips are cleared first if the storage is in
+    /// [`GenerationMode::Replace`].
+    ///
+    /// Returns `false` without modifying `strip_storage` if the path doesn't lie fully inside the
+    /// viewport for this placement, in which case the strips need to be generated as usual
+    /// (i.e. clipped to the viewport).
+    pub fn generate(
+        &mut self,
+        key: K,
+        path: &[PathEl],
+        style: &Style,
+        transform: Affine,
+        aliasing_threshold: Option<u8>,
+        strip_storage: &mut StripStorage,
+    ) -> bool {
+        let [a, b, c, d, tx, ty] = transform.as_coeffs();
+        if !transform.is_finite() {
+            return false;
+        }
+        let tile_width = f64::from(Tile::WIDTH);
+        let tile_height = f64::from(Tile::HEIGHT);
+        // The part of the translation which moves the path by whole tiles; only the remainder
+        // affects the generated strips.
+        let grid_x = (tx / tile_width).floor() * tile_width;
+        let grid_y = (ty / tile_height).floor() * tile_height;
+        let residue = Affine::new([a, b, c, d, tx - grid_x, ty - grid_y]);
+
+        let entry_key = EntryKey {
+            path: key,
+            linear: [a.to_bits(), b.to_bits(), c.to_bits(), d.to_bits()],
+            residue: [(tx - grid_x).to_bits(), (ty - grid_y).to_bits()],
+            aliasing_threshold,
+        };
+
+        let grid = Vec2::new(grid_x, grid_y);
+        let viewport = Rect::new(0.0, 0.0, f64::from(self.width), f64::from(self.height));
+        let entry = if let Some(entry) = self.entries.get_mut(&entry_key) {
+            entry
+        } else {
+            let bounds = path_bounds(path, style, residue);
+            if !fits(bounds + grid, viewport) {
+                return false;
+            }
+            if self.entries.len() >= MAX_ENTRIES {
+                self.maintain();
+                if self.entries.len() >= MAX_ENTRIES {
+                    self.entries.clear();
+                }
+            }
+            // Move the path as close to the origin as possible, so that it fits into the viewport
+            // whenever one of its placements does.
+            let offset = (
+                -(bounds.x0 / tile_width).floor() * tile_width,
+                -(bounds.y0 / tile_height).floor() * tile_height,
+            );
+            let mut storage = StripStorage::new(GenerationMode::Replace);
+            let transform = Affine::translate(offset) * residue;
+            match style {
+                Style::Fill(fill_rule) => self.strip_generator.generate_filled_path(
+                    path.iter().copied(),
+                    *fill_rule,
+                    transform,
+                    aliasing_threshold,
+                    &mut storage,
+                    None,
+                ),
+                Style::Stroke(stroke) => self.strip_generator.generate_stroked_path(
+                    path.iter().copied(),
+                    stroke,
+                    transform,
+                    aliasing_threshold,
+                    &mut storage,
+                    None,
+                ),
+            }
+            self.entries.entry(entry_key).or_insert(CachedStrips {
+                storage,
+                bounds,
+                offset,
+                used: false,
+            })
+        };
+
+        if !fits(entry.bounds + grid, viewport) {
+            return false;
+        }
+        entry.used = true;
+
+        // Both values are multiples of the tile size, and the bounds check above ensures that
+        // the moved strips stay inside the viewport.
+        let dx = (grid_x - entry.offset.0) as i32;
+        let dy = (grid_y - entry.offset.1) as i32;
+        let alpha_offset = strip_storage.alphas.len() as u32;
+        strip_storage.alphas.extend(&entry.storage.alphas);
+        if strip_storage.generation_mode() == GenerationMode::Replace {
+            strip_storage.strips.clear();
+        }
+        strip_storage
+            .strips
+            .extend(entry.storage.strips.iter().map(|strip| {
+                // Sentinel strips mark the end of a row, so only their row is moved.
+                let x = if strip.is_sentinel() {
+                    strip.x
+                } else {
+                    (i32::from(strip.x) + dx) as u16
+                };
+                let y = (i32::from(strip.y) + dy) as u16;
+                Strip::new(x, y, strip.alpha_idx() + alpha_offset, strip.fill_gap())
+            }));
+
+        true
+    }
+
+    /// Remove the entries which haven't been used since the previous call to this method.
+    ///
+    /// This is intended to be called once per frame. If it isn't, the size of the cache is still
+    /// bounded, but entries are evicted less selectively.
+    pub fn maintain(&mut self) {
+        self.entries
+            .retain(|_, entry| core::mem::take(&mut entry.used));
+    }
+
+    /// Remove all entries from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The number of cached placements.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Whether `bounds` lies fully inside `viewport`.
+fn fits(bounds: Rect, viewport: Rect) -> bool {
+    bounds.x0 >= viewport.x0
+        && bounds.y0 >= viewport.y0
+        && bounds.x1 <= viewport.x1
+        && bounds.y1 <= viewport.y1
+}
+
+/// A conservative bound of the area affected by drawing `path` with `style` and `transform`.
+fn path_bounds(path: &[PathEl], style: &Style, transform: Affine) -> Rect {
+    // The control points of a path contain its curves.
+    let mut bounds = Rect::new(
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for el in path {
+        let points: &[_] = match el {
+            PathEl::MoveTo(p0) | PathEl::LineTo(p0) => &[*p0],
+            PathEl::QuadTo(p0, p1) => &[*p0, *p1],
+            PathEl::CurveTo(p0, p1, p2) => &[*p0, *p1, *p2],
+            PathEl::ClosePath => &[],
+        };
+        for point in points {
+            let point = transform * *point;
+            bounds = Rect::new(
+                bounds.x0.min(point.x),
+                bounds.y0.min(point.y),
+                bounds.x1.max(point.x),
+                bounds.y1.max(point.y),
+            );
+        }
+    }
+    if bounds.x0 > bounds.x1 {
+        return Rect::ZERO;
+    }
+
+    let extent = match style {
+        Style::Fill(_) => 0.0,
+        Style::Stroke(stroke) => {
+            // Miter joins extend at most `miter_limit` half-widths from the path, and square caps
+            // extend at most `sqrt(2)` half-widths.
+            let [a, b, c, d, _, _] = transform.as_coeffs();
+            let scale = (a * a + b * b + c * c + d * d).sqrt();
+            0.5 * stroke.width * stroke.miter_limit.max(core::f64::consts::SQRT_2) * scale
+        }
+    };
+    // Add a pixel of margin to account for rounding during flattening.
+    bounds.inflate(extent + 1.0, extent + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_ENTRIES, StripCache};
+    use crate::fearless_simd::Level;
+    use crate::kurbo::{Affine, BezPath, Rect, Shape};
+    use crate::peniko::{Fill, Style};
+    use crate::strip_generator::{GenerationMode, StripGenerator, StripStorage};
+    use alloc::vec::Vec;
+
+    fn path() -> Vec<crate::kurbo::PathEl> {
+        let path: BezPath = Rect::new(0.5, 1.25, 7.75, 9.0)
+            .to_rounded_rect(2.0)
+            .into_path(0.1);
+        path.elements().to_vec()
+    }
+
+    fn generate(transform: Affine) -> StripStorage {
+        let mut generator = StripGenerator::new(100, 100, Level::baseline());
+        let mut storage = StripStorage::default();
+        generator.generate_filled_path(path(), Fill::NonZero, transform, None, &mut storage, None);
+        storage
+    }
+
+    #[test]
+    fn integer_translations_reuse_strips() {
+        let mut cache = StripCache::new(100, 100, Level::baseline());
+        let style = Style::Fill(Fill::NonZero);
+        for (tx, ty) in [(10.0, 20.0), (14.0, 28.0), (50.0, 36.0), (2.0, 4.0)] {
+            let transform = Affine::translate((tx, ty));
+            let mut storage = StripStorage::default();
+            assert!(cache.generate(0, &path(), &style, transform, None, &mut storage));
+            assert_eq!(storage, generate(transform));
+        }
+        // All of the translations are congruent modulo the tile size.
+        assert_eq!(cache.len(), 1);
+
+        let mut storage = StripStorage::default();
+        let transform = Affine::translate((11.0, 20.0));
+        assert!(cache.generate(0, &path(), &style, transform, None, &mut storage));
+        assert_eq!(storage, generate(transform));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn append_mode_offsets_alphas() {
+        let mut cache = StripCache::new(100, 100, Level::baseline());
+        let style = Style::Fill(Fill::NonZero);
+        let mut storage = StripStorage::new(GenerationMode::Append);
+        let first = Affine::translate((4.0, 4.0));
+        let second = Affine::translate((40.0, 40.0));
+        assert!(cache.generate(0, &path(), &style, first, None, &mut storage));
+        assert!(cache.generate(0, &path(), &style, second, None, &mut storage));
+
+        let mut expected = StripStorage::new(GenerationMode::Append);
+        expected.extend(&generate(first));
+        let second = generate(second);
+        let alpha_offset = expected.alphas.len() as u32;
+        expected.alphas.extend(&second.alphas);
+        expected.strips.extend(second.strips.iter().map(|strip| {
+            let mut strip = *strip;
+            strip.set_alpha_idx(strip.alpha_idx() + alpha_offset);
+            strip
+        }));
+        assert_eq!(storage, expected);
+    }
+
+    #[test]
+    fn outside_viewport_is_rejected() {
+        let mut cache = StripCache::new(100, 100, Level::baseline());
+        let style = Style::Fill(Fill::NonZero);
+        let mut storage = StripStorage::default();
+        for (tx, ty) in [(-4.0, 0.0), (0.0, -4.0), (96.0, 0.0), (0.0, 96.0)] {
+            let transform = Affine::translate((tx, ty));
+            assert!(!cache.generate(0, &path(), &style, transform, None, &mut storage));
+        }
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn maintain_evicts_unused_entries() {
+        let mut cache = StripCache::new(100, 100, Level::baseline());
+        let style = Style::Fill(Fill::NonZero);
+        let mut storage = StripStorage::default();
+        let transform = Affine::translate((8.0, 8.0));
+        assert!(cache.generate(0, &path(), &style, transform, None, &mut storage));
+        assert!(cache.generate(1, &path(), &style, transform, None, &mut storage));
+        cache.maintain();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.generate(1, &path(), &style, transform, None, &mut storage));
+        cache.maintain();
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn size_is_bounded_without_maintain() {
+        let mut cache = StripCache::new(100, 100, Level::baseline());
+        let style = Style::Fill(Fill::NonZero);
+        let mut storage = StripStorage::default();
+        for i in 0..2 * MAX_ENTRIES {
+            // A different sub-tile translation each time, as in an animated pan.
+            let transform = Affine::translate((8.0 + i as f64 / 4096.0, 8.0));
+            assert!(cache.generate(0, &path(), &style, transform, None, &mut storage));
+            assert!(cache.len() <= MAX_ENTRIES);
+        }
+    }
+}
@@ -9,8 +9,9 @@ use crate::flatten::{FlattenCtx, Line};
 use crate::kurbo::{Affine, PathEl, Rect, Stroke};
 use crate::peniko::Fill;
 use crate::strip::Strip;
+use crate::subpixel::{OVERSAMPLING, SubpixelOrder};
 use crate::tile::Tiles;
-use crate::{flatten, rect, strip};
+use crate::{flatten, rect, strip, subpixel};
 use alloc::vec::Vec;
 use peniko::kurbo::StrokeCtx;
 
@@ -55,6 +56,11 @@ impl StripStorage {
         self.generation_mode = mode;
     }
 
+    /// Return the generation mode of the storage.
+    pub fn generation_mode(&self) -> GenerationMode {
+        self.generation_mode
+    }
+
     /// Whether the strip storage is empty.
     pub fn is_empty(&self) -> bool {
         self.strips.is_empty() && self.alphas.is_empty()
@@ -75,6 +81,7 @@ pub struct StripGenerator {
     flatten_ctx: FlattenCtx,
     stroke_ctx: StrokeCtx,
     temp_storage: StripStorage,
+    subpixel_buf: Vec<u8>,
     tiles: Tiles,
     width: u16,
     height: u16,
@@ -90,11 +97,17 @@ impl StripGenerator {
             flatten_ctx: FlattenCtx::default(),
             stroke_ctx: StrokeCtx::default(),
             temp_storage: StripStorage::default(),
+            subpixel_buf: Vec::new(),
             width,
             height,
         }
     }
 
+    /// Return the SIMD level used by the strip generator.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
     /// Generate the strips for a filled path.
     pub fn generate_filled_path(
         &mut self,
@@ -142,6 +155,61 @@ impl StripGenerator {
         self.generate_with_clip(aliasing_threshold, strip_storage, Fill::NonZero, clip_path);
     }
 
+    /// Generate the strips for a filled path with subpixel coverage.
+    ///
+    /// The path is rendered with a higher horizontal resolution, and the coverage is then
+    /// resolved into a separate value for each color component of a pixel. See the
+    /// [`subpixel`] module for the layout of the generated alphas.
+    pub fn generate_subpixel_filled_path(
+        &mut self,
+        path: impl IntoIterator<Item = PathEl>,
+        fill_rule: Fill,
+        transform: Affine,
+        subpixel_order: SubpixelOrder,
+        strip_storage: &mut StripStorage,
+    ) {
+        let width = self.width.saturating_mul(OVERSAMPLING);
+        let transform = Affine::scale_non_uniform(f64::from(OVERSAMPLING), 1.0) * transform;
+
+        flatten::fill(
+            self.level,
+            path,
+            transform,
+            &mut self.line_buf,
+            &mut self.flatten_ctx,
+            width,
+            self.height,
+        );
+        self.tiles
+            .make_tiles_analytic_aa(&self.line_buf, width, self.height);
+        self.tiles.sort_tiles();
+
+        self.temp_storage.clear();
+        strip::render(
+            self.level,
+            &self.tiles,
+            &mut self.temp_storage.strips,
+            &mut self.temp_storage.alphas,
+            fill_rule,
+            None,
+            &self.line_buf,
+        );
+
+        if strip_storage.generation_mode == GenerationMode::Replace {
+            strip_storage.strips.clear();
+        }
+
+        subpixel::resolve(
+            subpixel_order,
+            &self.temp_storage.strips,
+            &self.temp_storage.alphas,
+            self.width,
+            &mut self.subpixel_buf,
+            &mut strip_storage.strips,
+            &mut strip_storage.alphas,
+        );
+    }
+
     fn generate_with_clip(
         &mut self,
         aliasing_threshold: Option<u8>,
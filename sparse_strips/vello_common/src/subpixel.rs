@@ -0,0 +1,351 @@
+// Copyright 2025 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Subpixel (LCD) coverage.
+//!
+//! Subpixel coverage is computed by rendering a path with [`OVERSAMPLING`] times the horizontal
+//! resolution, and then resolving the oversampled coverage into a separate coverage value for
+//! each color component of a pixel. A FIR filter is applied in the process to reduce color
+//! fringes.
+//!
+//! Unlike regular strips, which store one alpha value per pixel, strips with subpixel coverage
+//! store [`COMPONENTS`] values per pixel, in the same order as the components of a premultiplied
+//! RGBA color. For a single column of a strip, the alpha buffer therefore contains the red, green,
+//! blue and alpha coverage of the first row, followed by the coverage of the second row, and so on.
+
+use crate::strip::Strip;
+use crate::tile::Tile;
+use alloc::vec::Vec;
+
+/// The order of the subpixels within a pixel of the display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SubpixelOrder {
+    /// Red, green and blue subpixels, from left to right.
+    Rgb,
+    /// Blue, green and red subpixels, from left to right.
+    Bgr,
+}
+
+/// The horizontal oversampling factor used for subpixel coverage.
+pub const OVERSAMPLING: u16 = 3;
+
+/// The number of coverage values stored for each pixel.
+pub const COMPONENTS: usize = 4;
+
+/// The weights of the FIR filter, which add up to 256.
+///
+/// These are the same weights as the ones used by the default LCD filter of `FreeType`.
+const FILTER: [u32; 5] = [0x08, 0x4D, 0x56, 0x4D, 0x08];
+
+/// The number of subpixels on each side of a subpixel that contribute to its filtered coverage.
+const FILTER_RADIUS: u16 = (FILTER.len() / 2) as u16;
+
+/// The number of alpha values in a single column of a strip with subpixel coverage.
+const COLUMN_SIZE: usize = Tile::HEIGHT as usize * COMPONENTS;
+
+/// Resolve oversampled strips into strips with subpixel coverage.
+///
+/// `strips` and `alphas` must have been rendered with a horizontal scale of [`OVERSAMPLING`],
+/// and `width` is the width of the viewport in (non-oversampled) pixels. The resolved strips are
+/// appended to `strip_buf` and `alpha_buf`. `scratch` is used as temporary storage for the
+/// oversampled coverage of a strip row.
+pub fn resolve(
+    order: SubpixelOrder,
+    strips: &[Strip],
+    alphas: &[u8],
+    width: u16,
+    scratch: &mut Vec<u8>,
+    strip_buf: &mut Vec<Strip>,
+    alpha_buf: &mut Vec<u8>,
+) {
+    let viewport_end = u32::from(width) * u32::from(OVERSAMPLING);
+    let columns = |strip: &Strip| strip.alpha_idx() as usize / Tile::HEIGHT as usize;
+
+    let mut last_y = None;
+    let mut row_start = 0;
+
+    while row_start < strips.len() {
+        let y = strips[row_start].y;
+        let row_end = strips[row_start..]
+            .iter()
+            .position(|s| s.y != y)
+            .map_or(strips.len(), |pos| row_start + pos);
+        let row = &strips[row_start..row_end];
+        // The first strip of the next row tells us where the alphas of the last strip end.
+        let next_col = strips
+            .get(row_end)
+            .map_or(alphas.len() / Tile::HEIGHT as usize, columns);
+        row_start = row_end;
+
+        if row[0].is_sentinel() {
+            continue;
+        }
+
+        // Determine the oversampled horizontal extent of the row.
+        let start = u32::from(row[0].x);
+        let mut end = start;
+        for (i, strip) in row.iter().enumerate() {
+            if strip.is_sentinel() {
+                if strip.fill_gap() {
+                    end = viewport_end;
+                }
+            } else {
+                let strip_end_col = row.get(i + 1).map_or(next_col, columns);
+                end = u32::from(strip.x) + (strip_end_col - columns(strip)) as u32;
+            }
+        }
+
+        // The filter spreads the coverage of each subpixel to its neighbors. Round the range of
+        // resolved pixels to whole tiles, as that is what the later stages expect.
+        let radius = u32::from(FILTER_RADIUS);
+        let oversampling = u32::from(OVERSAMPLING);
+        let tile_width = u32::from(Tile::WIDTH);
+        let x0 = (start.saturating_sub(radius) / oversampling) / tile_width * tile_width;
+        let x1 = (end + radius)
+            .div_ceil(oversampling)
+            .min(u32::from(width))
+            .next_multiple_of(tile_width);
+
+        if x0 >= x1 {
+            continue;
+        }
+
+        // Gather the oversampled coverage of the row, with some padding for the filter.
+        let origin = x0 * oversampling;
+        let line_len = ((x1 - x0) * oversampling + 2 * radius) as usize;
+        scratch.clear();
+        scratch.resize(line_len * Tile::HEIGHT as usize, 0);
+
+        let mut prev_end = start;
+        for (i, strip) in row.iter().enumerate() {
+            let strip_x = if strip.is_sentinel() {
+                viewport_end
+            } else {
+                u32::from(strip.x)
+            };
+
+            if strip.fill_gap() {
+                fill_line(scratch, line_len, origin, prev_end, strip_x, |_, _| 255);
+            }
+
+            if strip.is_sentinel() {
+                break;
+            }
+
+            let first_col = columns(strip);
+            let strip_end_col = row.get(i + 1).map_or(next_col, columns);
+            let strip_end = strip_x + (strip_end_col - first_col) as u32;
+            let strip_alphas = &alphas[strip.alpha_idx() as usize..];
+            fill_line(scratch, line_len, origin, strip_x, strip_end, |x, y| {
+                strip_alphas[(x - strip_x) as usize * Tile::HEIGHT as usize + y]
+            });
+            prev_end = strip_end;
+        }
+
+        // Filter and resolve the coverage of each pixel.
+        let row_alpha_idx = alpha_buf.len();
+        for x in x0..x1 {
+            for y in 0..Tile::HEIGHT as usize {
+                let line = &scratch[y * line_len..][..line_len];
+                let sub_x = ((x - x0) * oversampling) as usize;
+                let filtered: [u8; 3] = core::array::from_fn(|i| {
+                    let taps = &line[sub_x + i..][..FILTER.len()];
+                    let sum = FILTER
+                        .iter()
+                        .zip(taps)
+                        .map(|(w, c)| w * u32::from(*c))
+                        .sum::<u32>();
+                    ((sum + 128) >> 8) as u8
+                });
+                let [r, g, b] = match order {
+                    SubpixelOrder::Rgb => filtered,
+                    SubpixelOrder::Bgr => [filtered[2], filtered[1], filtered[0]],
+                };
+                alpha_buf.extend_from_slice(&[r, g, b, r.max(g).max(b)]);
+            }
+        }
+
+        // Emit the strips of the row, turning runs of fully covered tiles into sparse fills.
+        let tile_size = COLUMN_SIZE * Tile::WIDTH as usize;
+        let mut write_idx = row_alpha_idx;
+        strip_buf.push(Strip::new(x0 as u16, y, write_idx as u32, false));
+
+        let mut x = x0;
+        while x < x1 {
+            let read_idx = row_alpha_idx + (x - x0) as usize * COLUMN_SIZE;
+            if alpha_buf[read_idx..][..tile_size].iter().all(|a| *a == 255) {
+                let mut gap_end = x + tile_width;
+                while gap_end < x1 {
+                    let idx = row_alpha_idx + (gap_end - x0) as usize * COLUMN_SIZE;
+                    if !alpha_buf[idx..][..tile_size].iter().all(|a| *a == 255) {
+                        break;
+                    }
+                    gap_end += tile_width;
+                }
+
+                strip_buf.push(Strip::new(gap_end as u16, y, write_idx as u32, true));
+                x = gap_end;
+            } else {
+                alpha_buf.copy_within(read_idx..read_idx + tile_size, write_idx);
+                write_idx += tile_size;
+                x += tile_width;
+            }
+        }
+
+        alpha_buf.truncate(write_idx);
+        last_y = Some(y);
+    }
+
+    if let Some(y) = last_y {
+        strip_buf.push(Strip::new(u16::MAX, y, alpha_buf.len() as u32, false));
+    }
+}
+
+/// Write the coverage of the oversampled range `x0..x1` into the lines of `scratch`.
+fn fill_line(
+    scratch: &mut [u8],
+    line_len: usize,
+    origin: u32,
+    x0: u32,
+    x1: u32,
+    coverage: impl Fn(u32, usize) -> u8,
+) {
+    let radius = u32::from(FILTER_RADIUS);
+    // Only the coverage that can contribute to a resolved pixel is relevant.
+    let min_x = origin.saturating_sub(radius);
+    let max_x = origin + line_len as u32 - radius;
+    let start = x0.max(min_x);
+    let end = x1.min(max_x);
+
+    for y in 0..Tile::HEIGHT as usize {
+        let line = &mut scratch[y * line_len..][..line_len];
+        for x in start..end {
+            line[(x + radius - origin) as usize] = coverage(x, y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strip::Strip;
+    use crate::subpixel::{COLUMN_SIZE, SubpixelOrder, resolve};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn resolve_strips(
+        order: SubpixelOrder,
+        strips: &[Strip],
+        alphas: &[u8],
+        width: u16,
+    ) -> (Vec<Strip>, Vec<u8>) {
+        let mut strip_buf = vec![];
+        let mut alpha_buf = vec![];
+        resolve(
+            order,
+            strips,
+            alphas,
+            width,
+            &mut vec![],
+            &mut strip_buf,
+            &mut alpha_buf,
+        );
+
+        (strip_buf, alpha_buf)
+    }
+
+    #[test]
+    fn empty() {
+        let (strips, alphas) = resolve_strips(SubpixelOrder::Rgb, &[], &[], 100);
+
+        assert!(strips.is_empty());
+        assert!(alphas.is_empty());
+    }
+
+    #[test]
+    fn single_subpixel() {
+        // A single covered subpixel at oversampled x = 13, which is the green subpixel of pixel 4.
+        let mut alphas = vec![0; 16];
+        alphas[4..8].copy_from_slice(&[255; 4]);
+        let strips = [
+            Strip::new(12, 0, 0, false),
+            Strip::new(u16::MAX, 0, 16, false),
+        ];
+
+        let (rgb_strips, rgb_alphas) = resolve_strips(SubpixelOrder::Rgb, &strips, &alphas, 100);
+        assert_eq!(
+            rgb_strips,
+            [
+                Strip::new(0, 0, 0, false),
+                Strip::new(u16::MAX, 0, 8 * COLUMN_SIZE as u32, false)
+            ]
+        );
+        let pixel = |alphas: &[u8], x: usize, y: usize| -> [u8; 4] {
+            alphas[x * COLUMN_SIZE + y * 4..][..4].try_into().unwrap()
+        };
+
+        for y in 0..4 {
+            assert_eq!(pixel(&rgb_alphas, 3, y), [0, 0, 8, 8]);
+            assert_eq!(pixel(&rgb_alphas, 4, y), [77, 86, 77, 86]);
+            assert_eq!(pixel(&rgb_alphas, 5, y), [8, 0, 0, 8]);
+        }
+        assert!(rgb_alphas[..3 * COLUMN_SIZE].iter().all(|a| *a == 0));
+        assert!(rgb_alphas[6 * COLUMN_SIZE..].iter().all(|a| *a == 0));
+
+        let (_, bgr_alphas) = resolve_strips(SubpixelOrder::Bgr, &strips, &alphas, 100);
+        assert_eq!(pixel(&bgr_alphas, 3, 0), [8, 0, 0, 8]);
+        assert_eq!(pixel(&bgr_alphas, 4, 0), [77, 86, 77, 86]);
+        assert_eq!(pixel(&bgr_alphas, 5, 0), [0, 0, 8, 8]);
+    }
+
+    #[test]
+    fn order() {
+        // A single covered subpixel at oversampled x = 12, which is the leftmost subpixel of
+        // pixel 4.
+        let mut alphas = vec![0; 16];
+        alphas[0..4].copy_from_slice(&[255; 4]);
+        let strips = [
+            Strip::new(12, 0, 0, false),
+            Strip::new(u16::MAX, 0, 16, false),
+        ];
+
+        let (_, rgb_alphas) = resolve_strips(SubpixelOrder::Rgb, &strips, &alphas, 100);
+        let (_, bgr_alphas) = resolve_strips(SubpixelOrder::Bgr, &strips, &alphas, 100);
+
+        assert_eq!(&rgb_alphas[4 * COLUMN_SIZE..][..4], &[86, 77, 8, 86]);
+        assert_eq!(&bgr_alphas[4 * COLUMN_SIZE..][..4], &[8, 77, 86, 86]);
+    }
+
+    #[test]
+    fn sparse_fill() {
+        // A row that is fully covered from oversampled x = 0 to x = 96.
+        let mut alphas = vec![255; 16];
+        alphas.extend([0; 16]);
+        let strips = [
+            Strip::new(0, 4, 0, false),
+            Strip::new(96, 4, 16, true),
+            Strip::new(u16::MAX, 4, 32, false),
+        ];
+
+        let (resolved, alphas) = resolve_strips(SubpixelOrder::Rgb, &strips, &alphas, 100);
+
+        // Pixels 1 to 30 are fully covered, the filter spreads the coverage of the edges into
+        // the neighboring pixels. Only whole tiles can be turned into sparse fills, so the first
+        // and the last two tiles are stored in the alpha buffer.
+        let tile_size = 4 * COLUMN_SIZE as u32;
+        assert_eq!(
+            resolved,
+            [
+                Strip::new(0, 4, 0, false),
+                Strip::new(28, 4, tile_size, true),
+                Strip::new(u16::MAX, 4, 3 * tile_size, false),
+            ]
+        );
+        assert_eq!(alphas.len(), 3 * tile_size as usize);
+        assert!(
+            alphas[COLUMN_SIZE..tile_size as usize]
+                .iter()
+                .all(|a| *a == 255)
+        );
+        assert_eq!(&alphas[..4], &[170, 247, 255, 255]);
+    }
+}
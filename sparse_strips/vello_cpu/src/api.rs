@@ -11,16 +11,21 @@
 //! </div>
 
 use vello_api::{
-    PaintScene, Scene,
+    PaintError, PaintScene, Scene,
     exact::ExactPathElements,
+    paths::{PathId, PathKey, PathSet},
     peniko::Style,
-    scene::{RenderCommand, extract_integer_translation},
-    texture::TextureId,
+    scene::{RenderCommand, check_balanced, extract_integer_translation},
+    texture::{TextureId, TextureReadback},
 };
 use vello_common::{
-    kurbo::{self, Affine, BezPath},
+    blurred_rounded_rect::BlurredRoundedRectangle,
+    kurbo::{self, Affine, BezPath, PathEl},
     paint::{ImageId, ImageSource},
     peniko::{BlendMode, Brush, Color, Fill, ImageBrush},
+    pixmap::Pixmap,
+    strip_cache::StripCache,
+    strip_generator::StripStorage,
 };
 
 use crate::RenderContext;
@@ -33,6 +38,63 @@ pub struct CPUScenePainter {
     /// The underlying render context. This is public on an interim basis, whilst we decide how
     /// Vello API will develop further.
     pub render_context: RenderContext,
+    strip_cache: StripCache<PathKey>,
+    strip_storage: StripStorage,
+}
+
+impl CPUScenePainter {
+    /// Create a new painter which draws into `render_context`.
+    pub fn new(render_context: RenderContext) -> Self {
+        let strip_cache = StripCache::new(
+            render_context.width(),
+            render_context.height(),
+            render_context.render_settings().level,
+        );
+        Self {
+            render_context,
+            strip_cache,
+            strip_storage: StripStorage::default(),
+        }
+    }
+
+    /// The cache of the strips of paths drawn using [`PaintScene::draw_prepared_path`].
+    ///
+    /// [`StripCache::maintain`] should be called once per frame, to evict the paths which are no longer drawn.
+    /// Otherwise, entries are only evicted once the cache is full.
+    pub fn path_cache_mut(&mut self) -> &mut StripCache<PathKey> {
+        &mut self.strip_cache
+    }
+
+    fn draw_cached_path(
+        &mut self,
+        transform: Affine,
+        elements: &[PathEl],
+        style: &Style,
+        key: PathKey,
+    ) {
+        self.render_context.set_transform(transform);
+        if self.render_context.draw_cached_path(
+            &mut self.strip_cache,
+            key,
+            elements,
+            style,
+            &mut self.strip_storage,
+        ) {
+            return;
+        }
+        // TODO: As in `fill_path`
+        let bezpath = BezPath::from_iter(elements.iter().copied());
+        match style {
+            Style::Stroke(stroke) => {
+                self.render_context.set_stroke(stroke.clone());
+                self.render_context.stroke_path(&bezpath);
+            }
+            Style::Fill(fill) => {
+                self.render_context.set_fill_rule(*fill);
+                self.render_context.fill_path(&bezpath);
+            }
+        }
+    }
 }
 
 impl PaintScene for CPUScenePainter {
@@ -46,7 +108,13 @@ impl PaintScene for CPUScenePainter {
 
             hinted: input_hinted,
         }: &Scene,
-    ) -> Result<(), ()> {
+    ) -> Result<(), PaintError> {
+        check_balanced(input_commands)?;
+        for command in input_commands {
+            if let RenderCommand::SetPaint(_, Brush::Image(brush)) = command {
+                image_id(brush.image)?;
+            }
+        }
         if *input_hinted {
             if let Some((dx, dy)) = extract_integer_translation(scene_transform) {
                 // Update the transform to be a pure integer translation.
@@ -55,8 +123,7 @@ impl PaintScene for CPUScenePainter {
                 // makes our reasoning about this easier.
                 scene_transform = Affine::translate((dx, dy));
             } else {
-                // Translation not hinting compatible.
-                return Err(());
+                return Err(PaintError::HintingIncompatibleTransform);
             }
         }
         for command in input_commands {
@@ -82,6 +149,15 @@ impl PaintScene for CPUScenePainter {
                         }
                     }
                 }
+                RenderCommand::DrawPreparedPath(affine, path_id, key) => {
+                    let path = &input_paths.meta[usize::try_from(path_id.0).unwrap()];
+                    self.draw_cached_path(
+                        scene_transform * *affine,
+                        input_paths.elements(*path_id),
+                        &path.operation,
+                        *key,
+                    );
+                }
                 RenderCommand::PushLayer(push_layer_command) => {
                     self.render_context
                         .set_transform(push_layer_command.clip_transform);
@@ -108,26 +184,42 @@ impl PaintScene for CPUScenePainter {
                     );
                 }
                 RenderCommand::PopLayer => self.render_context.pop_layer(),
+                RenderCommand::PushClipPath(clip_transform, path_id) => {
+                    let path = &input_paths.meta[usize::try_from(path_id.0).unwrap()];
+                    // TODO: Also correctly support the case where the meta has a `Style::Stroke`
+                    let fill_rule = match &path.operation {
+                        Style::Fill(fill) => *fill,
+                        Style::Stroke(_) => Fill::NonZero,
+                    };
+                    // TODO: As in `fill_path`
+                    let bezpath =
+                        BezPath::from_iter(input_paths.elements(*path_id).iter().copied());
+                    self.render_context.set_fill_rule(fill_rule);
+                    self.render_context
+                        .set_transform(scene_transform * *clip_transform);
+                    self.render_context.push_clip_path(&bezpath);
+                }
+                RenderCommand::PopClipPath => self.render_context.pop_clip_path(),
                 RenderCommand::SetPaint(paint_transform, brush) => {
                     self.render_context.set_paint_transform(*paint_transform);
                     let brush = match brush {
                         Brush::Solid(alpha_color) => Brush::Solid(*alpha_color),
                         Brush::Gradient(gradient) => Brush::Gradient(gradient.clone()),
-                        Brush::Image(brush) => {
-                            let image_index =
-                                brush.image.to_raw().try_into().expect("Handle this.");
-                            Brush::Image(ImageBrush {
-                                image: ImageSource::opaque_id(ImageId::new(image_index)),
-                                sampler: brush.sampler,
-                            })
-                        }
+                        Brush::Image(brush) => Brush::Image(ImageBrush {
+                            image: ImageSource::opaque_id(image_id(brush.image)?),
+                            sampler: brush.sampler,
+                        }),
                     };
                     self.render_context.set_paint(brush);
                 }
-                RenderCommand::BlurredRoundedRectPaint(_) => {
-                    unimplemented!(
-                        "Vello CPU doesn't expose drawing a blurred rounded rectangle in custom shapes yet."
-                    )
+                RenderCommand::BlurredRoundedRectPaint(brush) => {
+                    self.set_blurred_rounded_rect_brush(
+                        brush.paint_transform,
+                        brush.color,
+                        &brush.rect,
+                        brush.radius,
+                        brush.std_dev,
+                    );
                 }
             }
         }
@@ -158,39 +250,44 @@ impl PaintScene for CPUScenePainter {
             .stroke_path(&path.exact_path_elements().collect());
     }
 
+    fn draw_prepared_path(&mut self, transform: Affine, paths: &PathSet, path: PathId) {
+        let style = &paths.meta[usize::try_from(path.0).unwrap()].operation;
+        self.draw_cached_path(transform, paths.elements(path), style, paths.key(path));
+    }
+
     fn set_brush(
         &mut self,
         brush: impl Into<Brush<ImageBrush<TextureId>>>,
         paint_transform: Affine,
-    ) {
-        self.render_context.set_paint_transform(paint_transform);
+    ) -> Result<(), PaintError> {
         let brush = match brush.into() {
             Brush::Solid(alpha_color) => Brush::Solid(alpha_color),
             Brush::Gradient(gradient) => Brush::Gradient(gradient),
-            Brush::Image(brush) => {
-                // TODO: Make this read more easily.
-                let image_index = brush.image.to_raw().try_into().expect("Handle this.");
-                Brush::Image(ImageBrush {
-                    image: ImageSource::opaque_id(ImageId::new(image_index)),
-                    sampler: brush.sampler,
-                })
-            }
+            Brush::Image(brush) => Brush::Image(ImageBrush {
+                image: ImageSource::opaque_id(image_id(brush.image)?),
+                sampler: brush.sampler,
+            }),
         };
+        self.render_context.set_paint_transform(paint_transform);
         self.render_context.set_paint(brush);
+        Ok(())
     }
 
     fn set_blurred_rounded_rect_brush(
         &mut self,
-        _paint_transform: Affine,
-        _color: Color,
-        _rect: &kurbo::Rect,
-        _radius: f32,
-        _std_dev: f32,
+        paint_transform: Affine,
+        color: Color,
+        rect: &kurbo::Rect,
+        radius: f32,
+        std_dev: f32,
     ) {
-        // This is "trivially" fixable.
-        unimplemented!(
-            "Vello CPU doesn't expose drawing a blurred rounded rectangle in custom shapes yet."
-        )
+        self.render_context.set_paint_transform(paint_transform);
+        self.render_context.set_paint(BlurredRoundedRectangle {
+            rect: *rect,
+            color,
+            radius,
+            std_dev,
+        });
     }
 
     fn fill_blurred_rounded_rect(
@@ -242,4 +339,43 @@ impl PaintScene for CPUScenePainter {
     fn pop_layer(&mut self) {
         self.render_context.pop_layer();
     }
+
+    fn push_clip_path(
+        &mut self,
+        clip_transform: Affine,
+        fill_rule: Fill,
+        path: &impl ExactPathElements,
+    ) {
+        self.render_context.set_fill_rule(fill_rule);
+        self.render_context.set_transform(clip_transform);
+        // TODO: As in `fill_path`
+        self.render_context
+            .push_clip_path(&path.exact_path_elements().collect());
+    }
+
+    fn pop_clip_path(&mut self) {
+        self.render_context.pop_clip_path();
+    }
+}
+
+/// Textures are read back from the image registry of the render context, so are available immediately.
+impl TextureReadback for RenderContext {
+    fn read_texture(&self, texture: TextureId) -> impl Future<Output = Result<Pixmap, PaintError>> {
+        core::future::ready(self.read_texture_blocking(texture))
+    }
+
+    fn read_texture_blocking(&self, texture: TextureId) -> Result<Pixmap, PaintError> {
+        self.resolve_image(image_id(texture)?)
+            .map(|pixmap| Pixmap::clone(&pixmap))
+            .ok_or(PaintError::InvalidTexture(texture))
+    }
+}
+
+/// The image id corresponding to the texture `texture`.
+fn image_id(texture: TextureId) -> Result<ImageId, PaintError> {
+    texture
+        .to_raw()
+        .try_into()
+        .map(ImageId::new)
+        .map_err(|_| PaintError::InvalidTexture(texture))
 }
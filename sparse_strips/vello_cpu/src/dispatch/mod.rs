@@ -16,6 +16,7 @@ use vello_common::mask::Mask;
 use vello_common::paint::{ImageResolver, Paint};
 use vello_common::strip::Strip;
 use vello_common::strip_generator::StripStorage;
+use vello_common::subpixel::SubpixelOrder;
 
 pub(crate) trait Dispatcher: Debug + Send + Sync {
     fn wide(&self) -> &Wide;
@@ -37,6 +38,21 @@ pub(crate) trait Dispatcher: Debug + Send + Sync {
         mask: Option<Mask>,
         encoded_paints: &[EncodedPaint],
     );
+    /// Fill a path with subpixel coverage, using the default blend mode and no mask.
+    ///
+    /// Falls back to a regular fill if a clip path is active.
+    #[cfg_attr(
+        not(feature = "text"),
+        allow(dead_code, reason = "used when the `text` feature is enabled")
+    )]
+    fn fill_path_subpixel(
+        &mut self,
+        path: &BezPath,
+        transform: Affine,
+        paint: Paint,
+        subpixel_order: SubpixelOrder,
+        encoded_paints: &[EncodedPaint],
+    );
     fn stroke_path(
         &mut self,
         path: &BezPath,
@@ -65,6 +81,8 @@ pub(crate) trait Dispatcher: Debug + Send + Sync {
         aliasing_threshold: Option<u8>,
     );
     fn pop_clip_path(&mut self);
+    /// Whether a clip path is currently active.
+    fn has_clip_path(&self) -> bool;
     fn push_layer(
         &mut self,
         clip_path: Option<&BezPath>,
@@ -7,6 +7,7 @@ use crate::dispatch::multi_threaded::cost::{COST_THRESHOLD, estimate_render_task
 use crate::dispatch::multi_threaded::worker::Worker;
 use crate::fine::{Fine, FineKernel};
 use crate::kurbo::{Affine, BezPath, PathEl, Point, Rect, Stroke};
+use crate::layer_manager::LayerManager;
 use crate::peniko::{BlendMode, Fill};
 use crate::region::Regions;
 use alloc::boxed::Box;
@@ -15,6 +16,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::fmt::{Debug, Formatter};
 use crossbeam_channel::TryRecvError;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::cell::RefCell;
 use std::ops::Range;
@@ -22,15 +24,19 @@ use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::{Barrier, Mutex};
 use thread_local::ThreadLocal;
 use vello_common::clip::ClipContext;
-use vello_common::coarse::{Cmd, MODE_CPU, Wide};
+use vello_common::coarse::{Cmd, LayerKind, MODE_CPU, Wide, WideTile, WideTilesBbox};
+use vello_common::color::palette::css::TRANSPARENT;
 use vello_common::encode::EncodedPaint;
 use vello_common::fearless_simd::{Level, Simd, dispatch};
 use vello_common::filter_effects::Filter;
 use vello_common::mask::Mask;
-use vello_common::paint::{ImageResolver, Paint};
-use vello_common::render_graph::RenderGraph;
+use vello_common::paint::{ImageResolver, Paint, PremulColor};
+use vello_common::pixmap::Pixmap;
+use vello_common::render_graph::{NodeId, RenderGraph, RenderNodeKind};
 use vello_common::strip::Strip;
 use vello_common::strip_generator::{StripGenerator, StripStorage};
+use vello_common::subpixel::SubpixelOrder;
+use vello_common::tile::Tile;
 
 mod cost;
 mod worker;
@@ -105,7 +111,12 @@ pub(crate) struct MultiThreadedDispatcher {
     flushed: bool,
     // So that we can reuse memory allocations across different runs.
     allocations: Allocations,
-    /// Render graph (unused in multi-threaded, only needed for API compatibility with Wide).
+    /// Counter for generating unique layer IDs.
+    layer_id_next: u32,
+    /// Dependency graph tracking layer relationships and filter effects.
+    ///
+    /// Like the wide tile container, it is only ever updated on the main thread during coarse
+    /// rasterization.
     render_graph: RenderGraph,
 }
 
@@ -137,6 +148,16 @@ impl MultiThreadedDispatcher {
         let task_idx = 0;
         let batch_cost = 0.0;
         let flushed = false;
+        let mut render_graph = RenderGraph::new();
+
+        // Create root node (layer_id 0) as the first node (will be node 0), same as in the
+        // single-threaded dispatcher.
+        let wtile_bbox = WideTilesBbox::new([0, 0, wide.width_tiles(), wide.height_tiles()]);
+        let root_node = render_graph.add_node(RenderNodeKind::RootLayer {
+            layer_id: 0,
+            wtile_bbox,
+        });
+        assert_eq!(root_node, 0, "Root node must be node 0");
 
         let mut dispatcher = Self {
             wide,
@@ -155,7 +176,8 @@ impl MultiThreadedDispatcher {
             level,
             alpha_storage,
             num_threads,
-            render_graph: RenderGraph::new(),
+            layer_id_next: 0,
+            render_graph,
         };
 
         dispatcher.init();
@@ -189,6 +211,44 @@ impl MultiThreadedDispatcher {
         dispatch!(self.level, simd => self.rasterize_with::<_, U8Kernel>(simd, buffer, width, height, encoded_paints, image_resolver));
     }
 
+    #[cfg(feature = "f32_pipeline")]
+    fn composite_at_offset_f32(
+        &self,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        dst_x: u16,
+        dst_y: u16,
+        dst_buffer_width: u16,
+        dst_buffer_height: u16,
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
+    ) {
+        use crate::fine::F32Kernel;
+        dispatch!(self.level, simd => self.composite_at_offset_with::<_, F32Kernel>(
+            simd, buffer, width, height, dst_x, dst_y, dst_buffer_width, dst_buffer_height, encoded_paints, image_resolver
+        ));
+    }
+
+    #[cfg(feature = "u8_pipeline")]
+    fn composite_at_offset_u8(
+        &self,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        dst_x: u16,
+        dst_y: u16,
+        dst_buffer_width: u16,
+        dst_buffer_height: u16,
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
+    ) {
+        use crate::fine::U8Kernel;
+        dispatch!(self.level, simd => self.composite_at_offset_with::<_, U8Kernel>(
+            simd, buffer, width, height, dst_x, dst_y, dst_buffer_width, dst_buffer_height, encoded_paints, image_resolver
+        ));
+    }
+
     fn init(&mut self) {
         let (render_task_sender, render_task_receiver) = crossbeam_channel::unbounded();
         let (coarse_task_sender, coarse_task_receiver) = ordered_channel::unbounded();
@@ -315,6 +375,17 @@ impl MultiThreadedDispatcher {
                                 mask,
                                 encoded_paints,
                             ),
+                            CoarseTaskType::RenderSubpixelPath {
+                                strips: strip_range,
+                                paint,
+                                thread_id,
+                            } => self.wide.generate_subpixel(
+                                &task.allocation_group.strips
+                                    [strip_range.start as usize..strip_range.end as usize],
+                                paint.clone(),
+                                thread_id,
+                                encoded_paints,
+                            ),
                             CoarseTaskType::RenderWideCommand {
                                 strips,
                                 blend_mode,
@@ -331,26 +402,27 @@ impl MultiThreadedDispatcher {
                             ),
                             CoarseTaskType::PushLayer {
                                 thread_id,
+                                layer_id,
                                 clip_path,
                                 blend_mode,
                                 mask,
                                 opacity,
+                                filter,
+                                transform,
                             } => {
                                 let clip_path = clip_path.map(|strip_range| {
                                     &task.allocation_group.strips
                                         [strip_range.start as usize..strip_range.end as usize]
                                 });
 
-                                // layer_id 0 and filter None since filters aren't supported
                                 self.wide.push_layer(
-                                    0,
+                                    layer_id,
                                     clip_path,
                                     blend_mode,
                                     mask,
                                     opacity,
-                                    None,
-                                    // Transform can be IDENTITY because filters aren't supported in multi-threaded mode
-                                    Affine::IDENTITY,
+                                    filter,
+                                    transform,
                                     &mut self.render_graph,
                                     thread_id,
                                 );
@@ -374,6 +446,10 @@ impl MultiThreadedDispatcher {
         }
     }
 
+    /// Core rasterization dispatcher that chooses between simple and filter-aware paths.
+    ///
+    /// If the scene contains filter effects, uses the filter-aware path which maintains
+    /// intermediate layer buffers. Otherwise, uses the simpler direct rasterization path.
     fn rasterize_with<S: Simd, F: FineKernel<S>>(
         &self,
         simd: S,
@@ -382,14 +458,226 @@ impl MultiThreadedDispatcher {
         height: u16,
         encoded_paints: &[EncodedPaint],
         image_resolver: &dyn ImageResolver,
+    ) {
+        let alpha_slots = self.alpha_storage.take();
+
+        self.thread_pool.install(|| {
+            if self.render_graph.has_filters() {
+                self.rasterize_with_filters::<S, F>(
+                    simd,
+                    buffer,
+                    width,
+                    height,
+                    &alpha_slots,
+                    encoded_paints,
+                    image_resolver,
+                );
+            } else {
+                self.rasterize_simple::<S, F>(
+                    simd,
+                    buffer,
+                    width,
+                    height,
+                    &alpha_slots,
+                    encoded_paints,
+                    image_resolver,
+                );
+            }
+        });
+
+        // Don't forget to put back the alpha buffers, so that they can be re-used in
+        // the next path rendering iteration!
+        self.alpha_storage.init(alpha_slots);
+    }
+
+    /// Simple rasterization path for scenes without filter effects.
+    ///
+    /// All wide tiles are rendered in parallel, directly into the output buffer.
+    fn rasterize_simple<S: Simd, F: FineKernel<S>>(
+        &self,
+        simd: S,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        alpha_slots: &[Vec<u8>],
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
     ) {
         let mut buffer = Regions::new(width, height, buffer);
         let fines = ThreadLocal::new();
         let wide = &self.wide;
+
+        buffer.update_regions_par(|region| {
+            let x = region.x;
+            let y = region.y;
+
+            let mut fine = fines
+                .get_or(|| RefCell::new(Fine::<S, F>::new(simd)))
+                .borrow_mut();
+
+            let wtile = wide.get(x, y);
+            fine.set_coords(x, y);
+
+            fine.clear(wtile.bg);
+            for cmd in &wtile.cmds {
+                let alphas = cmd_alphas(wide, cmd, alpha_slots);
+                fine.run_cmd(cmd, alphas, encoded_paints, image_resolver, &wide.attrs);
+            }
+
+            fine.pack(region);
+        });
+    }
+
+    /// Rasterizes a scene with filter effects.
+    ///
+    /// This follows the same approach as the single-threaded dispatcher: each filtered layer
+    /// is rendered into its own pixmap, filtered and stored in the layer manager, from where it
+    /// is composited into its parent layer when that one is rendered. Work is spread across the
+    /// thread pool in two ways:
+    /// - The wide tiles of each layer are rendered in parallel.
+    /// - Filtered layers that don't depend on each other are rendered and filtered in parallel.
+    fn rasterize_with_filters<S: Simd, F: FineKernel<S>>(
+        &self,
+        simd: S,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        alpha_slots: &[Vec<u8>],
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
+    ) {
+        let fines = ThreadLocal::new();
+        // Filters only use the layer manager for scratch buffers, so each thread gets its own.
+        let scratch_managers = ThreadLocal::new();
+        // Filtered layers are only added in between waves, while tiles are rendered they
+        // are only read from.
+        let layer_manager = Mutex::new(LayerManager::new());
+
+        for wave in self.filter_layer_waves() {
+            let filtered_layers: Vec<_> = wave
+                .par_iter()
+                .map(|&node_id| {
+                    let RenderNodeKind::FilterLayer {
+                        layer_id,
+                        filter,
+                        wtile_bbox,
+                        transform,
+                    } = &self.render_graph.nodes[node_id].kind
+                    else {
+                        unreachable!("waves only contain filter layers");
+                    };
+
+                    // Allocate intermediate buffer for this filtered layer.
+                    let bbox_width = wtile_bbox.width_px();
+                    let bbox_height = wtile_bbox.height_px();
+                    let mut pixmap = Pixmap::new(bbox_width, bbox_height);
+                    let mut regions =
+                        Regions::new(bbox_width, bbox_height, pixmap.data_as_u8_slice_mut());
+
+                    regions.update_regions_par(|region| {
+                        let mut fine = fines
+                            .get_or(|| RefCell::new(Fine::<S, F>::new(simd)))
+                            .borrow_mut();
+
+                        // Convert region-local coords to global wtile coords.
+                        self.process_layer_tile(
+                            &mut fine,
+                            wtile_bbox.x0() + region.x,
+                            wtile_bbox.y0() + region.y,
+                            *layer_id,
+                            PremulColor::from_alpha_color(TRANSPARENT),
+                            alpha_slots,
+                            &layer_manager,
+                            encoded_paints,
+                            image_resolver,
+                        );
+
+                        fine.pack(region);
+                    });
+
+                    // Apply the filter effect to the completed layer. Filters expect a
+                    // transform to the pixel coordinates of the layer, not of the canvas.
+                    let layer_transform = Affine::translate((
+                        -f64::from(u32::from(wtile_bbox.x0()) * u32::from(WideTile::WIDTH)),
+                        -f64::from(u32::from(wtile_bbox.y0()) * u32::from(Tile::HEIGHT)),
+                    )) * *transform;
+                    let fine = fines
+                        .get_or(|| RefCell::new(Fine::<S, F>::new(simd)))
+                        .borrow();
+                    let mut scratch = scratch_managers
+                        .get_or(|| RefCell::new(LayerManager::new()))
+                        .borrow_mut();
+                    fine.filter_layer(&mut pixmap, filter, &mut scratch, layer_transform);
+
+                    (*layer_id, *wtile_bbox, pixmap)
+                })
+                .collect();
+
+            // Store the filtered results for use by dependent layers.
+            let mut layer_manager = layer_manager.lock().unwrap();
+            for (layer_id, wtile_bbox, pixmap) in filtered_layers {
+                layer_manager.register_layer(layer_id, wtile_bbox, pixmap);
+            }
+        }
+
+        // Final composition directly to output buffer.
+        let mut regions = Regions::new(width, height, buffer);
+        regions.update_regions_par(|region| {
+            let mut fine = fines
+                .get_or(|| RefCell::new(Fine::<S, F>::new(simd)))
+                .borrow_mut();
+
+            // Use the background color from the wide tile.
+            let bg = self.wide.get(region.x, region.y).bg;
+            self.process_layer_tile(
+                &mut fine,
+                region.x,
+                region.y,
+                0,
+                bg,
+                alpha_slots,
+                &layer_manager,
+                encoded_paints,
+                image_resolver,
+            );
+
+            fine.pack(region);
+        });
+    }
+
+    /// Core implementation for compositing at an offset.
+    ///
+    /// Like `rasterize_simple`, but the wide tiles are composited on top of the existing
+    /// contents of the destination buffer, starting at the given offset. Tiles that end up
+    /// outside of the destination buffer are skipped.
+    fn composite_at_offset_with<S: Simd, F: FineKernel<S>>(
+        &self,
+        simd: S,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        dst_x: u16,
+        dst_y: u16,
+        dst_buffer_width: u16,
+        dst_buffer_height: u16,
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
+    ) {
+        let mut regions = Regions::new_at_offset(
+            width,
+            height,
+            dst_x,
+            dst_y,
+            dst_buffer_width,
+            dst_buffer_height,
+            buffer,
+        );
+        let fines = ThreadLocal::new();
+        let wide = &self.wide;
         let alpha_slots = self.alpha_storage.take();
 
         self.thread_pool.install(|| {
-            buffer.update_regions_par(|region| {
+            regions.update_regions_par(|region| {
                 let x = region.x;
                 let y = region.y;
 
@@ -400,17 +688,11 @@ impl MultiThreadedDispatcher {
                 let wtile = wide.get(x, y);
                 fine.set_coords(x, y);
 
-                fine.clear(wtile.bg);
+                // Unpack existing pixel data from the region instead of clearing,
+                // so that rendering composites onto the existing pixmap contents.
+                fine.unpack(region);
                 for cmd in &wtile.cmds {
-                    let thread_idx = match cmd {
-                        Cmd::AlphaFill(a) => Some(wide.attrs.fill[a.attrs_idx as usize].thread_idx),
-                        Cmd::ClipStrip(a) => Some(wide.attrs.clip[a.attrs_idx as usize].thread_idx),
-                        _ => None,
-                    };
-
-                    let alphas = thread_idx
-                        .map(|i| alpha_slots[i as usize].as_slice())
-                        .unwrap_or(&[]);
+                    let alphas = cmd_alphas(wide, cmd, &alpha_slots);
                     fine.run_cmd(cmd, alphas, encoded_paints, image_resolver, &wide.attrs);
                 }
 
@@ -418,10 +700,143 @@ impl MultiThreadedDispatcher {
             });
         });
 
-        // Don't forget to put back the alpha buffers, so that they can be re-used in
-        // the next path rendering iteration!
         self.alpha_storage.init(alpha_slots);
     }
+
+    /// Group the filtered layers of the render graph into waves, such that each layer only
+    /// depends on layers of earlier waves.
+    ///
+    /// The layers within a wave are independent of each other and can be processed in parallel.
+    fn filter_layer_waves(&self) -> Vec<Vec<NodeId>> {
+        let graph = &self.render_graph;
+        let mut parents = vec![None; graph.nodes.len()];
+        for edge in &graph.edges {
+            parents[edge.from] = Some(edge.to);
+        }
+
+        let mut depths = vec![0; graph.nodes.len()];
+        let mut waves: Vec<Vec<NodeId>> = vec![];
+
+        // Children are always executed before their parents, so the depth of a node is
+        // final by the time we visit it.
+        for node_id in graph.execution_order() {
+            if !matches!(
+                graph.nodes[node_id].kind,
+                RenderNodeKind::FilterLayer { .. }
+            ) {
+                continue;
+            }
+
+            let depth = depths[node_id];
+            if waves.len() <= depth {
+                waves.resize_with(depth + 1, Vec::new);
+            }
+            waves[depth].push(node_id);
+
+            if let Some(parent) = parents[node_id] {
+                depths[parent] = depths[parent].max(depth + 1);
+            }
+        }
+
+        waves
+    }
+
+    /// Processes all rendering commands for a single layer within a specific tile.
+    ///
+    /// See the method of the same name in the single-threaded dispatcher for details. The only
+    /// differences are that alphas are looked up in the buffer of the thread that generated
+    /// them, and that the layer manager is shared between threads.
+    fn process_layer_tile<S: Simd, F: FineKernel<S>>(
+        &self,
+        fine: &mut Fine<S, F>,
+        x: u16,
+        y: u16,
+        layer_id: u32,
+        clear_color: PremulColor,
+        alpha_slots: &[Vec<u8>],
+        layer_manager: &Mutex<LayerManager>,
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
+    ) {
+        let wide = &self.wide;
+        let wtile = wide.get(x, y);
+        fine.set_coords(x, y);
+        fine.clear(clear_color);
+
+        let Some(ranges) = wtile.layer_cmd_ranges.get(&layer_id) else {
+            return;
+        };
+
+        let unpack_layer = |fine: &mut Fine<S, F>, child_layer_id: u32| {
+            let mut layer_manager = layer_manager.lock().unwrap();
+            if let Some(mut region) = layer_manager.layer_tile_region_mut(child_layer_id, x, y) {
+                fine.unpack(&mut region);
+            }
+        };
+
+        let mut cmd_idx = ranges.render_range.start;
+        while cmd_idx < ranges.render_range.end {
+            let cmd: &Cmd = &wtile.cmds[cmd_idx];
+            let alphas = cmd_alphas(wide, cmd, alpha_slots);
+            fine.run_cmd(cmd, alphas, encoded_paints, image_resolver, &wide.attrs);
+
+            if let Cmd::PushBuf(LayerKind::Filtered(child_layer_id), _) = cmd {
+                let filtered_ranges = wtile.layer_cmd_ranges.get(child_layer_id).unwrap();
+
+                match wtile.cmds.get(cmd_idx + 1) {
+                    // Zero-clip region: skip to the very end so that only `PopBuf` will be run.
+                    Some(Cmd::PushZeroClip(id)) if *id == *child_layer_id => {
+                        cmd_idx = filtered_ranges.full_range.end - 1;
+                        continue;
+                    }
+                    // Partial clip: push the clip buffer, then composite the filtered layer.
+                    Some(clip_cmd @ Cmd::PushBuf(LayerKind::Clip(_), _)) => {
+                        let alphas = cmd_alphas(wide, clip_cmd, alpha_slots);
+                        fine.run_cmd(
+                            clip_cmd,
+                            alphas,
+                            encoded_paints,
+                            image_resolver,
+                            &wide.attrs,
+                        );
+                        cmd_idx += 1;
+
+                        unpack_layer(fine, *child_layer_id);
+                    }
+                    // No clip or fully inside clip: composite the filtered layer directly.
+                    _ => unpack_layer(fine, *child_layer_id),
+                }
+
+                // Skip past the filtered layer's internal commands, as they were already
+                // rendered when the filtered layer was processed.
+                cmd_idx = filtered_ranges.render_range.end.max(cmd_idx + 1);
+            } else {
+                cmd_idx += 1;
+            }
+        }
+
+        debug_assert_eq!(
+            fine.blend_buf.len(),
+            1,
+            "blend buffer should contain exactly one layer after tile processing"
+        );
+    }
+}
+
+/// Return the alpha values referenced by a command.
+///
+/// Strips are generated on different threads, so the alphas of a command live in the
+/// buffer of the thread that generated it.
+fn cmd_alphas<'a>(wide: &Wide, cmd: &Cmd, alpha_slots: &'a [Vec<u8>]) -> &'a [u8] {
+    let thread_idx = match cmd {
+        Cmd::AlphaFill(a) => Some(wide.attrs.fill[a.attrs_idx as usize].thread_idx),
+        Cmd::ClipStrip(a) => Some(wide.attrs.clip[a.attrs_idx as usize].thread_idx),
+        _ => None,
+    };
+
+    thread_idx
+        .map(|i| alpha_slots[i as usize].as_slice())
+        .unwrap_or(&[])
 }
 
 impl Dispatcher for MultiThreadedDispatcher {
@@ -454,6 +869,41 @@ impl Dispatcher for MultiThreadedDispatcher {
         });
     }
 
+    fn fill_path_subpixel(
+        &mut self,
+        path: &BezPath,
+        transform: Affine,
+        paint: Paint,
+        subpixel_order: SubpixelOrder,
+        encoded_paints: &[EncodedPaint],
+    ) {
+        // Subpixel coverage can't be intersected with clip paths.
+        if self.clip_context.get().is_some() {
+            self.fill_path(
+                path,
+                Fill::NonZero,
+                transform,
+                paint,
+                BlendMode::default(),
+                None,
+                None,
+                encoded_paints,
+            );
+
+            return;
+        }
+
+        let start = self.allocation_group.path.len() as u32;
+        self.allocation_group.path.extend(path);
+        let end = self.allocation_group.path.len() as u32;
+        self.register_task(RenderTaskType::FillPathSubpixel {
+            path_range: start..end,
+            transform,
+            paint,
+            subpixel_order,
+        });
+    }
+
     fn stroke_path(
         &mut self,
         path: &BezPath,
@@ -513,32 +963,32 @@ impl Dispatcher for MultiThreadedDispatcher {
         &mut self,
         clip_path: Option<&BezPath>,
         fill_rule: Fill,
-        clip_transform: Affine,
+        transform: Affine,
         blend_mode: BlendMode,
         opacity: f32,
         aliasing_threshold: Option<u8>,
         mask: Option<Mask>,
         filter: Option<Filter>,
     ) {
-        // TODO: Implement filter support in multi-threaded dispatcher.
-        // The single-threaded dispatcher has full support via LayerManager and render graph execution,
-        // but multi-threaded needs additional infrastructure for cross-thread layer coordination.
-        if filter.is_some() {
-            unimplemented!("Filter effects are not yet supported in multi-threaded rendering");
-        }
+        // Layer IDs are allocated on the main thread, so that they are unique and stable
+        // regardless of which worker thread ends up processing the layer.
+        self.layer_id_next += 1;
 
         let mapped_clip = clip_path.map(|c| {
             let start = self.allocation_group.path.len() as u32;
             self.allocation_group.path.extend(c);
             let end = self.allocation_group.path.len() as u32;
-            (start..end, clip_transform)
+            (start..end, transform)
         });
 
         self.register_task(RenderTaskType::PushLayer {
+            layer_id: self.layer_id_next,
             clip_path: mapped_clip,
             blend_mode,
             opacity,
             mask,
+            filter,
+            transform,
             fill_rule,
             aliasing_threshold,
         });
@@ -559,6 +1009,21 @@ impl Dispatcher for MultiThreadedDispatcher {
         self.coarse_task_receiver = None;
         self.strip_generator.reset();
         self.strip_storage.clear();
+        self.render_graph.clear();
+        self.layer_id_next = 0;
+
+        // Recreate root node as node 0 (required for proper execution order).
+        let root_node = self.render_graph.add_node(RenderNodeKind::RootLayer {
+            layer_id: 0,
+            wtile_bbox: WideTilesBbox::new([
+                0,
+                0,
+                self.wide.width_tiles(),
+                self.wide.height_tiles(),
+            ]),
+        });
+        debug_assert_eq!(root_node, 0, "Root node must be node 0");
+
         self.alpha_storage.with_inner(|alphas| {
             for alpha in alphas {
                 alpha.clear();
@@ -642,19 +1107,82 @@ impl Dispatcher for MultiThreadedDispatcher {
 
     fn composite_at_offset(
         &self,
-        _buffer: &mut [u8],
-        _width: u16,
-        _height: u16,
-        _dst_x: u16,
-        _dst_y: u16,
-        _dst_buffer_width: u16,
-        _dst_buffer_height: u16,
-        _render_mode: RenderMode,
-        _encoded_paints: &[EncodedPaint],
-        _image_resolver: &dyn ImageResolver,
+        buffer: &mut [u8],
+        width: u16,
+        height: u16,
+        dst_x: u16,
+        dst_y: u16,
+        dst_buffer_width: u16,
+        dst_buffer_height: u16,
+        render_mode: RenderMode,
+        encoded_paints: &[EncodedPaint],
+        image_resolver: &dyn ImageResolver,
     ) {
-        // TODO: Implement composite_at_offset for multi-threaded dispatcher.
-        unimplemented!("composite_at_offset is not implemented for multi-threaded dispatcher");
+        assert!(self.flushed, "attempted to composite before flushing");
+
+        // Only u8 pipeline enabled
+        #[cfg(all(feature = "u8_pipeline", not(feature = "f32_pipeline")))]
+        {
+            let _ = render_mode;
+            self.composite_at_offset_u8(
+                buffer,
+                width,
+                height,
+                dst_x,
+                dst_y,
+                dst_buffer_width,
+                dst_buffer_height,
+                encoded_paints,
+                image_resolver,
+            );
+        }
+        // Only f32 pipeline enabled
+        #[cfg(all(feature = "f32_pipeline", not(feature = "u8_pipeline")))]
+        {
+            let _ = render_mode;
+            self.composite_at_offset_f32(
+                buffer,
+                width,
+                height,
+                dst_x,
+                dst_y,
+                dst_buffer_width,
+                dst_buffer_height,
+                encoded_paints,
+                image_resolver,
+            );
+        }
+
+        // Both pipelines enabled
+        #[cfg(all(feature = "f32_pipeline", feature = "u8_pipeline"))]
+        match render_mode {
+            RenderMode::OptimizeSpeed => {
+                self.composite_at_offset_u8(
+                    buffer,
+                    width,
+                    height,
+                    dst_x,
+                    dst_y,
+                    dst_buffer_width,
+                    dst_buffer_height,
+                    encoded_paints,
+                    image_resolver,
+                );
+            }
+            RenderMode::OptimizeQuality => {
+                self.composite_at_offset_f32(
+                    buffer,
+                    width,
+                    height,
+                    dst_x,
+                    dst_y,
+                    dst_buffer_width,
+                    dst_buffer_height,
+                    encoded_paints,
+                    image_resolver,
+                );
+            }
+        }
     }
 
     fn generate_wide_cmd(
@@ -706,6 +1234,10 @@ impl Dispatcher for MultiThreadedDispatcher {
         self.flush_tasks();
         self.clip_context.pop_clip();
     }
+
+    fn has_clip_path(&self) -> bool {
+        self.clip_context.get().is_some()
+    }
 }
 
 impl Debug for MultiThreadedDispatcher {
@@ -824,6 +1356,12 @@ pub(crate) enum RenderTaskType {
         aliasing_threshold: Option<u8>,
         mask: Option<Mask>,
     },
+    FillPathSubpixel {
+        path_range: Range<u32>,
+        transform: Affine,
+        paint: Paint,
+        subpixel_order: SubpixelOrder,
+    },
     WideCommand {
         strip_buf: Box<[Strip]>,
         thread_idx: u8,
@@ -840,10 +1378,13 @@ pub(crate) enum RenderTaskType {
         mask: Option<Mask>,
     },
     PushLayer {
+        layer_id: u32,
         clip_path: Option<(Range<u32>, Affine)>,
         blend_mode: BlendMode,
         opacity: f32,
         mask: Option<Mask>,
+        filter: Option<Filter>,
+        transform: Affine,
         fill_rule: Fill,
         aliasing_threshold: Option<u8>,
     },
@@ -863,6 +1404,11 @@ pub(crate) enum CoarseTaskType {
         paint: Paint,
         mask: Option<Mask>,
     },
+    RenderSubpixelPath {
+        thread_id: u8,
+        strips: Range<u32>,
+        paint: Paint,
+    },
     RenderWideCommand {
         thread_id: u8,
         strips: Box<[Strip]>,
@@ -872,10 +1418,13 @@ pub(crate) enum CoarseTaskType {
     },
     PushLayer {
         thread_id: u8,
+        layer_id: u32,
         clip_path: Option<Range<u32>>,
         blend_mode: BlendMode,
         mask: Option<Mask>,
         opacity: f32,
+        filter: Option<Filter>,
+        transform: Affine,
     },
     PopLayer,
 }
@@ -932,6 +1481,8 @@ mod tests {
     use crate::dispatch::multi_threaded::MultiThreadedDispatcher;
     use crate::kurbo::{Affine, Rect, Shape};
     use crate::peniko::{BlendMode, Fill};
+    use alloc::vec;
+    use vello_common::filter_effects::{Filter, FilterPrimitive};
     use vello_common::paint::{Paint, PremulColor};
 
     /// Ensure we don't cause a memory leak.
@@ -957,4 +1508,44 @@ mod tests {
         assert_eq!(dispatcher.allocations.render_tasks.entries.len(), 1);
         assert_eq!(dispatcher.allocations.coarse_tasks.entries.len(), 1);
     }
+
+    /// Sibling filter layers are independent of each other, but their parent depends on both.
+    #[test]
+    fn filter_layer_waves() {
+        let mut dispatcher = MultiThreadedDispatcher::new(100, 100, 4, Level::new());
+        let filter = Filter::from_primitive(FilterPrimitive::Offset { dx: 1.0, dy: 1.0 });
+        let push_filter_layer = |dispatcher: &mut MultiThreadedDispatcher| {
+            dispatcher.push_layer(
+                None,
+                Fill::NonZero,
+                Affine::IDENTITY,
+                BlendMode::default(),
+                1.0,
+                None,
+                None,
+                Some(filter.clone()),
+            );
+        };
+
+        push_filter_layer(&mut dispatcher);
+        for _ in 0..2 {
+            push_filter_layer(&mut dispatcher);
+            dispatcher.fill_path(
+                &Rect::new(0.0, 0.0, 50.0, 50.0).to_path(0.1),
+                Fill::NonZero,
+                Affine::IDENTITY,
+                Paint::Solid(PremulColor::from_alpha_color(BLUE)),
+                BlendMode::default(),
+                None,
+                None,
+                &[],
+            );
+            dispatcher.pop_layer();
+        }
+        dispatcher.pop_layer();
+        dispatcher.flush(&[]);
+
+        // Node 0 is the root layer, node 1 the parent and nodes 2 and 3 its children.
+        assert_eq!(dispatcher.filter_layer_waves(), [vec![2, 3], vec![1]]);
+    }
 }
@@ -8,6 +8,7 @@ pub(crate) const COST_THRESHOLD: f32 = 250.0;
 
 use crate::dispatch::multi_threaded::RenderTaskType;
 use crate::kurbo::{Affine, PathEl, PathSeg, Point, segments};
+use vello_common::subpixel;
 
 /// Try to estimate the cost of the render task.
 pub(crate) fn estimate_render_task_cost(task: &RenderTaskType, paths: &[PathEl]) -> f32 {
@@ -22,6 +23,18 @@ pub(crate) fn estimate_render_task_cost(task: &RenderTaskType, paths: &[PathEl])
             let path = &paths[path_range.start as usize..path_range.end as usize];
             estimate_path_cost(segments(path.iter().copied()), *transform, false)
         }
+        RenderTaskType::FillPathSubpixel {
+            path_range,
+            transform,
+            ..
+        } => {
+            let path = &paths[path_range.start as usize..path_range.end as usize];
+            // Subpixel coverage is computed at a higher horizontal resolution, so account for
+            // the longer path.
+            let transform =
+                Affine::scale_non_uniform(f64::from(subpixel::OVERSAMPLING), 1.0) * *transform;
+            estimate_path_cost(segments(path.iter().copied()), transform, false)
+        }
         RenderTaskType::StrokePath {
             path_range,
             transform,
@@ -5,6 +5,7 @@ use crate::Level;
 use crate::dispatch::multi_threaded::{
     CoarseTask, CoarseTaskSender, CoarseTaskType, RenderTask, RenderTaskType,
 };
+use crate::peniko::Fill;
 use std::vec::Vec;
 use vello_common::clip::PathDataRef;
 use vello_common::strip_generator::{GenerationMode, StripGenerator, StripStorage};
@@ -97,6 +98,36 @@ impl Worker {
                         .coarse_tasks
                         .push(coarse_command);
                 }
+                RenderTaskType::FillPathSubpixel {
+                    path_range,
+                    transform,
+                    paint,
+                    subpixel_order,
+                } => {
+                    let start = self.strip_storage.strips.len() as u32;
+                    let path = &render_task.allocation_group.path
+                        [path_range.start as usize..path_range.end as usize];
+
+                    self.strip_generator.generate_subpixel_filled_path(
+                        path.iter().copied(),
+                        Fill::NonZero,
+                        transform,
+                        subpixel_order,
+                        &mut self.strip_storage,
+                    );
+                    let end = self.strip_storage.strips.len() as u32;
+
+                    let coarse_command = CoarseTaskType::RenderSubpixelPath {
+                        thread_id: self.thread_id,
+                        strips: start..end,
+                        paint,
+                    };
+
+                    render_task
+                        .allocation_group
+                        .coarse_tasks
+                        .push(coarse_command);
+                }
                 RenderTaskType::StrokePath {
                     path_range,
                     transform,
@@ -134,10 +165,13 @@ impl Worker {
                         .push(coarse_command);
                 }
                 RenderTaskType::PushLayer {
+                    layer_id,
                     clip_path,
                     blend_mode,
                     opacity,
                     mask,
+                    filter,
+                    transform: layer_transform,
                     fill_rule,
                     aliasing_threshold,
                 } => {
@@ -164,10 +198,13 @@ impl Worker {
 
                     let coarse_command = CoarseTaskType::PushLayer {
                         thread_id: self.thread_id,
+                        layer_id,
                         clip_path: clip,
                         blend_mode,
                         mask,
                         opacity,
+                        filter,
+                        transform: layer_transform,
                     };
 
                     render_task
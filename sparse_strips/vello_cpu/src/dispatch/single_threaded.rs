@@ -9,7 +9,7 @@ use crate::layer_manager::LayerManager;
 use crate::peniko::{BlendMode, Fill};
 use crate::region::Regions;
 use vello_common::clip::ClipContext;
-use vello_common::coarse::{Cmd, LayerKind, MODE_CPU, Wide, WideTilesBbox};
+use vello_common::coarse::{Cmd, LayerKind, MODE_CPU, Wide, WideTile, WideTilesBbox};
 use vello_common::color::palette::css::TRANSPARENT;
 use vello_common::encode::EncodedPaint;
 use vello_common::fearless_simd::{Level, Simd};
@@ -20,6 +20,8 @@ use vello_common::pixmap::Pixmap;
 use vello_common::render_graph::{RenderGraph, RenderNodeKind};
 use vello_common::strip::Strip;
 use vello_common::strip_generator::{StripGenerator, StripStorage};
+use vello_common::subpixel::SubpixelOrder;
+use vello_common::tile::Tile;
 
 /// Single-threaded implementation of the rendering dispatcher.
 ///
@@ -230,8 +232,13 @@ impl SingleThreadedDispatcher {
                         fine.pack(region);
                     });
 
-                    // Apply the filter effect to the completed layer.
-                    fine.filter_layer(&mut pixmap, filter, layer_manager, *transform);
+                    // Apply the filter effect to the completed layer. Filters expect a
+                    // transform to the pixel coordinates of the layer, not of the canvas.
+                    let layer_transform = Affine::translate((
+                        -f64::from(u32::from(wtile_bbox.x0()) * u32::from(WideTile::WIDTH)),
+                        -f64::from(u32::from(wtile_bbox.y0()) * u32::from(Tile::HEIGHT)),
+                    )) * *transform;
+                    fine.filter_layer(&mut pixmap, filter, layer_manager, layer_transform);
 
                     // Save the filtered pixmap to disk for debugging.
                     // #[cfg(all(debug_assertions, feature = "std", feature = "png"))]
@@ -561,6 +568,44 @@ impl Dispatcher for SingleThreadedDispatcher {
         );
     }
 
+    fn fill_path_subpixel(
+        &mut self,
+        path: &BezPath,
+        transform: Affine,
+        paint: Paint,
+        subpixel_order: SubpixelOrder,
+        encoded_paints: &[EncodedPaint],
+    ) {
+        // Subpixel coverage can't be intersected with clip paths.
+        if self.clip_context.get().is_some() {
+            self.fill_path(
+                path,
+                Fill::NonZero,
+                transform,
+                paint,
+                BlendMode::default(),
+                None,
+                None,
+                encoded_paints,
+            );
+
+            return;
+        }
+
+        // Convert path to strips with subpixel coverage.
+        self.strip_generator.generate_subpixel_filled_path(
+            path,
+            Fill::NonZero,
+            transform,
+            subpixel_order,
+            &mut self.strip_storage,
+        );
+
+        // Generate coarse-level commands from strips (thread_idx 0 for single-threaded).
+        self.wide
+            .generate_subpixel(&self.strip_storage.strips, paint, 0, encoded_paints);
+    }
+
     fn stroke_path(
         &mut self,
         path: &BezPath,
@@ -879,6 +924,10 @@ impl Dispatcher for SingleThreadedDispatcher {
     fn pop_clip_path(&mut self) {
         self.clip_context.pop_clip();
     }
+
+    fn has_clip_path(&self) -> bool {
+        self.clip_context.get().is_some()
+    }
 }
 
 /// Saves a filtered pixmap to disk for debugging purposes.
@@ -0,0 +1,275 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `feColorMatrix` filter primitive implementation.
+//!
+//! This is also the backend for the CSS color filter functions (`brightness`, `contrast`,
+//! `grayscale`, `hue-rotate`, `invert`, `opacity`, `saturate` and `sepia`), which are
+//! lowered to a color matrix when the filter is built.
+//!
+//! Per the specification, the matrix operates on unpremultiplied colors, so each pixel
+//! is unpremultiplied before and premultiplied again after applying the matrix.
+//!
+//! @see <https://drafts.fxtf.org/filter-effects/#feColorMatrixElement>
+
+use super::FilterEffect;
+use crate::layer_manager::LayerManager;
+use vello_common::filter::color_matrix::ColorMatrix;
+use vello_common::peniko::color::PremulRgba8;
+#[cfg(not(feature = "std"))]
+use vello_common::peniko::kurbo::common::FloatFuncs as _;
+use vello_common::pixmap::Pixmap;
+
+/// Number of fractional bits used for the fixed-point matrix in the u8 path.
+const FIXED_SHIFT: u32 = 12;
+/// Coefficients are clamped to this range in the u8 path so that the fixed-point
+/// dot product of four channels plus the offset cannot overflow an `i32`.
+const MAX_FIXED_COEFFICIENT: f32 = 256.0;
+
+impl FilterEffect for ColorMatrix {
+    fn execute_lowp(&self, pixmap: &mut Pixmap, _layer_manager: &mut LayerManager) {
+        apply_color_matrix_lowp(pixmap, &self.matrix, self.preserves_alpha());
+    }
+
+    fn execute_highp(&self, pixmap: &mut Pixmap, _layer_manager: &mut LayerManager) {
+        apply_color_matrix_highp(pixmap, &self.matrix, self.preserves_alpha());
+    }
+}
+
+/// Apply a color matrix using fixed-point integer arithmetic.
+///
+/// Matrix coefficients are converted to 20.12 fixed point. The first four columns
+/// are multiplied with 8-bit channel values, while the offset column is pre-scaled
+/// by 255 so that all terms share the same scale.
+fn apply_color_matrix_lowp(pixmap: &mut Pixmap, matrix: &[f32; 20], preserves_alpha: bool) {
+    let to_fixed = |v: f32, scale: f32| {
+        (v.clamp(-MAX_FIXED_COEFFICIENT, MAX_FIXED_COEFFICIENT) * scale * (1 << FIXED_SHIFT) as f32)
+            .round() as i32
+    };
+
+    let mut fixed = [0_i32; 20];
+    for (row, out) in fixed.chunks_exact_mut(5).enumerate() {
+        for col in 0..4 {
+            out[col] = to_fixed(matrix[row * 5 + col], 1.0);
+        }
+        out[4] = to_fixed(matrix[row * 5 + 4], 255.0);
+    }
+
+    let round = 1 << (FIXED_SHIFT - 1);
+
+    for pixel in pixmap.data_mut() {
+        // A transparent pixel stays transparent if the alpha row is the identity.
+        if preserves_alpha && pixel.a == 0 {
+            continue;
+        }
+
+        let [r, g, b, a] = unpremultiply_u8(*pixel).map(i32::from);
+
+        let mut out = [0_u8; 4];
+        for (c, row) in out.iter_mut().zip(fixed.chunks_exact(5)) {
+            let v = row[0] * r + row[1] * g + row[2] * b + row[3] * a + row[4];
+            *c = ((v + round) >> FIXED_SHIFT).clamp(0, 255) as u8;
+        }
+
+        *pixel = premultiply_u8(out);
+    }
+}
+
+/// Apply a color matrix using floating-point arithmetic.
+fn apply_color_matrix_highp(pixmap: &mut Pixmap, matrix: &[f32; 20], preserves_alpha: bool) {
+    for pixel in pixmap.data_mut() {
+        if preserves_alpha && pixel.a == 0 {
+            continue;
+        }
+
+        let a = f32::from(pixel.a) / 255.0;
+        let unpremultiply = |c: u8| {
+            if a == 0.0 {
+                0.0
+            } else {
+                (f32::from(c) / 255.0 / a).min(1.0)
+            }
+        };
+        let input = [
+            unpremultiply(pixel.r),
+            unpremultiply(pixel.g),
+            unpremultiply(pixel.b),
+            a,
+        ];
+
+        let mut out = [0.0_f32; 4];
+        for (c, row) in out.iter_mut().zip(matrix.chunks_exact(5)) {
+            let v = row[0] * input[0]
+                + row[1] * input[1]
+                + row[2] * input[2]
+                + row[3] * input[3]
+                + row[4];
+            *c = v.clamp(0.0, 1.0);
+        }
+
+        let out_a = out[3];
+        let premultiply = |c: f32| (c * out_a * 255.0 + 0.5) as u8;
+
+        *pixel = PremulRgba8 {
+            r: premultiply(out[0]),
+            g: premultiply(out[1]),
+            b: premultiply(out[2]),
+            a: (out_a * 255.0 + 0.5) as u8,
+        };
+    }
+}
+
+/// Convert a premultiplied pixel to unpremultiplied 8-bit channels.
+#[inline]
+pub(crate) fn unpremultiply_u8(pixel: PremulRgba8) -> [u8; 4] {
+    let a = u32::from(pixel.a);
+
+    if a == 0 {
+        return [0; 4];
+    }
+
+    let unpremultiply = |c: u8| ((u32::from(c) * 255 + a / 2) / a).min(255) as u8;
+
+    [
+        unpremultiply(pixel.r),
+        unpremultiply(pixel.g),
+        unpremultiply(pixel.b),
+        pixel.a,
+    ]
+}
+
+/// Convert unpremultiplied 8-bit channels to a premultiplied pixel.
+#[inline]
+pub(crate) fn premultiply_u8([r, g, b, a]: [u8; 4]) -> PremulRgba8 {
+    let a16 = u16::from(a);
+    let premultiply = |c: u8| ((u16::from(c) * a16 + 127) / 255) as u8;
+
+    PremulRgba8 {
+        r: premultiply(r),
+        g: premultiply(g),
+        b: premultiply(b),
+        a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vello_common::filter_effects::matrices;
+
+    fn single_pixel(pixel: PremulRgba8) -> Pixmap {
+        let mut pixmap = Pixmap::new(1, 1);
+        pixmap.set_pixel(0, 0, pixel);
+        pixmap
+    }
+
+    fn assert_close(actual: PremulRgba8, expected: PremulRgba8) {
+        let diff = |a: u8, b: u8| a.abs_diff(b);
+        assert!(
+            diff(actual.r, expected.r) <= 1
+                && diff(actual.g, expected.g) <= 1
+                && diff(actual.b, expected.b) <= 1
+                && diff(actual.a, expected.a) <= 1,
+            "{actual:?} is not close to {expected:?}"
+        );
+    }
+
+    /// The identity matrix must leave pixels unchanged in both precisions.
+    #[test]
+    fn identity_is_noop() {
+        let pixel = PremulRgba8 {
+            r: 100,
+            g: 50,
+            b: 25,
+            a: 200,
+        };
+        let mut layer_manager = LayerManager::new();
+
+        let mut lowp = single_pixel(pixel);
+        ColorMatrix::new(matrices::IDENTITY).execute_lowp(&mut lowp, &mut layer_manager);
+        assert_close(lowp.sample(0, 0), pixel);
+
+        let mut highp = single_pixel(pixel);
+        ColorMatrix::new(matrices::IDENTITY).execute_highp(&mut highp, &mut layer_manager);
+        assert_close(highp.sample(0, 0), pixel);
+    }
+
+    /// Inverting operates on the unpremultiplied color.
+    #[test]
+    fn invert_semi_transparent() {
+        // Unpremultiplied: (255, 0, 0, 128).
+        let pixel = PremulRgba8 {
+            r: 128,
+            g: 0,
+            b: 0,
+            a: 128,
+        };
+        let expected = PremulRgba8 {
+            r: 0,
+            g: 128,
+            b: 128,
+            a: 128,
+        };
+        let mut layer_manager = LayerManager::new();
+
+        let mut lowp = single_pixel(pixel);
+        ColorMatrix::new(matrices::invert(1.0)).execute_lowp(&mut lowp, &mut layer_manager);
+        assert_close(lowp.sample(0, 0), expected);
+
+        let mut highp = single_pixel(pixel);
+        ColorMatrix::new(matrices::invert(1.0)).execute_highp(&mut highp, &mut layer_manager);
+        assert_close(highp.sample(0, 0), expected);
+    }
+
+    /// Opacity scales all premultiplied channels.
+    #[test]
+    fn opacity_halves_alpha() {
+        let pixel = PremulRgba8 {
+            r: 200,
+            g: 100,
+            b: 0,
+            a: 255,
+        };
+        let expected = PremulRgba8 {
+            r: 100,
+            g: 50,
+            b: 0,
+            a: 128,
+        };
+        let mut layer_manager = LayerManager::new();
+
+        let mut lowp = single_pixel(pixel);
+        ColorMatrix::new(matrices::opacity(0.5)).execute_lowp(&mut lowp, &mut layer_manager);
+        assert_close(lowp.sample(0, 0), expected);
+
+        let mut highp = single_pixel(pixel);
+        ColorMatrix::new(matrices::opacity(0.5)).execute_highp(&mut highp, &mut layer_manager);
+        assert_close(highp.sample(0, 0), expected);
+    }
+
+    /// Results outside of [0, 1] are clamped instead of wrapping around.
+    #[test]
+    fn brightness_clamps() {
+        let pixel = PremulRgba8 {
+            r: 200,
+            g: 100,
+            b: 10,
+            a: 255,
+        };
+        let expected = PremulRgba8 {
+            r: 255,
+            g: 200,
+            b: 20,
+            a: 255,
+        };
+        let mut layer_manager = LayerManager::new();
+
+        let mut lowp = single_pixel(pixel);
+        ColorMatrix::new(matrices::brightness(2.0)).execute_lowp(&mut lowp, &mut layer_manager);
+        assert_close(lowp.sample(0, 0), expected);
+
+        let mut highp = single_pixel(pixel);
+        ColorMatrix::new(matrices::brightness(2.0)).execute_highp(&mut highp, &mut layer_manager);
+        assert_close(highp.sample(0, 0), expected);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `feComponentTransfer` filter primitive implementation.
+//!
+//! Per the specification, the transfer functions operate on unpremultiplied colors, so
+//! each pixel is unpremultiplied before and premultiplied again after applying them.
+//!
+//! @see <https://drafts.fxtf.org/filter-effects/#feComponentTransferElement>
+
+use super::FilterEffect;
+use super::color_matrix::{premultiply_u8, unpremultiply_u8};
+use crate::layer_manager::LayerManager;
+use vello_common::filter::component_transfer::ComponentTransfer;
+use vello_common::filter_effects::TransferFunction;
+use vello_common::peniko::color::PremulRgba8;
+use vello_common::pixmap::Pixmap;
+
+impl FilterEffect for ComponentTransfer {
+    fn execute_lowp(&self, pixmap: &mut Pixmap, _layer_manager: &mut LayerManager) {
+        apply_component_transfer_lowp(pixmap, self);
+    }
+
+    fn execute_highp(&self, pixmap: &mut Pixmap, _layer_manager: &mut LayerManager) {
+        apply_component_transfer_highp(pixmap, self);
+    }
+}
+
+/// Apply the transfer functions using a lookup table for each channel.
+///
+/// Since unpremultiplied 8-bit channels can only take 256 different values, each transfer
+/// function is evaluated once per possible value instead of once per pixel.
+fn apply_component_transfer_lowp(pixmap: &mut Pixmap, filter: &ComponentTransfer) {
+    let tables = filter.functions.each_ref().map(lookup_table);
+    let preserves_alpha = filter.preserves_alpha();
+
+    for pixel in pixmap.data_mut() {
+        // A transparent pixel stays transparent if the alpha function is the identity.
+        if preserves_alpha && pixel.a == 0 {
+            continue;
+        }
+
+        let mut channels = unpremultiply_u8(*pixel);
+        for (c, table) in channels.iter_mut().zip(&tables) {
+            *c = table[usize::from(*c)];
+        }
+
+        *pixel = premultiply_u8(channels);
+    }
+}
+
+/// Apply the transfer functions using floating-point arithmetic.
+fn apply_component_transfer_highp(pixmap: &mut Pixmap, filter: &ComponentTransfer) {
+    let preserves_alpha = filter.preserves_alpha();
+
+    for pixel in pixmap.data_mut() {
+        if preserves_alpha && pixel.a == 0 {
+            continue;
+        }
+
+        let a = f32::from(pixel.a) / 255.0;
+        let unpremultiply = |c: u8| {
+            if a == 0.0 {
+                0.0
+            } else {
+                (f32::from(c) / 255.0 / a).min(1.0)
+            }
+        };
+        let input = [
+            unpremultiply(pixel.r),
+            unpremultiply(pixel.g),
+            unpremultiply(pixel.b),
+            a,
+        ];
+
+        let [r, g, b, out_a] = [0, 1, 2, 3].map(|i| filter.functions[i].apply(input[i]));
+        let premultiply = |c: f32| (c * out_a * 255.0 + 0.5) as u8;
+
+        *pixel = PremulRgba8 {
+            r: premultiply(r),
+            g: premultiply(g),
+            b: premultiply(b),
+            a: (out_a * 255.0 + 0.5) as u8,
+        };
+    }
+}
+
+/// Evaluate a transfer function for all 256 possible 8-bit channel values.
+fn lookup_table(function: &TransferFunction) -> [u8; 256] {
+    let mut table = [0_u8; 256];
+
+    for (i, entry) in table.iter_mut().enumerate() {
+        *entry = (function.apply(i as f32 / 255.0) * 255.0 + 0.5) as u8;
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn single_pixel(pixel: PremulRgba8) -> Pixmap {
+        let mut pixmap = Pixmap::new(1, 1);
+        pixmap.set_pixel(0, 0, pixel);
+        pixmap
+    }
+
+    fn run_both(filter: &ComponentTransfer, pixel: PremulRgba8) -> [PremulRgba8; 2] {
+        let mut layer_manager = LayerManager::new();
+
+        let mut lowp = single_pixel(pixel);
+        filter.execute_lowp(&mut lowp, &mut layer_manager);
+        let mut highp = single_pixel(pixel);
+        filter.execute_highp(&mut highp, &mut layer_manager);
+
+        [lowp.sample(0, 0), highp.sample(0, 0)]
+    }
+
+    #[test]
+    fn table_interpolates_between_entries() {
+        let table = TransferFunction::Table {
+            values: vec![0.0, 1.0, 0.0],
+        };
+        assert_eq!(table.apply(0.0), 0.0);
+        assert_eq!(table.apply(0.25), 0.5);
+        assert_eq!(table.apply(0.5), 1.0);
+        assert_eq!(table.apply(1.0), 0.0);
+    }
+
+    #[test]
+    fn discrete_steps() {
+        let discrete = TransferFunction::Discrete {
+            values: vec![0.2, 0.4, 0.8],
+        };
+        assert_eq!(discrete.apply(0.0), 0.2);
+        assert_eq!(discrete.apply(0.5), 0.4);
+        assert_eq!(discrete.apply(1.0), 0.8);
+    }
+
+    #[test]
+    fn empty_table_is_identity() {
+        let table = TransferFunction::Table { values: vec![] };
+        assert_eq!(table.apply(0.3), 0.3);
+        let discrete = TransferFunction::Discrete { values: vec![] };
+        assert_eq!(discrete.apply(0.3), 0.3);
+    }
+
+    /// The functions operate on the unpremultiplied color.
+    #[test]
+    fn linear_on_unpremultiplied_color() {
+        let filter = ComponentTransfer::new(
+            Some(&TransferFunction::Linear {
+                slope: 0.5,
+                intercept: 0.0,
+            }),
+            None,
+            Some(&TransferFunction::Linear {
+                slope: 0.0,
+                intercept: 1.0,
+            }),
+            None,
+        );
+        // Unpremultiplied: (255, 0, 0, 128).
+        let pixel = PremulRgba8 {
+            r: 128,
+            g: 0,
+            b: 0,
+            a: 128,
+        };
+
+        for result in run_both(&filter, pixel) {
+            assert_eq!(
+                result,
+                PremulRgba8 {
+                    r: 64,
+                    g: 0,
+                    b: 128,
+                    a: 128,
+                }
+            );
+        }
+    }
+
+    /// Transparent pixels are only affected if the alpha channel is transferred.
+    #[test]
+    fn alpha_function_affects_transparent_pixels() {
+        let transparent = PremulRgba8::from_u32(0);
+        let opaque_blue = TransferFunction::Linear {
+            slope: 0.0,
+            intercept: 1.0,
+        };
+
+        let color_only = ComponentTransfer::new(None, None, Some(&opaque_blue), None);
+        for result in run_both(&color_only, transparent) {
+            assert_eq!(result, transparent);
+        }
+
+        let with_alpha = ComponentTransfer::new(None, None, Some(&opaque_blue), Some(&opaque_blue));
+        for result in run_both(&with_alpha, transparent) {
+            assert_eq!(
+                result,
+                PremulRgba8 {
+                    r: 0,
+                    g: 0,
+                    b: 255,
+                    a: 255,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `feComposite` filter primitive implementation.
+//!
+//! The primary input (`in`) is the source and the secondary input (`in2`) is the
+//! destination. Both inputs are premultiplied, which is what the Porter-Duff operators
+//! and the arithmetic operator are defined on.
+//!
+//! @see <https://drafts.fxtf.org/filter-effects/#feCompositeElement>
+
+use super::BinaryFilterEffect;
+use crate::layer_manager::LayerManager;
+use vello_common::filter::composite::Composite;
+use vello_common::filter_effects::CompositeOperator;
+use vello_common::peniko::color::PremulRgba8;
+use vello_common::pixmap::Pixmap;
+
+impl BinaryFilterEffect for Composite {
+    fn execute_lowp(&self, pixmap: &mut Pixmap, input2: &Pixmap, _: &mut LayerManager) {
+        let op = self.operator;
+
+        for (src, dst) in pixmap.data_mut().iter_mut().zip(input2.data()) {
+            *src = composite_lowp(op, *src, *dst);
+        }
+    }
+
+    fn execute_highp(&self, pixmap: &mut Pixmap, input2: &Pixmap, _: &mut LayerManager) {
+        let op = self.operator;
+
+        for (src, dst) in pixmap.data_mut().iter_mut().zip(input2.data()) {
+            *src = composite_highp(op, *src, *dst);
+        }
+    }
+}
+
+/// Composite a single pixel using 8-bit integer arithmetic.
+fn composite_lowp(op: CompositeOperator, src: PremulRgba8, dst: PremulRgba8) -> PremulRgba8 {
+    // Weights for source and destination, such that `result = src * fa + dst * fb`.
+    let (fa, fb) = match op {
+        CompositeOperator::Over => (255, 255 - src.a),
+        CompositeOperator::In => (dst.a, 0),
+        CompositeOperator::Out => (255 - dst.a, 0),
+        CompositeOperator::Atop => (dst.a, 255 - src.a),
+        CompositeOperator::Xor => (255 - dst.a, 255 - src.a),
+        CompositeOperator::Arithmetic { .. } => {
+            // The arithmetic operator has unbounded coefficients, so we go through floats.
+            return composite_highp(op, src, dst);
+        }
+    };
+
+    let (fa, fb) = (u32::from(fa), u32::from(fb));
+    // For valid premultiplied colors, `src * fa + dst * fb` never exceeds `255 * 255`.
+    let blend = |s: u8, d: u8| ((u32::from(s) * fa + u32::from(d) * fb).min(255 * 255) + 127) / 255;
+
+    PremulRgba8 {
+        r: blend(src.r, dst.r) as u8,
+        g: blend(src.g, dst.g) as u8,
+        b: blend(src.b, dst.b) as u8,
+        a: blend(src.a, dst.a) as u8,
+    }
+}
+
+/// Composite a single pixel using floating-point arithmetic.
+fn composite_highp(op: CompositeOperator, src: PremulRgba8, dst: PremulRgba8) -> PremulRgba8 {
+    let s = [src.r, src.g, src.b, src.a].map(|c| f32::from(c) / 255.0);
+    let d = [dst.r, dst.g, dst.b, dst.a].map(|c| f32::from(c) / 255.0);
+    let (sa, da) = (s[3], d[3]);
+
+    let mut out = [0.0_f32; 4];
+    match op {
+        CompositeOperator::Arithmetic { k1, k2, k3, k4 } => {
+            for i in 0..4 {
+                out[i] = (k1 * s[i] * d[i] + k2 * s[i] + k3 * d[i] + k4).clamp(0.0, 1.0);
+            }
+            // Keep the result a valid premultiplied color.
+            let alpha = out[3];
+            for c in &mut out[..3] {
+                *c = c.min(alpha);
+            }
+        }
+        _ => {
+            let (fa, fb) = match op {
+                CompositeOperator::Over => (1.0, 1.0 - sa),
+                CompositeOperator::In => (da, 0.0),
+                CompositeOperator::Out => (1.0 - da, 0.0),
+                CompositeOperator::Atop => (da, 1.0 - sa),
+                CompositeOperator::Xor => (1.0 - da, 1.0 - sa),
+                CompositeOperator::Arithmetic { .. } => unreachable!(),
+            };
+
+            for i in 0..4 {
+                out[i] = (s[i] * fa + d[i] * fb).min(1.0);
+            }
+        }
+    }
+
+    let [r, g, b, a] = out.map(|c| (c * 255.0 + 0.5) as u8);
+    PremulRgba8 { r, g, b, a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: PremulRgba8 = PremulRgba8 {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const HALF_BLUE: PremulRgba8 = PremulRgba8 {
+        r: 0,
+        g: 0,
+        b: 128,
+        a: 128,
+    };
+    const TRANSPARENT: PremulRgba8 = PremulRgba8 {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 0,
+    };
+
+    fn both(op: CompositeOperator, src: PremulRgba8, dst: PremulRgba8) -> [PremulRgba8; 2] {
+        [composite_lowp(op, src, dst), composite_highp(op, src, dst)]
+    }
+
+    #[test]
+    fn over() {
+        for result in both(CompositeOperator::Over, HALF_BLUE, RED) {
+            assert_eq!(
+                result,
+                PremulRgba8 {
+                    r: 127,
+                    g: 0,
+                    b: 128,
+                    a: 255
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn in_and_out() {
+        for result in both(CompositeOperator::In, RED, HALF_BLUE) {
+            assert_eq!(
+                result,
+                PremulRgba8 {
+                    r: 128,
+                    g: 0,
+                    b: 0,
+                    a: 128
+                }
+            );
+        }
+        for result in both(CompositeOperator::Out, RED, TRANSPARENT) {
+            assert_eq!(result, RED);
+        }
+        for result in both(CompositeOperator::Out, RED, RED) {
+            assert_eq!(result, TRANSPARENT);
+        }
+    }
+
+    #[test]
+    fn xor_of_opaque_is_transparent() {
+        for result in both(CompositeOperator::Xor, RED, RED) {
+            assert_eq!(result, TRANSPARENT);
+        }
+    }
+
+    #[test]
+    fn arithmetic_stays_premultiplied() {
+        let op = CompositeOperator::Arithmetic {
+            k1: 0.0,
+            k2: 1.0,
+            k3: 1.0,
+            k4: 0.0,
+        };
+        // Sum of both inputs, with the color channels clamped to alpha.
+        for result in both(op, HALF_BLUE, HALF_BLUE) {
+            assert_eq!(
+                result,
+                PremulRgba8 {
+                    r: 0,
+                    g: 0,
+                    b: 255,
+                    a: 255
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `feConvolveMatrix` filter primitive implementation.
+//!
+//! Each output pixel is the weighted sum of the input pixels covered by the kernel, divided
+//! by the divisor (already folded into the weights) plus the bias. Kernel pixels that lie
+//! outside of the input are handled according to the edge mode.
+//!
+//! If `preserve_alpha` is false, all four channels of the premultiplied input are convolved
+//! and the bias is scaled by the resulting alpha. Otherwise, only the color channels of the
+//! unpremultiplied input are convolved and the alpha of the input pixel is kept.
+//!
+//! @see <https://drafts.fxtf.org/filter-effects/#feConvolveMatrixElement>
+
+use super::FilterEffect;
+use super::color_matrix::{premultiply_u8, unpremultiply_u8};
+use super::gaussian_blur::extend;
+use crate::layer_manager::LayerManager;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul};
+use vello_common::fearless_simd::{Level, Simd, SimdBase, dispatch, f32x16, i32x16};
+use vello_common::filter::convolve_matrix::ConvolveMatrix;
+use vello_common::filter_effects::EdgeMode;
+use vello_common::peniko::color::PremulRgba8;
+#[cfg(not(feature = "std"))]
+use vello_common::peniko::kurbo::common::FloatFuncs as _;
+use vello_common::pixmap::Pixmap;
+
+/// Number of fractional bits used for the fixed-point weights in the u8 path.
+const FIXED_SHIFT: u32 = 12;
+
+impl FilterEffect for ConvolveMatrix {
+    fn execute_lowp(&self, pixmap: &mut Pixmap, layer_manager: &mut LayerManager) {
+        let to_fixed = |w: f32| (w * (1 << FIXED_SHIFT) as f32).round();
+
+        // The accumulated sum must fit into an `i32`, otherwise fall back to floats.
+        let max_sum: f32 = self
+            .weights
+            .iter()
+            .map(|w| to_fixed(*w).abs() * 255.0)
+            .sum();
+        if max_sum >= i32::MAX as f32 / 2.0 {
+            return self.execute_highp(pixmap, layer_manager);
+        }
+
+        let weights: Vec<i32> = self.weights.iter().map(|w| to_fixed(*w) as i32).collect();
+        let bias = to_fixed(self.bias * 255.0) as i32;
+        let round = 1 << (FIXED_SHIFT - 1);
+        let to_u8 = |v: i32| ((v + round) >> FIXED_SHIFT).clamp(0, 255) as u8;
+
+        let src: Vec<i32> = pixmap
+            .data()
+            .iter()
+            .flat_map(|pixel| {
+                if self.preserve_alpha {
+                    unpremultiply_u8(*pixel)
+                } else {
+                    pixel.to_u8_array()
+                }
+            })
+            .map(i32::from)
+            .collect();
+
+        let finish = |acc: [i32; 4], input: [i32; 4]| {
+            if self.preserve_alpha {
+                // `input` is unpremultiplied, so its alpha is the original alpha.
+                let [r, g, b] = [0, 1, 2].map(|i| to_u8(acc[i] + bias));
+                premultiply_u8([r, g, b, input[3] as u8])
+            } else {
+                let a = to_u8(acc[3] + bias);
+                let scaled_bias = bias * i32::from(a) / 255;
+                let [r, g, b] = [0, 1, 2].map(|i| to_u8(acc[i] + scaled_bias).min(a));
+                PremulRgba8 { r, g, b, a }
+            }
+        };
+
+        dispatch!(detected_level(), simd => simd.vectorize(
+            #[inline(always)]
+            || convolve::<_, i32x16<_>>(simd, self, &src, pixmap, &weights, finish)
+        ));
+    }
+
+    fn execute_highp(&self, pixmap: &mut Pixmap, _layer_manager: &mut LayerManager) {
+        let src: Vec<f32> = pixmap
+            .data()
+            .iter()
+            .flat_map(|pixel| {
+                let [r, g, b, a] = pixel.to_u8_array().map(|c| f32::from(c) / 255.0);
+                if self.preserve_alpha {
+                    let unpremultiply = |c: f32| if a == 0.0 { 0.0 } else { (c / a).min(1.0) };
+                    [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+                } else {
+                    [r, g, b, a]
+                }
+            })
+            .collect();
+
+        let to_u8 = |v: f32| (v * 255.0 + 0.5) as u8;
+        let finish = |acc: [f32; 4], input: [f32; 4]| {
+            if self.preserve_alpha {
+                let a = input[3];
+                let [r, g, b] = [0, 1, 2].map(|i| to_u8((acc[i] + self.bias).clamp(0.0, 1.0) * a));
+                PremulRgba8 {
+                    r,
+                    g,
+                    b,
+                    a: to_u8(a),
+                }
+            } else {
+                let a = (acc[3] + self.bias).clamp(0.0, 1.0);
+                let [r, g, b] = [0, 1, 2].map(|i| to_u8((acc[i] + self.bias * a).clamp(0.0, a)));
+                PremulRgba8 {
+                    r,
+                    g,
+                    b,
+                    a: to_u8(a),
+                }
+            }
+        };
+
+        dispatch!(detected_level(), simd => simd.vectorize(
+            #[inline(always)]
+            || convolve::<_, f32x16<_>>(simd, self, &src, pixmap, &self.weights, finish)
+        ));
+    }
+}
+
+/// The SIMD level used for the convolution.
+///
+/// Filters don't have access to the level of the render context. The results don't depend
+/// on the level, so use the best one available.
+fn detected_level() -> Level {
+    Level::try_detect().unwrap_or(Level::baseline())
+}
+
+/// Convolve `src` with the kernel and write the results to `dst`.
+///
+/// `src` stores the four channels of each input pixel, and `weights` has the same type as
+/// the channels. `finish` converts the accumulated sum of each pixel to the output color,
+/// given the input pixel at the same position.
+///
+/// Each input row is extended by the kernel radius according to the edge mode first, so
+/// that a kernel weight can be applied to a whole output row at once. The multiply-adds of
+/// these contiguous rows are vectorized with `V`.
+#[inline(always)]
+fn convolve<S: Simd, V>(
+    simd: S,
+    filter: &ConvolveMatrix,
+    src: &[V::Element],
+    dst: &mut Pixmap,
+    weights: &[V::Element],
+    mut finish: impl FnMut([V::Element; 4], [V::Element; 4]) -> PremulRgba8,
+) where
+    V: SimdBase<S> + Add<Output = V> + Mul<V::Element, Output = V>,
+    V::Element: Copy + Default + Add<Output = V::Element> + Mul<Output = V::Element>,
+{
+    let width = dst.width();
+    let height = dst.height();
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let stride = usize::from(width) * 4;
+    let size = usize::from(filter.size);
+    let target = usize::from(filter.target);
+    let zero = V::Element::default();
+
+    // Sampling outside of the input with `EdgeMode::None` yields transparent black.
+    let padded_stride = (usize::from(width) + size - 1) * 4;
+    let mut padded = Vec::with_capacity(padded_stride * usize::from(height));
+    for row in src.chunks_exact(stride) {
+        for x in 0..padded_stride / 4 {
+            match edge_coord(x, target, width, filter.edge_mode) {
+                Some(sx) => padded.extend_from_slice(&row[sx * 4..sx * 4 + 4]),
+                None => padded.extend_from_slice(&[zero; 4]),
+            }
+        }
+    }
+
+    let mut acc = vec![zero; stride];
+    for (y, (src_row, dst_row)) in src
+        .chunks_exact(stride)
+        .zip(dst.data_mut().chunks_exact_mut(usize::from(width)))
+        .enumerate()
+    {
+        acc.fill(zero);
+        for (i, kernel_row) in weights.chunks_exact(size).enumerate() {
+            let Some(sy) = edge_coord(y + i, target, height, filter.edge_mode) else {
+                continue;
+            };
+            let padded_row = &padded[sy * padded_stride..][..padded_stride];
+            for (j, w) in kernel_row.iter().enumerate() {
+                mul_add::<S, V>(simd, &mut acc, &padded_row[j * 4..][..stride], *w);
+            }
+        }
+
+        for ((pixel, acc), input) in dst_row
+            .iter_mut()
+            .zip(acc.chunks_exact(4))
+            .zip(src_row.chunks_exact(4))
+        {
+            *pixel = finish(acc.try_into().unwrap(), input.try_into().unwrap());
+        }
+    }
+}
+
+/// Add `src` multiplied with `weight` to `acc`.
+#[inline(always)]
+fn mul_add<S: Simd, V>(simd: S, acc: &mut [V::Element], src: &[V::Element], weight: V::Element)
+where
+    V: SimdBase<S> + Add<Output = V> + Mul<V::Element, Output = V>,
+    V::Element: Copy + Add<Output = V::Element> + Mul<Output = V::Element>,
+{
+    let mut acc_chunks = acc.chunks_exact_mut(V::N);
+    let mut src_chunks = src.chunks_exact(V::N);
+    for (acc, src) in (&mut acc_chunks).zip(&mut src_chunks) {
+        let sum = V::from_slice(simd, acc) + V::from_slice(simd, src) * weight;
+        sum.store_slice(acc);
+    }
+    for (acc, src) in acc_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(src_chunks.remainder())
+    {
+        *acc = *acc + *src * weight;
+    }
+}
+
+/// Map the coordinate `coord - target` into the input according to the edge mode.
+///
+/// Returns `None` if the coordinate lies outside of the input for `EdgeMode::None`.
+#[inline(always)]
+fn edge_coord(coord: usize, target: usize, len: u16, edge_mode: EdgeMode) -> Option<usize> {
+    let coord = coord as i32 - target as i32;
+    if edge_mode == EdgeMode::None && (coord < 0 || coord >= i32::from(len)) {
+        return None;
+    }
+
+    Some(usize::from(extend(coord, len, edge_mode)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use vello_common::filter_effects::{ConvolutionKernel, kernels};
+
+    const RED: PremulRgba8 = PremulRgba8 {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn dot() -> Pixmap {
+        let mut pixmap = Pixmap::new(5, 5);
+        pixmap.set_pixel(2, 2, RED);
+        pixmap
+    }
+
+    fn run_both(filter: &ConvolveMatrix, pixmap: &Pixmap) -> [Pixmap; 2] {
+        let mut layer_manager = LayerManager::new();
+        let mut lowp = pixmap.clone();
+        filter.execute_lowp(&mut lowp, &mut layer_manager);
+        let mut highp = pixmap.clone();
+        filter.execute_highp(&mut highp, &mut layer_manager);
+        [lowp, highp]
+    }
+
+    /// The kernel is rotated by 180° before being applied, as in the specification.
+    #[test]
+    fn kernel_is_rotated() {
+        let kernel = ConvolutionKernel {
+            size: 3,
+            values: vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            divisor: 1.0,
+            bias: 0.0,
+            preserve_alpha: false,
+            edge_mode: EdgeMode::None,
+        };
+
+        for result in run_both(&ConvolveMatrix::new(&kernel), &dot()) {
+            // The top-left weight samples the pixel to the bottom right.
+            assert_eq!(result.sample(1, 1), RED);
+            assert_eq!(result.sample(2, 2), PremulRgba8::from_u32(0));
+        }
+    }
+
+    /// The divisor is applied to the weighted sum.
+    #[test]
+    fn gaussian_spreads_dot() {
+        let filter = ConvolveMatrix::new(&kernels::gaussian_3x3());
+
+        for result in run_both(&filter, &dot()) {
+            assert_eq!(result.sample(2, 2).a, 64);
+            assert_eq!(result.sample(1, 2).a, 32);
+            assert_eq!(result.sample(1, 1).a, 16);
+            assert_eq!(result.sample(0, 0).a, 0);
+        }
+    }
+
+    /// With `EdgeMode::Duplicate`, a uniform image is unaffected by a normalized kernel
+    /// even at its borders, while `EdgeMode::None` darkens them.
+    #[test]
+    fn edge_modes() {
+        let mut uniform = Pixmap::new(4, 4);
+        uniform.data_mut().fill(RED);
+
+        for (edge_mode, corner_alpha) in [(EdgeMode::Duplicate, 255), (EdgeMode::None, 143)] {
+            let kernel = ConvolutionKernel {
+                edge_mode,
+                ..kernels::gaussian_3x3()
+            };
+
+            for result in run_both(&ConvolveMatrix::new(&kernel), &uniform) {
+                assert_eq!(result.sample(0, 0).a, corner_alpha);
+                assert_eq!(result.sample(1, 1), RED);
+            }
+        }
+    }
+
+    /// Invalid kernels leave the input unchanged.
+    #[test]
+    fn invalid_kernel_is_passthrough() {
+        let kernel = ConvolutionKernel {
+            size: 3,
+            values: vec![1.0; 4],
+            divisor: 1.0,
+            bias: 0.0,
+            preserve_alpha: false,
+            edge_mode: EdgeMode::None,
+        };
+
+        for result in run_both(&ConvolveMatrix::new(&kernel), &dot()) {
+            assert_eq!(result.data(), dot().data());
+        }
+    }
+
+    /// With `preserve_alpha`, the bias applies to the unpremultiplied color and
+    /// the alpha of the input is kept.
+    #[test]
+    fn emboss_preserves_alpha() {
+        let mut pixmap = Pixmap::new(3, 3);
+        pixmap.data_mut().fill(PremulRgba8 {
+            r: 64,
+            g: 64,
+            b: 64,
+            a: 128,
+        });
+
+        for result in run_both(&ConvolveMatrix::new(&kernels::emboss_3x3()), &pixmap) {
+            // The weights sum to 1, so the flat color (0.5) is kept and the bias
+            // of 0.5 is added.
+            assert_eq!(
+                result.sample(1, 1),
+                PremulRgba8 {
+                    r: 128,
+                    g: 128,
+                    b: 128,
+                    a: 128,
+                }
+            );
+        }
+    }
+}
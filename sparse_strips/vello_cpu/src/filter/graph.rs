@@ -0,0 +1,424 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Evaluation of multi-primitive filter graphs.
+//!
+//! Primitives in a [`FilterGraph`] can only read from built-in sources or from primitives
+//! that were added before them, so the insertion order is already a valid execution order
+//! and no separate topological sort (as done by the render graph) is needed.
+//!
+//! The evaluator only executes primitives that contribute to the output of the graph.
+//! Before execution, it counts how often each result (and each built-in source) is read.
+//! When a primitive reads an input for the last time, it takes over that buffer and
+//! applies its effect in place. Otherwise, the input is copied into a buffer from a
+//! small pool. Buffers whose results are not needed anymore are returned to the pool,
+//! so a linear chain of primitives runs without any intermediate allocations.
+
+use super::{FilterPrecision, execute_binary, execute_unary};
+use crate::layer_manager::LayerManager;
+use alloc::vec;
+use alloc::vec::Vec;
+use vello_common::filter::PreparedFilter;
+use vello_common::filter_effects::{FilterGraph, FilterInput, FilterSource};
+use vello_common::kurbo::Affine;
+use vello_common::peniko::color::PremulRgba8;
+use vello_common::pixmap::Pixmap;
+
+/// Apply all primitives of a filter graph contributing to its output.
+///
+/// `pixmap` holds the `SourceGraphic` on entry and the output of the graph on return.
+pub(crate) fn apply_filter_graph(
+    graph: &FilterGraph,
+    pixmap: &mut Pixmap,
+    layer_manager: &mut LayerManager,
+    transform: Affine,
+    precision: FilterPrecision,
+) {
+    let output = usize::from(graph.output.0);
+    if output >= graph.primitives.len() {
+        return;
+    }
+
+    let mut evaluator = GraphEvaluator::new(graph, output, pixmap);
+
+    for idx in 0..=output {
+        if evaluator.result_uses[idx] == 0 {
+            // The result does not contribute to the output, skip it.
+            continue;
+        }
+
+        let inputs = &graph.inputs[idx];
+        let prepared = PreparedFilter::new(&graph.primitives[idx], &transform);
+
+        let mut target = evaluator.take_input(&inputs.primary, pixmap);
+
+        if graph.primitives[idx].num_inputs() == 2 {
+            // `FilterGraph::add` always assigns a secondary input to binary primitives, but
+            // if it is missing anyway, compose with transparent black (like `FillPaint`).
+            let secondary = inputs
+                .secondary
+                .clone()
+                .unwrap_or(FilterInput::Source(FilterSource::FillPaint));
+            let input2 = evaluator.borrow_input(&secondary, pixmap);
+            execute_binary(&prepared, &mut target, input2, layer_manager, precision);
+            evaluator.release_input(&secondary);
+        } else {
+            execute_unary(&prepared, &mut target, layer_manager, precision);
+        }
+
+        evaluator.results[idx] = Some(target);
+    }
+
+    *pixmap = evaluator.results[output]
+        .take()
+        .expect("the output of the graph has been evaluated");
+}
+
+/// Book-keeping for intermediate buffers while evaluating a filter graph.
+struct GraphEvaluator {
+    /// The evaluated results of each primitive, released once they are not needed anymore.
+    results: Vec<Option<Pixmap>>,
+    /// The number of remaining reads of each result (the output counts as one read).
+    result_uses: Vec<u32>,
+    /// The number of remaining reads of `SourceGraphic`.
+    source_graphic_uses: u32,
+    /// Whether the `SourceGraphic` has been moved out of the layer pixmap.
+    source_graphic_taken: bool,
+    /// The lazily computed `SourceAlpha`.
+    source_alpha: Option<Pixmap>,
+    /// The number of remaining reads of `SourceAlpha`.
+    source_alpha_uses: u32,
+    /// Buffers that are not in use anymore and can be recycled.
+    pool: Vec<Pixmap>,
+    /// A transparent buffer for sources that are not available.
+    transparent: Option<Pixmap>,
+    /// The width of all buffers.
+    width: u16,
+    /// The height of all buffers.
+    height: u16,
+}
+
+impl GraphEvaluator {
+    fn new(graph: &FilterGraph, output: usize, source: &Pixmap) -> Self {
+        let mut result_uses = vec![0_u32; graph.primitives.len()];
+        let mut source_graphic_uses = 0;
+        let mut source_alpha_uses = 0;
+
+        // Walk the graph backwards from the output to find all primitives that contribute
+        // to it, and count how often each of their inputs is read.
+        result_uses[output] = 1;
+        for idx in (0..=output).rev() {
+            if result_uses[idx] == 0 {
+                continue;
+            }
+
+            let inputs = &graph.inputs[idx];
+            for input in core::iter::once(&inputs.primary).chain(inputs.secondary.as_ref()) {
+                match input {
+                    FilterInput::Result(id) => result_uses[usize::from(id.0)] += 1,
+                    FilterInput::Source(FilterSource::SourceGraphic) => source_graphic_uses += 1,
+                    FilterInput::Source(FilterSource::SourceAlpha) => source_alpha_uses += 1,
+                    FilterInput::Source(_) => {}
+                }
+            }
+        }
+
+        let source_alpha = (source_alpha_uses > 0).then(|| to_alpha(source));
+
+        Self {
+            results: (0..graph.primitives.len()).map(|_| None).collect(),
+            result_uses,
+            source_graphic_uses,
+            source_graphic_taken: false,
+            source_alpha,
+            source_alpha_uses,
+            pool: Vec::new(),
+            transparent: None,
+            width: source.width(),
+            height: source.height(),
+        }
+    }
+
+    /// Get an owned buffer containing the given input, which the caller can modify.
+    ///
+    /// If this is the last read of the input, its buffer is moved out, otherwise it is copied.
+    fn take_input(&mut self, input: &FilterInput, source: &mut Pixmap) -> Pixmap {
+        match input {
+            FilterInput::Result(id) => {
+                let idx = usize::from(id.0);
+                self.result_uses[idx] -= 1;
+
+                if self.result_uses[idx] == 0 {
+                    self.results[idx]
+                        .take()
+                        .expect("inputs are evaluated first")
+                } else {
+                    let result = self.results[idx]
+                        .as_ref()
+                        .expect("inputs are evaluated first");
+                    copy_into_pooled(&mut self.pool, result)
+                }
+            }
+            FilterInput::Source(FilterSource::SourceGraphic) => {
+                self.source_graphic_uses -= 1;
+
+                if self.source_graphic_uses == 0 {
+                    self.source_graphic_taken = true;
+                    core::mem::replace(source, Pixmap::new(0, 0))
+                } else {
+                    copy_into_pooled(&mut self.pool, source)
+                }
+            }
+            FilterInput::Source(FilterSource::SourceAlpha) => {
+                self.source_alpha_uses -= 1;
+
+                if self.source_alpha_uses == 0 {
+                    self.source_alpha
+                        .take()
+                        .expect("source alpha has been computed")
+                } else {
+                    let alpha = self
+                        .source_alpha
+                        .as_ref()
+                        .expect("source alpha has been computed");
+                    copy_into_pooled(&mut self.pool, alpha)
+                }
+            }
+            FilterInput::Source(_) => self.alloc_transparent(),
+        }
+    }
+
+    /// Borrow the given input without modifying it.
+    ///
+    /// The caller must call [`Self::release_input`] once it is done with the input.
+    fn borrow_input<'a>(&'a mut self, input: &FilterInput, source: &'a Pixmap) -> &'a Pixmap {
+        match input {
+            FilterInput::Result(id) => self.results[usize::from(id.0)]
+                .as_ref()
+                .expect("inputs are evaluated first"),
+            FilterInput::Source(FilterSource::SourceGraphic) => {
+                debug_assert!(
+                    !self.source_graphic_taken,
+                    "source graphic is only moved out on its last read"
+                );
+                source
+            }
+            FilterInput::Source(FilterSource::SourceAlpha) => self
+                .source_alpha
+                .as_ref()
+                .expect("source alpha has been computed"),
+            FilterInput::Source(_) => {
+                let (width, height) = (self.width, self.height);
+                self.transparent
+                    .get_or_insert_with(|| Pixmap::new(width, height))
+            }
+        }
+    }
+
+    /// Release an input previously borrowed with [`Self::borrow_input`], recycling its
+    /// buffer if it is not read anymore.
+    fn release_input(&mut self, input: &FilterInput) {
+        match input {
+            FilterInput::Result(id) => {
+                let idx = usize::from(id.0);
+                self.result_uses[idx] -= 1;
+
+                if self.result_uses[idx] == 0
+                    && let Some(buf) = self.results[idx].take()
+                {
+                    self.pool.push(buf);
+                }
+            }
+            FilterInput::Source(FilterSource::SourceGraphic) => {
+                // The source graphic lives in the layer pixmap, which is reused for the output.
+                self.source_graphic_uses -= 1;
+            }
+            FilterInput::Source(FilterSource::SourceAlpha) => {
+                self.source_alpha_uses -= 1;
+
+                if self.source_alpha_uses == 0
+                    && let Some(buf) = self.source_alpha.take()
+                {
+                    self.pool.push(buf);
+                }
+            }
+            FilterInput::Source(_) => {}
+        }
+    }
+
+    /// Get a transparent buffer, reusing a pooled buffer if possible.
+    fn alloc_transparent(&mut self) -> Pixmap {
+        match self.pool.pop() {
+            Some(mut buf) => {
+                buf.data_mut().fill(PremulRgba8::from_u32(0));
+                buf
+            }
+            None => Pixmap::new(self.width, self.height),
+        }
+    }
+}
+
+/// Copy `src` into a buffer from the pool, allocating a new one if the pool is empty.
+fn copy_into_pooled(pool: &mut Vec<Pixmap>, src: &Pixmap) -> Pixmap {
+    match pool.pop() {
+        Some(mut buf) => {
+            buf.data_mut().copy_from_slice(src.data());
+            buf
+        }
+        None => src.clone(),
+    }
+}
+
+/// Create the `SourceAlpha` of a pixmap, i.e. transparent black with the same alpha.
+fn to_alpha(src: &Pixmap) -> Pixmap {
+    let mut alpha = src.clone();
+
+    for pixel in alpha.data_mut() {
+        *pixel = PremulRgba8 {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: pixel.a,
+        };
+    }
+
+    alpha
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::FilterPrecision;
+    use vello_common::color::palette::css::{BLUE, RED};
+    use vello_common::filter_effects::{
+        CompositeOperator, FilterInputs, FilterPrimitive, matrices,
+    };
+
+    fn source() -> Pixmap {
+        let mut pixmap = Pixmap::new(4, 1);
+        pixmap.set_pixel(
+            1,
+            0,
+            PremulRgba8 {
+                r: 0,
+                g: 128,
+                b: 0,
+                a: 128,
+            },
+        );
+        pixmap
+    }
+
+    /// `SourceAlpha` is flooded and clipped back to the shape of the source.
+    #[test]
+    fn flood_in_source_alpha() {
+        let mut graph = FilterGraph::new();
+        let flood = graph.add(FilterPrimitive::Flood { color: RED }, None);
+        let composite = graph.add(
+            FilterPrimitive::Composite {
+                operator: CompositeOperator::In,
+            },
+            Some(FilterInputs::dual(
+                FilterInput::Result(flood),
+                FilterInput::Source(FilterSource::SourceAlpha),
+            )),
+        );
+        graph.set_output(composite);
+
+        let mut pixmap = source();
+        apply_filter_graph(
+            &graph,
+            &mut pixmap,
+            &mut LayerManager::new(),
+            Affine::IDENTITY,
+            FilterPrecision::Lowp,
+        );
+
+        assert_eq!(pixmap.sample(0, 0), PremulRgba8::from_u32(0));
+        assert_eq!(
+            pixmap.sample(1, 0),
+            PremulRgba8 {
+                r: 128,
+                g: 0,
+                b: 0,
+                a: 128,
+            }
+        );
+    }
+
+    /// Primitives that don't contribute to the output are not evaluated, and results
+    /// can be read multiple times.
+    #[test]
+    fn shared_and_unused_results() {
+        let mut graph = FilterGraph::new();
+        let inverted = graph.add(
+            FilterPrimitive::ColorMatrix {
+                matrix: matrices::invert(1.0),
+            },
+            None,
+        );
+        // Unused: would make the whole output blue.
+        graph.add(FilterPrimitive::Flood { color: BLUE }, None);
+        let merged = graph.add(
+            FilterPrimitive::Composite {
+                operator: CompositeOperator::Over,
+            },
+            Some(FilterInputs::dual(
+                FilterInput::Result(inverted),
+                FilterInput::Result(inverted),
+            )),
+        );
+        graph.set_output(merged);
+
+        let mut pixmap = source();
+        apply_filter_graph(
+            &graph,
+            &mut pixmap,
+            &mut LayerManager::new(),
+            Affine::IDENTITY,
+            FilterPrecision::Highp,
+        );
+
+        assert_eq!(pixmap.sample(0, 0), PremulRgba8::from_u32(0));
+        // Inverted green (magenta) at 50%, composited over itself.
+        assert_eq!(
+            pixmap.sample(1, 0),
+            PremulRgba8 {
+                r: 192,
+                g: 0,
+                b: 192,
+                a: 192,
+            }
+        );
+    }
+
+    /// The default inputs chain each primitive to the previous one.
+    #[test]
+    fn default_inputs_chain() {
+        let mut graph = FilterGraph::new();
+        graph.add(
+            FilterPrimitive::ColorMatrix {
+                matrix: matrices::invert(1.0),
+            },
+            None,
+        );
+        let last = graph.add(
+            FilterPrimitive::ColorMatrix {
+                matrix: matrices::invert(1.0),
+            },
+            None,
+        );
+        graph.set_output(last);
+
+        let mut pixmap = source();
+        apply_filter_graph(
+            &graph,
+            &mut pixmap,
+            &mut LayerManager::new(),
+            Affine::IDENTITY,
+            FilterPrecision::Lowp,
+        );
+
+        assert_eq!(pixmap.data(), source().data());
+    }
+}
@@ -8,11 +8,20 @@
 //! Filters are applied to layers through the layer manager, which handles
 //! intermediate storage.
 
+mod color_matrix;
+mod component_transfer;
+mod composite;
+mod convolve_matrix;
+mod displacement_map;
 mod drop_shadow;
 mod flood;
 mod gaussian_blur;
+mod graph;
+mod lighting;
+mod morphology;
 mod offset;
 mod shift;
+mod turbulence;
 
 use crate::layer_manager::LayerManager;
 use vello_common::filter::PreparedFilter;
@@ -43,10 +52,40 @@ pub(crate) trait FilterEffect {
     fn execute_highp(&self, pixmap: &mut Pixmap, layer_manager: &mut LayerManager);
 }
 
+/// Trait for filter effects that combine two inputs.
+///
+/// The result is written to the pixmap holding the primary input.
+pub(crate) trait BinaryFilterEffect {
+    /// Apply the low-precision (u8) version of the filter.
+    ///
+    /// # Arguments
+    /// * `pixmap` - The primary input, which is replaced by the result
+    /// * `input2` - The secondary input, with the same dimensions as `pixmap`
+    /// * `layer_manager` - Manager for allocating and accessing intermediate layers
+    fn execute_lowp(&self, pixmap: &mut Pixmap, input2: &Pixmap, layer_manager: &mut LayerManager);
+
+    /// Apply the high-precision (f32) version of the filter.
+    ///
+    /// # Arguments
+    /// * `pixmap` - The primary input, which is replaced by the result
+    /// * `input2` - The secondary input, with the same dimensions as `pixmap`
+    /// * `layer_manager` - Manager for allocating and accessing intermediate layers
+    fn execute_highp(&self, pixmap: &mut Pixmap, input2: &Pixmap, layer_manager: &mut LayerManager);
+}
+
+/// The precision a filter graph is evaluated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterPrecision {
+    /// Use the low-precision (u8) implementations.
+    Lowp,
+    /// Use the high-precision (f32) implementations.
+    Highp,
+}
+
 /// Apply the low-precision (u8) version of a filter effect to a layer.
 ///
-/// This function dispatches filter primitives from a filter graph to their
-/// corresponding CPU implementations using 8-bit color channels.
+/// This function evaluates the filter graph, dispatching each primitive to its
+/// corresponding CPU implementation using 8-bit color channels.
 ///
 /// # Arguments
 /// * `filter` - The filter containing the graph of primitives to apply
@@ -55,36 +94,27 @@ pub(crate) trait FilterEffect {
 /// * `transform` - The transformation matrix to extract scale from for filter parameters
 ///
 /// # Limitations
-/// Currently only supports filter graphs with a single primitive.
-/// Multi-primitive filter graphs are not yet implemented.
+/// `BackgroundImage`, `BackgroundAlpha`, `FillPaint` and `StrokePaint` inputs
+/// evaluate to transparent black.
 pub(crate) fn filter_lowp(
     filter: &Filter,
     pixmap: &mut Pixmap,
     layer_manager: &mut LayerManager,
     transform: Affine,
 ) {
-    let prepared_filter = PreparedFilter::new(filter, &transform);
-
-    match prepared_filter {
-        PreparedFilter::Flood(flood) => {
-            flood.execute_lowp(pixmap, layer_manager);
-        }
-        PreparedFilter::GaussianBlur(blur) => {
-            blur.execute_lowp(pixmap, layer_manager);
-        }
-        PreparedFilter::Offset(offset) => {
-            offset.execute_lowp(pixmap, layer_manager);
-        }
-        PreparedFilter::DropShadow(drop_shadow) => {
-            drop_shadow.execute_lowp(pixmap, layer_manager);
-        }
-    }
+    graph::apply_filter_graph(
+        &filter.graph,
+        pixmap,
+        layer_manager,
+        transform,
+        FilterPrecision::Lowp,
+    );
 }
 
 /// Apply the high-precision (f32) version of a filter effect to a layer.
 ///
-/// This function dispatches filter primitives from a filter graph to their
-/// corresponding CPU implementations using 32-bit floating-point color channels.
+/// This function evaluates the filter graph, dispatching each primitive to its
+/// corresponding CPU implementation using 32-bit floating-point color channels.
 ///
 /// # Arguments
 /// * `filter` - The filter containing the graph of primitives to apply
@@ -93,28 +123,68 @@ pub(crate) fn filter_lowp(
 /// * `transform` - The transformation matrix to extract scale from for filter parameters
 ///
 /// # Limitations
-/// Currently only supports filter graphs with a single primitive.
-/// Multi-primitive filter graphs are not yet implemented.
+/// `BackgroundImage`, `BackgroundAlpha`, `FillPaint` and `StrokePaint` inputs
+/// evaluate to transparent black.
 pub(crate) fn filter_highp(
     filter: &Filter,
     pixmap: &mut Pixmap,
     layer_manager: &mut LayerManager,
     transform: Affine,
 ) {
-    let prepared_filter = PreparedFilter::new(filter, &transform);
+    graph::apply_filter_graph(
+        &filter.graph,
+        pixmap,
+        layer_manager,
+        transform,
+        FilterPrecision::Highp,
+    );
+}
 
-    match prepared_filter {
-        PreparedFilter::Flood(flood) => {
-            flood.execute_highp(pixmap, layer_manager);
-        }
-        PreparedFilter::GaussianBlur(blur) => {
-            blur.execute_highp(pixmap, layer_manager);
-        }
-        PreparedFilter::Offset(offset) => {
-            offset.execute_highp(pixmap, layer_manager);
-        }
-        PreparedFilter::DropShadow(drop_shadow) => {
-            drop_shadow.execute_highp(pixmap, layer_manager);
+/// Apply a single-input filter primitive in place.
+fn execute_unary(
+    prepared_filter: &PreparedFilter,
+    pixmap: &mut Pixmap,
+    layer_manager: &mut LayerManager,
+    precision: FilterPrecision,
+) {
+    let effect: &dyn FilterEffect = match prepared_filter {
+        PreparedFilter::Flood(flood) => flood,
+        PreparedFilter::GaussianBlur(blur) => blur,
+        PreparedFilter::Offset(offset) => offset,
+        PreparedFilter::DropShadow(drop_shadow) => drop_shadow,
+        PreparedFilter::ColorMatrix(color_matrix) => color_matrix,
+        PreparedFilter::ComponentTransfer(component_transfer) => component_transfer,
+        PreparedFilter::Morphology(morphology) => morphology,
+        PreparedFilter::ConvolveMatrix(convolve_matrix) => convolve_matrix,
+        PreparedFilter::Turbulence(turbulence) => turbulence,
+        PreparedFilter::Lighting(lighting) => lighting,
+        PreparedFilter::Composite(_) | PreparedFilter::DisplacementMap(_) => {
+            unreachable!("binary filter primitives are applied with `execute_binary`")
         }
+    };
+
+    match precision {
+        FilterPrecision::Lowp => effect.execute_lowp(pixmap, layer_manager),
+        FilterPrecision::Highp => effect.execute_highp(pixmap, layer_manager),
+    }
+}
+
+/// Apply a filter primitive with two inputs, writing the result to `pixmap`.
+fn execute_binary(
+    prepared_filter: &PreparedFilter,
+    pixmap: &mut Pixmap,
+    input2: &Pixmap,
+    layer_manager: &mut LayerManager,
+    precision: FilterPrecision,
+) {
+    let effect: &dyn BinaryFilterEffect = match prepared_filter {
+        PreparedFilter::Composite(composite) => composite,
+        PreparedFilter::DisplacementMap(displacement_map) => displacement_map,
+        _ => unreachable!("single-input filter primitives are applied with `execute_unary`"),
+    };
+
+    match precision {
+        FilterPrecision::Lowp => effect.execute_lowp(pixmap, input2, layer_manager),
+        FilterPrecision::Highp => effect.execute_highp(pixmap, input2, layer_manager),
     }
 }
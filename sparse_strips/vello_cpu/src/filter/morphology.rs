@@ -0,0 +1,327 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `feMorphology` filter primitive implementation.
+//!
+//! Eroding (dilating) replaces each channel of a pixel with the minimum (maximum) of that
+//! channel in a rectangle of `(2 * radius_x + 1) × (2 * radius_y + 1)` pixels around it.
+//! Pixels outside of the input are transparent black.
+//!
+//! Since the minimum and maximum over a rectangle are separable, the filter is applied
+//! as a horizontal pass followed by a vertical pass. Each pass uses the van Herk/Gil-Werman
+//! algorithm, which needs a constant number of comparisons per pixel regardless of the radius.
+//! The vertical pass processes whole rows at once, so that all columns are compared with
+//! contiguous SIMD operations.
+//!
+//! Both operations only select existing channel values, so the low- and high-precision
+//! paths produce the same results.
+//!
+//! @see <https://drafts.fxtf.org/filter-effects/#feMorphologyElement>
+
+use super::FilterEffect;
+use crate::layer_manager::LayerManager;
+use alloc::vec::Vec;
+use vello_common::fearless_simd::{Level, Simd, SimdBase, SimdInt, dispatch, u8x64};
+use vello_common::filter::morphology::Morphology;
+use vello_common::filter_effects::MorphologyOperator;
+use vello_common::pixmap::Pixmap;
+
+impl FilterEffect for Morphology {
+    fn execute_lowp(&self, pixmap: &mut Pixmap, layer_manager: &mut LayerManager) {
+        if self.is_noop() {
+            return;
+        }
+
+        let scratch = layer_manager.get_scratch_buffer(pixmap.width(), pixmap.height());
+        // Filters don't have access to the level of the render context. The results don't
+        // depend on the level, so use the best one available.
+        dispatch!(Level::try_detect().unwrap_or(Level::baseline()), simd => simd.vectorize(
+            #[inline(always)]
+            || apply_morphology(simd, pixmap, scratch, self)
+        ));
+    }
+
+    fn execute_highp(&self, pixmap: &mut Pixmap, layer_manager: &mut LayerManager) {
+        // Selecting minima and maxima is exact, so there is nothing to gain from floats.
+        Self::execute_lowp(self, pixmap, layer_manager);
+    }
+}
+
+/// Apply erosion or dilation using a horizontal and a vertical pass.
+///
+/// The `scratch` buffer holds the result of the horizontal pass and must be at least as
+/// large as the source pixmap.
+#[inline(always)]
+fn apply_morphology<S: Simd>(
+    simd: S,
+    pixmap: &mut Pixmap,
+    scratch: &mut Pixmap,
+    filter: &Morphology,
+) {
+    let stride = usize::from(pixmap.width()) * 4;
+    let len = stride * usize::from(pixmap.height());
+    if len == 0 {
+        return;
+    }
+
+    let mut lines = LineBuffers::default();
+    let tmp = &mut scratch.data_as_u8_slice_mut()[..len];
+
+    // Horizontal pass: pixmap -> scratch, treating each pixel of a row as one cell.
+    let src = pixmap.data_as_u8_slice();
+    for (src_row, dst_row) in src.chunks_exact(stride).zip(tmp.chunks_exact_mut(stride)) {
+        lines.process(
+            simd,
+            src_row,
+            4,
+            usize::from(filter.radius_x),
+            filter.operator,
+            dst_row,
+        );
+    }
+
+    // Vertical pass: scratch -> pixmap, treating each row as one cell.
+    lines.process(
+        simd,
+        tmp,
+        stride,
+        usize::from(filter.radius_y),
+        filter.operator,
+        pixmap.data_as_u8_slice_mut(),
+    );
+}
+
+/// Reusable buffers for processing a line of cells.
+#[derive(Default)]
+struct LineBuffers {
+    /// Running extremum from the start of each block of `2 * radius + 1` cells.
+    prefix: Vec<u8>,
+    /// Running extremum from the end of each block of `2 * radius + 1` cells.
+    suffix: Vec<u8>,
+}
+
+impl LineBuffers {
+    /// Compute the extremum of the window around each cell of `src` (van Herk/Gil-Werman)
+    /// and write it to `dst`.
+    ///
+    /// `src` consists of cells of `cell` bytes each, which are compared byte-wise. The line is
+    /// padded with `radius` transparent cells on both sides. Any window of `2 * radius + 1`
+    /// cells spans at most two consecutive blocks of the same size, so its extremum is the
+    /// combination of the suffix extremum at its start and the prefix extremum at its end.
+    #[inline(always)]
+    fn process<S: Simd>(
+        &mut self,
+        simd: S,
+        src: &[u8],
+        cell: usize,
+        radius: usize,
+        operator: MorphologyOperator,
+        dst: &mut [u8],
+    ) {
+        let n = src.len() / cell;
+        // A window larger than twice the line already covers all cells and padding.
+        let radius = radius.min(n);
+        let window = 2 * radius + 1;
+        let m = n + 2 * radius;
+
+        for buffer in [&mut self.prefix, &mut self.suffix] {
+            buffer.clear();
+            buffer.resize(radius * cell, 0);
+            buffer.extend_from_slice(src);
+            buffer.resize(m * cell, 0);
+        }
+
+        for i in 1..m {
+            if i % window != 0 {
+                let (previous, current) =
+                    self.prefix[(i - 1) * cell..][..2 * cell].split_at_mut(cell);
+                pick(simd, operator, current, previous);
+            }
+        }
+
+        for i in (0..m - 1).rev() {
+            if (i + 1) % window != 0 {
+                let (current, next) = self.suffix[i * cell..][..2 * cell].split_at_mut(cell);
+                pick(simd, operator, current, next);
+            }
+        }
+
+        dst.copy_from_slice(&self.suffix[..n * cell]);
+        pick(
+            simd,
+            operator,
+            dst,
+            &self.prefix[(window - 1) * cell..][..n * cell],
+        );
+    }
+}
+
+/// Replace each byte of `dst` with the minimum (erode) or maximum (dilate) of it and the
+/// corresponding byte of `other`.
+#[inline(always)]
+fn pick<S: Simd>(simd: S, operator: MorphologyOperator, dst: &mut [u8], other: &[u8]) {
+    let mut dst_chunks = dst.chunks_exact_mut(u8x64::<S>::N);
+    let mut other_chunks = other.chunks_exact(u8x64::<S>::N);
+    for (dst, other) in (&mut dst_chunks).zip(&mut other_chunks) {
+        let a = u8x64::from_slice(simd, dst);
+        let b = u8x64::from_slice(simd, other);
+        let result = match operator {
+            MorphologyOperator::Erode => a.min(b),
+            MorphologyOperator::Dilate => a.max(b),
+        };
+        result.store_slice(dst);
+    }
+    for (dst, other) in dst_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(other_chunks.remainder())
+    {
+        *dst = match operator {
+            MorphologyOperator::Erode => (*dst).min(*other),
+            MorphologyOperator::Dilate => (*dst).max(*other),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use vello_common::fearless_simd::Fallback;
+    use vello_common::peniko::color::PremulRgba8;
+
+    const OPAQUE: PremulRgba8 = PremulRgba8 {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    /// A 7x5 pixmap with an opaque 3x1 bar in the middle.
+    fn bar() -> Pixmap {
+        let mut pixmap = Pixmap::new(7, 5);
+        for x in 2..5 {
+            pixmap.set_pixel(x, 2, OPAQUE);
+        }
+        pixmap
+    }
+
+    fn opaque_pixels(pixmap: &Pixmap) -> Vec<(u16, u16)> {
+        let mut result = Vec::new();
+        for y in 0..pixmap.height() {
+            for x in 0..pixmap.width() {
+                if pixmap.sample(x, y).a == 255 {
+                    result.push((x, y));
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn dilate_grows_by_radius() {
+        let mut pixmap = bar();
+        Morphology::new(MorphologyOperator::Dilate, 1.0, 1.0)
+            .execute_lowp(&mut pixmap, &mut LayerManager::new());
+
+        let expected: Vec<_> = (1..4).flat_map(|y| (1..6).map(move |x| (x, y))).collect();
+        assert_eq!(opaque_pixels(&pixmap), expected);
+    }
+
+    #[test]
+    fn erode_shrinks_by_radius() {
+        let mut pixmap = bar();
+        Morphology::new(MorphologyOperator::Erode, 1.0, 0.0)
+            .execute_lowp(&mut pixmap, &mut LayerManager::new());
+
+        assert_eq!(opaque_pixels(&pixmap), [(3, 2)]);
+    }
+
+    /// Pixels outside of the pixmap are transparent black, so eroding by a radius larger
+    /// than the pixmap clears it.
+    #[test]
+    fn erode_with_large_radius() {
+        let mut pixmap = Pixmap::new(3, 3);
+        pixmap.data_mut().fill(OPAQUE);
+        Morphology::new(MorphologyOperator::Erode, 10.0, 10.0)
+            .execute_lowp(&mut pixmap, &mut LayerManager::new());
+
+        assert!(opaque_pixels(&pixmap).is_empty());
+    }
+
+    /// The result must match a brute-force search over the window for every radius.
+    #[test]
+    fn matches_brute_force() {
+        let values = [3_u8, 9, 1, 7, 7, 0, 4, 8, 2, 6, 5];
+        let line: Vec<u8> = values.iter().flat_map(|v| [*v, 9 - *v, *v, 9]).collect();
+        let max = |a: [u8; 4], b: [u8; 4]| -> [u8; 4] { core::array::from_fn(|i| a[i].max(b[i])) };
+
+        let mut buffers = LineBuffers::default();
+        let mut out = vec![0; line.len()];
+        for radius in 0..14 {
+            buffers.process(
+                Fallback::new(),
+                &line,
+                4,
+                radius,
+                MorphologyOperator::Dilate,
+                &mut out,
+            );
+
+            for (x, out) in out.chunks_exact(4).enumerate() {
+                let expected = (x as isize - radius as isize..=(x + radius) as isize)
+                    .map(|i| {
+                        usize::try_from(i)
+                            .ok()
+                            .and_then(|i| line.chunks_exact(4).nth(i))
+                            .map_or([0; 4], |c| c.try_into().unwrap())
+                    })
+                    .fold([0; 4], max);
+                assert_eq!(out, expected, "radius {radius}, x {x}");
+            }
+        }
+    }
+
+    /// Rows are compared as a whole in the vertical pass, which must match comparing each
+    /// column separately, including rows that are longer than a SIMD vector.
+    #[test]
+    fn vertical_pass_matches_columns() {
+        let (width, height) = (37_u16, 9_u16);
+        let mut pixmap = Pixmap::new(width, height);
+        for (i, pixel) in pixmap.data_mut().iter_mut().enumerate() {
+            let v = (i * 37 % 251) as u8;
+            *pixel = PremulRgba8::from_u8_array([v / 2, v / 3, v / 4, v]);
+        }
+        let columns: Vec<Vec<u8>> = (0..width)
+            .map(|x| {
+                (0..height)
+                    .flat_map(|y| pixmap.sample(x, y).to_u8_array())
+                    .collect()
+            })
+            .collect();
+
+        let mut filtered = pixmap.clone();
+        Morphology::new(MorphologyOperator::Erode, 0.0, 2.0)
+            .execute_lowp(&mut filtered, &mut LayerManager::new());
+
+        let mut buffers = LineBuffers::default();
+        for (x, column) in columns.iter().enumerate() {
+            let mut out = vec![0; column.len()];
+            buffers.process(
+                Fallback::new(),
+                column,
+                4,
+                2,
+                MorphologyOperator::Erode,
+                &mut out,
+            );
+            for (y, expected) in out.chunks_exact(4).enumerate() {
+                assert_eq!(
+                    filtered.sample(x as u16, y as u16).to_u8_array(),
+                    expected,
+                    "x {x}, y {y}"
+                );
+            }
+        }
+    }
+}
@@ -190,6 +190,21 @@ impl<S: Simd> FineKernel<S> for F32Kernel {
         );
     }
 
+    #[inline(always)]
+    fn apply_opacity(simd: S, dest: &mut [Self::Numeric], opacity: f32) {
+        let opacity = f32x16::splat(simd, opacity);
+
+        simd.vectorize(
+            #[inline(always)]
+            || {
+                for chunk in dest.chunks_exact_mut(16) {
+                    let pixel = f32x16::from_slice(simd, chunk);
+                    chunk.copy_from_slice((pixel * opacity).as_slice());
+                }
+            },
+        );
+    }
+
     /// Composites a solid color onto a buffer using alpha blending.
     ///
     /// Dispatches to either the masked or unmasked implementation based on the
@@ -213,6 +228,18 @@ impl<S: Simd> FineKernel<S> for F32Kernel {
         }
     }
 
+    /// Composites a solid color onto a buffer using a separate alpha mask value
+    /// for each color component.
+    #[inline(always)]
+    fn alpha_composite_solid_subpixel(
+        simd: S,
+        dest: &mut [Self::Numeric],
+        src: [Self::Numeric; 4],
+        alphas: &[u8],
+    ) {
+        alpha_fill::alpha_composite_solid_subpixel(simd, dest, src, alphas);
+    }
+
     /// Composites a source buffer onto a destination buffer using alpha blending.
     ///
     /// Dispatches to either the masked or unmasked implementation based on the
@@ -416,9 +443,9 @@ mod alpha_fill {
     //! This module handles compositing when each pixel has an additional mask value
     //! (e.g., from anti-aliasing or clip masks) that modulates the source alpha.
 
-    use crate::fine::Splat4thExt;
     use crate::fine::highp::compose::ComposeExt;
     use crate::fine::highp::{blend, extract_masks};
+    use crate::fine::{NumericVec, Splat4thExt};
     use crate::peniko::BlendMode;
     use vello_common::fearless_simd::*;
 
@@ -440,7 +467,35 @@ mod alpha_fill {
                 let one = f32x16::splat(s, 1.0);
 
                 for (next_dest, next_mask) in dest.chunks_exact_mut(16).zip(alphas) {
-                    alpha_composite_inner(s, next_dest, &next_mask, src_c, src_a, one);
+                    let mask_a = extract_masks(s, &next_mask);
+                    alpha_composite_inner(s, next_dest, mask_a, src_c, src_a, one);
+                }
+            },
+        );
+    }
+
+    /// Composites a solid color with a separate mask value for each color component.
+    ///
+    /// Works like [`alpha_composite_solid`], but `alphas` contains one mask value per
+    /// component instead of one per pixel.
+    #[inline(always)]
+    pub(super) fn alpha_composite_solid_subpixel<S: Simd>(
+        s: S,
+        dest: &mut [f32],
+        src: [f32; 4],
+        alphas: &[u8],
+    ) {
+        s.vectorize(
+            #[inline(always)]
+            || {
+                let src_a = f32x16::splat(s, src[3]);
+                let src_c = f32x16::block_splat(src.simd_into(s));
+                let one = f32x16::splat(s, 1.0);
+
+                for (next_dest, next_mask) in dest.chunks_exact_mut(16).zip(alphas.chunks_exact(16))
+                {
+                    let mask_a = f32x16::from_u8(s, u8x16::from_slice(s, next_mask));
+                    alpha_composite_inner(s, next_dest, mask_a, src_c, src_a, one);
                 }
             },
         );
@@ -464,7 +519,8 @@ mod alpha_fill {
                     dest.chunks_exact_mut(16).zip(alphas).zip(src)
                 {
                     let src_a = next_src.splat_4th();
-                    alpha_composite_inner(simd, next_dest, &next_mask, next_src, src_a, one);
+                    let mask_a = extract_masks(simd, &next_mask);
+                    alpha_composite_inner(simd, next_dest, mask_a, next_src, src_a, one);
                 }
             },
         );
@@ -503,13 +559,12 @@ mod alpha_fill {
     fn alpha_composite_inner<S: Simd>(
         s: S,
         dest: &mut [f32],
-        masks: &[u8; 4],
+        mask_a: f32x16<S>,
         src_c: f32x16<S>,
         src_a: f32x16<S>,
         one: f32x16<S>,
     ) {
         let bg_c = f32x16::from_slice(s, dest);
-        let mask_a = extract_masks(s, masks);
         // 1 - src_a * mask_a
         let inv_src_a_mask_a = src_a.mul_add(-mask_a, one);
 
@@ -64,8 +64,25 @@ impl<S: Simd> crate::fine::Painter for GradientPainter<'_, S> {
         );
     }
 
-    fn paint_f32(&mut self, _: &mut [f32]) {
-        unimplemented!()
+    fn paint_f32(&mut self, buf: &mut [f32]) {
+        use crate::fine::NumericVec;
+
+        self.simd.vectorize(
+            #[inline(always)]
+            || {
+                for chunk in buf.chunks_exact_mut(64) {
+                    let next = self.next().unwrap();
+                    let (lo, hi) = self.simd.split_u8x64(next);
+                    let (p1, p2) = self.simd.split_u8x32(lo);
+                    let (p3, p4) = self.simd.split_u8x32(hi);
+
+                    for (dest, part) in chunk.chunks_exact_mut(16).zip([p1, p2, p3, p4]) {
+                        let converted = f32x16::from_u8(self.simd, part);
+                        dest.copy_from_slice(converted.as_slice());
+                    }
+                }
+            },
+        );
     }
 }
 
@@ -82,3 +99,39 @@ pub(crate) fn apply_extend<S: Simd>(val: f32x16<S>, extend: peniko::Extend) -> f
             .min(1.0),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::palette::css::{BLUE, RED};
+    use crate::fine::Painter;
+    use crate::kurbo::Affine;
+    use crate::peniko::Gradient;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use vello_common::encode::{EncodeExt, EncodedPaint};
+    use vello_common::fearless_simd::dispatch;
+
+    #[test]
+    fn paint_f32_matches_paint_u8() {
+        let gradient =
+            Gradient::new_linear((0.0, 0.0), (64.0, 0.0)).with_stops([RED.with_alpha(0.5), BLUE]);
+        let mut paints = vec![];
+        gradient.encode_into(&mut paints, Affine::IDENTITY, None);
+        let EncodedPaint::Gradient(gradient) = &paints[0] else {
+            panic!("expected an encoded gradient");
+        };
+
+        let t_vals = (0..64).map(|i| i as f32 / 63.0).collect::<Vec<_>>();
+        dispatch!(Level::try_detect().unwrap_or(Level::baseline()), simd => {
+            let mut u8_buf = vec![0_u8; t_vals.len() * 4];
+            let mut f32_buf = vec![0.0_f32; t_vals.len() * 4];
+            GradientPainter::new(simd, gradient, &t_vals).paint_u8(&mut u8_buf);
+            GradientPainter::new(simd, gradient, &t_vals).paint_f32(&mut f32_buf);
+
+            for (u, f) in u8_buf.iter().zip(&f32_buf) {
+                assert!((*u as f32 / 255.0 - f).abs() < 1e-6);
+            }
+        });
+    }
+}
@@ -226,6 +226,21 @@ impl<S: Simd> FineKernel<S> for U8Kernel {
         );
     }
 
+    #[inline(always)]
+    fn apply_opacity(simd: S, dest: &mut [Self::Numeric], opacity: f32) {
+        let opacity = u8x32::splat(simd, (opacity * 255.0 + 0.5) as u8);
+
+        simd.vectorize(
+            #[inline(always)]
+            || {
+                for chunk in dest.chunks_exact_mut(32) {
+                    let pixel = u8x32::from_slice(simd, chunk);
+                    chunk.copy_from_slice(pixel.normalized_mul(opacity).as_slice());
+                }
+            },
+        );
+    }
+
     /// Composites a solid color onto a buffer using alpha blending.
     ///
     /// Dispatches to either the masked or unmasked implementation based on the
@@ -249,6 +264,18 @@ impl<S: Simd> FineKernel<S> for U8Kernel {
         }
     }
 
+    /// Composites a solid color onto a buffer using a separate alpha mask value
+    /// for each color component.
+    #[inline(always)]
+    fn alpha_composite_solid_subpixel(
+        simd: S,
+        dest: &mut [Self::Numeric],
+        src: [Self::Numeric; 4],
+        alphas: &[u8],
+    ) {
+        alpha_fill::alpha_composite_solid_subpixel(simd, dest, src, alphas);
+    }
+
     /// Composites a source buffer onto a destination buffer using alpha blending.
     ///
     /// Dispatches to either the masked or unmasked implementation based on the
@@ -503,7 +530,34 @@ mod alpha_fill {
                 let one = u8x32::splat(s, 255);
 
                 for (next_bg, next_mask) in dest.chunks_exact_mut(32).zip(alphas) {
-                    alpha_composite_inner(s, next_bg, &next_mask, src_c, src_a, one);
+                    let mask_v = extract_masks(s, &next_mask);
+                    alpha_composite_inner(s, next_bg, mask_v, src_c, src_a, one);
+                }
+            },
+        );
+    }
+
+    /// Composites a solid color with a separate mask value for each color component.
+    ///
+    /// Works like [`alpha_composite_solid`], but `alphas` contains one mask value per
+    /// component instead of one per pixel.
+    #[inline(always)]
+    pub(super) fn alpha_composite_solid_subpixel<S: Simd>(
+        s: S,
+        dest: &mut [u8],
+        src: [u8; 4],
+        alphas: &[u8],
+    ) {
+        s.vectorize(
+            #[inline(always)]
+            || {
+                let src_a = u8x32::splat(s, src[3]);
+                let src_c = u32x8::splat(s, u32::from_ne_bytes(src)).to_bytes();
+                let one = u8x32::splat(s, 255);
+
+                for (next_bg, next_mask) in dest.chunks_exact_mut(32).zip(alphas.chunks_exact(32)) {
+                    let mask_v = u8x32::from_slice(s, next_mask);
+                    alpha_composite_inner(s, next_bg, mask_v, src_c, src_a, one);
                 }
             },
         );
@@ -528,7 +582,8 @@ mod alpha_fill {
                     dest.chunks_exact_mut(32).zip(alphas).zip(src)
                 {
                     let src_a = next_src.splat_4th();
-                    alpha_composite_inner(simd, next_dest, &next_mask, next_src, src_a, one);
+                    let mask_v = extract_masks(simd, &next_mask);
+                    alpha_composite_inner(simd, next_dest, mask_v, next_src, src_a, one);
                 }
             },
         );
@@ -542,7 +597,7 @@ mod alpha_fill {
     fn alpha_composite_inner<S: Simd>(
         s: S,
         dest: &mut [u8],
-        masks: &[u8; 8],
+        mask_v: u8x32<S>,
         src_c: u8x32<S>,
         src_a: u8x32<S>,
         one: u8x32<S>,
@@ -551,8 +606,6 @@ mod alpha_fill {
             #[inline(always)]
             || {
                 let bg_v = u8x32::from_slice(s, dest);
-
-                let mask_v = extract_masks(s, masks);
                 let inv_src_a_mask_a = one - s.narrow_u16x32(normalized_mul_u8x32(src_a, mask_v));
 
                 let p1 = s.widen_u8x32(bg_v) * s.widen_u8x32(inv_src_a_mask_a);
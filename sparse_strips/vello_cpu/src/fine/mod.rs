@@ -404,6 +404,12 @@ pub trait FineKernel<S: Simd>: Send + Sync + 'static {
     /// regressing the non-tinted fast path.
     fn apply_tint(simd: S, dest: &mut [Self::Numeric], tint: &Tint);
 
+    /// Apply an image's opacity to an already-painted buffer.
+    ///
+    /// Like [`FineKernel::apply_tint`], this is a post-pass after `apply_painter`, which
+    /// is only performed when the image sampler's alpha is below 1.
+    fn apply_opacity(simd: S, dest: &mut [Self::Numeric], opacity: f32);
+
     /// Perform alpha compositing with a solid color over the target buffer.
     ///
     /// Blends a solid RGBA color over the existing contents using standard alpha compositing
@@ -415,6 +421,18 @@ pub trait FineKernel<S: Simd>: Send + Sync + 'static {
         alphas: Option<&[u8]>,
     );
 
+    /// Perform alpha compositing with a solid color over the target buffer, using a separate
+    /// coverage value for each color component.
+    ///
+    /// This is used for subpixel rendering, in which case `alphas` stores four coverage values
+    /// per pixel instead of one.
+    fn alpha_composite_solid_subpixel(
+        simd: S,
+        target: &mut [Self::Numeric],
+        src: [Self::Numeric; 4],
+        alphas: &[u8],
+    );
+
     /// Perform alpha compositing with a source buffer over the destination buffer.
     ///
     /// Blends the source buffer contents over the destination using standard alpha compositing.
@@ -560,6 +578,16 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                     fill_attrs.mask.as_ref(),
                 );
             }
+            Cmd::AlphaFill(s) if attrs.fill[s.attrs_idx as usize].subpixel => {
+                let fill_attrs = &attrs.fill[s.attrs_idx as usize];
+                let alpha_idx = fill_attrs.alpha_idx(s.alpha_offset) as usize;
+                self.fill_subpixel(
+                    usize::from(s.x),
+                    usize::from(s.width),
+                    &fill_attrs.paint,
+                    &alphas[alpha_idx..],
+                );
+            }
             Cmd::AlphaFill(s) => {
                 let fill_attrs = &attrs.fill[s.attrs_idx as usize];
                 let alpha_idx = fill_attrs.alpha_idx(s.alpha_offset) as usize;
@@ -726,14 +754,17 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                 // we would have to repeatedly provide all arguments if we made it a function.
                 macro_rules! fill_complex_paint {
                     ($may_have_opacities:expr, $filler:expr) => {
-                        fill_complex_paint!($may_have_opacities, $filler, None::<&Tint>)
+                        fill_complex_paint!($may_have_opacities, $filler, None::<&Tint>, 1.0)
                     };
-                    ($may_have_opacities:expr, $filler:expr, $tint:expr) => {
+                    ($may_have_opacities:expr, $filler:expr, $tint:expr, $opacity:expr) => {
                         if $may_have_opacities || alphas.is_some() {
                             T::apply_painter(self.simd, color_buf, $filler);
                             if let Some(t) = $tint {
                                 T::apply_tint(self.simd, color_buf, t);
                             }
+                            if $opacity < 1.0 {
+                                T::apply_opacity(self.simd, color_buf, $opacity);
+                            }
 
                             if default_blend && mask.is_none() {
                                 T::alpha_composite_buffer(self.simd, blend_buf, color_buf, alphas);
@@ -841,6 +872,7 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                         };
 
                         let tint = i.tint.as_ref();
+                        let opacity = i.sampler.alpha;
 
                         match (i.has_skew(), i.nearest_neighbor()) {
                             (false, false) => {
@@ -851,7 +883,8 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                                         T::plain_medium_quality_image_painter(
                                             self.simd, i, &pixmap, start_x, start_y
                                         ),
-                                        tint
+                                        tint,
+                                        opacity
                                     );
                                 } else {
                                     fill_complex_paint!(
@@ -859,7 +892,8 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                                         T::high_quality_image_painter(
                                             self.simd, i, &pixmap, start_x, start_y
                                         ),
-                                        tint
+                                        tint,
+                                        opacity
                                     );
                                 }
                             }
@@ -871,7 +905,8 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                                         T::medium_quality_image_painter(
                                             self.simd, i, &pixmap, start_x, start_y
                                         ),
-                                        tint
+                                        tint,
+                                        opacity
                                     );
                                 } else {
                                     fill_complex_paint!(
@@ -879,7 +914,8 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                                         T::high_quality_image_painter(
                                             self.simd, i, &pixmap, start_x, start_y
                                         ),
-                                        tint
+                                        tint,
+                                        opacity
                                     );
                                 }
                             }
@@ -889,14 +925,16 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
                                     T::plain_nn_image_painter(
                                         self.simd, i, &pixmap, start_x, start_y
                                     ),
-                                    tint
+                                    tint,
+                                    opacity
                                 );
                             }
                             (true, true) => {
                                 fill_complex_paint!(
                                     i.may_have_opacities,
                                     T::nn_image_painter(self.simd, i, &pixmap, start_x, start_y),
-                                    tint
+                                    tint,
+                                    opacity
                                 );
                             }
                         }
@@ -906,6 +944,26 @@ impl<S: Simd, T: FineKernel<S>> Fine<S, T> {
         }
     }
 
+    /// Fill a horizontal strip within the current tile using subpixel coverage.
+    ///
+    /// Unlike [`Fine::fill`], `alphas` stores a separate coverage value for each color
+    /// component of a pixel. Only solid paints are supported.
+    fn fill_subpixel(&mut self, x: usize, width: usize, fill: &Paint, alphas: &[u8]) {
+        let blend_buf = &mut self.blend_buf.last_mut().unwrap()[x * TILE_HEIGHT_COMPONENTS..]
+            [..TILE_HEIGHT_COMPONENTS * width];
+
+        let Paint::Solid(color) = fill else {
+            unreachable!("subpixel fills are only generated for solid paints");
+        };
+
+        T::alpha_composite_solid_subpixel(
+            self.simd,
+            blend_buf,
+            T::extract_color(*color),
+            &alphas[..TILE_HEIGHT_COMPONENTS * width],
+        );
+    }
+
     /// Blend the top blend buffer into the buffer below it.
     ///
     /// This pops the top buffer from the blend stack and composites it onto the
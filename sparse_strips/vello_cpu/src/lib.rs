@@ -156,10 +156,12 @@ pub mod region;
 pub use render::{RenderContext, RenderSettings, RenderState};
 pub use vello_common::fearless_simd::Level;
 #[cfg(feature = "text")]
-pub use vello_common::glyph::Glyph;
+pub use vello_common::glyph::{Glyph, GlyphCacheBudget, GlyphCacheStats, GlyphCaches};
 pub use vello_common::mask::Mask;
 pub use vello_common::paint::{Image, ImageSource, Paint, PaintType};
 pub use vello_common::pixmap::Pixmap;
+#[cfg(feature = "text")]
+pub use vello_common::subpixel::SubpixelOrder;
 pub use vello_common::{color, kurbo, peniko};
 
 /// The selected rendering mode.
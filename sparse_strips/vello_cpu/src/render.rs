@@ -14,6 +14,7 @@ use alloc::boxed::Box;
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::hash::Hash;
 use hashbrown::HashMap;
 use vello_common::blurred_rounded_rect::BlurredRoundedRectangle;
 use vello_common::encode::{EncodeExt, EncodedPaint};
@@ -24,11 +25,17 @@ use vello_common::mask::Mask;
 #[cfg(feature = "text")]
 use vello_common::paint::{Image, ImageSource};
 use vello_common::paint::{ImageId, ImageResolver, Paint, PaintType, Tint};
+use vello_common::peniko::Style;
 use vello_common::peniko::color::palette::css::BLACK;
 use vello_common::peniko::{BlendMode, Fill};
+#[cfg(feature = "text")]
+use vello_common::peniko::{Extend, ImageQuality, ImageSampler};
 use vello_common::pixmap::Pixmap;
+#[cfg(feature = "text")]
+use vello_common::recording::{BitmapGlyphCommand, ColrGlyphCommand};
 use vello_common::recording::{PushLayerCommand, Recordable, Recorder, Recording, RenderCommand};
 use vello_common::strip::Strip;
+use vello_common::strip_cache::StripCache;
 use vello_common::strip_generator::{GenerationMode, StripGenerator, StripStorage};
 use vello_common::util::{is_integer_rect, is_integer_translation};
 #[cfg(feature = "text")]
@@ -36,6 +43,7 @@ use vello_common::{
     color::{AlphaColor, Srgb},
     colr::{ColrPainter, ColrRenderer},
     glyph::{GlyphCaches, GlyphRenderer, GlyphRunBuilder, GlyphType, PreparedGlyph},
+    subpixel::SubpixelOrder,
 };
 
 /// A render context for CPU-based 2D graphics rendering.
@@ -79,6 +87,9 @@ pub struct RenderContext {
     dispatcher: Box<dyn Dispatcher>,
     #[cfg(feature = "text")]
     pub(crate) glyph_caches: Option<GlyphCaches>,
+    /// The subpixel order used for filling outline glyphs, if subpixel rendering is enabled.
+    #[cfg(feature = "text")]
+    pub(crate) subpixel_order: Option<SubpixelOrder>,
     /// Registry for resolving `ImageSource::OpaqueId` to pixmap data.
     image_registry: ImageRegistry,
 }
@@ -177,6 +188,8 @@ impl RenderContext {
             filter: None,
             #[cfg(feature = "text")]
             glyph_caches: Some(GlyphCaches::default()),
+            #[cfg(feature = "text")]
+            subpixel_order: None,
             image_registry: ImageRegistry::new(),
         }
     }
@@ -197,6 +210,11 @@ impl RenderContext {
                 self.transform * self.paint_transform,
                 self.tint,
             ),
+            PaintType::BlurredRoundedRect(r) => r.encode_into(
+                &mut self.encoded_paints,
+                self.transform * self.paint_transform,
+                None,
+            ),
         }
     }
 
@@ -354,6 +372,44 @@ impl RenderContext {
         GlyphRunBuilder::new(font.clone(), self.transform, self)
     }
 
+    /// Returns the caches used for glyph rendering.
+    ///
+    /// This can be used to query [statistics](GlyphCaches::stats) about the caches.
+    #[cfg(feature = "text")]
+    pub fn glyph_caches(&self) -> &GlyphCaches {
+        self.glyph_caches.as_ref().unwrap()
+    }
+
+    /// Returns the caches used for glyph rendering mutably.
+    ///
+    /// This can be used to configure the [budget](GlyphCaches::set_budget) of the caches.
+    #[cfg(feature = "text")]
+    pub fn glyph_caches_mut(&mut self) -> &mut GlyphCaches {
+        self.glyph_caches.as_mut().unwrap()
+    }
+
+    /// Set the subpixel order used for filling outline glyphs.
+    ///
+    /// If set to `Some`, outline glyphs are filled with subpixel antialiasing (similar to
+    /// ClearType), which makes small text on low-DPI displays look sharper. The order needs to
+    /// match the physical layout of the subpixels of the display. If set to `None` (the
+    /// default), glyphs are rendered with grayscale antialiasing.
+    ///
+    /// Subpixel antialiasing only looks correct when drawing onto an opaque background. Glyphs
+    /// still fall back to grayscale antialiasing if the current paint is not a solid color, a
+    /// non-default blend mode, a mask or an aliasing threshold is set, a clip path is active or
+    /// the glyphs are stroked or recorded.
+    #[cfg(feature = "text")]
+    pub fn set_subpixel_order(&mut self, subpixel_order: Option<SubpixelOrder>) {
+        self.subpixel_order = subpixel_order;
+    }
+
+    /// Get the subpixel order used for filling outline glyphs.
+    #[cfg(feature = "text")]
+    pub fn subpixel_order(&self) -> Option<SubpixelOrder> {
+        self.subpixel_order
+    }
+
     /// Push a new layer with the given properties.
     ///
     /// Note that the mask, if provided, needs to have the same size as the render context. Otherwise,
@@ -420,9 +476,7 @@ impl RenderContext {
 
     /// Push a filter layer that affects all subsequent drawing operations.
     ///
-    /// WARNING: Note that filters are currently incomplete and experimental. In
-    /// particular, they will lead to a panic when used in combination with
-    /// multi-threaded rendering.
+    /// WARNING: Note that filters are currently incomplete and experimental.
     pub fn push_filter_layer(&mut self, filter: Filter) {
         self.push_layer(None, None, None, None, Some(filter));
     }
@@ -662,11 +716,6 @@ impl RenderContext {
     ///
     /// This is useful for rendering individual elements (like glyphs) into
     /// a spritesheet at specific coordinates.
-    ///
-    /// # Panics
-    ///
-    /// This method is only supported with the single-threaded dispatcher and will
-    /// **panic** if called on a `RenderContext` using the multi-threaded dispatcher.
     pub fn composite_to_pixmap_at_offset(&self, pixmap: &mut Pixmap, dst_x: u16, dst_y: u16) {
         let dst_buffer_width = pixmap.width();
         let dst_buffer_height = pixmap.height();
@@ -757,12 +806,122 @@ impl RenderContext {
     }
 }
 
+#[cfg(feature = "text")]
+impl RenderContext {
+    /// Fill a glyph with `image`, drawn with `transform`.
+    ///
+    /// `fill` is called to fill the area of the glyph.
+    fn fill_glyph_image(&mut self, image: Image, transform: Affine, fill: impl FnOnce(&mut Self)) {
+        // We need to change the state of the render context
+        // to render the image, but don't want to pollute the context,
+        // so simulate a `save` and `restore` operation.
+        let old_transform = self.transform;
+        let old_paint = self.paint.clone();
+
+        self.set_paint(image);
+        self.set_transform(transform);
+        fill(self);
+
+        // Restore the state.
+        self.set_paint(old_paint);
+        self.transform = old_transform;
+    }
+
+    /// Render a COLR glyph into a pixmap of size `width` x `height`.
+    ///
+    /// `paint` is called to paint the glyph into the render context.
+    fn render_colr_glyph(&self, width: u16, height: u16, paint: impl FnOnce(&mut Self)) -> Pixmap {
+        let settings = RenderSettings {
+            level: self.render_settings.level,
+            render_mode: self.render_settings.render_mode,
+            num_threads: 0,
+        };
+
+        let mut ctx = Self::new_with(width, height, settings);
+        let mut pix = Pixmap::new(width, height);
+
+        paint(&mut ctx);
+
+        // Technically not necessary since we always render single-threaded, but just
+        // to be safe.
+        ctx.flush();
+        ctx.render_to_pixmap(&mut pix);
+
+        pix
+    }
+}
+
+/// Return the image for filling a bitmap glyph drawn with `transform`.
+#[cfg(feature = "text")]
+fn bitmap_glyph_image(pixmap: Arc<Pixmap>, transform: Affine) -> Image {
+    // If we scale down by a large factor, fall back to cubic scaling.
+    let quality = if transform.as_coeffs()[0] < 0.5 || transform.as_coeffs()[3] < 0.5 {
+        ImageQuality::High
+    } else {
+        ImageQuality::Medium
+    };
+
+    Image {
+        image: ImageSource::Pixmap(pixmap),
+        sampler: ImageSampler {
+            x_extend: Extend::Pad,
+            y_extend: Extend::Pad,
+            quality,
+            alpha: 1.0,
+        },
+    }
+}
+
+/// Return the image for filling a COLR glyph rendered into `pixmap`, drawn with `transform`.
+#[cfg(feature = "text")]
+fn colr_glyph_image(pixmap: Pixmap, transform: Affine) -> Image {
+    let has_skew = transform.as_coeffs()[1] != 0.0 || transform.as_coeffs()[2] != 0.0;
+
+    Image {
+        image: ImageSource::Pixmap(Arc::new(pixmap)),
+        sampler: ImageSampler {
+            x_extend: Extend::Pad,
+            y_extend: Extend::Pad,
+
+            quality: if has_skew {
+                // Even though the pixmap has the "correct" size, the skewing
+                // might cause aliasing artifacts since the pixels don't map
+                // perfectly to the pixmap, so we use bilinear scaling here.
+                ImageQuality::Medium
+            } else {
+                // Since the pixmap will already have the correct size, no need to
+                // use a different image quality here.
+                ImageQuality::Low
+            },
+            alpha: 1.0,
+        },
+    }
+}
+
 #[cfg(feature = "text")]
 impl GlyphRenderer for RenderContext {
     fn fill_glyph(&mut self, prepared_glyph: PreparedGlyph<'_>) {
         match prepared_glyph.glyph_type {
             GlyphType::Outline(glyph) => {
                 let paint = self.encode_current_paint();
+
+                if let Some(subpixel_order) = self.subpixel_order
+                    && matches!(paint, Paint::Solid(_))
+                    && self.blend_mode == BlendMode::default()
+                    && self.mask.is_none()
+                    && self.aliasing_threshold.is_none()
+                {
+                    self.dispatcher.fill_path_subpixel(
+                        glyph.path,
+                        prepared_glyph.transform,
+                        paint,
+                        subpixel_order,
+                        &self.encoded_paints,
+                    );
+
+                    return;
+                }
+
                 self.dispatcher.fill_path(
                     glyph.path,
                     Fill::NonZero,
@@ -775,105 +934,27 @@ impl GlyphRenderer for RenderContext {
                 );
             }
             GlyphType::Bitmap(glyph) => {
-                // We need to change the state of the render context
-                // to render the bitmap, but don't want to pollute the context,
-                // so simulate a `save` and `restore` operation.
-
-                use vello_common::peniko::ImageSampler;
-                let old_transform = self.transform;
-                let old_paint = self.paint.clone();
-
-                // If we scale down by a large factor, fall back to cubic scaling.
-                let quality = if prepared_glyph.transform.as_coeffs()[0] < 0.5
-                    || prepared_glyph.transform.as_coeffs()[3] < 0.5
-                {
-                    crate::peniko::ImageQuality::High
-                } else {
-                    crate::peniko::ImageQuality::Medium
-                };
-
-                let image = Image {
-                    image: ImageSource::Pixmap(Arc::new(glyph.pixmap)),
-                    sampler: ImageSampler {
-                        x_extend: crate::peniko::Extend::Pad,
-                        y_extend: crate::peniko::Extend::Pad,
-                        quality,
-                        alpha: 1.0,
-                    },
-                };
-
-                self.set_paint(image);
-                self.set_transform(prepared_glyph.transform);
-                self.fill_rect(&glyph.area);
-
-                // Restore the state.
-                self.set_paint(old_paint);
-                self.transform = old_transform;
+                let image = bitmap_glyph_image(Arc::new(glyph.pixmap), prepared_glyph.transform);
+                self.fill_glyph_image(image, prepared_glyph.transform, |ctx| {
+                    ctx.fill_rect(&glyph.area);
+                });
             }
             GlyphType::Colr(glyph) => {
-                // Same as for bitmap glyphs, save the state and restore it later on.
-
-                use vello_common::peniko::ImageSampler;
-                let old_transform = self.transform;
-                let old_paint = self.paint.clone();
-                let context_color = match old_paint {
+                let context_color = match self.paint {
                     PaintType::Solid(s) => s,
                     _ => BLACK,
                 };
-
                 let area = glyph.area;
 
-                let glyph_pixmap = {
-                    let settings = RenderSettings {
-                        level: self.render_settings.level,
-                        render_mode: self.render_settings.render_mode,
-                        num_threads: 0,
-                    };
+                let glyph_pixmap =
+                    self.render_colr_glyph(glyph.pix_width, glyph.pix_height, |ctx| {
+                        ColrPainter::new(glyph, context_color, ctx).paint();
+                    });
 
-                    let mut ctx = Self::new_with(glyph.pix_width, glyph.pix_height, settings);
-                    let mut pix = Pixmap::new(glyph.pix_width, glyph.pix_height);
-
-                    let mut colr_painter = ColrPainter::new(glyph, context_color, &mut ctx);
-                    colr_painter.paint();
-
-                    // Technically not necessary since we always render single-threaded, but just
-                    // to be safe.
-                    ctx.flush();
-                    ctx.render_to_pixmap(&mut pix);
-
-                    pix
-                };
-
-                let has_skew = prepared_glyph.transform.as_coeffs()[1] != 0.0
-                    || prepared_glyph.transform.as_coeffs()[2] != 0.0;
-
-                let image = Image {
-                    image: ImageSource::Pixmap(Arc::new(glyph_pixmap)),
-                    sampler: ImageSampler {
-                        x_extend: crate::peniko::Extend::Pad,
-                        y_extend: crate::peniko::Extend::Pad,
-
-                        quality: if has_skew {
-                            // Even though the pixmap has the "correct" size, the skewing
-                            // might cause aliasing artifacts since the pixels don't map
-                            // perfectly to the pixmap, so we use bilinear scaling here.
-                            crate::peniko::ImageQuality::Medium
-                        } else {
-                            // Since the pixmap will already have the correct size, no need to
-                            // use a different image quality here.
-                            crate::peniko::ImageQuality::Low
-                        },
-                        alpha: 1.0,
-                    },
-                };
-
-                self.set_paint(image);
-                self.set_transform(prepared_glyph.transform);
-                self.fill_rect(&area);
-
-                // Restore the state.
-                self.set_paint(old_paint);
-                self.transform = old_transform;
+                let image = colr_glyph_image(glyph_pixmap, prepared_glyph.transform);
+                self.fill_glyph_image(image, prepared_glyph.transform, |ctx| {
+                    ctx.fill_rect(&area);
+                });
             }
         }
     }
@@ -1005,6 +1086,34 @@ impl Recordable for RenderContext {
                     );
                     range_index += 1;
                 }
+                #[cfg(feature = "text")]
+                RenderCommand::FillBitmapGlyph(glyph) => {
+                    let image = bitmap_glyph_image(glyph.pixmap.clone(), glyph.transform);
+                    self.fill_glyph_image(image, glyph.transform, |ctx| {
+                        ctx.process_geometry_command(
+                            strip_start_indices,
+                            range_index,
+                            &adjusted_strips,
+                        );
+                    });
+                    range_index += 1;
+                }
+                #[cfg(feature = "text")]
+                RenderCommand::FillColrGlyph(glyph) => {
+                    let glyph_pixmap =
+                        self.render_colr_glyph(glyph.pix_width, glyph.pix_height, |ctx| {
+                            glyph.recording.replay(ctx);
+                        });
+                    let image = colr_glyph_image(glyph_pixmap, glyph.transform);
+                    self.fill_glyph_image(image, glyph.transform, |ctx| {
+                        ctx.process_geometry_command(
+                            strip_start_indices,
+                            range_index,
+                            &adjusted_strips,
+                        );
+                    });
+                    range_index += 1;
+                }
                 RenderCommand::SetPaint(paint) => {
                     self.set_paint(paint.clone());
                 }
@@ -1205,6 +1314,24 @@ impl RenderContext {
                     );
                     strip_start_indices.push(start_index);
                 }
+                #[cfg(feature = "text")]
+                RenderCommand::FillBitmapGlyph(BitmapGlyphCommand {
+                    area, transform, ..
+                })
+                | RenderCommand::FillColrGlyph(ColrGlyphCommand {
+                    area, transform, ..
+                }) => {
+                    self.rect_to_temp_path(area);
+                    strip_generator.generate_filled_path(
+                        &self.temp_path,
+                        Fill::NonZero,
+                        *transform,
+                        self.aliasing_threshold,
+                        &mut strip_storage,
+                        None,
+                    );
+                    strip_start_indices.push(start_index);
+                }
                 RenderCommand::SetTransform(transform) => {
                     self.transform = *transform;
                 }
@@ -1287,6 +1414,50 @@ impl RenderContext {
     }
 }
 
+/// Path caching implementation.
+impl RenderContext {
+    /// Draw `path` with `style` and the current transform and paint, reusing the strips cached in
+    /// `strip_cache` under `key` where possible.
+    ///
+    /// `strip_storage` is used as scratch space. Returns `false` without drawing anything if
+    /// cached strips can't be used for this draw, in which case the path needs to be drawn as usual.
+    pub(crate) fn draw_cached_path<K: Hash + Eq>(
+        &mut self,
+        strip_cache: &mut StripCache<K>,
+        key: K,
+        path: &[PathEl],
+        style: &Style,
+        strip_storage: &mut StripStorage,
+    ) -> bool {
+        // Cached strips are not intersected with clip paths, and don't go through
+        // masks or filter layers.
+        if self.mask.is_some() || self.filter.is_some() || self.dispatcher.has_clip_path() {
+            return false;
+        }
+        strip_storage.clear();
+        if !strip_cache.generate(
+            key,
+            path,
+            style,
+            self.transform,
+            self.aliasing_threshold,
+            strip_storage,
+        ) {
+            return false;
+        }
+
+        if strip_storage.strips.is_empty() {
+            return true;
+        }
+        let strips = self.prepare_cached_strips(&strip_storage.strips, &strip_storage.alphas);
+        let paint = self.encode_current_paint();
+        self.dispatcher
+            .generate_wide_cmd(&strips, paint, self.blend_mode, &self.encoded_paints);
+
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::RenderContext;
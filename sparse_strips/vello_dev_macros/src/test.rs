@@ -185,10 +185,7 @@ pub(crate) fn vello_test_inner(attr: TokenStream, item: TokenStream) -> TokenStr
 
     // These tests currently don't work with `vello_hybrid`.
     skip_hybrid |= {
-        input_fn_name_str.contains("layer_multiple_properties")
-            || input_fn_name_str.contains("mask")
-            || input_fn_name_str.contains("blurred_rounded_rect")
-            || input_fn_name_str.contains("clip_clear")
+        input_fn_name_str.contains("clip_clear")
             || input_fn_name_str.contains("mix_non_isolated")
             || input_fn_name_str.contains("compose_non_isolated")
     };
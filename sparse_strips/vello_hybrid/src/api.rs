@@ -11,19 +11,26 @@
 //! </div>
 
 use vello_api::{
-    PaintScene, Scene,
+    PaintError, PaintScene, Scene,
     exact::ExactPathElements,
+    paths::{PathId, PathKey, PathSet},
     peniko::Style,
-    scene::{RenderCommand, extract_integer_translation},
+    scene::{RenderCommand, check_balanced, extract_integer_translation},
     texture::TextureId,
 };
 use vello_common::{
-    kurbo::{self, Affine, BezPath},
+    blurred_rounded_rect::BlurredRoundedRectangle,
+    kurbo::{self, Affine, BezPath, PathEl},
     paint::{ImageId, ImageSource},
     peniko::{BlendMode, Brush, Color, Fill, ImageBrush},
+    strip_cache::StripCache,
 };
 
 use crate::Scene as HybridScene;
+#[cfg(feature = "wgpu")]
+use crate::{ImageReadback, Pixmap, Renderer};
+#[cfg(feature = "wgpu")]
+use vello_api::texture::TextureReadback;
 
 /// An adapter to implement [`PaintScene`] for Vello Hybrid's [`Scene`][HybridScene], which is the same for both backends.
 ///
@@ -33,6 +40,52 @@ pub struct HybridScenePainter {
     /// The underlying render context. This is public on an interim basis, whilst we decide how
     /// Vello API will develop further.
     pub scene: HybridScene,
+    strip_cache: StripCache<PathKey>,
+}
+
+impl HybridScenePainter {
+    /// Create a new painter which draws into `scene`.
+    pub fn new(scene: HybridScene) -> Self {
+        let strip_cache =
+            StripCache::new(scene.width(), scene.height(), scene.strip_generator.level());
+        Self { scene, strip_cache }
+    }
+
+    /// The cache of the strips of paths drawn using [`PaintScene::draw_prepared_path`].
+    ///
+    /// [`StripCache::maintain`] should be called once per frame, to evict the paths which are no longer drawn.
+    /// Otherwise, entries are only evicted once the cache is full.
+    pub fn path_cache_mut(&mut self) -> &mut StripCache<PathKey> {
+        &mut self.strip_cache
+    }
+
+    fn draw_cached_path(
+        &mut self,
+        transform: Affine,
+        elements: &[PathEl],
+        style: &Style,
+        key: PathKey,
+    ) {
+        self.scene.set_transform(transform);
+        if self
+            .scene
+            .draw_cached_path(&mut self.strip_cache, key, elements, style)
+        {
+            return;
+        }
+        // TODO: As in `fill_path`
+        let bezpath = BezPath::from_iter(elements.iter().copied());
+        match style {
+            Style::Stroke(stroke) => {
+                self.scene.set_stroke(stroke.clone());
+                self.scene.stroke_path(&bezpath);
+            }
+            Style::Fill(fill) => {
+                self.scene.set_fill_rule(*fill);
+                self.scene.fill_path(&bezpath);
+            }
+        }
+    }
 }
 
 impl PaintScene for HybridScenePainter {
@@ -45,7 +98,13 @@ impl PaintScene for HybridScenePainter {
             commands: input_commands,
             hinted: input_hinted,
         }: &Scene,
-    ) -> Result<(), ()> {
+    ) -> Result<(), PaintError> {
+        check_balanced(input_commands)?;
+        for command in input_commands {
+            if let RenderCommand::SetPaint(_, Brush::Image(brush)) = command {
+                image_id(brush.image)?;
+            }
+        }
         if *input_hinted {
             if let Some((dx, dy)) = extract_integer_translation(scene_transform) {
                 // Update the transform to be a pure integer translation.
@@ -54,8 +113,7 @@ impl PaintScene for HybridScenePainter {
                 // makes our reasoning about this easier.
                 scene_transform = Affine::translate((dx, dy));
             } else {
-                // Translation not hinting compatible.
-                return Err(());
+                return Err(PaintError::HintingIncompatibleTransform);
             }
         }
         for command in input_commands {
@@ -81,6 +139,15 @@ impl PaintScene for HybridScenePainter {
                         }
                     }
                 }
+                RenderCommand::DrawPreparedPath(affine, path_id, key) => {
+                    let path = &input_paths.meta[usize::try_from(path_id.0).unwrap()];
+                    self.draw_cached_path(
+                        scene_transform * *affine,
+                        input_paths.elements(*path_id),
+                        &path.operation,
+                        *key,
+                    );
+                }
                 RenderCommand::PushLayer(push_layer_command) => {
                     self.scene.set_transform(push_layer_command.clip_transform);
                     let clip_path = if let Some(path_id) = push_layer_command.clip_path {
@@ -106,26 +173,41 @@ impl PaintScene for HybridScenePainter {
                     );
                 }
                 RenderCommand::PopLayer => self.scene.pop_layer(),
+                RenderCommand::PushClipPath(clip_transform, path_id) => {
+                    let path = &input_paths.meta[usize::try_from(path_id.0).unwrap()];
+                    // TODO: Also correctly support the case where the meta has a `Style::Stroke`
+                    let fill_rule = match &path.operation {
+                        Style::Fill(fill) => *fill,
+                        Style::Stroke(_) => Fill::NonZero,
+                    };
+                    // TODO: As in `fill_path`
+                    let bezpath =
+                        BezPath::from_iter(input_paths.elements(*path_id).iter().copied());
+                    self.scene.set_fill_rule(fill_rule);
+                    self.scene.set_transform(scene_transform * *clip_transform);
+                    self.scene.push_clip_path(&bezpath);
+                }
+                RenderCommand::PopClipPath => self.scene.pop_clip_path(),
                 RenderCommand::SetPaint(paint_transform, brush) => {
                     self.scene.set_paint_transform(*paint_transform);
                     let brush = match brush {
                         Brush::Solid(alpha_color) => Brush::Solid(*alpha_color),
                         Brush::Gradient(gradient) => Brush::Gradient(gradient.clone()),
-                        Brush::Image(brush) => {
-                            let image_index =
-                                brush.image.to_raw().try_into().expect("Handle this.");
-                            Brush::Image(ImageBrush {
-                                image: ImageSource::opaque_id(ImageId::new(image_index)),
-                                sampler: brush.sampler,
-                            })
-                        }
+                        Brush::Image(brush) => Brush::Image(ImageBrush {
+                            image: ImageSource::opaque_id(image_id(brush.image)?),
+                            sampler: brush.sampler,
+                        }),
                     };
                     self.scene.set_paint(brush);
                 }
-                RenderCommand::BlurredRoundedRectPaint(_) => {
-                    unimplemented!(
-                        "Vello Hybrid doesn't support drawing blurred rounded rectangles yet."
-                    )
+                RenderCommand::BlurredRoundedRectPaint(brush) => {
+                    self.set_blurred_rounded_rect_brush(
+                        brush.paint_transform,
+                        brush.color,
+                        &brush.rect,
+                        brush.radius,
+                        brush.std_dev,
+                    );
                 }
             }
         }
@@ -155,39 +237,57 @@ impl PaintScene for HybridScenePainter {
             .stroke_path(&path.exact_path_elements().collect());
     }
 
+    fn draw_prepared_path(&mut self, transform: Affine, paths: &PathSet, path: PathId) {
+        let style = &paths.meta[usize::try_from(path.0).unwrap()].operation;
+        self.draw_cached_path(transform, paths.elements(path), style, paths.key(path));
+    }
+
     fn set_brush(
         &mut self,
         brush: impl Into<Brush<ImageBrush<TextureId>>>,
         paint_transform: Affine,
-    ) {
-        self.scene.set_paint_transform(paint_transform);
+    ) -> Result<(), PaintError> {
         let brush = match brush.into() {
             Brush::Solid(alpha_color) => Brush::Solid(alpha_color),
             Brush::Gradient(gradient) => Brush::Gradient(gradient),
-            Brush::Image(brush) => {
-                // TODO: Make this read more easily.
-                let image_index = brush.image.to_raw().try_into().expect("Handle this.");
-                Brush::Image(ImageBrush {
-                    image: ImageSource::opaque_id(ImageId::new(image_index)),
-                    sampler: brush.sampler,
-                })
-            }
+            Brush::Image(brush) => Brush::Image(ImageBrush {
+                image: ImageSource::opaque_id(image_id(brush.image)?),
+                sampler: brush.sampler,
+            }),
         };
+        self.scene.set_paint_transform(paint_transform);
         self.scene.set_paint(brush);
+        Ok(())
     }
 
     fn set_blurred_rounded_rect_brush(
         &mut self,
-        _paint_transform: Affine,
-        _color: Color,
-        _rect: &kurbo::Rect,
-        _radius: f32,
-        _std_dev: f32,
+        paint_transform: Affine,
+        color: Color,
+        rect: &kurbo::Rect,
+        radius: f32,
+        std_dev: f32,
+    ) {
+        self.scene.set_paint_transform(paint_transform);
+        self.scene.set_paint(BlurredRoundedRectangle {
+            rect: *rect,
+            color,
+            radius,
+            std_dev,
+        });
+    }
+
+    fn fill_blurred_rounded_rect(
+        &mut self,
+        transform: Affine,
+        color: Color,
+        rect: &kurbo::Rect,
+        radius: f32,
+        std_dev: f32,
     ) {
-        unimplemented!(
-            "Vello Hybrid doesn't support drawing blurred rounded rectangles yet.\n\
-            This is tracked in https://github.com/linebender/vello/issues/1373."
-        )
+        self.scene.set_paint(color);
+        self.scene.set_transform(transform);
+        self.scene.fill_blurred_rounded_rect(rect, radius, std_dev);
     }
 
     fn push_layer(
@@ -226,4 +326,78 @@ impl PaintScene for HybridScenePainter {
     fn pop_layer(&mut self) {
         self.scene.pop_layer();
     }
+
+    fn push_clip_path(
+        &mut self,
+        clip_transform: Affine,
+        fill_rule: Fill,
+        path: &impl ExactPathElements,
+    ) {
+        self.scene.set_fill_rule(fill_rule);
+        self.scene.set_transform(clip_transform);
+        // TODO: As in `fill_path`
+        self.scene
+            .push_clip_path(&path.exact_path_elements().collect());
+    }
+
+    fn pop_clip_path(&mut self) {
+        self.scene.pop_clip_path();
+    }
+}
+
+/// An adapter to implement [`TextureReadback`] for Vello Hybrid's wgpu [`Renderer`].
+///
+/// Textures are read back from the renderer's image atlas using buffer mapping.
+/// On native platforms, the futures returned by [`TextureReadback::read_texture`] only
+/// complete once `device` is polled.
+#[cfg(feature = "wgpu")]
+#[derive(Debug, Clone, Copy)]
+pub struct HybridTextureReader<'a> {
+    /// The renderer which owns the textures.
+    pub renderer: &'a Renderer,
+    /// The device used by `renderer`.
+    pub device: &'a wgpu::Device,
+    /// The queue used by `renderer`.
+    pub queue: &'a wgpu::Queue,
+}
+
+#[cfg(feature = "wgpu")]
+impl<'a> HybridTextureReader<'a> {
+    /// Create a new reader for the textures of `renderer`.
+    pub fn new(renderer: &'a Renderer, device: &'a wgpu::Device, queue: &'a wgpu::Queue) -> Self {
+        Self {
+            renderer,
+            device,
+            queue,
+        }
+    }
+
+    fn start_readback(&self, texture: TextureId) -> Result<ImageReadback, PaintError> {
+        self.renderer
+            .read_image(self.device, self.queue, image_id(texture)?)
+            .ok_or(PaintError::InvalidTexture(texture))
+    }
+}
+
+#[cfg(feature = "wgpu")]
+impl TextureReadback for HybridTextureReader<'_> {
+    fn read_texture(&self, texture: TextureId) -> impl Future<Output = Result<Pixmap, PaintError>> {
+        let readback = self.start_readback(texture);
+        async move { readback?.await.map_err(|_| PaintError::ReadbackFailed) }
+    }
+
+    fn read_texture_blocking(&self, texture: TextureId) -> Result<Pixmap, PaintError> {
+        self.start_readback(texture)?
+            .wait(self.device)
+            .map_err(|_| PaintError::ReadbackFailed)
+    }
+}
+
+/// The image id corresponding to the texture `texture`.
+fn image_id(texture: TextureId) -> Result<ImageId, PaintError> {
+    texture
+        .to_raw()
+        .try_into()
+        .map(ImageId::new)
+        .map_err(|_| PaintError::InvalidTexture(texture))
 }
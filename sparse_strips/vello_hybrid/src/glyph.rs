@@ -0,0 +1,113 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Bitmap and COLR glyphs of a scene.
+//!
+//! Unlike outline glyphs, bitmap and COLR glyphs are drawn by filling their area with an
+//! image of the glyph. The scene collects the images of these glyphs, and the renderer caches
+//! them in the image atlas, so that they only need to be uploaded or rendered the first time
+//! they are drawn.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use vello_common::colr::ColrRecording;
+use vello_common::glyph::GlyphCacheKey;
+use vello_common::peniko::color::{AlphaColor, Srgb};
+use vello_common::pixmap::Pixmap;
+
+/// Identifies the image of a bitmap or COLR glyph.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct GlyphImageKey {
+    glyph: GlyphCacheKey,
+    /// The bits of the context color that COLR glyphs are painted with.
+    context_color: Option<[u32; 4]>,
+}
+
+impl GlyphImageKey {
+    /// Create the key of a bitmap glyph image.
+    pub(crate) fn bitmap(glyph: GlyphCacheKey) -> Self {
+        Self {
+            glyph,
+            context_color: None,
+        }
+    }
+
+    /// Create the key of a COLR glyph image, painted with `context_color`.
+    pub(crate) fn colr(glyph: GlyphCacheKey, context_color: AlphaColor<Srgb>) -> Self {
+        Self {
+            glyph,
+            context_color: Some(context_color.components.map(f32::to_bits)),
+        }
+    }
+}
+
+/// How the image of a glyph is created.
+#[derive(Debug)]
+pub(crate) enum GlyphImageSource {
+    /// The pixmap of a bitmap glyph, which is uploaded to the image atlas.
+    Bitmap(Arc<Pixmap>),
+    /// The painting commands of a COLR glyph, which are rendered into the image atlas.
+    Colr(Arc<ColrRecording>),
+}
+
+/// The image of a bitmap or COLR glyph.
+#[derive(Debug)]
+pub(crate) struct GlyphImage {
+    /// The key identifying the image.
+    pub(crate) key: GlyphImageKey,
+    /// The width of the image in pixels.
+    pub(crate) width: u16,
+    /// The height of the image in pixels.
+    pub(crate) height: u16,
+    /// How the image is created.
+    pub(crate) source: GlyphImageSource,
+}
+
+/// The glyph images drawn in a scene.
+#[derive(Debug, Default)]
+pub(crate) struct GlyphImages {
+    /// The distinct glyph images of the scene.
+    pub(crate) images: Vec<GlyphImage>,
+    /// The index of each image in `images`.
+    indices: HashMap<GlyphImageKey, usize>,
+    /// The encoded paints sampling from a glyph image, with the index of the image.
+    ///
+    /// The renderer resolves these paints to the atlas allocations of the images. The entries
+    /// are sorted by paint index.
+    pub(crate) paints: Vec<(usize, usize)>,
+}
+
+impl GlyphImages {
+    /// Return the index of the image identified by `key`, adding the image created by
+    /// `source` if the scene doesn't contain it yet.
+    pub(crate) fn insert(
+        &mut self,
+        key: GlyphImageKey,
+        width: u16,
+        height: u16,
+        source: impl FnOnce() -> GlyphImageSource,
+    ) -> usize {
+        if let Some(idx) = self.indices.get(&key) {
+            return *idx;
+        }
+
+        let idx = self.images.len();
+        self.images.push(GlyphImage {
+            key: key.clone(),
+            width,
+            height,
+            source: source(),
+        });
+        self.indices.insert(key, idx);
+
+        idx
+    }
+
+    /// Clear all glyph images.
+    pub(crate) fn clear(&mut self) {
+        self.images.clear();
+        self.indices.clear();
+        self.paints.clear();
+    }
+}
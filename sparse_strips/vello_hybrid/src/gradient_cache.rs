@@ -286,7 +286,9 @@ mod tests {
     use vello_common::color::{ColorSpaceTag, DynamicColor, HueDirection};
     use vello_common::encode::{EncodeExt, EncodedPaint};
     use vello_common::kurbo::{Affine, Point};
-    use vello_common::peniko::{Color, ColorStop, ColorStops, Gradient, LinearGradientPosition};
+    use vello_common::peniko::{
+        Color, ColorStop, ColorStops, Gradient, LinearGradientPosition, RadialGradientPosition,
+    };
 
     fn insert_entries(cache: &mut GradientRampCache, count: usize) {
         for i in 0..count {
@@ -552,4 +554,28 @@ mod tests {
             cache.cache.get(&gradient_3.cache_key).unwrap().0.lut_start
         );
     }
+
+    #[test]
+    fn test_undefined_positions_not_shared() {
+        let mut cache = GradientRampCache::new(5, Level::baseline());
+
+        // A strip gradient is undefined outside of the strip, so its ramp needs a trailing
+        // transparent texel that the ramp of a linear gradient with the same stops lacks.
+        let linear = create_gradient(0.5);
+        let strip = Gradient {
+            kind: RadialGradientPosition::new_two_point((0.0, 0.0), 10.0, (50.0, 0.0), 10.0).into(),
+            ..linear.clone()
+        };
+
+        let (_, width) = cache.get_or_create_ramp(&create_encoded_gradient(linear));
+        let linear_size = cache.luts_size();
+        cache.get_or_create_ramp(&create_encoded_gradient(strip));
+        cache.maintain();
+
+        assert_eq!(cache.cache.len(), 2);
+        assert_eq!(
+            cache.luts_size() - linear_size,
+            ((width + 1) * BYTES_PER_TEXEL) as usize
+        );
+    }
 }
@@ -45,6 +45,8 @@
 
 extern crate alloc;
 
+#[cfg(feature = "text")]
+mod glyph;
 mod gradient_cache;
 mod render;
 mod scene;
@@ -55,7 +57,7 @@ pub mod api;
 pub mod util;
 
 #[cfg(feature = "wgpu")]
-pub use render::{AtlasWriter, RenderTargetConfig, Renderer};
+pub use render::{AtlasWriter, ImageReadback, RenderTargetConfig, Renderer};
 pub use render::{Config, GpuStrip, RenderSize};
 #[cfg(all(target_arch = "wasm32", feature = "webgl"))]
 pub use render::{WebGlAtlasWriter, WebGlRenderer, WebGlTextureWithDimensions};
@@ -77,6 +79,9 @@ pub enum RenderError {
     /// TODO: Consider supporting more than a single column of slots in slot textures.
     #[error("No slots available for rendering")]
     SlotsExhausted,
+    /// Reading an image back from the GPU failed.
+    #[error("Failed to read back image from the GPU")]
+    ReadbackFailed,
     // TODO: Consider expanding `RenderError` to replace some `.unwrap` and `.expect`.
 }
 
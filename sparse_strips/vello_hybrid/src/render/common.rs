@@ -9,6 +9,8 @@
 )]
 
 use bytemuck::{Pod, Zeroable};
+#[cfg(any(all(target_arch = "wasm32", feature = "webgl"), feature = "wgpu"))]
+use vello_common::{paint::ImageId, pixmap::Pixmap};
 
 // GPU paint structure sizes in texels (1 texel = 16 bytes for RGBA32Uint texture format).
 pub(crate) const GPU_ENCODED_IMAGE_SIZE_TEXELS: u32 = (size_of::<GpuEncodedImage>() / 16) as u32;
@@ -17,6 +19,8 @@ pub(crate) const GPU_LINEAR_GRADIENT_SIZE_TEXELS: u32 =
 pub(crate) const GPU_RADIAL_GRADIENT_SIZE_TEXELS: u32 =
     (size_of::<GpuRadialGradient>() / 16) as u32;
 pub(crate) const GPU_SWEEP_GRADIENT_SIZE_TEXELS: u32 = (size_of::<GpuSweepGradient>() / 16) as u32;
+pub(crate) const GPU_BLURRED_ROUNDED_RECT_SIZE_TEXELS: u32 =
+    (size_of::<GpuBlurredRoundedRect>() / 16) as u32;
 
 /// Dimensions of the rendering target.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -81,6 +85,8 @@ pub(crate) enum GpuEncodedPaint {
     RadialGradient(GpuRadialGradient),
     /// An encoded sweep gradient.
     SweepGradient(GpuSweepGradient),
+    /// An encoded blurred rounded rectangle.
+    BlurredRoundedRect(GpuBlurredRoundedRect),
 }
 
 impl GpuEncodedPaint {
@@ -92,6 +98,7 @@ impl GpuEncodedPaint {
             Self::LinearGradient(paint) => bytemuck::bytes_of(paint),
             Self::RadialGradient(paint) => bytemuck::bytes_of(paint),
             Self::SweepGradient(paint) => bytemuck::bytes_of(paint),
+            Self::BlurredRoundedRect(paint) => bytemuck::bytes_of(paint),
         }
     }
 
@@ -130,8 +137,8 @@ pub(crate) struct GpuEncodedImage {
     pub tint: u32,
     /// [`TintMode`](vello_common::paint::TintMode) discriminant. Only meaningful when `tint != 0`.
     pub tint_mode: u32,
-    /// Padding for 16-byte alignment.
-    pub _padding: u32,
+    /// Opacity applied to the sampled image, from the image sampler's alpha.
+    pub alpha: f32,
 }
 
 /// GPU encoded linear gradient data.
@@ -198,6 +205,43 @@ pub(crate) struct GpuSweepGradient {
     pub _padding: [u32; 2],
 }
 
+/// GPU encoded blurred rounded rectangle data.
+/// Align to 16 bytes for `RGBA32Uint` alignment.
+///
+/// See [`EncodedBlurredRoundedRectangle`](vello_common::encode::EncodedBlurredRoundedRectangle)
+/// for the meaning of the blur parameters.
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+#[allow(dead_code, reason = "Clippy fails when --no-default-features")]
+pub(crate) struct GpuBlurredRoundedRect {
+    /// Transform matrix [a, b, c, d, tx, ty] from pixel centers to rectangle coordinates.
+    pub transform: [f32; 6],
+    /// Premultiplied color packed as RGBA8 unorm (`pack4x8unorm` layout).
+    pub color: u32,
+    /// Exponent of the superellipse used to approximate the blurred corners.
+    pub exponent: f32,
+    /// Reciprocal of `exponent`.
+    pub recip_exponent: f32,
+    /// Scale factor applied to the blurred coverage.
+    pub scale: f32,
+    /// Reciprocal of the standard deviation of the blur.
+    pub std_dev_inv: f32,
+    /// The length of the shorter edge of the rectangle.
+    pub min_edge: f32,
+    /// Adjusted width of the rectangle.
+    pub w: f32,
+    /// Adjusted height of the rectangle.
+    pub h: f32,
+    /// Width of the rectangle.
+    pub width: f32,
+    /// Height of the rectangle.
+    pub height: f32,
+    /// Corner radius including the blur.
+    pub r1: f32,
+    /// Padding for 16-byte alignment.
+    pub _padding: [u32; 3],
+}
+
 // Constants for packing extend_mode and texture_width.
 const EXTEND_MODE_MASK: u32 = 1 << 30;
 const TEXTURE_WIDTH_MASK: u32 = !EXTEND_MODE_MASK;
@@ -276,6 +320,16 @@ pub(crate) fn pack_tint(tint: Option<vello_common::paint::Tint>) -> (u32, u32) {
     }
 }
 
+/// Pixels that need to be written to their region in the image atlas before rendering a frame.
+#[cfg(any(all(target_arch = "wasm32", feature = "webgl"), feature = "wgpu"))]
+#[derive(Debug)]
+pub(crate) struct AtlasUpload {
+    /// The pixels to write.
+    pub(crate) pixmap: Pixmap,
+    /// The atlas allocation the pixels are written to.
+    pub(crate) image_id: ImageId,
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "webgl", feature = "wgpu"))]
 pub(crate) fn maybe_warn_about_webgl_feature_conflict() {
     use core::sync::atomic::{AtomicBool, Ordering};
@@ -0,0 +1,577 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Backend agnostic planning of filter effects.
+//!
+//! Filter layers are rendered before the rest of the scene, in the execution order of the
+//! render graph. Each layer is first scheduled into an offscreen layer texture that has the
+//! size of the render target. The filter is then applied by a sequence of passes of the
+//! filter pipeline (see `filters.wgsl`), which ping-pong between scratch textures. The last
+//! pass writes the result into a region of the image atlas, from where it is composited like
+//! an image when scheduling the parent layer.
+//!
+//! Currently, filter graphs consisting of a chain of flood, Gaussian blur, offset and drop
+//! shadow primitives are supported.
+
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    reason = "layer sizes and offsets are small enough to fit into the target types"
+)]
+
+use crate::Scene;
+use crate::render::common::{
+    GPU_ENCODED_IMAGE_SIZE_TEXELS, GpuEncodedImage, GpuEncodedPaint, RenderSize, pack_image_offset,
+    pack_image_params, pack_image_size, pack_tint,
+};
+use crate::schedule::FilteredLayer;
+use alloc::vec::Vec;
+use bytemuck::{Pod, Zeroable};
+use core::ops::Range;
+use hashbrown::HashMap;
+use vello_common::coarse::{WideTile, WideTilesBbox};
+use vello_common::filter::PreparedFilter;
+use vello_common::filter::gaussian_blur::MAX_KERNEL_SIZE;
+use vello_common::filter_effects::{
+    EdgeMode, Filter, FilterGraph, FilterInput, FilterPrimitive, FilterSource,
+};
+use vello_common::image_cache::ImageCache;
+use vello_common::kurbo::Affine;
+use vello_common::multi_atlas::AtlasId;
+use vello_common::paint::ImageId;
+use vello_common::render_graph::{LayerId, RenderNodeKind};
+use vello_common::tile::Tile;
+
+// Filter operations, matching `filters.wgsl`.
+const OP_COPY: u32 = 0;
+const OP_FLOOD: u32 = 1;
+const OP_DOWNSCALE_X: u32 = 2;
+const OP_DOWNSCALE_Y: u32 = 3;
+const OP_CONVOLVE_X: u32 = 4;
+const OP_CONVOLVE_Y: u32 = 5;
+const OP_UPSCALE_X: u32 = 6;
+const OP_UPSCALE_Y: u32 = 7;
+const OP_DROP_SHADOW: u32 = 8;
+
+// Edge modes, matching `filters.wgsl`.
+const EDGE_MODE_NONE: u32 = 0;
+const EDGE_MODE_DUPLICATE: u32 = 1;
+const EDGE_MODE_WRAP: u32 = 2;
+const EDGE_MODE_MIRROR: u32 = 3;
+
+/// The number of scratch textures required by the filter passes.
+pub(crate) const FILTER_SCRATCH_TEXTURES: usize = 3;
+
+/// Configuration of a single filter pass.
+///
+/// This struct corresponds to the `Config` struct in `filters.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub(crate) struct FilterConfig {
+    /// The filter operation of this pass.
+    pub op: u32,
+    /// How to sample pixels outside of the input.
+    pub edge_mode: u32,
+    /// The number of valid weights in `kernel`.
+    pub kernel_size: u32,
+    /// Padding for 16-byte alignment.
+    pub _padding0: u32,
+    /// The origin of the output region in the output texture.
+    pub dst_origin: [u32; 2],
+    /// The offset added to output coordinates to get the corresponding input coordinates.
+    pub src_offset: [i32; 2],
+    /// The size of the input region, starting at the origin of the input texture.
+    pub src_size: [u32; 2],
+    /// Padding for 16-byte alignment.
+    pub _padding1: [u32; 2],
+    /// The flood color (premultiplied) or the shadow color (unpremultiplied).
+    pub color: [f32; 4],
+    /// The weights of the convolution kernel, packed into vectors.
+    pub kernel: [[f32; 4]; 4],
+}
+
+const _: () = assert!(
+    MAX_KERNEL_SIZE <= 16,
+    "the kernel of `FilterConfig` holds at most 16 weights"
+);
+
+/// A texture that is read or written by a filter pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterTexture {
+    /// The layer texture holding the unfiltered contents of the layer.
+    Layer,
+    /// One of the scratch textures, which are large enough to hold the largest filter layer.
+    Scratch(usize),
+    /// The atlas layer of the filter layer that is being processed.
+    Atlas,
+}
+
+/// A single pass of the filter pipeline.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FilterPass {
+    /// The configuration of the pass.
+    pub(crate) config: FilterConfig,
+    /// The primary input.
+    pub(crate) input: FilterTexture,
+    /// The secondary input, only read by drop shadows.
+    pub(crate) input2: FilterTexture,
+    /// The output.
+    pub(crate) output: FilterTexture,
+    /// The size of the output region, with its origin at `config.dst_origin`.
+    pub(crate) size: [u32; 2],
+}
+
+impl FilterPass {
+    fn new(
+        op: u32,
+        input: FilterTexture,
+        output: FilterTexture,
+        src_size: [u32; 2],
+        size: [u32; 2],
+    ) -> Self {
+        Self {
+            config: FilterConfig {
+                op,
+                src_size,
+                ..Zeroable::zeroed()
+            },
+            input,
+            // The layer texture is never written by a filter pass, so it can always be bound.
+            input2: FilterTexture::Layer,
+            output,
+            size,
+        }
+    }
+}
+
+/// A filter layer that is rendered in the current frame.
+#[derive(Debug)]
+pub(crate) struct PlannedFilterLayer {
+    /// The ID of the layer.
+    pub(crate) layer_id: LayerId,
+    /// The bounding box of the layer in wide tile coordinates.
+    pub(crate) wtile_bbox: WideTilesBbox,
+    /// The atlas holding the filtered contents of the layer.
+    pub(crate) atlas_id: AtlasId,
+    /// The range of the passes of this layer in [`FilterFrame::passes`].
+    pub(crate) passes: Range<usize>,
+}
+
+/// The filter layers of a frame, and the passes applying their filters.
+#[derive(Debug, Default)]
+pub(crate) struct FilterFrame {
+    /// The filter layers, in execution order.
+    pub(crate) layers: Vec<PlannedFilterLayer>,
+    /// The filter passes of all layers.
+    pub(crate) passes: Vec<FilterPass>,
+    /// The minimum size of the scratch textures.
+    pub(crate) scratch_size: [u32; 2],
+    /// The atlas allocations holding the filtered layers.
+    ///
+    /// These are kept until the next frame is prepared, so that they are not reused while
+    /// the frame might still be rendered.
+    image_ids: Vec<ImageId>,
+}
+
+impl FilterFrame {
+    /// Plan the filter layers of `scene`.
+    ///
+    /// This allocates a region in the image atlas for each filter layer and appends an image
+    /// paint sampling from it to `encoded_paints` and `paint_idxs`, which must have been
+    /// prepared for the paints of the scene before. The filtered layers are registered in
+    /// `filtered_layers`, so that the scheduler can composite them.
+    pub(crate) fn prepare(
+        &mut self,
+        scene: &Scene,
+        render_size: &RenderSize,
+        image_cache: &mut ImageCache,
+        encoded_paints: &mut Vec<GpuEncodedPaint>,
+        paint_idxs: &mut [u32],
+        filtered_layers: &mut HashMap<LayerId, FilteredLayer>,
+    ) {
+        for image_id in self.image_ids.drain(..) {
+            image_cache.deallocate(image_id);
+        }
+        self.layers.clear();
+        self.passes.clear();
+        self.scratch_size = [0, 0];
+        filtered_layers.clear();
+
+        if !scene.render_graph.has_filters() {
+            return;
+        }
+
+        let render_graph = &scene.render_graph;
+        let mut next_paint_idx = *paint_idxs.last().unwrap();
+        for node_id in render_graph.execution_order() {
+            let RenderNodeKind::FilterLayer {
+                layer_id,
+                filter,
+                wtile_bbox,
+                transform,
+            } = &render_graph.nodes[node_id].kind
+            else {
+                continue;
+            };
+
+            let width = u32::from(wtile_bbox.width_px());
+            let height = u32::from(wtile_bbox.height_px());
+            if width == 0 || height == 0 {
+                continue;
+            }
+
+            let image_id = match image_cache.allocate(width, height) {
+                Ok(image_id) => image_id,
+                Err(err) => {
+                    log::warn!("Filter layer could not be allocated in the image atlas: {err:?}");
+                    continue;
+                }
+            };
+            self.image_ids.push(image_id);
+            let image_resource = image_cache.get(image_id).unwrap();
+
+            // The layer is sampled pixel by pixel, mapping the origin of the bounding box
+            // to the origin of the atlas region.
+            let origin = [
+                u32::from(wtile_bbox.x0()) * u32::from(WideTile::WIDTH),
+                u32::from(wtile_bbox.y0()) * u32::from(Tile::HEIGHT),
+            ];
+            let image_transform = Affine::translate((-f64::from(origin[0]), -f64::from(origin[1])));
+            let (tint, tint_mode) = pack_tint(None);
+            encoded_paints.push(GpuEncodedPaint::Image(GpuEncodedImage {
+                image_params: pack_image_params(0, 0, 0, image_resource.atlas_id.as_u32()),
+                image_size: pack_image_size(image_resource.width, image_resource.height),
+                image_offset: pack_image_offset(image_resource.offset[0], image_resource.offset[1]),
+                transform: image_transform.as_coeffs().map(|x| x as f32),
+                tint,
+                tint_mode,
+                alpha: 1.0,
+            }));
+            filtered_layers.insert(
+                *layer_id,
+                FilteredLayer {
+                    paint_idx: next_paint_idx,
+                    wtile_bbox: *wtile_bbox,
+                },
+            );
+            next_paint_idx += GPU_ENCODED_IMAGE_SIZE_TEXELS;
+
+            // Same as in `vello_cpu`, filter parameters are relative to the layer origin.
+            let layer_transform =
+                Affine::translate((-f64::from(origin[0]), -f64::from(origin[1]))) * *transform;
+            let passes_start = self.passes.len();
+            plan_filter_passes(
+                filter,
+                &layer_transform,
+                origin,
+                [render_size.width, render_size.height],
+                [width, height],
+                [
+                    u32::from(image_resource.offset[0]),
+                    u32::from(image_resource.offset[1]),
+                ],
+                &mut self.passes,
+            );
+
+            self.scratch_size = [
+                self.scratch_size[0].max(width),
+                self.scratch_size[1].max(height),
+            ];
+            self.layers.push(PlannedFilterLayer {
+                layer_id: *layer_id,
+                wtile_bbox: *wtile_bbox,
+                atlas_id: image_resource.atlas_id,
+                passes: passes_start..self.passes.len(),
+            });
+        }
+
+        *paint_idxs.last_mut().unwrap() = next_paint_idx;
+    }
+}
+
+/// Plan the passes applying `filter` to a layer.
+///
+/// The unfiltered contents are read from `origin` in the layer texture of size `layer_size`,
+/// and the filtered result of size `size` is written to `atlas_offset` in the atlas.
+fn plan_filter_passes(
+    filter: &Filter,
+    transform: &Affine,
+    origin: [u32; 2],
+    layer_size: [u32; 2],
+    size: [u32; 2],
+    atlas_offset: [u32; 2],
+    passes: &mut Vec<FilterPass>,
+) {
+    let chain = filter_chain(&filter.graph).unwrap_or_else(|| {
+        log::warn!(
+            "Filter graph is not yet supported in sparse strips hybrid renderer, rendering the layer without filter"
+        );
+        Vec::new()
+    });
+
+    // Copy the contents of the layer into a scratch texture first, so that all primitives
+    // operate on textures with the layer origin at the texture origin.
+    let mut current = 0;
+    let mut copy = FilterPass::new(
+        OP_COPY,
+        FilterTexture::Layer,
+        FilterTexture::Scratch(current),
+        layer_size,
+        size,
+    );
+    copy.config.src_offset = origin.map(|x| x as i32);
+    passes.push(copy);
+
+    for idx in chain {
+        let next = (current + 1) % FILTER_SCRATCH_TEXTURES;
+        match PreparedFilter::new(&filter.graph.primitives[idx], transform) {
+            PreparedFilter::Flood(flood) => {
+                let mut pass = FilterPass::new(
+                    OP_FLOOD,
+                    FilterTexture::Layer,
+                    FilterTexture::Scratch(current),
+                    size,
+                    size,
+                );
+                let color = flood.color.premultiply().to_rgba8();
+                pass.config.color =
+                    [color.r, color.g, color.b, color.a].map(|c| f32::from(c) / 255.0);
+                passes.push(pass);
+            }
+            PreparedFilter::Offset(offset) => {
+                push_offset(passes, current, next, offset.dx, offset.dy, size);
+                current = next;
+            }
+            PreparedFilter::GaussianBlur(blur) => {
+                if blur.std_deviation > 0.0 {
+                    push_blur(
+                        passes,
+                        current,
+                        next,
+                        size,
+                        blur.n_decimations,
+                        &blur.kernel[..usize::from(blur.kernel_size)],
+                        blur.edge_mode,
+                    );
+                }
+            }
+            PreparedFilter::DropShadow(drop_shadow) => {
+                let shadow = next;
+                let result = (current + 2) % FILTER_SCRATCH_TEXTURES;
+                push_offset(
+                    passes,
+                    current,
+                    shadow,
+                    drop_shadow.dx,
+                    drop_shadow.dy,
+                    size,
+                );
+                if drop_shadow.std_deviation > 0.0 {
+                    push_blur(
+                        passes,
+                        shadow,
+                        result,
+                        size,
+                        drop_shadow.n_decimations,
+                        &drop_shadow.kernel[..usize::from(drop_shadow.kernel_size)],
+                        drop_shadow.edge_mode,
+                    );
+                }
+
+                let mut pass = FilterPass::new(
+                    OP_DROP_SHADOW,
+                    FilterTexture::Scratch(current),
+                    FilterTexture::Scratch(result),
+                    size,
+                    size,
+                );
+                pass.input2 = FilterTexture::Scratch(shadow);
+                pass.config.color = drop_shadow.color.components;
+                passes.push(pass);
+                current = result;
+            }
+            _ => unreachable!("unsupported primitives are rejected by `filter_chain`"),
+        }
+    }
+
+    let mut copy = FilterPass::new(
+        OP_COPY,
+        FilterTexture::Scratch(current),
+        FilterTexture::Atlas,
+        size,
+        size,
+    );
+    copy.config.dst_origin = atlas_offset;
+    passes.push(copy);
+}
+
+/// Return the indices of the primitives contributing to the output of `graph`, in execution
+/// order, or `None` if the graph is not supported.
+///
+/// Supported graphs are chains of supported primitives, each reading the result of the previous
+/// one, with the first one reading the source graphic.
+fn filter_chain(graph: &FilterGraph) -> Option<Vec<usize>> {
+    let mut chain = Vec::new();
+    let mut idx = usize::from(graph.output.0);
+    if idx >= graph.primitives.len() {
+        // Same as in `vello_cpu`, a graph without a valid output leaves the layer unchanged.
+        return Some(chain);
+    }
+
+    loop {
+        chain.push(idx);
+        match &graph.primitives[idx] {
+            // A flood does not depend on its input.
+            FilterPrimitive::Flood { .. } => break,
+            FilterPrimitive::GaussianBlur { .. }
+            | FilterPrimitive::Offset { .. }
+            | FilterPrimitive::DropShadow { .. } => {}
+            _ => return None,
+        }
+
+        match graph.inputs[idx].primary {
+            FilterInput::Source(FilterSource::SourceGraphic) => break,
+            FilterInput::Result(id) if usize::from(id.0) < idx => idx = usize::from(id.0),
+            _ => return None,
+        }
+    }
+
+    chain.reverse();
+    Some(chain)
+}
+
+/// Push a pass shifting the contents of `input` by the rounded offset.
+fn push_offset(
+    passes: &mut Vec<FilterPass>,
+    input: usize,
+    output: usize,
+    dx: f32,
+    dy: f32,
+    size: [u32; 2],
+) {
+    let mut pass = FilterPass::new(
+        OP_COPY,
+        FilterTexture::Scratch(input),
+        FilterTexture::Scratch(output),
+        size,
+        size,
+    );
+    pass.config.src_offset = [-(dx.round() as i32), -(dy.round() as i32)];
+    passes.push(pass);
+}
+
+/// Push the passes of a Gaussian blur of `texture`, using `scratch` for intermediate results.
+///
+/// Like in `vello_cpu`, the image is first downscaled `n_decimations` times, then convolved
+/// with the kernel and finally upscaled to its original size again. Since every step consists
+/// of a horizontal and a vertical pass, the result always ends up in `texture`.
+fn push_blur(
+    passes: &mut Vec<FilterPass>,
+    texture: usize,
+    scratch: usize,
+    size: [u32; 2],
+    n_decimations: usize,
+    kernel: &[f32],
+    edge_mode: EdgeMode,
+) {
+    let edge_mode = match edge_mode {
+        EdgeMode::None => EDGE_MODE_NONE,
+        EdgeMode::Duplicate => EDGE_MODE_DUPLICATE,
+        EdgeMode::Wrap => EDGE_MODE_WRAP,
+        EdgeMode::Mirror => EDGE_MODE_MIRROR,
+    };
+    let mut textures = [texture, scratch];
+    let mut push = |op: u32, src_size: [u32; 2], size: [u32; 2]| {
+        let mut pass = FilterPass::new(
+            op,
+            FilterTexture::Scratch(textures[0]),
+            FilterTexture::Scratch(textures[1]),
+            src_size,
+            size,
+        );
+        pass.config.edge_mode = edge_mode;
+        if matches!(op, OP_CONVOLVE_X | OP_CONVOLVE_Y) {
+            pass.config.kernel_size = kernel.len() as u32;
+            for (i, weight) in kernel.iter().enumerate() {
+                pass.config.kernel[i / 4][i % 4] = *weight;
+            }
+        }
+        passes.push(pass);
+        textures.swap(0, 1);
+    };
+
+    let mut sizes = Vec::with_capacity(n_decimations + 1);
+    let mut current = size;
+    for _ in 0..n_decimations {
+        sizes.push(current);
+        let downscaled_x = [current[0].div_ceil(2), current[1]];
+        push(OP_DOWNSCALE_X, current, downscaled_x);
+        let downscaled = [downscaled_x[0], current[1].div_ceil(2)];
+        push(OP_DOWNSCALE_Y, downscaled_x, downscaled);
+        current = downscaled;
+    }
+
+    push(OP_CONVOLVE_X, current, current);
+    push(OP_CONVOLVE_Y, current, current);
+
+    while let Some(target) = sizes.pop() {
+        let upscaled_x = [(current[0] * 2).min(target[0]), current[1]];
+        push(OP_UPSCALE_X, current, upscaled_x);
+        let upscaled = [upscaled_x[0], (current[1] * 2).min(target[1])];
+        push(OP_UPSCALE_Y, upscaled_x, upscaled);
+        current = upscaled;
+    }
+
+    debug_assert_eq!(
+        current, size,
+        "the blurred image must have the original size"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use vello_common::filter_effects::FilterId;
+
+    fn blur(std_deviation: f32) -> FilterPrimitive {
+        FilterPrimitive::GaussianBlur {
+            std_deviation,
+            edge_mode: EdgeMode::None,
+        }
+    }
+
+    #[test]
+    fn blur_ends_in_source_texture() {
+        let mut passes = Vec::new();
+        let kernel = [0.25, 0.5, 0.25];
+        push_blur(&mut passes, 1, 2, [13, 7], 2, &kernel, EdgeMode::None);
+
+        assert_eq!(passes.len(), 10);
+        assert_eq!(passes[0].input, FilterTexture::Scratch(1));
+        assert_eq!(passes.last().unwrap().output, FilterTexture::Scratch(1));
+        // 13x7 -> 7x4 -> 4x2, and back.
+        assert_eq!(passes[3].size, [4, 2]);
+        assert_eq!(passes[6].size, [7, 2]);
+        assert_eq!(passes[7].size, [7, 4]);
+        assert_eq!(passes[9].size, [13, 7]);
+    }
+
+    #[test]
+    fn unsupported_graphs_are_rejected() {
+        let mut graph = FilterGraph::new();
+        let blurred = graph.add(blur(2.0), None);
+        graph.set_output(blurred);
+        assert_eq!(filter_chain(&graph), Some(vec![0]));
+
+        let mut graph = FilterGraph::new();
+        graph.add(FilterPrimitive::ColorMatrix { matrix: [0.0; 20] }, None);
+        let blurred = graph.add(blur(2.0), None);
+        graph.set_output(blurred);
+        assert_eq!(filter_chain(&graph), None);
+
+        let mut graph = FilterGraph::new();
+        graph.set_output(FilterId(3));
+        assert_eq!(filter_chain(&graph), Some(vec![]));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Backend agnostic caching of bitmap and COLR glyphs.
+//!
+//! The images of the bitmap and COLR glyphs of a scene are cached in the image atlas, keyed by
+//! glyph, size and variation coordinates. When an image isn't cached yet, bitmap glyphs are
+//! uploaded to their atlas region, while COLR glyphs are painted into a scene that has the size
+//! of an atlas layer and is rendered into that layer before the frame itself.
+
+use crate::Scene;
+use crate::glyph::{GlyphImage, GlyphImageKey, GlyphImageSource};
+use crate::render::common::AtlasUpload;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use vello_common::colr::ColrRenderer;
+use vello_common::image_cache::ImageCache;
+use vello_common::kurbo::{Affine, BezPath, Rect, Shape};
+use vello_common::multi_atlas::{AtlasConfig, AtlasId};
+use vello_common::paint::ImageId;
+use vello_common::peniko::color::{AlphaColor, Srgb};
+use vello_common::peniko::{BlendMode, Gradient};
+use vello_common::pixmap::Pixmap;
+
+/// The maximum number of frames a glyph image is kept in the atlas without being drawn.
+const MAX_ENTRY_AGE: u32 = 64;
+
+/// A glyph image cached in the image atlas.
+#[derive(Debug)]
+struct GlyphAtlasEntry {
+    /// The atlas allocation of the image.
+    image_id: ImageId,
+    /// The serial of the last frame that drew the image.
+    serial: u32,
+}
+
+/// COLR glyphs that need to be rendered into the same atlas layer.
+#[derive(Debug)]
+pub(crate) struct ColrBatch {
+    /// The atlas layer to render into.
+    pub(crate) atlas_id: AtlasId,
+    /// The atlas regions of the glyphs as `[x, y, width, height]`, which need to be cleared
+    /// before rendering.
+    pub(crate) regions: Vec<[u32; 4]>,
+    /// A scene with the size of an atlas layer, containing the glyphs at their regions.
+    pub(crate) scene: Scene,
+}
+
+/// The cache of glyph images in the image atlas.
+#[derive(Debug, Default)]
+pub(crate) struct GlyphAtlas {
+    /// The cached glyph images.
+    entries: HashMap<GlyphImageKey, GlyphAtlasEntry>,
+    /// The serial of the current frame.
+    serial: u32,
+    /// The atlas allocation of each glyph image of the current frame, if it could be allocated.
+    image_ids: Vec<Option<ImageId>>,
+    /// The bitmap glyphs that need to be written to the image atlas before rendering the frame.
+    pub(crate) uploads: Vec<AtlasUpload>,
+    /// The COLR glyphs that need to be rendered into the image atlas before rendering the
+    /// frame.
+    pub(crate) colr_batches: Vec<ColrBatch>,
+    /// Scenes of previous COLR batches, kept for reuse.
+    scene_pool: Vec<Scene>,
+}
+
+impl GlyphAtlas {
+    /// Prepare the glyph images of `scene`.
+    ///
+    /// This allocates a region in the image atlas for each image that isn't cached yet, and
+    /// evicts the images that haven't been drawn for a while.
+    pub(crate) fn prepare(&mut self, scene: &Scene, image_cache: &mut ImageCache) {
+        self.serial = self.serial.wrapping_add(1);
+        self.image_ids.clear();
+        self.uploads.clear();
+        self.scene_pool
+            .extend(self.colr_batches.drain(..).map(|batch| batch.scene));
+
+        for image in &scene.glyph_images.images {
+            if let Some(entry) = self.entries.get_mut(&image.key) {
+                entry.serial = self.serial;
+                self.image_ids.push(Some(entry.image_id));
+                continue;
+            }
+
+            let image_id =
+                match image_cache.allocate(u32::from(image.width), u32::from(image.height)) {
+                    Ok(image_id) => image_id,
+                    Err(err) => {
+                        log::warn!("Glyph could not be allocated in the image atlas: {err:?}");
+                        self.image_ids.push(None);
+                        continue;
+                    }
+                };
+            self.entries.insert(
+                image.key.clone(),
+                GlyphAtlasEntry {
+                    image_id,
+                    serial: self.serial,
+                },
+            );
+            self.image_ids.push(Some(image_id));
+
+            match &image.source {
+                GlyphImageSource::Bitmap(pixmap) => self.uploads.push(AtlasUpload {
+                    pixmap: Pixmap::clone(pixmap),
+                    image_id,
+                }),
+                GlyphImageSource::Colr(_) => {
+                    let resource = image_cache.get(image_id).unwrap();
+                    let batch = self.colr_batch(resource.atlas_id, image_cache);
+                    let [x, y] = resource.offset;
+                    batch.regions.push([
+                        u32::from(x),
+                        u32::from(y),
+                        u32::from(image.width),
+                        u32::from(image.height),
+                    ]);
+                    paint_colr_glyph(&mut batch.scene, image, f64::from(x), f64::from(y));
+                }
+            }
+        }
+
+        let serial = self.serial;
+        self.entries.retain(|_, entry| {
+            if serial.wrapping_sub(entry.serial) > MAX_ENTRY_AGE {
+                image_cache.deallocate(entry.image_id);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Return the atlas allocation of the glyph image at `image_idx` of the current frame.
+    pub(crate) fn image_id(&self, image_idx: usize) -> Option<ImageId> {
+        self.image_ids.get(image_idx).copied().flatten()
+    }
+
+    /// Return the batch of COLR glyphs rendered into the atlas layer `atlas_id`.
+    fn colr_batch(&mut self, atlas_id: AtlasId, image_cache: &ImageCache) -> &mut ColrBatch {
+        if let Some(idx) = self
+            .colr_batches
+            .iter()
+            .position(|batch| batch.atlas_id == atlas_id)
+        {
+            return &mut self.colr_batches[idx];
+        }
+
+        let scene = match self.scene_pool.pop() {
+            Some(mut scene) => {
+                scene.reset();
+                scene
+            }
+            None => {
+                let AtlasConfig {
+                    atlas_size: (width, height),
+                    ..
+                } = image_cache.atlas_manager().config();
+                Scene::new(
+                    u16::try_from(*width).unwrap(),
+                    u16::try_from(*height).unwrap(),
+                )
+            }
+        };
+        self.colr_batches.push(ColrBatch {
+            atlas_id,
+            regions: Vec::new(),
+            scene,
+        });
+
+        self.colr_batches.last_mut().unwrap()
+    }
+}
+
+/// Paint the COLR glyph `image` into `scene`, with its top left corner at `(x, y)`.
+fn paint_colr_glyph(scene: &mut Scene, image: &GlyphImage, x: f64, y: f64) {
+    let GlyphImageSource::Colr(recording) = &image.source else {
+        unreachable!();
+    };
+
+    let area = Rect::new(0.0, 0.0, f64::from(image.width), f64::from(image.height));
+    scene.set_transform(Affine::translate((x, y)));
+    // Isolate the glyph, so that its blend layers don't affect the other regions of the atlas
+    // layer.
+    scene.push_clip_layer(&area.to_path(0.1));
+    recording.replay(&mut ColrGlyphPainter { scene, area });
+    scene.pop_layer();
+    scene.reset_paint_transform();
+}
+
+/// Paints a COLR glyph into a scene, filling the area of the glyph.
+struct ColrGlyphPainter<'a> {
+    scene: &'a mut Scene,
+    area: Rect,
+}
+
+impl ColrRenderer for ColrGlyphPainter<'_> {
+    fn push_clip_layer(&mut self, clip: &BezPath) {
+        self.scene.push_clip_layer(clip);
+    }
+
+    fn push_blend_layer(&mut self, blend_mode: BlendMode) {
+        self.scene.push_blend_layer(blend_mode);
+    }
+
+    fn fill_solid(&mut self, color: AlphaColor<Srgb>) {
+        self.scene.set_paint(color);
+        self.scene.fill_rect(&self.area);
+    }
+
+    fn fill_gradient(&mut self, gradient: Gradient) {
+        self.scene.set_paint(gradient);
+        self.scene.fill_rect(&self.area);
+    }
+
+    fn set_paint_transform(&mut self, affine: Affine) {
+        self.scene.set_paint_transform(affine);
+    }
+
+    fn pop_layer(&mut self) {
+        self.scene.pop_layer();
+    }
+}
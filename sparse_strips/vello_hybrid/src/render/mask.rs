@@ -0,0 +1,132 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Backend agnostic preparation of masks.
+//!
+//! The masks of a scene are uploaded to the image atlas, and an image paint describing the
+//! location of each mask is stored at the start of the encoded paints. When a masked layer is
+//! composited into the layer below, the scheduler tags the strip with the number of the mask,
+//! and the fine shader multiplies the color of the layer with the value sampled from the mask.
+
+use crate::Scene;
+use crate::render::common::{
+    AtlasUpload, GpuEncodedImage, GpuEncodedPaint, pack_image_offset, pack_image_params,
+    pack_image_size, pack_tint,
+};
+use alloc::vec::Vec;
+use vello_common::image_cache::ImageCache;
+use vello_common::mask::Mask;
+use vello_common::paint::ImageId;
+use vello_common::peniko::color::PremulRgba8;
+use vello_common::pixmap::Pixmap;
+
+/// The maximum number of masks that can be used in a single frame.
+///
+/// This is limited by the number of bits available for the mask number in a strip.
+const MAX_MASKS: usize = (1 << 10) - 1;
+
+/// The masks of a frame.
+#[derive(Debug, Default)]
+pub(crate) struct MaskFrame {
+    /// The masks that need to be written to the image atlas before rendering the frame.
+    ///
+    /// The mask values are stored in all channels of the pixels.
+    pub(crate) uploads: Vec<AtlasUpload>,
+    /// The masks stored in the image atlas, together with their allocations.
+    ///
+    /// Masks are identified by [`Mask::ptr_eq`], so a mask which is used again in the next
+    /// frame isn't uploaded again. Holding on to the masks ensures that a new mask can't have
+    /// the same identity as a cached one.
+    ///
+    /// The allocations of masks which are no longer used are kept until the next frame is
+    /// prepared, so that they are not reused while the frame might still be rendered.
+    cached: Vec<(Mask, ImageId)>,
+}
+
+impl MaskFrame {
+    /// Prepare the masks of `scene`.
+    ///
+    /// This uploads each mask which isn't stored in the image atlas yet. `encoded_paints` is
+    /// cleared and filled with an image paint for each mask in the atlas, so the paint of
+    /// the mask with number `n` starts at texel `n * GPU_ENCODED_IMAGE_SIZE_TEXELS`, which is
+    /// where the fine shader looks for it. The paints of the scene need to be appended after
+    /// the masks. The masks are stored in `masks` in the same order, so that the scheduler can
+    /// look up their numbers.
+    pub(crate) fn prepare(
+        &mut self,
+        scene: &Scene,
+        image_cache: &mut ImageCache,
+        encoded_paints: &mut Vec<GpuEncodedPaint>,
+        masks: &mut Vec<Mask>,
+    ) {
+        self.uploads.clear();
+        encoded_paints.clear();
+        masks.clear();
+
+        // Free the allocations of masks which aren't used anymore first, so that their space
+        // can be reused by new masks.
+        self.cached.retain(|(cached, image_id)| {
+            let used = scene.masks.iter().any(|mask| mask.ptr_eq(cached));
+            if !used {
+                image_cache.deallocate(*image_id);
+            }
+            used
+        });
+
+        for mask in &scene.masks {
+            if masks.len() == MAX_MASKS {
+                log::warn!("Too many masks, only the first {MAX_MASKS} are applied");
+                break;
+            }
+
+            let image_id = if let Some(&(_, image_id)) =
+                self.cached.iter().find(|(cached, _)| cached.ptr_eq(mask))
+            {
+                image_id
+            } else {
+                let width = mask.width();
+                let height = mask.height();
+                let image_id = match image_cache.allocate(u32::from(width), u32::from(height)) {
+                    Ok(image_id) => image_id,
+                    Err(err) => {
+                        log::warn!("Mask could not be allocated in the image atlas: {err:?}");
+                        continue;
+                    }
+                };
+                self.cached.push((mask.clone(), image_id));
+
+                // The fine shader only reads the alpha channel, but storing the value in all
+                // channels keeps the pixels valid premultiplied colors.
+                let data = (0..height)
+                    .flat_map(|y| (0..width).map(move |x| mask.sample(x, y)))
+                    .map(|value| PremulRgba8 {
+                        r: value,
+                        g: value,
+                        b: value,
+                        a: value,
+                    })
+                    .collect();
+                self.uploads.push(AtlasUpload {
+                    pixmap: Pixmap::from_parts(data, width, height),
+                    image_id,
+                });
+
+                image_id
+            };
+            let image_resource = image_cache.get(image_id).unwrap();
+
+            // Masks have the size of the scene, so they are sampled without a transform.
+            let (tint, tint_mode) = pack_tint(None);
+            encoded_paints.push(GpuEncodedPaint::Image(GpuEncodedImage {
+                image_params: pack_image_params(0, 0, 0, image_resource.atlas_id.as_u32()),
+                image_size: pack_image_size(image_resource.width, image_resource.height),
+                image_offset: pack_image_offset(image_resource.offset[0], image_resource.offset[1]),
+                transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                tint,
+                tint_mode,
+                alpha: 1.0,
+            }));
+            masks.push(mask.clone());
+        }
+    }
+}
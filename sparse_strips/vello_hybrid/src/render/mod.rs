@@ -9,6 +9,17 @@
 //! - `webgl` contains a WebGL2 backend specifically for `wasm32` if the `webgl` feature is active.
 
 pub(crate) mod common;
+#[cfg(any(all(target_arch = "wasm32", feature = "webgl"), feature = "wgpu"))]
+pub(crate) mod filter;
+#[cfg(all(
+    feature = "text",
+    any(all(target_arch = "wasm32", feature = "webgl"), feature = "wgpu")
+))]
+pub(crate) mod glyph;
+#[cfg(any(all(target_arch = "wasm32", feature = "webgl"), feature = "wgpu"))]
+pub(crate) mod mask;
+#[cfg(feature = "wgpu")]
+mod readback;
 #[cfg(all(target_arch = "wasm32", feature = "webgl"))]
 mod webgl;
 #[cfg(feature = "wgpu")]
@@ -16,6 +27,8 @@ mod wgpu;
 
 pub use common::{Config, GpuStrip, RenderSize};
 
+#[cfg(feature = "wgpu")]
+pub use readback::ImageReadback;
 #[cfg(all(target_arch = "wasm32", feature = "webgl"))]
 pub use webgl::{WebGlAtlasWriter, WebGlRenderer, WebGlTextureWithDimensions};
 #[cfg(feature = "wgpu")]
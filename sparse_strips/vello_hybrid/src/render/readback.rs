@@ -0,0 +1,154 @@
+// Copyright 2025 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Reading images back from the atlas to the CPU, using buffer mapping.
+
+extern crate std;
+
+use alloc::sync::Arc;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::Mutex;
+
+use vello_common::image_cache::ImageResource;
+use vello_common::pixmap::Pixmap;
+use wgpu::{Buffer, BufferAsyncError, Device, Queue, Texture};
+
+use crate::RenderError;
+
+/// An in-progress read of an image from the atlas back to the CPU.
+///
+/// This is created using [`Renderer::read_image`](crate::Renderer::read_image).
+/// The image can be received either by awaiting this future, or by blocking using [`wait`](Self::wait).
+///
+/// On native platforms, the readback only completes once the `Device` is polled, which
+/// [`wait`](Self::wait) does.
+#[derive(Debug)]
+pub struct ImageReadback {
+    buffer: Buffer,
+    width: u16,
+    height: u16,
+    /// The number of bytes in each row of `buffer`, which is padded to meet wgpu's alignment requirements.
+    bytes_per_row: u32,
+    state: Arc<Mutex<MapState>>,
+}
+
+/// The state of mapping the readback buffer, shared with the `map_async` callback.
+#[derive(Debug, Default)]
+struct MapState {
+    result: Option<Result<(), BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+impl ImageReadback {
+    /// Copy `image` from `atlas_texture` into a new buffer, and start mapping that buffer.
+    pub(crate) fn new(
+        device: &Device,
+        queue: &Queue,
+        atlas_texture: &Texture,
+        image: &ImageResource,
+    ) -> Self {
+        let bytes_per_row =
+            (u32::from(image.width) * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Image Readback Buffer"),
+            size: u64::from(bytes_per_row) * u64::from(image.height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Image Readback Encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: u32::from(image.offset[0]),
+                    y: u32::from(image.offset[1]),
+                    z: image.atlas_id.as_u32(),
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: u32::from(image.width),
+                height: u32::from(image.height),
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
+
+        let state = Arc::new(Mutex::new(MapState::default()));
+        let callback_state = state.clone();
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let mut state = callback_state.lock().unwrap();
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+
+        Self {
+            buffer,
+            width: image.width,
+            height: image.height,
+            bytes_per_row,
+            state,
+        }
+    }
+
+    /// Block the current thread until the image has been read back.
+    pub fn wait(self, device: &Device) -> Result<Pixmap, RenderError> {
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|_| RenderError::ReadbackFailed)?;
+        let result = self.state.lock().unwrap().result.take();
+        self.finish(result)
+    }
+
+    /// Copy the contents of the mapped buffer into a new pixmap.
+    fn finish(&self, result: Option<Result<(), BufferAsyncError>>) -> Result<Pixmap, RenderError> {
+        if !matches!(result, Some(Ok(()))) {
+            return Err(RenderError::ReadbackFailed);
+        }
+        let mut pixmap = Pixmap::new(self.width, self.height);
+        let row_len = usize::from(self.width) * 4;
+        for (row, buf) in self
+            .buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks_exact(self.bytes_per_row as usize)
+            .zip(pixmap.data_as_u8_slice_mut().chunks_exact_mut(row_len))
+        {
+            buf.copy_from_slice(&row[..row_len]);
+        }
+        self.buffer.unmap();
+        Ok(pixmap)
+    }
+}
+
+impl Future for ImageReadback {
+    type Output = Result<Pixmap, RenderError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(result) = state.result.take() {
+            drop(state);
+            Poll::Ready(self.finish(Some(result)))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
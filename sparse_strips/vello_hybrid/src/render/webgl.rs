@@ -20,18 +20,23 @@
 only break in edge cases, and some of them are also only related to conversions from f64 to f32."
 )]
 
+#[cfg(feature = "text")]
+use crate::render::glyph::GlyphAtlas;
 use crate::{
     GpuStrip, RenderError, RenderSettings, RenderSize,
     gradient_cache::GradientRampCache,
     render::{
         Config,
         common::{
-            GPU_ENCODED_IMAGE_SIZE_TEXELS, GPU_LINEAR_GRADIENT_SIZE_TEXELS,
-            GPU_RADIAL_GRADIENT_SIZE_TEXELS, GPU_SWEEP_GRADIENT_SIZE_TEXELS, GpuEncodedImage,
+            GPU_BLURRED_ROUNDED_RECT_SIZE_TEXELS, GPU_ENCODED_IMAGE_SIZE_TEXELS,
+            GPU_LINEAR_GRADIENT_SIZE_TEXELS, GPU_RADIAL_GRADIENT_SIZE_TEXELS,
+            GPU_SWEEP_GRADIENT_SIZE_TEXELS, GpuBlurredRoundedRect, GpuEncodedImage,
             GpuEncodedPaint, GpuLinearGradient, GpuRadialGradient, GpuSweepGradient,
             pack_image_offset, pack_image_params, pack_image_size, pack_radial_kind_and_swapped,
             pack_texture_width_and_extend_mode, pack_tint,
         },
+        filter::{FILTER_SCRATCH_TEXTURES, FilterFrame, FilterTexture},
+        mask::MaskFrame,
     },
     scene::Scene,
     schedule::{
@@ -47,14 +52,17 @@ use vello_common::image_cache::{ImageCache, ImageResource};
 use vello_common::multi_atlas::{AtlasConfig, AtlasId};
 use vello_common::{
     coarse::WideTile,
-    encode::{EncodedGradient, EncodedKind, EncodedPaint, MAX_GRADIENT_LUT_SIZE, RadialKind},
+    encode::{
+        EncodedBlurredRoundedRectangle, EncodedGradient, EncodedKind, EncodedPaint,
+        MAX_GRADIENT_LUT_SIZE, RadialKind,
+    },
     kurbo::Affine,
     paint::ImageSource,
     peniko,
     pixmap::Pixmap,
     tile::Tile,
 };
-use vello_sparse_shaders::{clear_slots, render_strips};
+use vello_sparse_shaders::{clear_slots, filters, render_strips};
 use web_sys::wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
     WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlTexture,
@@ -97,6 +105,15 @@ pub struct WebGlRenderer {
     gradient_cache: GradientRampCache,
     /// Reusable buffer for GPU strips produced by the fast path.
     fast_path_gpu_strips: Vec<GpuStrip>,
+    /// The filter layers of the current frame and the passes applying their filters.
+    filter_frame: FilterFrame,
+    /// Textures and framebuffers for rendering filter layers, created on first use.
+    filter_resources: Option<WebGlFilterResources>,
+    /// The masks of the current frame.
+    mask_frame: MaskFrame,
+    /// The images of bitmap and COLR glyphs cached in the image atlas.
+    #[cfg(feature = "text")]
+    glyph_atlas: GlyphAtlas,
 }
 
 impl WebGlRenderer {
@@ -164,6 +181,11 @@ impl WebGlRenderer {
             paint_idxs: Vec::new(),
             gradient_cache,
             fast_path_gpu_strips: Vec::new(),
+            filter_frame: FilterFrame::default(),
+            mask_frame: MaskFrame::default(),
+            filter_resources: None,
+            #[cfg(feature = "text")]
+            glyph_atlas: GlyphAtlas::default(),
         }
     }
 
@@ -180,6 +202,8 @@ impl WebGlRenderer {
             "Render size must match drawing buffer size"
         );
 
+        #[cfg(feature = "text")]
+        self.prepare_glyphs(scene)?;
         self.render_scene(scene, render_size, true)?;
 
         // Blit the view framebuffer to the default framebuffer (canvas element), reflecting the
@@ -258,6 +282,17 @@ impl WebGlRenderer {
     /// are staged by `queue.write_*` and only applied on the next `queue.submit`).
     #[doc(hidden)]
     pub fn render_to_atlas(&mut self, scene: &Scene, atlas_id: AtlasId) -> Result<(), RenderError> {
+        #[cfg(feature = "text")]
+        self.prepare_glyphs(scene)?;
+        self.render_to_atlas_layer(scene, atlas_id)
+    }
+
+    /// Render `scene` into the atlas layer `atlas_id`.
+    fn render_to_atlas_layer(
+        &mut self,
+        scene: &Scene,
+        atlas_id: AtlasId,
+    ) -> Result<(), RenderError> {
         self.programs
             .maybe_resize_atlas_texture_array(&self.gl, self.image_cache.atlas_count() as u32);
 
@@ -321,6 +356,34 @@ impl WebGlRenderer {
         result
     }
 
+    /// Write the images of the bitmap and COLR glyphs of `scene` that aren't cached yet to the
+    /// image atlas.
+    #[cfg(feature = "text")]
+    fn prepare_glyphs(&mut self, scene: &Scene) -> Result<(), RenderError> {
+        self.glyph_atlas.prepare(scene, &mut self.image_cache);
+        for upload in core::mem::take(&mut self.glyph_atlas.uploads) {
+            self.write_to_atlas(upload.image_id, &upload.pixmap, None);
+        }
+
+        let colr_batches = core::mem::take(&mut self.glyph_atlas.colr_batches);
+        let mut result = Ok(());
+        for batch in &colr_batches {
+            self.programs
+                .maybe_resize_atlas_texture_array(&self.gl, self.image_cache.atlas_count() as u32);
+            // The regions might still contain images that have been evicted before.
+            for &[x, y, width, height] in &batch.regions {
+                self.clear_atlas_region(batch.atlas_id, [x, y], width, height);
+            }
+            result = self.render_to_atlas_layer(&batch.scene, batch.atlas_id);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.glyph_atlas.colr_batches = colr_batches;
+
+        result
+    }
+
     /// Shared render pipeline: prepares GPU resources, runs the scheduler, and
     /// maintains caches.
     ///
@@ -334,7 +397,30 @@ impl WebGlRenderer {
         render_size: &RenderSize,
         clear: bool,
     ) -> Result<(), RenderError> {
-        self.prepare_gpu_encoded_paints(&scene.encoded_paints);
+        self.mask_frame.prepare(
+            scene,
+            &mut self.image_cache,
+            &mut self.encoded_paints,
+            &mut self.scheduler_state.masks,
+        );
+        self.prepare_gpu_encoded_paints(scene);
+        self.filter_frame.prepare(
+            scene,
+            render_size,
+            &mut self.image_cache,
+            &mut self.encoded_paints,
+            &mut self.paint_idxs,
+            &mut self.scheduler_state.filtered_layers,
+        );
+        if !self.filter_frame.layers.is_empty() || !self.mask_frame.uploads.is_empty() {
+            // The filtered layers and masks are stored in the image atlas, which might need
+            // to grow.
+            self.programs
+                .maybe_resize_atlas_texture_array(&self.gl, self.image_cache.atlas_count() as u32);
+        }
+        for upload in core::mem::take(&mut self.mask_frame.uploads) {
+            self.write_to_atlas(upload.image_id, &upload.pixmap, None);
+        }
         // TODO: For the time being, we upload the entire alpha buffer as one big chunk. As a future
         // refinement, we could have a bounded alpha buffer, and break draws when the alpha
         // buffer fills.
@@ -369,18 +455,145 @@ impl WebGlRenderer {
             ctx.render_strips(&self.fast_path_gpu_strips, 2, load_op);
             Ok(())
         } else {
-            let mut ctx = WebGlRendererContext {
-                programs: &mut self.programs,
-                gl: &self.gl,
-            };
-            self.scheduler
-                .do_scene(&mut self.scheduler_state, &mut ctx, scene, &self.paint_idxs)
+            self.render_filter_layers(scene, render_size)
+                .and_then(|()| {
+                    let mut ctx = WebGlRendererContext {
+                        programs: &mut self.programs,
+                        gl: &self.gl,
+                    };
+                    self.scheduler.do_scene(
+                        &mut self.scheduler_state,
+                        &mut ctx,
+                        scene,
+                        &self.paint_idxs,
+                    )
+                })
         };
         self.gradient_cache.maintain();
 
         result
     }
 
+    /// Render the filter layers of the current frame into the image atlas.
+    ///
+    /// Each layer is first scheduled into the layer texture, from where the filter passes read
+    /// it and write the filtered result into the atlas region allocated by
+    /// [`FilterFrame::prepare`].
+    fn render_filter_layers(
+        &mut self,
+        scene: &Scene,
+        render_size: &RenderSize,
+    ) -> Result<(), RenderError> {
+        if self.filter_frame.layers.is_empty() {
+            return Ok(());
+        }
+
+        let gl = &self.gl;
+        let resources = WebGlFilterResources::prepare(
+            &mut self.filter_resources,
+            gl,
+            render_size,
+            self.filter_frame.scratch_size,
+        );
+
+        for layer in &self.filter_frame.layers {
+            // Clear the layer texture, since the scheduler only draws the tiles of the layer.
+            gl.bind_framebuffer(
+                WebGl2RenderingContext::FRAMEBUFFER,
+                Some(&resources.layer_framebuffer),
+            );
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+
+            // Swap the view framebuffer so the scheduler renders to the layer texture instead of
+            // the normal view.
+            core::mem::swap(
+                &mut self.programs.resources.view_framebuffer,
+                &mut resources.layer_framebuffer,
+            );
+            let mut ctx = WebGlRendererContext {
+                programs: &mut self.programs,
+                gl,
+            };
+            let result = self.scheduler.do_filter_layer(
+                &mut self.scheduler_state,
+                &mut ctx,
+                scene,
+                layer.layer_id,
+                &layer.wtile_bbox,
+                &self.paint_idxs,
+            );
+            core::mem::swap(
+                &mut self.programs.resources.view_framebuffer,
+                &mut resources.layer_framebuffer,
+            );
+            result?;
+
+            gl.bind_framebuffer(
+                WebGl2RenderingContext::FRAMEBUFFER,
+                Some(&resources.atlas_framebuffer),
+            );
+            gl.framebuffer_texture_layer(
+                WebGl2RenderingContext::FRAMEBUFFER,
+                WebGl2RenderingContext::COLOR_ATTACHMENT0,
+                Some(&self.programs.resources.atlas_texture_array.texture),
+                0,
+                layer.atlas_id.as_u32() as i32,
+            );
+
+            // Filter passes overwrite their output region.
+            gl.disable(WebGl2RenderingContext::BLEND);
+            gl.use_program(Some(&self.programs.filter_program));
+            gl.bind_buffer_base(
+                WebGl2RenderingContext::UNIFORM_BUFFER,
+                self.programs.filter_uniforms.config_block_index,
+                Some(&resources.config_buffer),
+            );
+
+            for pass in &self.filter_frame.passes[layer.passes.clone()] {
+                gl.bind_buffer(
+                    WebGl2RenderingContext::UNIFORM_BUFFER,
+                    Some(&resources.config_buffer),
+                );
+                gl.buffer_data_with_u8_array(
+                    WebGl2RenderingContext::UNIFORM_BUFFER,
+                    bytemuck::bytes_of(&pass.config),
+                    WebGl2RenderingContext::DYNAMIC_DRAW,
+                );
+
+                gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+                gl.bind_texture(
+                    WebGl2RenderingContext::TEXTURE_2D,
+                    Some(resources.input_texture(pass.input)),
+                );
+                gl.uniform1i(Some(&self.programs.filter_uniforms.input_texture), 0);
+                gl.active_texture(WebGl2RenderingContext::TEXTURE1);
+                gl.bind_texture(
+                    WebGl2RenderingContext::TEXTURE_2D,
+                    Some(resources.input_texture(pass.input2)),
+                );
+                gl.uniform1i(Some(&self.programs.filter_uniforms.input2_texture), 1);
+
+                gl.bind_framebuffer(
+                    WebGl2RenderingContext::FRAMEBUFFER,
+                    Some(resources.output_framebuffer(pass.output)),
+                );
+                // Only the region selected by the viewport is written.
+                gl.viewport(
+                    pass.config.dst_origin[0] as i32,
+                    pass.config.dst_origin[1] as i32,
+                    pass.size[0] as i32,
+                    pass.size[1] as i32,
+                );
+                gl.draw_arrays(WebGl2RenderingContext::TRIANGLE_STRIP, 0, 4);
+            }
+
+            gl.enable(WebGl2RenderingContext::BLEND);
+        }
+
+        Ok(())
+    }
+
     /// Get a reference to the underlying WebGL context.
     ///
     /// This allows direct access to WebGL operations for advanced use cases like texture creation.
@@ -504,21 +717,38 @@ impl WebGlRenderer {
         self.gl.delete_framebuffer(Some(&temp_framebuffer));
     }
 
-    fn prepare_gpu_encoded_paints(&mut self, encoded_paints: &[EncodedPaint]) {
+    fn prepare_gpu_encoded_paints(&mut self, scene: &Scene) {
+        let encoded_paints = &scene.encoded_paints;
+        // The paints of bitmap and COLR glyphs sample from the atlas allocations of their
+        // glyph images.
+        #[cfg(feature = "text")]
+        let mut glyph_paints = scene.glyph_images.paints.iter().peekable();
+        // The paints of the scene are stored after the image paints of the masks.
+        let first = self.encoded_paints.len();
         self.encoded_paints
-            .resize_with(encoded_paints.len(), || GPU_PAINT_PLACEHOLDER);
+            .resize_with(first + encoded_paints.len(), || GPU_PAINT_PLACEHOLDER);
         self.paint_idxs.resize(encoded_paints.len() + 1, 0);
 
-        let mut current_idx = 0;
+        let mut current_idx = first as u32 * GPU_ENCODED_IMAGE_SIZE_TEXELS;
         for (encoded_paint_idx, paint) in encoded_paints.iter().enumerate() {
             self.paint_idxs[encoded_paint_idx] = current_idx;
             match paint {
                 EncodedPaint::Image(img) => {
                     if let ImageSource::OpaqueId { id: image_id, .. } = img.source {
-                        let image_resource: Option<&ImageResource> = self.image_cache.get(image_id);
+                        #[cfg(feature = "text")]
+                        let image_id = match glyph_paints
+                            .next_if(|(paint_idx, _)| *paint_idx == encoded_paint_idx)
+                        {
+                            Some((_, image_idx)) => self.glyph_atlas.image_id(*image_idx),
+                            None => Some(image_id),
+                        };
+                        #[cfg(not(feature = "text"))]
+                        let image_id = Some(image_id);
+                        let image_resource: Option<&ImageResource> =
+                            image_id.and_then(|image_id| self.image_cache.get(image_id));
                         if let Some(image_resource) = image_resource {
                             let gpu_image = self.encode_image_paint(img, image_resource);
-                            self.encoded_paints[encoded_paint_idx] = gpu_image;
+                            self.encoded_paints[first + encoded_paint_idx] = gpu_image;
                             current_idx += GPU_ENCODED_IMAGE_SIZE_TEXELS;
                         }
                     }
@@ -534,14 +764,13 @@ impl WebGlRenderer {
                         GpuEncodedPaint::SweepGradient(_) => GPU_SWEEP_GRADIENT_SIZE_TEXELS,
                         _ => unreachable!("encode_gradient_for_gpu only returns gradient types"),
                     };
-                    self.encoded_paints[encoded_paint_idx] = gpu_gradient;
+                    self.encoded_paints[first + encoded_paint_idx] = gpu_gradient;
                     current_idx += gradient_size_texels;
                 }
-                EncodedPaint::BlurredRoundedRect(_blurred_rect) => {
-                    // TODO: Blurred rounded rectangles are not yet supported
-                    log::warn!(
-                        "Blurred rounded rectangles are not yet supported in sparse strips hybrid renderer"
-                    );
+                EncodedPaint::BlurredRoundedRect(rect) => {
+                    self.encoded_paints[first + encoded_paint_idx] =
+                        self.encode_blurred_rounded_rect_paint(rect);
+                    current_idx += GPU_BLURRED_ROUNDED_RECT_SIZE_TEXELS;
                 }
             }
         }
@@ -572,7 +801,33 @@ impl WebGlRenderer {
             transform,
             tint,
             tint_mode,
-            _padding: 0,
+            alpha: image.sampler.alpha,
+        })
+    }
+
+    fn encode_blurred_rounded_rect_paint(
+        &self,
+        rect: &EncodedBlurredRoundedRectangle,
+    ) -> GpuEncodedPaint {
+        // The shader evaluates the rectangle at pixel centers, while the encoded
+        // transform expects the top-left corner of a pixel.
+        let rect_transform = rect.transform * Affine::translate((-0.5, -0.5));
+        let transform = rect_transform.as_coeffs().map(|x| x as f32);
+
+        GpuEncodedPaint::BlurredRoundedRect(GpuBlurredRoundedRect {
+            transform,
+            color: rect.color.as_premul_rgba8().to_u32(),
+            exponent: rect.exponent,
+            recip_exponent: rect.recip_exponent,
+            scale: rect.scale,
+            std_dev_inv: rect.std_dev_inv,
+            min_edge: rect.min_edge,
+            w: rect.w,
+            h: rect.h,
+            width: rect.width,
+            height: rect.height,
+            r1: rect.r1,
+            _padding: [0; 3],
         })
     }
 
@@ -660,6 +915,10 @@ struct WebGlPrograms {
     clear_program: WebGlProgram,
     /// Uniform locations for the `clear_program`.
     clear_uniforms: ClearUniforms,
+    /// Program for applying filter passes.
+    filter_program: WebGlProgram,
+    /// Uniform locations for the `filter_program`.
+    filter_uniforms: FilterUniforms,
     /// WebGL resources for rendering.
     resources: WebGlResources,
     /// Dimensions of the rendering target.
@@ -696,6 +955,17 @@ struct ClearUniforms {
     config_block_index: u32,
 }
 
+/// Uniform locations for `filter_program`.
+#[derive(Debug)]
+struct FilterUniforms {
+    /// Config uniform block index.
+    config_block_index: u32,
+    /// Input texture location.
+    input_texture: WebGlUniformLocation,
+    /// Secondary input texture location.
+    input2_texture: WebGlUniformLocation,
+}
+
 /// Contains all WebGL resources needed for rendering.
 #[derive(Debug)]
 struct WebGlResources {
@@ -781,8 +1051,12 @@ impl WebGlPrograms {
             clear_slots::FRAGMENT_SOURCE,
         );
 
+        let filter_program =
+            create_shader_program(&gl, filters::VERTEX_SOURCE, filters::FRAGMENT_SOURCE);
+
         let strip_uniforms = get_strip_uniforms(&gl, &strip_program);
         let clear_uniforms = get_clear_uniforms(&gl, &clear_program);
+        let filter_uniforms = get_filter_uniforms(&gl, &filter_program);
 
         let resources = create_webgl_resources(&gl, image_cache, slot_count);
 
@@ -802,6 +1076,8 @@ impl WebGlPrograms {
             clear_program,
             strip_uniforms,
             clear_uniforms,
+            filter_program,
+            filter_uniforms,
             resources,
             render_size: RenderSize {
                 width: 0,
@@ -1523,6 +1799,31 @@ fn get_clear_uniforms(gl: &WebGl2RenderingContext, program: &WebGlProgram) -> Cl
     ClearUniforms { config_block_index }
 }
 
+/// Get the uniform locations for the `filters` program.
+fn get_filter_uniforms(gl: &WebGl2RenderingContext, program: &WebGlProgram) -> FilterUniforms {
+    let config_name = filters::fragment::CONFIG;
+    let config_block_index = gl.get_uniform_block_index(program, config_name);
+
+    debug_assert_ne!(
+        config_block_index,
+        WebGl2RenderingContext::INVALID_INDEX,
+        "invalid uniform index"
+    );
+
+    // Bind uniform block to binding point.
+    gl.uniform_block_binding(program, config_block_index, 0);
+
+    FilterUniforms {
+        config_block_index,
+        input_texture: gl
+            .get_uniform_location(program, filters::fragment::INPUT_TEXTURE)
+            .unwrap(),
+        input2_texture: gl
+            .get_uniform_location(program, filters::fragment::INPUT2_TEXTURE)
+            .unwrap(),
+    }
+}
+
 /// Create a texture with nearest neighbor sampling and clamp-to-edge wrapping.
 fn create_texture(gl: &WebGl2RenderingContext) -> WebGlTexture {
     create_texture_inner(gl, WebGl2RenderingContext::TEXTURE_2D)
@@ -1651,6 +1952,114 @@ fn create_webgl_resources(
     }
 }
 
+/// Textures and framebuffers for rendering filter layers.
+#[derive(Debug)]
+struct WebGlFilterResources {
+    /// Texture holding the unfiltered contents of a filter layer, with the size of the render
+    /// target.
+    layer_texture: WebGlTexture,
+    /// Framebuffer for the layer texture.
+    layer_framebuffer: WebGlFramebuffer,
+    /// Size of the layer texture.
+    layer_size: [u32; 2],
+    /// Textures for intermediate results of filter passes.
+    scratch_textures: [WebGlTexture; FILTER_SCRATCH_TEXTURES],
+    /// Framebuffers for the scratch textures.
+    scratch_framebuffers: [WebGlFramebuffer; FILTER_SCRATCH_TEXTURES],
+    /// Size of the scratch textures.
+    scratch_size: [u32; 2],
+    /// Framebuffer for the atlas layer of the filter layer that is being processed.
+    atlas_framebuffer: WebGlFramebuffer,
+    /// Config buffer for the current filter pass.
+    config_buffer: WebGlBuffer,
+}
+
+impl WebGlFilterResources {
+    /// Create the filter resources or resize them if they are too small.
+    fn prepare<'a>(
+        resources: &'a mut Option<Self>,
+        gl: &WebGl2RenderingContext,
+        render_size: &RenderSize,
+        scratch_size: [u32; 2],
+    ) -> &'a mut Self {
+        let resources = resources.get_or_insert_with(|| {
+            let layer_texture = create_texture(gl);
+            let scratch_textures: [WebGlTexture; FILTER_SCRATCH_TEXTURES] =
+                core::array::from_fn(|_| create_texture(gl));
+            Self {
+                layer_framebuffer: create_framebuffer_for_texture(gl, &layer_texture),
+                layer_texture,
+                layer_size: [0, 0],
+                scratch_framebuffers: core::array::from_fn(|idx| {
+                    create_framebuffer_for_texture(gl, &scratch_textures[idx])
+                }),
+                scratch_textures,
+                scratch_size: [0, 0],
+                atlas_framebuffer: gl.create_framebuffer().unwrap(),
+                config_buffer: gl.create_buffer().unwrap(),
+            }
+        });
+
+        // The layer texture is rendered to like the view texture, so it needs to have the exact
+        // same size.
+        let layer_size = [render_size.width, render_size.height];
+        if resources.layer_size != layer_size {
+            resize_texture(gl, &resources.layer_texture, layer_size);
+            resources.layer_size = layer_size;
+        }
+
+        if resources.scratch_size[0] < scratch_size[0]
+            || resources.scratch_size[1] < scratch_size[1]
+        {
+            let size = [
+                resources.scratch_size[0].max(scratch_size[0]),
+                resources.scratch_size[1].max(scratch_size[1]),
+            ];
+            for texture in &resources.scratch_textures {
+                resize_texture(gl, texture, size);
+            }
+            resources.scratch_size = size;
+        }
+
+        resources
+    }
+
+    /// Return the texture to bind for a filter pass input.
+    fn input_texture(&self, texture: FilterTexture) -> &WebGlTexture {
+        match texture {
+            FilterTexture::Layer => &self.layer_texture,
+            FilterTexture::Scratch(idx) => &self.scratch_textures[idx],
+            FilterTexture::Atlas => unreachable!("the atlas is never read by filter passes"),
+        }
+    }
+
+    /// Return the framebuffer to bind for a filter pass output.
+    fn output_framebuffer(&self, texture: FilterTexture) -> &WebGlFramebuffer {
+        match texture {
+            FilterTexture::Layer => &self.layer_framebuffer,
+            FilterTexture::Scratch(idx) => &self.scratch_framebuffers[idx],
+            FilterTexture::Atlas => &self.atlas_framebuffer,
+        }
+    }
+}
+
+/// (Re)allocate the storage of an RGBA8 texture with the given size.
+fn resize_texture(gl: &WebGl2RenderingContext, texture: &WebGlTexture, size: [u32; 2]) {
+    gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(texture));
+    gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_array_buffer_view(
+        WebGl2RenderingContext::TEXTURE_2D,
+        0,
+        WebGl2RenderingContext::RGBA8 as i32,
+        size[0].max(1) as i32,
+        size[1].max(1) as i32,
+        0,
+        WebGl2RenderingContext::RGBA,
+        WebGl2RenderingContext::UNSIGNED_BYTE,
+        None,
+    )
+    .unwrap();
+}
+
 /// Create an atlas texture array.
 fn create_atlas_texture_array(
     gl: &WebGl2RenderingContext,
@@ -18,18 +18,24 @@
 only break in edge cases, and some of them are also only related to conversions from f64 to f32."
 )]
 
+#[cfg(feature = "text")]
+use crate::render::glyph::GlyphAtlas;
 use crate::{
     GpuStrip, RenderError, RenderSettings, RenderSize,
     gradient_cache::GradientRampCache,
     render::{
         Config,
         common::{
-            GPU_ENCODED_IMAGE_SIZE_TEXELS, GPU_LINEAR_GRADIENT_SIZE_TEXELS,
-            GPU_RADIAL_GRADIENT_SIZE_TEXELS, GPU_SWEEP_GRADIENT_SIZE_TEXELS, GpuEncodedImage,
+            AtlasUpload, GPU_BLURRED_ROUNDED_RECT_SIZE_TEXELS, GPU_ENCODED_IMAGE_SIZE_TEXELS,
+            GPU_LINEAR_GRADIENT_SIZE_TEXELS, GPU_RADIAL_GRADIENT_SIZE_TEXELS,
+            GPU_SWEEP_GRADIENT_SIZE_TEXELS, GpuBlurredRoundedRect, GpuEncodedImage,
             GpuEncodedPaint, GpuLinearGradient, GpuRadialGradient, GpuSweepGradient,
             pack_image_offset, pack_image_params, pack_image_size, pack_radial_kind_and_swapped,
             pack_texture_width_and_extend_mode, pack_tint,
         },
+        filter::{FILTER_SCRATCH_TEXTURES, FilterConfig, FilterFrame, FilterTexture},
+        mask::MaskFrame,
+        readback::ImageReadback,
     },
     scene::Scene,
     schedule::{
@@ -44,7 +50,10 @@ use vello_common::image_cache::{ImageCache, ImageResource};
 use vello_common::multi_atlas::{AtlasConfig, AtlasId};
 use vello_common::{
     coarse::WideTile,
-    encode::{EncodedGradient, EncodedKind, EncodedPaint, MAX_GRADIENT_LUT_SIZE, RadialKind},
+    encode::{
+        EncodedBlurredRoundedRectangle, EncodedGradient, EncodedKind, EncodedPaint,
+        MAX_GRADIENT_LUT_SIZE, RadialKind,
+    },
     kurbo::Affine,
     paint::ImageSource,
     peniko,
@@ -95,6 +104,15 @@ pub struct Renderer {
     gradient_cache: GradientRampCache,
     /// Reusable buffer for GPU strips produced by the fast path.
     fast_path_gpu_strips: Vec<GpuStrip>,
+    /// The filter layers of the current frame and the passes applying their filters.
+    filter_frame: FilterFrame,
+    /// Textures and buffers for rendering filter layers, created on first use.
+    filter_resources: Option<FilterResources>,
+    /// The masks of the current frame.
+    mask_frame: MaskFrame,
+    /// The images of bitmap and COLR glyphs cached in the image atlas.
+    #[cfg(feature = "text")]
+    glyph_atlas: GlyphAtlas,
 }
 
 impl Renderer {
@@ -129,6 +147,11 @@ impl Renderer {
             encoded_paints: Vec::new(),
             paint_idxs: Vec::new(),
             fast_path_gpu_strips: Vec::new(),
+            filter_frame: FilterFrame::default(),
+            filter_resources: None,
+            mask_frame: MaskFrame::default(),
+            #[cfg(feature = "text")]
+            glyph_atlas: GlyphAtlas::default(),
         }
     }
 
@@ -145,6 +168,8 @@ impl Renderer {
         render_size: &RenderSize,
         view: &TextureView,
     ) -> Result<(), RenderError> {
+        #[cfg(feature = "text")]
+        self.prepare_glyphs(scene, device, queue, encoder)?;
         self.render_scene(scene, device, queue, encoder, render_size, view, true)
     }
 
@@ -174,9 +199,31 @@ impl Renderer {
             label: Some("Render to Atlas Encoder"),
         });
 
+        #[cfg(feature = "text")]
+        self.prepare_glyphs(scene, device, queue, &mut encoder)?;
+        let result = self.render_to_atlas_layer(scene, device, queue, &mut encoder, atlas_id);
+
+        // Submit immediately so the atlas content is committed before subsequent
+        // render() calls overwrite the shared alpha/config/paint resources.
+        queue.submit(Some(encoder.finish()));
+
+        result
+    }
+
+    /// Render `scene` into the atlas layer `atlas_id`, recording the commands into `encoder`.
+    ///
+    /// The encoder needs to be submitted before rendering another scene.
+    fn render_to_atlas_layer(
+        &mut self,
+        scene: &Scene,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        atlas_id: AtlasId,
+    ) -> Result<(), RenderError> {
         Programs::maybe_resize_atlas_texture_array(
             device,
-            &mut encoder,
+            encoder,
             &mut self.programs.resources,
             &self.programs.atlas_bind_group_layout,
             self.image_cache.atlas_count() as u32,
@@ -223,7 +270,7 @@ impl Renderer {
             scene,
             device,
             queue,
-            &mut encoder,
+            encoder,
             &atlas_render_size,
             &layer_view,
             false,
@@ -235,9 +282,66 @@ impl Renderer {
             &mut self.programs.resources.stub_atlas_bind_group,
         );
 
-        // Submit immediately so the atlas content is committed before subsequent
-        // render() calls overwrite the shared alpha/config/paint resources.
-        queue.submit(Some(encoder.finish()));
+        result
+    }
+
+    /// Write the images of the bitmap and COLR glyphs of `scene` that aren't cached yet to the
+    /// image atlas.
+    ///
+    /// Bitmap glyphs are uploaded through `encoder`, while COLR glyphs are rendered and
+    /// submitted immediately, since rendering them uses the same resources as rendering the
+    /// scene.
+    #[cfg(feature = "text")]
+    fn prepare_glyphs(
+        &mut self,
+        scene: &Scene,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+    ) -> Result<(), RenderError> {
+        self.glyph_atlas.prepare(scene, &mut self.image_cache);
+        for upload in core::mem::take(&mut self.glyph_atlas.uploads) {
+            self.write_to_atlas(device, queue, encoder, upload.image_id, &upload, None);
+        }
+
+        let colr_batches = core::mem::take(&mut self.glyph_atlas.colr_batches);
+        let mut result = Ok(());
+        for batch in &colr_batches {
+            let mut colr_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("COLR Glyphs Encoder"),
+            });
+            Programs::maybe_resize_atlas_texture_array(
+                device,
+                &mut colr_encoder,
+                &mut self.programs.resources,
+                &self.programs.atlas_bind_group_layout,
+                self.image_cache.atlas_count() as u32,
+            );
+            // The regions might still contain images that have been evicted before.
+            for &[x, y, width, height] in &batch.regions {
+                self.clear_atlas_region(
+                    device,
+                    queue,
+                    &mut colr_encoder,
+                    batch.atlas_id,
+                    [x, y],
+                    width,
+                    height,
+                );
+            }
+            result = self.render_to_atlas_layer(
+                &batch.scene,
+                device,
+                queue,
+                &mut colr_encoder,
+                batch.atlas_id,
+            );
+            queue.submit(Some(colr_encoder.finish()));
+            if result.is_err() {
+                break;
+            }
+        }
+        self.glyph_atlas.colr_batches = colr_batches;
 
         result
     }
@@ -257,7 +361,35 @@ impl Renderer {
         view: &TextureView,
         clear: bool,
     ) -> Result<(), RenderError> {
-        self.prepare_gpu_encoded_paints(&scene.encoded_paints);
+        self.mask_frame.prepare(
+            scene,
+            &mut self.image_cache,
+            &mut self.encoded_paints,
+            &mut self.scheduler_state.masks,
+        );
+        self.prepare_gpu_encoded_paints(scene);
+        self.filter_frame.prepare(
+            scene,
+            render_size,
+            &mut self.image_cache,
+            &mut self.encoded_paints,
+            &mut self.paint_idxs,
+            &mut self.scheduler_state.filtered_layers,
+        );
+        if !self.filter_frame.layers.is_empty() || !self.mask_frame.uploads.is_empty() {
+            // The filtered layers and masks are stored in the image atlas, which might need
+            // to grow.
+            Programs::maybe_resize_atlas_texture_array(
+                device,
+                encoder,
+                &mut self.programs.resources,
+                &self.programs.atlas_bind_group_layout,
+                self.image_cache.atlas_count() as u32,
+            );
+        }
+        for upload in core::mem::take(&mut self.mask_frame.uploads) {
+            self.write_to_atlas(device, queue, encoder, upload.image_id, &upload, None);
+        }
         // TODO: For the time being, we upload the entire alpha buffer as one big chunk. As a future
         // refinement, we could have a bounded alpha buffer, and break draws when the alpha
         // buffer fills.
@@ -292,19 +424,180 @@ impl Renderer {
 
             Ok(())
         } else {
+            self.render_filter_layers(scene, device, queue, encoder, render_size)
+                .and_then(|()| {
+                    let mut ctx = RendererContext {
+                        programs: &mut self.programs,
+                        device,
+                        queue,
+                        encoder,
+                        view,
+                    };
+                    self.scheduler.do_scene(
+                        &mut self.scheduler_state,
+                        &mut ctx,
+                        scene,
+                        &self.paint_idxs,
+                    )
+                })
+        };
+        self.gradient_cache.maintain();
+
+        result
+    }
+
+    /// Render the filter layers of the current frame into the image atlas.
+    ///
+    /// Each layer is first scheduled into the layer texture, from where the filter passes read
+    /// it and write the filtered result into the atlas region allocated by
+    /// [`FilterFrame::prepare`].
+    fn render_filter_layers(
+        &mut self,
+        scene: &Scene,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        render_size: &RenderSize,
+    ) -> Result<(), RenderError> {
+        if self.filter_frame.layers.is_empty() {
+            return Ok(());
+        }
+
+        let config_stride = (size_of::<FilterConfig>() as u64).next_multiple_of(u64::from(
+            device.limits().min_uniform_buffer_offset_alignment,
+        ));
+        let resources = FilterResources::prepare(
+            &mut self.filter_resources,
+            device,
+            self.programs.render_target_format,
+            render_size,
+            self.filter_frame.scratch_size,
+            config_stride * self.filter_frame.passes.len() as u64,
+        );
+        let mut configs = vec![0_u8; (config_stride as usize) * self.filter_frame.passes.len()];
+        for (pass, config) in self
+            .filter_frame
+            .passes
+            .iter()
+            .zip(configs.chunks_exact_mut(config_stride as usize))
+        {
+            config[..size_of::<FilterConfig>()].copy_from_slice(bytemuck::bytes_of(&pass.config));
+        }
+        queue.write_buffer(&resources.config_buffer, 0, &configs);
+
+        for layer in &self.filter_frame.layers {
+            // Clear the layer texture, since the scheduler only draws the tiles of the layer.
+            encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("Clear Filter Layer"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &resources.layer_view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            });
+
             let mut ctx = RendererContext {
                 programs: &mut self.programs,
                 device,
                 queue,
                 encoder,
-                view,
+                view: &resources.layer_view,
             };
-            self.scheduler
-                .do_scene(&mut self.scheduler_state, &mut ctx, scene, &self.paint_idxs)
-        };
-        self.gradient_cache.maintain();
+            self.scheduler.do_filter_layer(
+                &mut self.scheduler_state,
+                &mut ctx,
+                scene,
+                layer.layer_id,
+                &layer.wtile_bbox,
+                &self.paint_idxs,
+            )?;
 
-        result
+            let atlas_view =
+                self.programs
+                    .resources
+                    .atlas_texture_array
+                    .create_view(&TextureViewDescriptor {
+                        label: Some("Atlas Layer Filter View"),
+                        format: Some(wgpu::TextureFormat::Rgba8Unorm),
+                        dimension: Some(wgpu::TextureViewDimension::D2),
+                        aspect: wgpu::TextureAspect::All,
+                        base_mip_level: 0,
+                        mip_level_count: Some(1),
+                        base_array_layer: layer.atlas_id.as_u32(),
+                        array_layer_count: Some(1),
+                        usage: None,
+                    });
+            let texture_view = |texture: FilterTexture| match texture {
+                FilterTexture::Layer => &resources.layer_view,
+                FilterTexture::Scratch(idx) => &resources.scratch_views[idx],
+                FilterTexture::Atlas => &atlas_view,
+            };
+
+            for pass_idx in layer.passes.clone() {
+                let pass = &self.filter_frame.passes[pass_idx];
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Filter Bind Group"),
+                    layout: &self.programs.filter_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                buffer: &resources.config_buffer,
+                                offset: pass_idx as u64 * config_stride,
+                                size: NonZeroU64::new(size_of::<FilterConfig>() as u64),
+                            }),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(texture_view(pass.input)),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(texture_view(pass.input2)),
+                        },
+                    ],
+                });
+
+                let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("Filter Pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: texture_view(pass.output),
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            // Only the region selected by the viewport is written.
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    occlusion_query_set: None,
+                    timestamp_writes: None,
+                    multiview_mask: None,
+                });
+                render_pass.set_viewport(
+                    pass.config.dst_origin[0] as f32,
+                    pass.config.dst_origin[1] as f32,
+                    pass.size[0] as f32,
+                    pass.size[1] as f32,
+                    0.0,
+                    1.0,
+                );
+                render_pass.set_pipeline(&self.programs.filter_pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                render_pass.draw(0..4, 0..1);
+            }
+        }
+
+        Ok(())
     }
 
     /// Upload image to cache and atlas in one step. Returns the `ImageId`.
@@ -400,6 +693,27 @@ impl Renderer {
         }
     }
 
+    /// Read the image `image_id` back from the atlas to the CPU.
+    ///
+    /// This submits a copy of the image to `queue`, so commands which write the image (such as
+    /// those recorded by [`upload_image`](Self::upload_image)) must have already been submitted.
+    ///
+    /// Returns `None` if `image_id` is not in the image cache.
+    pub fn read_image(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        image_id: vello_common::paint::ImageId,
+    ) -> Option<ImageReadback> {
+        let image_resource = self.image_cache.get(image_id)?;
+        Some(ImageReadback::new(
+            device,
+            queue,
+            &self.programs.resources.atlas_texture_array,
+            image_resource,
+        ))
+    }
+
     /// Returns a reference to the underlying atlas texture array.
     ///
     /// This is a 2D array texture (`TextureViewDimension::D2Array`) containing all
@@ -464,21 +778,38 @@ impl Renderer {
         render_pass.draw(0..4, 0..1);
     }
 
-    fn prepare_gpu_encoded_paints(&mut self, encoded_paints: &[EncodedPaint]) {
+    fn prepare_gpu_encoded_paints(&mut self, scene: &Scene) {
+        let encoded_paints = &scene.encoded_paints;
+        // The paints of bitmap and COLR glyphs sample from the atlas allocations of their
+        // glyph images.
+        #[cfg(feature = "text")]
+        let mut glyph_paints = scene.glyph_images.paints.iter().peekable();
+        // The paints of the scene are stored after the image paints of the masks.
+        let first = self.encoded_paints.len();
         self.encoded_paints
-            .resize_with(encoded_paints.len(), || GPU_PAINT_PLACEHOLDER);
+            .resize_with(first + encoded_paints.len(), || GPU_PAINT_PLACEHOLDER);
         self.paint_idxs.resize(encoded_paints.len() + 1, 0);
 
-        let mut current_idx = 0;
+        let mut current_idx = first as u32 * GPU_ENCODED_IMAGE_SIZE_TEXELS;
         for (encoded_paint_idx, paint) in encoded_paints.iter().enumerate() {
             self.paint_idxs[encoded_paint_idx] = current_idx;
             match paint {
                 EncodedPaint::Image(img) => {
                     if let ImageSource::OpaqueId { id: image_id, .. } = img.source {
-                        let image_resource: Option<&ImageResource> = self.image_cache.get(image_id);
+                        #[cfg(feature = "text")]
+                        let image_id = match glyph_paints
+                            .next_if(|(paint_idx, _)| *paint_idx == encoded_paint_idx)
+                        {
+                            Some((_, image_idx)) => self.glyph_atlas.image_id(*image_idx),
+                            None => Some(image_id),
+                        };
+                        #[cfg(not(feature = "text"))]
+                        let image_id = Some(image_id);
+                        let image_resource: Option<&ImageResource> =
+                            image_id.and_then(|image_id| self.image_cache.get(image_id));
                         if let Some(image_resource) = image_resource {
                             let image_paint = self.encode_image_paint(img, image_resource);
-                            self.encoded_paints[encoded_paint_idx] = image_paint;
+                            self.encoded_paints[first + encoded_paint_idx] = image_paint;
                             current_idx += GPU_ENCODED_IMAGE_SIZE_TEXELS;
                         }
                     }
@@ -494,14 +825,13 @@ impl Renderer {
                         GpuEncodedPaint::SweepGradient(_) => GPU_SWEEP_GRADIENT_SIZE_TEXELS,
                         _ => unreachable!("encode_gradient_for_gpu only returns gradient types"),
                     };
-                    self.encoded_paints[encoded_paint_idx] = gradient_paint;
+                    self.encoded_paints[first + encoded_paint_idx] = gradient_paint;
                     current_idx += gradient_size_texels;
                 }
-                EncodedPaint::BlurredRoundedRect(_blurred_rect) => {
-                    // TODO: Blurred rounded rectangles are not yet supported
-                    log::warn!(
-                        "Blurred rounded rectangles are not yet supported in sparse strips hybrid renderer"
-                    );
+                EncodedPaint::BlurredRoundedRect(rect) => {
+                    self.encoded_paints[first + encoded_paint_idx] =
+                        self.encode_blurred_rounded_rect_paint(rect);
+                    current_idx += GPU_BLURRED_ROUNDED_RECT_SIZE_TEXELS;
                 }
             }
         }
@@ -532,7 +862,33 @@ impl Renderer {
             transform,
             tint,
             tint_mode,
-            _padding: 0,
+            alpha: image.sampler.alpha,
+        })
+    }
+
+    fn encode_blurred_rounded_rect_paint(
+        &self,
+        rect: &EncodedBlurredRoundedRectangle,
+    ) -> GpuEncodedPaint {
+        // The shader evaluates the rectangle at pixel centers, while the encoded
+        // transform expects the top-left corner of a pixel.
+        let rect_transform = rect.transform * Affine::translate((-0.5, -0.5));
+        let transform = rect_transform.as_coeffs().map(|x| x as f32);
+
+        GpuEncodedPaint::BlurredRoundedRect(GpuBlurredRoundedRect {
+            transform,
+            color: rect.color.as_premul_rgba8().to_u32(),
+            exponent: rect.exponent,
+            recip_exponent: rect.recip_exponent,
+            scale: rect.scale,
+            std_dev_inv: rect.std_dev_inv,
+            min_edge: rect.min_edge,
+            w: rect.w,
+            h: rect.h,
+            width: rect.width,
+            height: rect.height,
+            r1: rect.r1,
+            _padding: [0; 3],
         })
     }
 
@@ -626,6 +982,12 @@ struct Programs {
     clear_pipeline: RenderPipeline,
     /// Pipeline for clearing atlas regions.
     atlas_clear_pipeline: RenderPipeline,
+    /// Pipeline for applying filter passes.
+    filter_pipeline: RenderPipeline,
+    /// Bind group layout for filter passes
+    filter_bind_group_layout: BindGroupLayout,
+    /// Format of the rendering target
+    render_target_format: wgpu::TextureFormat,
     /// GPU resources for rendering (created during prepare)
     resources: GpuResources,
     /// Dimensions of the rendering target
@@ -676,6 +1038,126 @@ struct GpuResources {
     stub_atlas_bind_group: BindGroup,
 }
 
+/// Textures and buffers for rendering filter layers.
+#[derive(Debug)]
+struct FilterResources {
+    /// Texture holding the unfiltered contents of a filter layer, with the size of the render
+    /// target.
+    layer_texture: Texture,
+    /// View for the layer texture
+    layer_view: TextureView,
+    /// Textures for intermediate results of filter passes
+    scratch_textures: [Texture; FILTER_SCRATCH_TEXTURES],
+    /// Views for the scratch textures
+    scratch_views: [TextureView; FILTER_SCRATCH_TEXTURES],
+    /// Buffer holding the configs of all filter passes of a frame
+    config_buffer: Buffer,
+}
+
+impl FilterResources {
+    /// Create the filter resources or resize them if they are too small.
+    fn prepare<'a>(
+        resources: &'a mut Option<Self>,
+        device: &Device,
+        format: wgpu::TextureFormat,
+        render_size: &RenderSize,
+        scratch_size: [u32; 2],
+        config_size: u64,
+    ) -> &'a Self {
+        let create_texture = |label, format, width: u32, height: u32| {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&TextureViewDescriptor::default());
+            (texture, view)
+        };
+        let create_config_buffer = |size| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Filter Config Buffer"),
+                size,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+
+        let resources = resources.get_or_insert_with(|| {
+            let (layer_texture, layer_view) = create_texture(
+                "Filter Layer Texture",
+                format,
+                render_size.width,
+                render_size.height,
+            );
+            let mut scratch_views = Vec::with_capacity(FILTER_SCRATCH_TEXTURES);
+            let scratch_textures = core::array::from_fn(|_| {
+                let (texture, view) = create_texture(
+                    "Filter Scratch Texture",
+                    wgpu::TextureFormat::Rgba8Unorm,
+                    scratch_size[0],
+                    scratch_size[1],
+                );
+                scratch_views.push(view);
+                texture
+            });
+            Self {
+                layer_texture,
+                layer_view,
+                scratch_textures,
+                scratch_views: scratch_views.try_into().unwrap(),
+                config_buffer: create_config_buffer(config_size),
+            }
+        });
+
+        // The layer texture is rendered to like the render target, so it needs to have the
+        // exact same size.
+        let layer_size = resources.layer_texture.size();
+        if layer_size.width != render_size.width || layer_size.height != render_size.height {
+            (resources.layer_texture, resources.layer_view) = create_texture(
+                "Filter Layer Texture",
+                format,
+                render_size.width,
+                render_size.height,
+            );
+        }
+
+        let current_scratch_size = resources.scratch_textures[0].size();
+        if current_scratch_size.width < scratch_size[0]
+            || current_scratch_size.height < scratch_size[1]
+        {
+            let width = current_scratch_size.width.max(scratch_size[0]);
+            let height = current_scratch_size.height.max(scratch_size[1]);
+            for idx in 0..FILTER_SCRATCH_TEXTURES {
+                (
+                    resources.scratch_textures[idx],
+                    resources.scratch_views[idx],
+                ) = create_texture(
+                    "Filter Scratch Texture",
+                    wgpu::TextureFormat::Rgba8Unorm,
+                    width,
+                    height,
+                );
+            }
+        }
+
+        if resources.config_buffer.size() < config_size {
+            resources.config_buffer = create_config_buffer(config_size);
+        }
+
+        resources
+    }
+}
+
 const SIZE_OF_CONFIG: NonZeroU64 = NonZeroU64::new(size_of::<Config>() as u64).unwrap();
 
 /// Config for the clear slots pipeline
@@ -947,6 +1429,82 @@ impl Programs {
             cache: None,
         });
 
+        let filter_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Filter Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let filter_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Filter Shader"),
+            source: wgpu::ShaderSource::Wgsl(vello_sparse_shaders::wgsl::FILTERS.into()),
+        });
+        let filter_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Filter Pipeline Layout"),
+                bind_group_layouts: &[Some(&filter_bind_group_layout)],
+                immediate_size: 0,
+            });
+        let filter_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Filter Pipeline"),
+            layout: Some(&filter_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &filter_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &filter_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(ColorTargetState {
+                    // Filter passes write to the scratch textures and the atlas.
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+                compilation_options: PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        });
+
         let slot_texture_views: [TextureView; 2] = core::array::from_fn(|_| {
             device
                 .create_texture(&wgpu::TextureDescriptor {
@@ -1112,6 +1670,9 @@ impl Programs {
             },
             clear_pipeline,
             atlas_clear_pipeline,
+            filter_pipeline,
+            filter_bind_group_layout,
+            render_target_format: render_target_config.format,
         }
     }
 
@@ -1972,6 +2533,73 @@ impl AtlasWriter for Pixmap {
     }
 }
 
+/// Implementation for masks and glyphs - upload through a staging buffer
+///
+/// Unlike `queue.write_texture`, copying from a buffer is recorded into the command encoder,
+/// so the write can't be overwritten by an atlas resize recorded earlier in the same frame.
+impl AtlasWriter for AtlasUpload {
+    fn width(&self) -> u32 {
+        self.pixmap.width() as u32
+    }
+
+    fn height(&self) -> u32 {
+        self.pixmap.height() as u32
+    }
+
+    fn write_to_atlas_layer(
+        &self,
+        device: &Device,
+        _queue: &Queue,
+        encoder: &mut CommandEncoder,
+        atlas_texture: &Texture,
+        layer: u32,
+        offset: [u32; 2],
+        width: u32,
+        height: u32,
+    ) {
+        let row_size = 4 * width as usize;
+        let bytes_per_row = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+        let mut data = vec![0_u8; bytes_per_row * height as usize];
+        for (dst, src) in data
+            .chunks_exact_mut(bytes_per_row)
+            .zip(self.pixmap.data_as_u8_slice().chunks_exact(row_size))
+        {
+            dst[..row_size].copy_from_slice(src);
+        }
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Atlas Upload Staging Buffer"),
+            contents: &data,
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+
+        encoder.copy_buffer_to_texture(
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row as u32),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::TexelCopyTextureInfo {
+                texture: atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: offset[0],
+                    y: offset[1],
+                    z: layer,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
+
 /// Implementation for `Arc<Pixmap>`
 impl AtlasWriter for Arc<Pixmap> {
     fn width(&self) -> u32 {
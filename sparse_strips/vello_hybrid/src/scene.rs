@@ -3,28 +3,47 @@
 
 //! Basic render operations.
 
+#[cfg(feature = "text")]
+use crate::glyph::{GlyphImageKey, GlyphImageSource, GlyphImages};
+#[cfg(feature = "text")]
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::hash::Hash;
 use core::ops::Range;
+use vello_common::blurred_rounded_rect::BlurredRoundedRectangle;
 use vello_common::clip::ClipContext;
-use vello_common::coarse::{MODE_HYBRID, Wide};
+use vello_common::coarse::{MODE_HYBRID, Wide, WideTilesBbox};
+#[cfg(feature = "text")]
+use vello_common::colr::{ColrPainter, ColrRecording};
 use vello_common::encode::{EncodeExt, EncodedPaint};
 use vello_common::fearless_simd::Level;
 use vello_common::filter_effects::Filter;
 #[cfg(feature = "text")]
-use vello_common::glyph::{GlyphCaches, GlyphRenderer, GlyphRunBuilder, GlyphType, PreparedGlyph};
-use vello_common::kurbo::{Affine, BezPath, Cap, Join, Rect, Shape, Stroke};
+use vello_common::glyph::{
+    GlyphCacheKey, GlyphCaches, GlyphRenderer, GlyphRunBuilder, GlyphType, PreparedGlyph,
+};
+use vello_common::kurbo::{Affine, BezPath, Cap, Join, PathEl, Rect, Shape, Stroke};
 use vello_common::mask::Mask;
 use vello_common::multi_atlas::AtlasConfig;
+#[cfg(feature = "text")]
+use vello_common::paint::{Image, ImageId, ImageSource};
 use vello_common::paint::{Paint, PaintType, Tint};
 #[cfg(feature = "text")]
 use vello_common::peniko::FontData;
 use vello_common::peniko::color::palette::css::BLACK;
-use vello_common::peniko::{BlendMode, Compose, Fill, Mix};
+use vello_common::peniko::{BlendMode, Compose, Fill, Mix, Style};
+#[cfg(feature = "text")]
+use vello_common::peniko::{Extend, ImageQuality, ImageSampler};
+#[cfg(feature = "text")]
+use vello_common::pixmap::Pixmap;
+#[cfg(feature = "text")]
+use vello_common::recording::{BitmapGlyphCommand, ColrGlyphCommand};
 use vello_common::recording::{PushLayerCommand, Recordable, Recorder, Recording, RenderCommand};
-use vello_common::render_graph::RenderGraph;
+use vello_common::render_graph::{RenderGraph, RenderNodeKind};
 use vello_common::strip::Strip;
+use vello_common::strip_cache::StripCache;
 use vello_common::strip_generator::{GenerationMode, StripGenerator, StripStorage};
 use vello_common::util::{is_integer_rect, is_integer_translation};
 
@@ -179,6 +198,13 @@ pub struct Scene {
     pub(crate) encoded_paints: Vec<EncodedPaint>,
     /// Optional tint applied to image paints.
     pub(crate) tint: Option<Tint>,
+    /// Filter applied to the next drawn elements.
+    pub(crate) filter: Option<Filter>,
+    /// The distinct masks used by the layers of the scene.
+    ///
+    /// Masks are identified by [`Mask::ptr_eq`], so that clones of a mask are stored once.
+    /// These are uploaded to the image atlas before rendering.
+    pub(crate) masks: Vec<Mask>,
     /// Whether the current paint is visible (e.g., alpha > 0).
     paint_visible: bool,
     /// Current stroke style for path stroking operations.
@@ -196,8 +222,15 @@ pub struct Scene {
     /// Cache for rasterized glyphs to improve text rendering performance.
     #[cfg(feature = "text")]
     pub(crate) glyph_caches: Option<GlyphCaches>,
+    /// The images of the bitmap and COLR glyphs drawn in the scene.
+    ///
+    /// These are uploaded or rendered to the image atlas before rendering.
+    #[cfg(feature = "text")]
+    pub(crate) glyph_images: GlyphImages,
     /// Dependency graph for managing layer rendering order and filter effects.
     pub(crate) render_graph: RenderGraph,
+    /// Counter for generating unique layer IDs.
+    layer_id_next: u32,
     /// A buffer that stores the strips of path drawing calls that have been emitted before
     /// (a potential) first `push_layer` command.
     ///
@@ -249,18 +282,22 @@ impl Scene {
     /// Create a new render context with specific settings.
     pub fn new_with(width: u16, height: u16, settings: RenderSettings) -> Self {
         let render_state = Self::default_render_state();
-        let render_graph = RenderGraph::new();
+        let wide = Wide::<MODE_HYBRID>::new(width, height);
+        let mut render_graph = RenderGraph::new();
+        Self::add_root_node(&mut render_graph, &wide);
         Self {
             constraints: settings.constraints,
             width,
             height,
-            wide: Wide::<MODE_HYBRID>::new(width, height),
+            wide,
             clip_context: ClipContext::new(),
             aliasing_threshold: None,
             paint: render_state.paint,
             paint_transform: render_state.paint_transform,
             encoded_paints: vec![],
             tint: None,
+            filter: None,
+            masks: vec![],
             paint_visible: true,
             stroke: render_state.stroke,
             strip_generator: StripGenerator::new(width, height, settings.level),
@@ -271,12 +308,25 @@ impl Scene {
             blend_mode: render_state.blend_mode,
             #[cfg(feature = "text")]
             glyph_caches: Some(GlyphCaches::default()),
+            #[cfg(feature = "text")]
+            glyph_images: GlyphImages::default(),
             render_graph,
+            layer_id_next: 0,
             fast_strips_buffer: FastStripsBuffer::default(),
             strips_fast_path_active: true,
         }
     }
 
+    /// Add the root layer as the first node of the render graph, so that it is always
+    /// rendered last.
+    fn add_root_node(render_graph: &mut RenderGraph, wide: &Wide<MODE_HYBRID>) {
+        let root_node = render_graph.add_node(RenderNodeKind::RootLayer {
+            layer_id: 0,
+            wtile_bbox: WideTilesBbox::new([0, 0, wide.width_tiles(), wide.height_tiles()]),
+        });
+        debug_assert_eq!(root_node, 0, "Root node must be node 0");
+    }
+
     /// Create default rendering state.
     fn default_render_state() -> RenderState {
         let transform = Affine::IDENTITY;
@@ -302,7 +352,7 @@ impl Scene {
 
     /// Encode the current paint into a `Paint` that can be used for rendering.
     ///
-    /// For solid colors, this is a simple conversion. For gradients and images,
+    /// For solid colors, this is a simple conversion. For other paints,
     /// this encodes the paint data into the `encoded_paints` buffer and returns
     /// a `Paint` that references that data. The combined transform (geometry + paint)
     /// is applied during encoding.
@@ -319,6 +369,11 @@ impl Scene {
                 self.transform * self.paint_transform,
                 self.tint,
             ),
+            PaintType::BlurredRoundedRect(r) => r.encode_into(
+                &mut self.encoded_paints,
+                self.transform * self.paint_transform,
+                None,
+            ),
         }
     }
 
@@ -328,14 +383,16 @@ impl Scene {
             return;
         }
 
-        let paint = self.encode_current_paint();
-        self.fill_path_with(
-            path,
-            self.transform,
-            self.fill_rule,
-            paint,
-            self.aliasing_threshold,
-        );
+        self.with_optional_filter(|scene| {
+            let paint = scene.encode_current_paint();
+            scene.fill_path_with(
+                path,
+                scene.transform,
+                scene.fill_rule,
+                paint,
+                scene.aliasing_threshold,
+            );
+        });
     }
 
     /// Build strips for a filled path with the given properties.
@@ -366,6 +423,44 @@ impl Scene {
         submit_strips!(self, strip_storage, strip_start, paint);
     }
 
+    /// Draw `path` with `style` and the current transform and paint, reusing the strips cached in
+    /// `strip_cache` under `key` where possible.
+    ///
+    /// Returns `false` without drawing anything if cached strips can't be used for this draw,
+    /// in which case the path needs to be drawn as usual.
+    pub(crate) fn draw_cached_path<K: Hash + Eq>(
+        &mut self,
+        strip_cache: &mut StripCache<K>,
+        key: K,
+        path: &[PathEl],
+        style: &Style,
+    ) -> bool {
+        // Cached strips are not intersected with clip paths, and don't go through filter layers.
+        if self.filter.is_some() || self.clip_context.get().is_some() {
+            return false;
+        }
+        if !self.paint_visible {
+            return true;
+        }
+
+        let strip_start = self.strip_storage.borrow().strips.len();
+        if !strip_cache.generate(
+            key,
+            path,
+            style,
+            self.transform,
+            self.aliasing_threshold,
+            &mut self.strip_storage.borrow_mut(),
+        ) {
+            return false;
+        }
+        let paint = self.encode_current_paint();
+        let strip_storage = &mut self.strip_storage.borrow_mut();
+        submit_strips!(self, strip_storage, strip_start, paint);
+
+        true
+    }
+
     /// Push a new clip path to the clip stack.
     ///
     /// See the explanation in the [clipping](https://github.com/linebender/vello/tree/main/sparse_strips/vello_cpu/examples)
@@ -394,8 +489,10 @@ impl Scene {
             return;
         }
 
-        let paint = self.encode_current_paint();
-        self.stroke_path_with(path, self.transform, paint, self.aliasing_threshold);
+        self.with_optional_filter(|scene| {
+            let paint = scene.encode_current_paint();
+            scene.stroke_path_with(path, scene.transform, paint, scene.aliasing_threshold);
+        });
     }
 
     /// Build strips for a stroked path with the given properties.
@@ -457,7 +554,7 @@ impl Scene {
             && is_integer_translation(&self.paint_transform)
             && is_integer_rect(rect)
         {
-            self.fill_rect_fast(rect);
+            self.with_optional_filter(|scene| scene.fill_rect_fast(rect));
         } else {
             self.fill_path(&rect.to_path(DEFAULT_TOLERANCE));
         }
@@ -482,6 +579,49 @@ impl Scene {
         submit_strips!(self, strip_storage, strip_start, paint);
     }
 
+    /// Fill a blurred rectangle with the given corner radius and standard deviation.
+    ///
+    /// Note that this only works properly if the current paint is set to a solid color.
+    /// If not, it will fall back to using black as the fill color.
+    pub fn fill_blurred_rounded_rect(&mut self, rect: &Rect, radius: f32, std_dev: f32) {
+        if !self.paint_visible {
+            return;
+        }
+
+        let color = match self.paint {
+            PaintType::Solid(s) => s,
+            // Fallback to black when attempting to blur a rectangle with an image/gradient paint
+            _ => BLACK,
+        };
+
+        let blurred_rect = BlurredRoundedRectangle {
+            rect: *rect,
+            color,
+            radius,
+            std_dev,
+        };
+
+        // The actual rectangle we paint needs to be larger so that the blurring effect
+        // is not cut off.
+        // The impulse response of a gaussian filter is infinite.
+        // For performance reason we cut off the filter at some extent where the response is close to zero.
+        let kernel_size = 2.5 * std_dev;
+        let inflated_rect = rect.inflate(f64::from(kernel_size), f64::from(kernel_size));
+        let path = inflated_rect.to_path(DEFAULT_TOLERANCE);
+        let transform = self.transform * self.paint_transform;
+
+        self.with_optional_filter(|scene| {
+            let paint = blurred_rect.encode_into(&mut scene.encoded_paints, transform, None);
+            scene.fill_path_with(
+                &path,
+                scene.transform,
+                Fill::NonZero,
+                paint,
+                scene.aliasing_threshold,
+            );
+        });
+    }
+
     /// Stroke a rectangle with the current paint and stroke settings.
     pub fn stroke_rect(&mut self, rect: &Rect) {
         self.stroke_path(&rect.to_path(DEFAULT_TOLERANCE));
@@ -493,6 +633,22 @@ impl Scene {
         GlyphRunBuilder::new(font.clone(), self.transform, self)
     }
 
+    /// Returns the caches used for glyph rendering.
+    ///
+    /// This can be used to query [statistics](GlyphCaches::stats) about the caches.
+    #[cfg(feature = "text")]
+    pub fn glyph_caches(&self) -> &GlyphCaches {
+        self.glyph_caches.as_ref().unwrap()
+    }
+
+    /// Returns the caches used for glyph rendering mutably.
+    ///
+    /// This can be used to configure the [budget](GlyphCaches::set_budget) of the caches.
+    #[cfg(feature = "text")]
+    pub fn glyph_caches_mut(&mut self) -> &mut GlyphCaches {
+        self.glyph_caches.as_mut().unwrap()
+    }
+
     /// Flush the fast path buffer through the normal coarse rasterization pipeline.
     ///
     /// This retroactively generates wide tile commands for all strips that have been generated
@@ -520,10 +676,28 @@ impl Scene {
         self.strips_fast_path_active = false;
     }
 
+    /// Execute a drawing operation, optionally wrapping it in a filter layer.
+    fn with_optional_filter<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Self),
+    {
+        if let Some(filter) = self.filter.clone() {
+            self.push_filter_layer(filter);
+            f(self);
+            self.pop_layer();
+        } else {
+            f(self);
+        }
+    }
+
     /// Push a new layer with the given properties.
     ///
-    /// Only `clip_path` is supported for now.
-    // TODO: Implement filter integration.
+    /// Note that the mask, if provided, needs to have the same size as the scene. Otherwise,
+    /// a warning is logged and the mask is ignored. In addition to that, the mask will not be affected by the current
+    /// transformation matrix in place.
+    ///
+    /// Filters support flood, Gaussian blur, offset and drop shadow primitives; other filter
+    /// graphs are rendered without their effect.
     pub fn push_layer(
         &mut self,
         clip_path: Option<&BezPath>,
@@ -535,11 +709,29 @@ impl Scene {
         let blend_mode_val = blend_mode.unwrap_or(DEFAULT_BLEND_MODE);
         self.constraints.assert_blend_mode(blend_mode_val);
 
+        let mask = mask.filter(|m| {
+            let fits = m.width() == self.width && m.height() == self.height;
+            if !fits {
+                log::warn!(
+                    "Mask size {}x{} doesn't match the scene size {}x{}, rendering the layer without mask",
+                    m.width(),
+                    m.height(),
+                    self.width,
+                    self.height
+                );
+            }
+            fits
+        });
+        if let Some(mask) = &mask
+            && !self.masks.iter().any(|m| m.ptr_eq(mask))
+        {
+            self.masks.push(mask.clone());
+        }
+
         self.flush_fast_path();
 
-        if filter.is_some() {
-            unimplemented!("Filter effects are not yet supported in vello_hybrid");
-        }
+        // Allocate a new unique layer ID.
+        self.layer_id_next += 1;
 
         let mut strip_storage = self.strip_storage.borrow_mut();
 
@@ -558,18 +750,13 @@ impl Scene {
             None
         };
 
-        // Mask is unsupported. Blend is partially supported.
-        if mask.is_some() {
-            unimplemented!()
-        }
-
         self.wide.push_layer(
-            0,
+            self.layer_id_next,
             clip,
             blend_mode_val,
-            None,
+            mask,
             opacity.unwrap_or(1.),
-            None,
+            filter,
             self.transform,
             &mut self.render_graph,
             0,
@@ -594,16 +781,16 @@ impl Scene {
         self.push_layer(None, None, Some(opacity), None, None);
     }
 
-    /// Push a new mask layer.
-    ///
-    /// Note that masks are not yet supported in `vello_hybrid`.
+    /// Push a new mask layer. The mask needs to have the same dimensions as the
+    /// scene. The mask will not be affected by the current transform in place.
     pub fn push_mask_layer(&mut self, mask: Mask) {
         self.push_layer(None, None, None, Some(mask), None);
     }
 
-    /// Push a new filter layer.
+    /// Push a filter layer that affects all subsequent drawing operations.
     ///
-    /// Note that filters are currently ignored in `vello_hybrid`.
+    /// Note that only flood, Gaussian blur, offset and drop shadow primitives are
+    /// supported in `vello_hybrid` for now.
     pub fn push_filter_layer(&mut self, filter: Filter) {
         self.push_layer(None, None, None, None, Some(filter));
     }
@@ -679,14 +866,17 @@ impl Scene {
         self.transform = Affine::IDENTITY;
     }
 
-    /// Apply filter to the current paint (affects next drawn element)
-    pub fn set_filter_effect(&mut self, _filter: Filter) {
-        unimplemented!("Filter effects integration with Scene")
+    /// Apply filter to the current paint (affects next drawn elements).
+    ///
+    /// This sets a filter that will be applied to the next drawn element.
+    /// To apply a filter to multiple elements, use `push_filter_layer` instead.
+    pub fn set_filter_effect(&mut self, filter: Filter) {
+        self.filter = Some(filter);
     }
 
     /// Reset the current filter effect.
     pub fn reset_filter_effect(&mut self) {
-        unimplemented!("Filter effects integration with Scene")
+        self.filter = None;
     }
 
     /// Reset scene to default values.
@@ -710,9 +900,18 @@ impl Scene {
         self.stroke = render_state.stroke;
         self.blend_mode = render_state.blend_mode;
         self.tint = None;
+        self.filter = None;
+        self.masks.clear();
+
+        self.render_graph.clear();
+        Self::add_root_node(&mut self.render_graph, &self.wide);
+        self.layer_id_next = 0;
 
         #[cfg(feature = "text")]
-        self.glyph_caches.as_mut().unwrap().maintain();
+        {
+            self.glyph_caches.as_mut().unwrap().maintain();
+            self.glyph_images.clear();
+        }
         self.fast_strips_buffer.clear();
         self.strips_fast_path_active = true;
     }
@@ -728,6 +927,90 @@ impl Scene {
     }
 }
 
+#[cfg(feature = "text")]
+impl Scene {
+    /// Fill a glyph with the glyph image at `image_idx`, drawn with `transform`.
+    ///
+    /// `fill` is called to fill the area of the glyph.
+    fn fill_glyph_image(
+        &mut self,
+        image_idx: usize,
+        quality: ImageQuality,
+        transform: Affine,
+        fill: impl FnOnce(&mut Self),
+    ) {
+        // We need to change the state of the scene to draw the image, but don't want to
+        // pollute it, so simulate a `save` and `restore` operation.
+        let old_transform = self.transform;
+        let old_paint = self.paint.clone();
+
+        // The image is only allocated in the atlas by the renderer, so use a placeholder id
+        // that is resolved using `glyph_images.paints`.
+        let image = Image {
+            image: ImageSource::opaque_id(ImageId::new(0)),
+            sampler: ImageSampler {
+                x_extend: Extend::Pad,
+                y_extend: Extend::Pad,
+                quality,
+                alpha: 1.0,
+            },
+        };
+
+        let first_paint_id = self.encoded_paints.len();
+        self.set_paint(image);
+        self.set_transform(transform);
+        fill(self);
+        for paint_id in first_paint_id..self.encoded_paints.len() {
+            self.glyph_images.paints.push((paint_id, image_idx));
+        }
+
+        // Restore the state.
+        self.set_paint(old_paint);
+        self.transform = old_transform;
+    }
+
+    /// Add the image of a bitmap glyph to the scene and return its index.
+    fn insert_bitmap_glyph_image(
+        &mut self,
+        cache_key: GlyphCacheKey,
+        pixmap: &Arc<Pixmap>,
+    ) -> usize {
+        self.glyph_images.insert(
+            GlyphImageKey::bitmap(cache_key),
+            pixmap.width(),
+            pixmap.height(),
+            || GlyphImageSource::Bitmap(pixmap.clone()),
+        )
+    }
+}
+
+/// Return the image quality for drawing a bitmap glyph with `transform`.
+#[cfg(feature = "text")]
+fn bitmap_glyph_quality(transform: Affine) -> ImageQuality {
+    // If we scale down by a large factor, fall back to cubic scaling.
+    if transform.as_coeffs()[0] < 0.5 || transform.as_coeffs()[3] < 0.5 {
+        ImageQuality::High
+    } else {
+        ImageQuality::Medium
+    }
+}
+
+/// Return the image quality for drawing a COLR glyph with `transform`.
+#[cfg(feature = "text")]
+fn colr_glyph_quality(transform: Affine) -> ImageQuality {
+    let has_skew = transform.as_coeffs()[1] != 0.0 || transform.as_coeffs()[2] != 0.0;
+    if has_skew {
+        // Even though the image has the "correct" size, the skewing might cause aliasing
+        // artifacts since the pixels don't map perfectly to the image, so we use bilinear
+        // scaling here.
+        ImageQuality::Medium
+    } else {
+        // Since the image will already have the correct size, no need to use a different
+        // image quality here.
+        ImageQuality::Low
+    }
+}
+
 #[cfg(feature = "text")]
 impl GlyphRenderer for Scene {
     fn fill_glyph(&mut self, prepared_glyph: PreparedGlyph<'_>) {
@@ -742,8 +1025,47 @@ impl GlyphRenderer for Scene {
                     self.aliasing_threshold,
                 );
             }
-            GlyphType::Bitmap(_) => {}
-            GlyphType::Colr(_) => {}
+            GlyphType::Bitmap(glyph) => {
+                let transform = prepared_glyph.transform;
+                let image_idx =
+                    self.insert_bitmap_glyph_image(glyph.cache_key, &Arc::new(glyph.pixmap));
+                self.fill_glyph_image(
+                    image_idx,
+                    bitmap_glyph_quality(transform),
+                    transform,
+                    |scene| {
+                        scene.fill_rect(&glyph.area);
+                    },
+                );
+            }
+            GlyphType::Colr(glyph) => {
+                let context_color = match self.paint {
+                    PaintType::Solid(s) => s,
+                    _ => BLACK,
+                };
+
+                let transform = prepared_glyph.transform;
+                let area = glyph.area;
+                let (width, height) = (glyph.pix_width, glyph.pix_height);
+                let image_idx = self.glyph_images.insert(
+                    GlyphImageKey::colr(glyph.cache_key.clone(), context_color),
+                    width,
+                    height,
+                    || {
+                        let mut recording = ColrRecording::new();
+                        ColrPainter::new(glyph, context_color, &mut recording).paint();
+                        GlyphImageSource::Colr(Arc::new(recording))
+                    },
+                );
+                self.fill_glyph_image(
+                    image_idx,
+                    colr_glyph_quality(transform),
+                    transform,
+                    |scene| {
+                        scene.fill_rect(&area);
+                    },
+                );
+            }
         }
     }
 
@@ -758,8 +1080,11 @@ impl GlyphRenderer for Scene {
                     self.aliasing_threshold,
                 );
             }
-            GlyphType::Bitmap(_) => {}
-            GlyphType::Colr(_) => {}
+            GlyphType::Bitmap(_) | GlyphType::Colr(_) => {
+                // The definitions of COLR and bitmap glyphs can't meaningfully support being stroked.
+                // (COLR's imaging model only has fills)
+                self.fill_glyph(prepared_glyph);
+            }
         }
     }
 
@@ -828,6 +1153,38 @@ impl Recordable for Scene {
                     );
                     range_index += 1;
                 }
+                #[cfg(feature = "text")]
+                RenderCommand::FillBitmapGlyph(glyph) => {
+                    let image_idx =
+                        self.insert_bitmap_glyph_image(glyph.cache_key.clone(), &glyph.pixmap);
+                    let quality = bitmap_glyph_quality(glyph.transform);
+                    self.fill_glyph_image(image_idx, quality, glyph.transform, |scene| {
+                        scene.process_geometry_command(
+                            strip_start_indices,
+                            range_index,
+                            &adjusted_strips,
+                        );
+                    });
+                    range_index += 1;
+                }
+                #[cfg(feature = "text")]
+                RenderCommand::FillColrGlyph(glyph) => {
+                    let image_idx = self.glyph_images.insert(
+                        GlyphImageKey::colr(glyph.cache_key.clone(), glyph.context_color),
+                        glyph.pix_width,
+                        glyph.pix_height,
+                        || GlyphImageSource::Colr(glyph.recording.clone()),
+                    );
+                    let quality = colr_glyph_quality(glyph.transform);
+                    self.fill_glyph_image(image_idx, quality, glyph.transform, |scene| {
+                        scene.process_geometry_command(
+                            strip_start_indices,
+                            range_index,
+                            &adjusted_strips,
+                        );
+                    });
+                    range_index += 1;
+                }
                 RenderCommand::SetPaint(paint) => {
                     self.set_paint(paint.clone());
                 }
@@ -970,6 +1327,23 @@ impl Scene {
                     );
                     strip_start_indices.push(start_index);
                 }
+                #[cfg(feature = "text")]
+                RenderCommand::FillBitmapGlyph(BitmapGlyphCommand {
+                    area, transform, ..
+                })
+                | RenderCommand::FillColrGlyph(ColrGlyphCommand {
+                    area, transform, ..
+                }) => {
+                    self.strip_generator.generate_filled_path(
+                        area.to_path(DEFAULT_TOLERANCE),
+                        Fill::NonZero,
+                        *transform,
+                        self.aliasing_threshold,
+                        &mut strip_storage,
+                        None,
+                    );
+                    strip_start_indices.push(start_index);
+                }
                 RenderCommand::SetTransform(transform) => {
                     self.transform = *transform;
                 }
@@ -180,12 +180,17 @@ use crate::scene::FastStripsPath;
 use crate::{GpuStrip, RenderError, Scene};
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
-use vello_common::coarse::{CommandAttrs, MODE_HYBRID};
+use core::ops::Range;
+use hashbrown::HashMap;
+use vello_common::coarse::{CommandAttrs, LayerKind, MODE_HYBRID, WideTilesBbox};
+use vello_common::color::palette::css::TRANSPARENT;
+use vello_common::mask::Mask;
 use vello_common::peniko::BlendMode;
+use vello_common::render_graph::LayerId;
 use vello_common::{
     coarse::{Cmd, WideTile},
     encode::EncodedPaint,
-    paint::{ImageSource, Paint},
+    paint::{ImageSource, Paint, PremulColor},
     tile::Tile,
 };
 
@@ -199,6 +204,7 @@ const PAINT_TYPE_IMAGE: u32 = 1;
 const PAINT_TYPE_LINEAR_GRADIENT: u32 = 2;
 const PAINT_TYPE_RADIAL_GRADIENT: u32 = 3;
 const PAINT_TYPE_SWEEP_GRADIENT: u32 = 4;
+const PAINT_TYPE_BLURRED_ROUNDED_RECT: u32 = 5;
 
 // The sentinel tile index representing the surface.
 const SENTINEL_SLOT_IDX: usize = usize::MAX;
@@ -297,6 +303,16 @@ impl Round {
 pub(crate) struct SchedulerState {
     /// The state of the current wide tile that is being processed.
     tile_state: TileState,
+    /// The filter layers of the current frame whose filtered contents have already been
+    /// rendered into the image atlas, keyed by their layer ID.
+    ///
+    /// This is populated by the renderer before scheduling the layers that sample from them.
+    pub(crate) filtered_layers: HashMap<LayerId, FilteredLayer>,
+    /// The masks of the current frame that are stored in the image atlas.
+    ///
+    /// The position of a mask in this list is the number that identifies it in the fine shader.
+    /// This is populated by the renderer before scheduling.
+    pub(crate) masks: Vec<Mask>,
 }
 
 impl SchedulerState {
@@ -305,6 +321,15 @@ impl SchedulerState {
     }
 }
 
+/// A filter layer whose filtered contents are stored in the image atlas.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FilteredLayer {
+    /// The index of the encoded image paint that samples the filtered contents.
+    pub(crate) paint_idx: u32,
+    /// The bounding box of the layer in wide tile coordinates.
+    pub(crate) wtile_bbox: WideTilesBbox,
+}
+
 /// State for a single wide tile.
 #[derive(Debug, Default)]
 struct TileState {
@@ -370,6 +395,8 @@ struct TileEl {
     temporary_slot: TemporarySlot,
     round: usize,
     opacity: f32,
+    /// The number of the mask that is applied when compositing this tile into the tile below.
+    mask: Option<u32>,
 }
 
 impl TileEl {
@@ -451,6 +478,7 @@ impl Scheduler {
     ) -> Result<(), RenderError> {
         let wide_tiles_per_row = scene.wide.width_tiles();
         let wide_tiles_per_col = scene.wide.height_tiles();
+        let has_filters = scene.render_graph.has_filters();
 
         // Left to right, top to bottom iteration over wide tiles.
         for wide_tile_row in 0..wide_tiles_per_col {
@@ -459,11 +487,23 @@ impl Scheduler {
                 let wide_tile_x = wide_tile_col * WideTile::WIDTH;
                 let wide_tile_y = wide_tile_row * Tile::HEIGHT;
 
+                // With filters, only the commands of the root layer are scheduled. If the root
+                // layer has no commands in this tile, only the background is drawn.
+                let cmd_range = if has_filters {
+                    wide_tile
+                        .layer_cmd_ranges
+                        .get(&0)
+                        .map(|ranges| ranges.render_range.clone())
+                        .unwrap_or_default()
+                } else {
+                    0..wide_tile.cmds.len()
+                };
+
                 state.clear();
 
                 self.initialize_tile_state(
                     &mut state.tile_state,
-                    wide_tile,
+                    wide_tile.bg,
                     wide_tile_x,
                     wide_tile_y,
                     scene,
@@ -475,7 +515,8 @@ impl Scheduler {
                     scene,
                     wide_tile_x,
                     wide_tile_y,
-                    &wide_tile.cmds,
+                    wide_tile,
+                    cmd_range,
                     wide_tile.surface_is_blend_target(),
                     paint_idxs,
                     &scene.wide.attrs,
@@ -483,6 +524,84 @@ impl Scheduler {
             }
         }
 
+        self.flush_all(renderer);
+
+        Ok(())
+    }
+
+    /// Schedules the commands of a filter layer.
+    ///
+    /// The layer is drawn to the final target as if it was the root layer, with the difference
+    /// that the target is expected to be cleared to transparent and that only the wide tiles
+    /// inside of `wtile_bbox` are processed. Nested filter layers are sampled from the image
+    /// atlas, so they must have been rendered beforehand.
+    pub(crate) fn do_filter_layer<R: RendererBackend>(
+        &mut self,
+        state: &mut SchedulerState,
+        renderer: &mut R,
+        scene: &Scene,
+        layer_id: LayerId,
+        wtile_bbox: &WideTilesBbox,
+        paint_idxs: &[u32],
+    ) -> Result<(), RenderError> {
+        for wide_tile_row in wtile_bbox.y0()..wtile_bbox.y1() {
+            for wide_tile_col in wtile_bbox.x0()..wtile_bbox.x1() {
+                let wide_tile = scene.wide.get(wide_tile_col, wide_tile_row);
+                let Some(ranges) = wide_tile.layer_cmd_ranges.get(&layer_id) else {
+                    continue;
+                };
+                let wide_tile_x = wide_tile_col * WideTile::WIDTH;
+                let wide_tile_y = wide_tile_row * Tile::HEIGHT;
+
+                // The contents of the layer are drawn into the buffer that was pushed right
+                // before the render range (the clip buffer, if the layer is clipped), so
+                // whether that buffer is a blend target decides whether we need to wrap
+                // everything into another buffer, like for the surface of the root layer.
+                let is_blend_target = [
+                    ranges.render_range.start.wrapping_sub(1),
+                    ranges.full_range.start,
+                ]
+                .into_iter()
+                .find_map(|idx| match wide_tile.cmds.get(idx) {
+                    Some(Cmd::PushBuf(kind, is_blend_target)) if kind.id() == layer_id => {
+                        Some(*is_blend_target)
+                    }
+                    _ => None,
+                })
+                .unwrap_or(false);
+
+                state.clear();
+
+                self.initialize_tile_state(
+                    &mut state.tile_state,
+                    PremulColor::from_alpha_color(TRANSPARENT),
+                    wide_tile_x,
+                    wide_tile_y,
+                    scene,
+                    paint_idxs,
+                );
+                self.do_tile(
+                    state,
+                    renderer,
+                    scene,
+                    wide_tile_x,
+                    wide_tile_y,
+                    wide_tile,
+                    ranges.render_range.clone(),
+                    is_blend_target,
+                    paint_idxs,
+                    &scene.wide.attrs,
+                )?;
+            }
+        }
+
+        self.flush_all(renderer);
+
+        Ok(())
+    }
+
+    /// Flush all remaining rounds and restore the state for the next scheduling pass.
+    fn flush_all<R: RendererBackend>(&mut self, renderer: &mut R) {
         while !self.rounds_queue.is_empty() {
             self.flush(renderer);
         }
@@ -497,8 +616,6 @@ impl Scheduler {
             }
         }
         debug_assert!(self.rounds_queue.is_empty(), "rounds_queue is not empty");
-
-        Ok(())
     }
 
     /// Flush one round.
@@ -576,7 +693,7 @@ impl Scheduler {
     fn initialize_tile_state(
         &mut self,
         tile_state: &mut TileState,
-        tile: &WideTile<MODE_HYBRID>,
+        bg: PremulColor,
         wide_tile_x: u16,
         wide_tile_y: u16,
         scene: &Scene,
@@ -591,17 +708,13 @@ impl Scheduler {
             temporary_slot: TemporarySlot::None,
             round: self.round,
             opacity: 1.,
+            mask: None,
         });
         {
             // If the background has a non-zero alpha then we need to render it.
-            let bg = tile.bg.as_premul_rgba8().to_u32();
-            if has_non_zero_alpha(bg) {
-                let (payload, paint) = Self::process_paint(
-                    &Paint::Solid(tile.bg),
-                    scene,
-                    (wide_tile_x, wide_tile_y),
-                    idxs,
-                );
+            if has_non_zero_alpha(bg.as_premul_rgba8().to_u32()) {
+                let (payload, paint) =
+                    Self::process_paint(&Paint::Solid(bg), scene, (wide_tile_x, wide_tile_y), idxs);
 
                 let draw = self.draw_mut(self.round, 2);
                 draw.push(
@@ -612,10 +725,7 @@ impl Scheduler {
         }
     }
 
-    /// Iterates over wide tile commands and schedules them for rendering.
-    ///
-    /// Returns `Some(command_idx)` if there is more work to be done. Returns `None` if the wide
-    /// tile has been fully consumed.
+    /// Iterates over the wide tile commands in `cmd_range` and schedules them for rendering.
     fn do_tile<R: RendererBackend>(
         &mut self,
         state: &mut SchedulerState,
@@ -623,7 +733,8 @@ impl Scheduler {
         scene: &Scene,
         wide_tile_x: u16,
         wide_tile_y: u16,
-        wide_tile_cmds: &[Cmd],
+        wide_tile: &WideTile<MODE_HYBRID>,
+        cmd_range: Range<usize>,
         surface_is_blend_target: bool,
         paint_idxs: &[u32],
         attrs: &CommandAttrs,
@@ -654,7 +765,11 @@ impl Scheduler {
             self.do_push_buf(state, renderer, true)?;
         }
 
-        for cmd in wide_tile_cmds {
+        let cmds = &wide_tile.cmds;
+        let mut cmd_idx = cmd_range.start;
+        while cmd_idx < cmd_range.end {
+            let cmd = &cmds[cmd_idx];
+            cmd_idx += 1;
             // Note: this starts at 1 (for the final target)
             let depth = state.tile_state.stack.len();
 
@@ -717,6 +832,47 @@ impl Scheduler {
                             .paint(payload, paint),
                     );
                 }
+                Cmd::PushBuf(LayerKind::Filtered(layer_id), _) => {
+                    // The contents of filter layers have already been rendered and filtered,
+                    // so instead of scheduling the commands of the layer, we sample the result
+                    // from the image atlas.
+                    let ranges = &wide_tile.layer_cmd_ranges[layer_id];
+                    if matches!(cmds.get(cmd_idx), Some(Cmd::PushZeroClip(id)) if id == layer_id) {
+                        // The layer is completely clipped in this tile.
+                        cmd_idx = ranges.full_range.end;
+                        continue;
+                    }
+
+                    // None of the children of the layer are blended into the buffers here, so
+                    // they never need a temporary slot.
+                    self.do_push_buf(state, renderer, false)?;
+                    if let Some(Cmd::PushBuf(LayerKind::Clip(id), _)) = cmds.get(cmd_idx)
+                        && id == layer_id
+                    {
+                        self.do_push_buf(state, renderer, false)?;
+                        cmd_idx += 1;
+                    }
+
+                    if let Some(filtered_layer) = state.filtered_layers.get(layer_id)
+                        && filtered_layer
+                            .wtile_bbox
+                            .contains(wide_tile_x / WideTile::WIDTH, wide_tile_y / Tile::HEIGHT)
+                    {
+                        let depth = state.tile_state.stack.len();
+                        let el = state.tile_state.stack.last().unwrap();
+                        let draw = self.draw_mut(el.round, el.get_draw_texture(depth));
+                        let payload = (u32::from(wide_tile_y) << 16) | u32::from(wide_tile_x);
+                        let paint = (COLOR_SOURCE_PAYLOAD << 30)
+                            | (PAINT_TYPE_IMAGE << 27)
+                            | (filtered_layer.paint_idx & 0x07FFFFFF);
+                        draw.push(
+                            GpuStripBuilder::at_slot(el.dest_slot.get_idx(), 0, WideTile::WIDTH)
+                                .paint(payload, paint),
+                        );
+                    }
+
+                    cmd_idx = ranges.render_range.end.max(cmd_idx);
+                }
                 Cmd::PushBuf(_, is_blend_target) => {
                     self.do_push_buf(state, renderer, *is_blend_target)?;
                 }
@@ -836,7 +992,16 @@ impl Scheduler {
                 Cmd::Blend(mode) => {
                     self.do_blend(state, wide_tile_x, wide_tile_y, mode);
                 }
-                _ => unreachable!(),
+                Cmd::Mask(mask) => {
+                    // Masks are applied by the fine shader when compositing the buffer into the
+                    // buffer below, see `do_blend`.
+                    let mask_idx = state.masks.iter().position(|m| m.ptr_eq(mask));
+                    state.tile_state.stack.last_mut().unwrap().mask =
+                        mask_idx.map(|idx| idx as u32);
+                }
+                // Filters are applied when rendering the filter layers, and the commands of
+                // zero clips are skipped when sampling from the filtered layer.
+                Cmd::Filter(..) | Cmd::PushZeroClip(_) | Cmd::PopZeroClip => {}
             }
         }
 
@@ -858,7 +1023,6 @@ impl Scheduler {
     ) -> Result<(), RenderError> {
         let depth = state.tile_state.stack.len();
 
-        // TODO: Handle layer_id for filter effects when implemented.
         // `wgpu` does not allow reading/writing from the same slot texture. This means
         // that to represent the binary function `Blend(src_tile, dest_tile)` we need
         // both slots being blended to be on the same texture. This is accomplished as
@@ -926,6 +1090,7 @@ impl Scheduler {
             temporary_slot,
             round: self.round,
             opacity: 1.,
+            mask: None,
         });
 
         Ok(())
@@ -997,11 +1162,14 @@ impl Scheduler {
             },
         );
 
-        let gpu_strip_builder = if depth <= 2 {
+        let mut gpu_strip_builder = if depth <= 2 {
             GpuStripBuilder::at_surface(wide_tile_x, wide_tile_y, WideTile::WIDTH)
         } else {
             GpuStripBuilder::at_slot(nos.dest_slot.get_idx(), 0, WideTile::WIDTH)
         };
+        if let Some(mask_idx) = tos.mask {
+            gpu_strip_builder = gpu_strip_builder.with_mask(mask_idx, wide_tile_x, wide_tile_y);
+        }
 
         if let TemporarySlot::Valid(temp_slot) = nos.temporary_slot {
             let opacity_u8 = (tos.opacity * 255.0) as u8;
@@ -1086,6 +1254,14 @@ impl Scheduler {
                             ((scene_strip_y as u32) << 16) | (scene_strip_x as u32);
                         (scene_strip_xy, paint_packed)
                     }
+                    Some(EncodedPaint::BlurredRoundedRect(_)) => {
+                        let paint_packed = (COLOR_SOURCE_PAYLOAD << 30)
+                            | (PAINT_TYPE_BLURRED_ROUNDED_RECT << 27)
+                            | (paint_idx & 0x07FFFFFF);
+                        let scene_strip_xy =
+                            ((scene_strip_y as u32) << 16) | (scene_strip_x as u32);
+                        (scene_strip_xy, paint_packed)
+                    }
 
                     _ => unimplemented!("Unsupported paint type"),
                 }
@@ -1133,6 +1309,23 @@ impl GpuStripBuilder {
         self
     }
 
+    /// Apply the mask with number `mask_idx` to the source color of the strip.
+    ///
+    /// The mask is sampled at the scene coordinates of the wide tile at `wide_tile_x` and
+    /// `wide_tile_y`. Since masks are only applied when compositing whole buffers, the strip
+    /// has no dense region, so `col_idx` is free to store the mask number and the position of
+    /// the wide tile (see `render_strips.wgsl`).
+    fn with_mask(mut self, mask_idx: u32, wide_tile_x: u16, wide_tile_y: u16) -> Self {
+        debug_assert_eq!(
+            self.dense_width, 0,
+            "masked strips can't have a dense region"
+        );
+        self.col_idx = ((mask_idx + 1) << 22)
+            | (u32::from(wide_tile_y / Tile::HEIGHT) << 8)
+            | u32::from(wide_tile_x / WideTile::WIDTH);
+        self
+    }
+
     /// Paint into strip.
     fn paint(self, payload: u32, paint: u32) -> GpuStrip {
         GpuStrip {
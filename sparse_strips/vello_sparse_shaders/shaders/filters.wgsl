@@ -0,0 +1,211 @@
+// Copyright 2026 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This shader executes a single pass of a filter effect.
+//
+// Filter effects are split into a sequence of passes, where each pass reads from up to two
+// input textures and writes to a region of the output texture. The region is selected with
+// the viewport, and the vertex shader simply draws a quad covering the whole viewport.
+//
+// All operations work on 8-bit premultiplied colors and use the same integer arithmetic as
+// the low-precision filters in `vello_cpu`, so that both renderers produce matching results.
+
+// Keep in sync with MAX_KERNEL_SIZE in vello_common/src/filter/gaussian_blur.rs
+const MAX_KERNEL_SIZE: u32 = 13u;
+
+// Filter operations, keep in sync with `vello_hybrid/src/render/filter.rs`.
+// Copy the input, shifted by `src_offset`.
+const OP_COPY: u32 = 0u;
+// Fill with `color`.
+const OP_FLOOD: u32 = 1u;
+// Halve the width of the input with a [1, 3, 3, 1] / 8 filter.
+const OP_DOWNSCALE_X: u32 = 2u;
+// Halve the height of the input with a [1, 3, 3, 1] / 8 filter.
+const OP_DOWNSCALE_Y: u32 = 3u;
+// Convolve the input horizontally with `kernel`.
+const OP_CONVOLVE_X: u32 = 4u;
+// Convolve the input vertically with `kernel`.
+const OP_CONVOLVE_Y: u32 = 5u;
+// Double the width of the input with [0.25, 0.75] interpolation.
+const OP_UPSCALE_X: u32 = 6u;
+// Double the height of the input with [0.25, 0.75] interpolation.
+const OP_UPSCALE_Y: u32 = 7u;
+// Composite the input over the alpha of the second input, colored with `color`.
+const OP_DROP_SHADOW: u32 = 8u;
+
+// Edge modes, keep in sync with `vello_hybrid/src/render/filter.rs`.
+const EDGE_MODE_NONE: u32 = 0u;
+const EDGE_MODE_DUPLICATE: u32 = 1u;
+const EDGE_MODE_WRAP: u32 = 2u;
+const EDGE_MODE_MIRROR: u32 = 3u;
+
+struct Config {
+    // The filter operation of this pass.
+    op: u32,
+    // How to sample pixels outside of the input.
+    edge_mode: u32,
+    // The number of valid weights in `kernel`.
+    kernel_size: u32,
+    _padding0: u32,
+    // The origin of the output region in the output texture.
+    dst_origin: vec2<u32>,
+    // The offset added to output coordinates to get the corresponding input coordinates.
+    src_offset: vec2<i32>,
+    // The size of the input region, starting at the origin of the input texture.
+    src_size: vec2<u32>,
+    _padding1: vec2<u32>,
+    // The flood color (premultiplied) or the shadow color (unpremultiplied).
+    color: vec4<f32>,
+    // The weights of the convolution kernel, packed into vectors.
+    kernel: array<vec4<f32>, 4>,
+}
+
+@group(0) @binding(0)
+var<uniform> config: Config;
+
+@group(0) @binding(1)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var input2_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Map vertex_index (0-3) to quad corners in NDC:
+    // 0 → (-1,-1), 1 → (1,-1), 2 → (-1,1), 3 → (1,1)
+    let x = f32((vertex_index & 1u) * 2u) - 1.0;
+    let y = f32((vertex_index & 2u)) - 1.0;
+
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let xy = vec2<i32>(floor(position.xy)) - vec2<i32>(config.dst_origin);
+
+    switch config.op {
+        case OP_COPY: {
+            let src_xy = xy + config.src_offset;
+            let size = vec2<i32>(config.src_size);
+            if any(src_xy < vec2<i32>(0)) || any(src_xy >= size) {
+                return vec4<f32>(0.0);
+            }
+            return textureLoad(input_texture, src_xy, 0);
+        }
+        case OP_FLOOD: {
+            return config.color;
+        }
+        case OP_DOWNSCALE_X, OP_DOWNSCALE_Y: {
+            let axis = config.op - OP_DOWNSCALE_X;
+            let c = xy[axis] * 2;
+            let sum = sample_axis(xy, c - 1, axis)
+                + sample_axis(xy, c, axis) * 3u
+                + sample_axis(xy, c + 1, axis) * 3u
+                + sample_axis(xy, c + 2, axis);
+            return to_unorm((sum + 4u) >> vec4<u32>(3u));
+        }
+        case OP_CONVOLVE_X, OP_CONVOLVE_Y: {
+            let axis = config.op - OP_CONVOLVE_X;
+            let radius = i32(config.kernel_size / 2u);
+            var sum = vec4<f32>(0.0);
+            for (var j = 0u; j < min(config.kernel_size, MAX_KERNEL_SIZE); j++) {
+                let weight = config.kernel[j / 4u][j % 4u];
+                let p = sample_axis(xy, xy[axis] + i32(j) - radius, axis);
+                sum += vec4<f32>(p) * weight;
+            }
+            return to_unorm(vec4<u32>(clamp(round(sum), vec4<f32>(0.0), vec4<f32>(255.0))));
+        }
+        case OP_UPSCALE_X, OP_UPSCALE_Y: {
+            let axis = config.op - OP_UPSCALE_X;
+            let size = i32(config.src_size[axis]);
+            let c = xy[axis] / 2;
+            // The sliding window of `vello_cpu` starts one pixel past the end of the input,
+            // which shifts the samples of the last two input pixels. Do the same so that
+            // both renderers produce the same results at the edges.
+            var center_coord = c;
+            var next_coord = c + 1;
+            if c == size - 1 {
+                center_coord = size;
+                next_coord = size + 1;
+            } else if c == size - 2 {
+                next_coord = size;
+            }
+            let center = sample_axis(xy, center_coord, axis) * 3u;
+            // Even pixels are closer to the previous pixel of the input, odd pixels to the
+            // next one.
+            var neighbor: vec4<u32>;
+            if (xy[axis] & 1) == 0 {
+                neighbor = sample_axis(xy, c - 1, axis);
+            } else {
+                neighbor = sample_axis(xy, next_coord, axis);
+            }
+            return to_unorm((center + neighbor + 2u) >> vec4<u32>(2u));
+        }
+        case OP_DROP_SHADOW: {
+            let src = to_u8(textureLoad(input_texture, xy, 0));
+            let shadow_alpha = to_u8(textureLoad(input2_texture, xy, 0)).a;
+
+            let final_alpha = u32(round(min(f32(shadow_alpha) / 255.0 * config.color.a, 1.0) * 255.0));
+            let shadow_rgb = vec3<u32>(round(config.color.rgb * 255.0));
+            let shadow = vec4<u32>(shadow_rgb * final_alpha / 255u, final_alpha);
+
+            // Source-over compositing of the original graphic over the shadow.
+            let src_norm = vec4<f32>(src) / 255.0;
+            let result = src_norm + vec4<f32>(shadow) / 255.0 * (1.0 - src_norm.a);
+            return to_unorm(vec4<u32>(round(result * 255.0)));
+        }
+        default: {
+            return vec4<f32>(0.0);
+        }
+    }
+}
+
+// Sample the input at `xy`, with the coordinate along `axis` replaced by `coord`. Coordinates
+// outside of the input region are handled according to the edge mode.
+fn sample_axis(xy: vec2<i32>, coord: i32, axis: u32) -> vec4<u32> {
+    let extended = extend(coord, i32(config.src_size[axis]));
+    if extended < 0 {
+        return vec4<u32>(0u);
+    }
+    var src_xy = xy;
+    src_xy[axis] = extended;
+    return to_u8(textureLoad(input_texture, src_xy, 0));
+}
+
+// Extend a coordinate beyond the boundaries of the input according to the edge mode.
+//
+// Returns -1 for coordinates outside of the input if the edge mode is `EDGE_MODE_NONE`.
+fn extend(coord: i32, size: i32) -> i32 {
+    switch config.edge_mode {
+        case EDGE_MODE_DUPLICATE: {
+            return clamp(coord, 0, size - 1);
+        }
+        case EDGE_MODE_WRAP: {
+            return ((coord % size) + size) % size;
+        }
+        case EDGE_MODE_MIRROR: {
+            let period = size * 2;
+            var c = ((coord % period) + period) % period;
+            if c >= size {
+                c = period - c - 1;
+            }
+            return c;
+        }
+        default: {
+            if coord < 0 || coord >= size {
+                return -1;
+            }
+            return coord;
+        }
+    }
+}
+
+// Convert normalized color channels to 8-bit values.
+fn to_u8(color: vec4<f32>) -> vec4<u32> {
+    return vec4<u32>(round(color * 255.0));
+}
+
+// Convert 8-bit color channels to normalized values.
+fn to_unorm(color: vec4<u32>) -> vec4<f32> {
+    return vec4<f32>(color) / 255.0;
+}
@@ -37,10 +37,16 @@ const PAINT_TYPE_IMAGE: u32 = 1u;
 const PAINT_TYPE_LINEAR_GRADIENT: u32 = 2u;
 const PAINT_TYPE_RADIAL_GRADIENT: u32 = 3u;
 const PAINT_TYPE_SWEEP_GRADIENT: u32 = 4u;
+const PAINT_TYPE_BLURRED_ROUNDED_RECT: u32 = 5u;
 
 // Paint texture index mask (extracts lower 27 bits from paint field).
 const PAINT_TEXTURE_INDEX_MASK: u32 = 0x07FFFFFFu; 
 
+// The number of texels of an encoded image in `encoded_paints_texture`.
+const ENCODED_IMAGE_SIZE_TEXELS: u32 = 3u;
+// The width of a wide tile in pixels.
+const WIDE_TILE_WIDTH: u32 = 256u;
+
 // Image quality
 const IMAGE_QUALITY_LOW = 0u;
 const IMAGE_QUALITY_MEDIUM = 1u;
@@ -59,6 +65,7 @@ const RADIAL_GRADIENT_TYPE_FOCAL: u32 = 2u;
 // Mathematical constants.
 const PI: f32 = 3.1415926535897932384626433832795028;
 const TWO_PI: f32 = 2.0 * PI;
+const FRAC_2_SQRT_PI: f32 = 1.1283791670955126;
 // Tolerance for nearly zero comparisons (matching vello_cpu implementation).
 // Note: This must match SCALAR_NEARLY_ZERO in vello_common/src/math.rs
 // @see {@link https://github.com/linebender/vello/blob/748ba4c7a8973f642f778591b09658d8ee6e1132/sparse_strips/vello_common/src/math.rs#L21}
@@ -124,7 +131,8 @@ struct Config {
 //   - Bits 0-29:  Usage depends on color_source:
 //
 //     When color_source = 0 (COLOR_SOURCE_PAYLOAD):
-//       - Bits 27-29: `paint_type` (0 = solid, 1 = image, 2 = linear_gradient, 3 = radial_gradient, 4 = sweep_gradient)
+//       - Bits 27-29: `paint_type` (0 = solid, 1 = image, 2 = linear_gradient, 3 = radial_gradient, 4 = sweep_gradient,
+//                                   5 = blurred_rounded_rect)
 //       - Bits 0-26: 
 //         - If paint_type = 0: unused
 //         - If paint_type >= 1: `paint_texture_idx`
@@ -149,7 +157,8 @@ struct Config {
 // │
 // ├── paint_type = 2 (PAINT_TYPE_LINEAR_GRADIENT) - Linear gradient rendering
 // ├── paint_type = 3 (PAINT_TYPE_RADIAL_GRADIENT) - Radial gradient (with kind discriminator)
-// └── paint_type = 4 (PAINT_TYPE_SWEEP_GRADIENT) - Sweep gradient rendering
+// ├── paint_type = 4 (PAINT_TYPE_SWEEP_GRADIENT) - Sweep gradient rendering
+// └── paint_type = 5 (PAINT_TYPE_BLURRED_ROUNDED_RECT) - Blurred rounded rectangle rendering
 //     ├── payload = [x, y] scene coordinates (packed as u16s)
 //     └── bits 0-27 = paint_texture_idx
 //
@@ -165,6 +174,13 @@ struct Config {
 //     ├── bits 16-23 = opacity (0-255, applied to blend result)
 //     ├── bits 8-15 = mix_mode (blend mixing mode)
 //     └── bits 0-7 = compose_mode (compositing operation)
+//
+// When color_source = 1 or 2 and the strip has no dense region, `col_idx` can tag the strip
+// with a mask that is multiplied with the source color:
+//   - Bits 22-31: mask number + 1, or 0 if there is no mask. The encoded image of mask `n`
+//                 is stored at texel `n * ENCODED_IMAGE_SIZE_TEXELS` in `encoded_paints_texture`.
+//   - Bits 8-21: row of the wide tile that is composited.
+//   - Bits 0-7: column of the wide tile that is composited.
 struct StripInstance {
     // [x, y] packed as u16's
     // x, y — coordinates of the strip
@@ -180,6 +196,7 @@ struct StripInstance {
     @location(1) widths: u32,
     // Alpha texture column index where this strip's alpha values begin
     // There are [`Config::strip_height`] alpha values per column.
+    // If the strip has no dense region, this can store a mask instead, see the layout above.
     @location(2) col_idx: u32,
     // See StripInstance documentation above.
     @location(3) payload: u32,
@@ -198,6 +215,8 @@ struct VertexOutput {
     @location(3) @interpolate(flat) dense_end: u32,
     // Color value or slot index when alpha is 0
     @location(4) @interpolate(flat) payload: u32,
+    // Mask applied to the source color, see the `col_idx` layout above.
+    @location(5) @interpolate(flat) mask: u32,
     // Normalized device coordinates (NDC) for the current vertex
     @builtin(position) position: vec4<f32>,
 };
@@ -233,7 +252,8 @@ fn vs_main(
     let dense_width = instance.widths >> 16u;
     // Calculate the ending x-position of the dense (alpha) region
     // This boundary is used in the fragment shader to determine if alpha sampling is needed
-    out.dense_end = instance.col_idx + dense_width;
+    // If there is no dense region, `col_idx` might store a mask instead.
+    out.dense_end = select(instance.col_idx + dense_width, 0u, dense_width == 0u);
     // Calculate the pixel coordinates of the current vertex within the strip
     let pix_x = f32(x0) + x * f32(width);
     let pix_y = f32(y0) + y * f32(config.strip_height);
@@ -243,6 +263,8 @@ fn vs_main(
     let ndc_y = 1.0 - pix_y * 2.0 / f32(config.height);
 
     let color_source = (instance.paint >> 30u) & 0x3u;
+    let has_mask = dense_width == 0u && color_source != COLOR_SOURCE_PAYLOAD;
+    out.mask = select(0u, instance.col_idx, has_mask);
     if color_source == COLOR_SOURCE_PAYLOAD {
         let paint_type = (instance.paint >> 27u) & 0x7u;
         // Unpack view coordinates for image sampling and gradient calculations
@@ -259,8 +281,8 @@ fn vs_main(
                 + encoded_image.transform.zw * f32(scene_strip_y)
                 + encoded_image.transform.xy * x * f32(width)
                 + encoded_image.transform.zw * y * f32(config.strip_height);
-        } else if paint_type == PAINT_TYPE_LINEAR_GRADIENT || paint_type == PAINT_TYPE_RADIAL_GRADIENT || paint_type == PAINT_TYPE_SWEEP_GRADIENT {
-            // Use view coordinates for gradient transform (always in global view space)
+        } else if paint_type == PAINT_TYPE_LINEAR_GRADIENT || paint_type == PAINT_TYPE_RADIAL_GRADIENT || paint_type == PAINT_TYPE_SWEEP_GRADIENT || paint_type == PAINT_TYPE_BLURRED_ROUNDED_RECT {
+            // Use view coordinates for gradient and blurred rectangle transforms (always in global view space)
             out.sample_xy = vec2<f32>(
                 f32(scene_strip_x) + x * f32(width),
                 f32(scene_strip_y) + y * f32(config.strip_height)
@@ -374,7 +396,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             }
 
             let is_multiply = bool(encoded_image.tint_mode);
-            final_color = alpha * select(
+            final_color = alpha * encoded_image.alpha * select(
                 encoded_image.tint * sample_color.a,
                 sample_color * encoded_image.tint,
                 is_multiply
@@ -468,6 +490,22 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 true
             );
             final_color = alpha * gradient_color;
+        } else if paint_type == PAINT_TYPE_BLURRED_ROUNDED_RECT {
+            let paint_tex_idx = in.paint & PAINT_TEXTURE_INDEX_MASK;
+            let rect = unpack_blurred_rounded_rect(paint_tex_idx);
+
+            // Calculate fragment position and apply transform
+            let fragment_pos = in.sample_xy;
+            let rect_pos = vec2<f32>(
+                rect.transform[0] * fragment_pos.x +
+                rect.transform[2] * fragment_pos.y +
+                rect.transform[4],
+                rect.transform[1] * fragment_pos.x +
+                rect.transform[3] * fragment_pos.y +
+                rect.transform[5]
+            );
+
+            final_color = alpha * blurred_rounded_rect_coverage(rect_pos, rect) * rect.color;
         }
     } else if color_source == COLOR_SOURCE_SLOT {
         // in.payload encodes a slot in the source clip texture
@@ -477,8 +515,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
         // Extract opacity from first 8 bits (quantized from [0, 255])
         let opacity = f32(in.paint & 0xFFu) * (1.0 / 255.0);
+        let mask = sample_mask(in.mask, in.position);
 
-        final_color = alpha * opacity * clip_in_color;
+        final_color = alpha * opacity * mask * clip_in_color;
     } else if color_source == COLOR_SOURCE_BLEND {
         let opacity = f32((in.paint >> 16u) & 0xFFu) * (1.0 / 255.0);
         let mix_mode = (in.paint >> 8u) & 0xFFu;
@@ -494,12 +533,40 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         // Read destination color from slot
         let dest_y = (u32(in.position.y) & 3u) + dest_slot * config.strip_height;
         let dest_color = textureLoad(clip_input_texture, vec2(clip_x, dest_y), 0);
+        let mask = sample_mask(in.mask, in.position);
 
-        final_color = blend_mix_compose(dest_color, src_color * opacity * alpha, compose_mode, mix_mode);
+        final_color = blend_mix_compose(dest_color, src_color * mask * opacity * alpha, compose_mode, mix_mode);
     }
     return final_color;
 }
 
+// Sample the mask of a strip at the position of the fragment, see the `col_idx` layout above.
+// Returns 1 if the strip has no mask.
+fn sample_mask(mask: u32, position: vec4<f32>) -> f32 {
+    if mask == 0u {
+        return 1.0;
+    }
+
+    let wide_tile_col = mask & 0xFFu;
+    let wide_tile_row = (mask >> 8u) & 0x3FFFu;
+    let mask_idx = (mask >> 22u) - 1u;
+    // Masks are only applied to whole wide tiles, so the position within the wide tile is the
+    // same whether the strip is drawn to the surface or to a slot.
+    let scene_xy = vec2<u32>(
+        wide_tile_col * WIDE_TILE_WIDTH + (u32(position.x) & 0xFFu),
+        wide_tile_row * config.strip_height + (u32(position.y) & 3u)
+    );
+    let encoded_image = unpack_encoded_image(mask_idx * ENCODED_IMAGE_SIZE_TEXELS);
+    let image_size = vec2<u32>(encoded_image.image_size);
+    // Like in `vello_cpu`, everything outside of the mask is masked out.
+    if scene_xy.x >= image_size.x || scene_xy.y >= image_size.y {
+        return 0.0;
+    }
+
+    let image_xy = vec2<u32>(encoded_image.image_offset) + scene_xy;
+    return textureLoad(atlas_texture_array, image_xy, i32(encoded_image.atlas_index), 0).a;
+}
+
 // Apply color mixing and composition. Both input and output colors are premultiplied RGB.
 // Referenced from:
 //   <https://github.com/linebender/vello/blob/b0e2e598ac62c7b3d04d8660e7b1b7659b596970/vello_shaders/shader/shared/blend.wgsl#L288-L310>
@@ -800,6 +867,8 @@ struct EncodedImage {
     tint: vec4<f32>,
     /// Tint mode: TINT_MODE_ALPHA_MASK (`0`) or TINT_MODE_MULTIPLY (`1`).
     tint_mode: u32,
+    /// Opacity applied to the sampled image.
+    alpha: f32,
 }
 
 // Convert a flat texel index to 2D texture coordinates for the encoded paints texture.
@@ -834,6 +903,7 @@ fn unpack_encoded_image(paint_tex_idx: u32) -> EncodedImage {
     let packed_tint = texel2.y;
     let tint = select(vec4<f32>(1.0), unpack4x8unorm(packed_tint), packed_tint != 0u);
     let tint_mode = select(TINT_MODE_MULTIPLY, texel2.z, packed_tint != 0u);
+    let alpha = bitcast<f32>(texel2.w);
 
     return EncodedImage(
         quality, 
@@ -844,7 +914,8 @@ fn unpack_encoded_image(paint_tex_idx: u32) -> EncodedImage {
         transform,
         translate,
         tint,
-        tint_mode
+        tint_mode,
+        alpha
     );
 }
 
@@ -1300,3 +1371,94 @@ fn unpack_radial_kind_and_swapped(packed: u32) -> vec2<u32> {
     let f_is_swapped = (packed >> 2u) & 1u;  // Extract bit 2
     return vec2<u32>(kind, f_is_swapped);
 }
+
+struct BlurredRoundedRect {
+    /// Transform matrix [a, b, c, d, tx, ty].
+    transform: array<f32, 6>,
+    /// Premultiplied color of the rectangle.
+    color: vec4<f32>,
+    /// Exponent of the superellipse used to approximate the blurred corners.
+    exponent: f32,
+    /// Reciprocal of `exponent`.
+    recip_exponent: f32,
+    /// Scale factor applied to the blurred coverage.
+    scale: f32,
+    /// Reciprocal of the standard deviation of the blur.
+    std_dev_inv: f32,
+    /// The length of the shorter edge of the rectangle.
+    min_edge: f32,
+    /// Adjusted width of the rectangle.
+    w: f32,
+    /// Adjusted height of the rectangle.
+    h: f32,
+    /// Width of the rectangle.
+    width: f32,
+    /// Height of the rectangle.
+    height: f32,
+    /// Corner radius including the blur.
+    r1: f32,
+}
+
+// Unpack blurred rounded rectangle from the encoded paints texture.
+fn unpack_blurred_rounded_rect(paint_tex_idx: u32) -> BlurredRoundedRect {
+    let texel0 = textureLoad(encoded_paints_texture, encoded_paint_coord(paint_tex_idx), 0);
+    let texel1 = textureLoad(encoded_paints_texture, encoded_paint_coord(paint_tex_idx + 1u), 0);
+    let texel2 = textureLoad(encoded_paints_texture, encoded_paint_coord(paint_tex_idx + 2u), 0);
+    let texel3 = textureLoad(encoded_paints_texture, encoded_paint_coord(paint_tex_idx + 3u), 0);
+    let texel4 = textureLoad(encoded_paints_texture, encoded_paint_coord(paint_tex_idx + 4u), 0);
+
+    let transform = array<f32, 6>(
+        bitcast<f32>(texel0.x), bitcast<f32>(texel0.y), bitcast<f32>(texel0.z),
+        bitcast<f32>(texel0.w), bitcast<f32>(texel1.x), bitcast<f32>(texel1.y)
+    );
+    let color = unpack4x8unorm(texel1.z);
+
+    return BlurredRoundedRect(
+        transform,
+        color,
+        bitcast<f32>(texel1.w),
+        bitcast<f32>(texel2.x),
+        bitcast<f32>(texel2.y),
+        bitcast<f32>(texel2.z),
+        bitcast<f32>(texel2.w),
+        bitcast<f32>(texel3.x),
+        bitcast<f32>(texel3.y),
+        bitcast<f32>(texel3.z),
+        bitcast<f32>(texel3.w),
+        bitcast<f32>(texel4.x)
+    );
+}
+
+// Calculate the coverage of a blurred rounded rectangle at the given position in
+// rectangle space; matches vello_cpu implementation.
+//
+// Adapted from <https://git.sr.ht/~raph/blurrr/tree/master/src/distfield.rs>.
+fn blurred_rounded_rect_coverage(pos: vec2<f32>, rect: BlurredRoundedRect) -> f32 {
+    let y = pos.y + 0.5 - 0.5 * rect.height;
+    let y0 = rect.r1 + abs(y) - 0.5 * rect.h;
+    let y1 = max(y0, 0.0);
+
+    let x = pos.x + 0.5 - 0.5 * rect.width;
+    let x0 = rect.r1 + abs(x) - 0.5 * rect.w;
+    let x1 = max(x0, 0.0);
+
+    let d_pos = pow(pow(x1, rect.exponent) + pow(y1, rect.exponent), rect.recip_exponent);
+    let d_neg = min(max(x0, y0), 0.0);
+    let d = d_pos + d_neg - rect.r1;
+
+    return rect.scale * (
+        compute_erf7(rect.std_dev_inv * (rect.min_edge + d)) - compute_erf7(rect.std_dev_inv * d)
+    );
+}
+
+// Approximate the erf function.
+//
+// See <https://raphlinus.github.io/audio/2018/09/05/sigmoid.html> for an explanation of
+// this approximation.
+fn compute_erf7(x_in: f32) -> f32 {
+    // Clamp `x`, because for large `x` the terms here become `inf`, causing the result to be 0 or `NaN`.
+    let x = clamp(x_in, -10.0, 10.0) * FRAC_2_SQRT_PI;
+    let xx = x * x;
+    let p = x + (0.24295 + (0.03395 + 0.0104 * xx) * xx) * (x * xx);
+    return p / sqrt(1.0 + p * p);
+}
@@ -0,0 +1,417 @@
+// Copyright 2025 the Vello Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tests for the implementations of Vello API.
+
+use crate::renderer::{HybridRenderer, Renderer};
+use crate::util::render_pixmap;
+use vello_api::paths::{PathId, PathSet};
+use vello_api::texture::TextureId;
+use vello_api::{PaintError, PaintScene, TextureReadback};
+use vello_common::kurbo::{Affine, BezPath, Circle, Rect, Shape, Stroke};
+use vello_common::peniko::color::PremulRgba8;
+use vello_common::peniko::color::palette::css::{REBECCA_PURPLE, SEA_GREEN};
+use vello_common::peniko::{Fill, ImageBrush, ImageSampler};
+use vello_cpu::api::CPUScenePainter;
+use vello_cpu::{Level, Pixmap, RenderContext, RenderMode, RenderSettings};
+use vello_hybrid::api::{HybridScenePainter, HybridTextureReader};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 100;
+
+fn prepared_paths() -> (PathSet, PathId, PathId) {
+    let mut outline = Circle::new((6.0, 6.0), 5.5).to_path(0.1);
+    outline.extend(Rect::new(3.0, 3.0, 9.0, 9.0).path_elements(0.1));
+    let mut paths = PathSet::new();
+    let fill = paths.prepare_shape(&outline, Fill::EvenOdd);
+    let stroke = paths.prepare_shape(&outline, Stroke::new(1.5));
+    (paths, fill, stroke)
+}
+
+/// The transforms which the paths are drawn with, including ones which can't reuse strips.
+fn transforms() -> impl Iterator<Item = Affine> {
+    (0..8)
+        .flat_map(|y| {
+            (0..8).map(move |x| Affine::translate((f64::from(x) * 13.0, f64::from(y) * 11.0)))
+        })
+        .chain([
+            // Partially outside the viewport.
+            Affine::translate((-3.0, 40.0)),
+            Affine::translate((92.0, 95.0)),
+            // Not an integer translation.
+            Affine::translate((20.5, 30.25)),
+            Affine::scale(1.5).then_translate((40.0, 40.0).into()),
+        ])
+}
+
+fn new_context(num_threads: u16) -> RenderContext {
+    RenderContext::new_with(
+        WIDTH,
+        HEIGHT,
+        RenderSettings {
+            num_threads,
+            render_mode: RenderMode::OptimizeQuality,
+            ..RenderSettings::default()
+        },
+    )
+}
+
+fn render(ctx: &mut RenderContext) -> Pixmap {
+    let mut pixmap = Pixmap::new(WIDTH, HEIGHT);
+    ctx.flush();
+    ctx.render_to_pixmap(&mut pixmap);
+    pixmap
+}
+
+fn api_prepared_path_matches_fill(num_threads: u16) {
+    let (paths, fill, stroke) = prepared_paths();
+
+    let mut painter = CPUScenePainter::new(new_context(num_threads));
+    for _ in 0..2 {
+        for transform in transforms() {
+            painter.set_solid_brush(REBECCA_PURPLE);
+            painter.draw_prepared_path(transform, &paths, fill);
+            painter.set_solid_brush(SEA_GREEN.with_alpha(0.5));
+            painter.draw_prepared_path(transform, &paths, stroke);
+        }
+    }
+    // Each path is cached once for each translation modulo the tile size, and for the
+    // non-integer transforms. The draws partially outside the viewport are not cached.
+    assert_eq!(
+        painter.path_cache_mut().len(),
+        2 * (4 * 4 + 2),
+        "unexpected number of cached paths"
+    );
+
+    let mut ctx = new_context(num_threads);
+    let outline = BezPath::from_iter(paths.elements(fill).iter().copied());
+    for _ in 0..2 {
+        for transform in transforms() {
+            ctx.set_transform(transform);
+            ctx.set_paint(REBECCA_PURPLE);
+            ctx.set_fill_rule(Fill::EvenOdd);
+            ctx.fill_path(&outline);
+            ctx.set_paint(SEA_GREEN.with_alpha(0.5));
+            ctx.set_stroke(Stroke::new(1.5));
+            ctx.stroke_path(&outline);
+        }
+    }
+
+    // Reusing strips for non-integer translations can lead to different rounding.
+    let cached = render(&mut painter.render_context);
+    let expected = render(&mut ctx);
+    assert!(
+        cached
+            .data_as_u8_slice()
+            .iter()
+            .zip(expected.data_as_u8_slice())
+            .all(|(a, b)| a.abs_diff(*b) <= 1),
+        "drawing prepared paths should match filling and stroking them"
+    );
+}
+
+#[test]
+fn api_prepared_path_cpu() {
+    api_prepared_path_matches_fill(0);
+}
+
+#[test]
+fn api_prepared_path_cpu_multithreaded() {
+    api_prepared_path_matches_fill(4);
+}
+
+#[test]
+fn api_prepared_path_appended_scene() {
+    let (paths, fill, _) = prepared_paths();
+
+    let mut scene = vello_api::Scene::new(false);
+    scene.set_solid_brush(REBECCA_PURPLE);
+    scene.draw_prepared_path(Affine::IDENTITY, &paths, fill);
+
+    let mut painter = CPUScenePainter::new(new_context(0));
+    for x in 0..4 {
+        painter
+            .append(Affine::translate((f64::from(x) * 16.0, 20.0)), &scene)
+            .unwrap();
+    }
+    // The copied path in the scene is still cached using the original path set.
+    assert_eq!(painter.path_cache_mut().len(), 1);
+    painter.draw_prepared_path(Affine::translate((80.0, 20.0)), &paths, fill);
+    assert_eq!(painter.path_cache_mut().len(), 1);
+
+    painter.path_cache_mut().maintain();
+    painter.path_cache_mut().maintain();
+    assert!(painter.path_cache_mut().is_empty());
+}
+
+#[test]
+fn api_prepared_path_hybrid() {
+    let (mut paths, fill, stroke) = prepared_paths();
+
+    let mut painter = HybridScenePainter::new(vello_hybrid::Scene::new(WIDTH, HEIGHT));
+    painter.set_solid_brush(REBECCA_PURPLE);
+    for transform in transforms() {
+        painter.draw_prepared_path(transform, &paths, fill);
+        painter.draw_prepared_path(transform, &paths, stroke);
+    }
+    assert_eq!(painter.path_cache_mut().len(), 2 * (4 * 4 + 2));
+
+    // Clearing the path set invalidates the cached paths.
+    paths.clear();
+    let fill = paths.prepare_shape(&Rect::new(0.0, 0.0, 10.0, 10.0), Fill::NonZero);
+    painter.draw_prepared_path(Affine::translate((4.0, 4.0)), &paths, fill);
+    assert_eq!(painter.path_cache_mut().len(), 2 * (4 * 4 + 2) + 1);
+}
+
+#[test]
+fn api_clip_path_matches_render_context() {
+    let clip = Circle::new((50.0, 50.0), 40.0).to_path(0.1);
+    let mut inner_clip = Rect::new(20.0, 20.0, 60.0, 60.0).to_path(0.1);
+    inner_clip.extend(Rect::new(30.0, 30.0, 50.0, 50.0).path_elements(0.1));
+    let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+    let mut scene = vello_api::Scene::new(false);
+    scene.push_clip_path(Affine::translate((5.0, 0.0)), Fill::NonZero, &clip);
+    scene.set_solid_brush(REBECCA_PURPLE);
+    scene.fill_path(Affine::IDENTITY, Fill::NonZero, &rect);
+    scene.push_clip_path(Affine::IDENTITY, Fill::EvenOdd, &inner_clip);
+    scene.set_solid_brush(SEA_GREEN);
+    scene.fill_path(Affine::IDENTITY, Fill::NonZero, &rect);
+    scene.pop_clip_path();
+    scene.pop_clip_path();
+    scene.fill_path(
+        Affine::IDENTITY,
+        Fill::NonZero,
+        &Rect::new(0.0, 90.0, 100.0, 100.0),
+    );
+
+    for scene_transform in [
+        Affine::IDENTITY,
+        Affine::translate((13.0, 7.0)) * Affine::scale(0.5),
+    ] {
+        let mut painter = CPUScenePainter::new(new_context(0));
+        painter.append(scene_transform, &scene).unwrap();
+
+        let mut ctx = new_context(0);
+        ctx.set_transform(scene_transform * Affine::translate((5.0, 0.0)));
+        ctx.push_clip_path(&clip);
+        ctx.set_transform(scene_transform);
+        ctx.set_paint(REBECCA_PURPLE);
+        ctx.fill_rect(&rect);
+        ctx.set_fill_rule(Fill::EvenOdd);
+        ctx.push_clip_path(&inner_clip);
+        ctx.set_fill_rule(Fill::NonZero);
+        ctx.set_paint(SEA_GREEN);
+        ctx.fill_rect(&rect);
+        ctx.pop_clip_path();
+        ctx.pop_clip_path();
+        ctx.fill_rect(&Rect::new(0.0, 90.0, 100.0, 100.0));
+
+        assert!(render(&mut painter.render_context).data() == render(&mut ctx).data());
+    }
+}
+
+#[test]
+fn api_append_invalid_texture() {
+    let mut scene = vello_api::Scene::new(false);
+    let texture = TextureId::from_raw(u64::MAX);
+    let brush = ImageBrush {
+        image: texture,
+        sampler: ImageSampler::default(),
+    };
+    scene.set_brush(brush, Affine::IDENTITY).unwrap();
+    scene.fill_path(
+        Affine::IDENTITY,
+        Fill::NonZero,
+        &Rect::new(0.0, 0.0, 10.0, 10.0),
+    );
+
+    let mut painter = CPUScenePainter::new(new_context(0));
+    assert_eq!(
+        painter.append(Affine::IDENTITY, &scene),
+        Err(PaintError::InvalidTexture(texture))
+    );
+    assert_eq!(
+        painter.set_brush(brush, Affine::IDENTITY),
+        Err(PaintError::InvalidTexture(texture))
+    );
+    let mut painter = HybridScenePainter::new(vello_hybrid::Scene::new(WIDTH, HEIGHT));
+    assert_eq!(
+        painter.append(Affine::IDENTITY, &scene),
+        Err(PaintError::InvalidTexture(texture))
+    );
+    assert_eq!(
+        painter.set_brush(brush, Affine::IDENTITY),
+        Err(PaintError::InvalidTexture(texture))
+    );
+}
+
+#[test]
+fn api_append_unbalanced_scene() {
+    let mut scene = vello_api::Scene::new(false);
+    scene.push_clip_path(
+        Affine::IDENTITY,
+        Fill::NonZero,
+        &Rect::new(0.0, 0.0, 10.0, 10.0),
+    );
+
+    let mut painter = CPUScenePainter::new(new_context(0));
+    assert_eq!(
+        painter.append(Affine::IDENTITY, &scene),
+        Err(PaintError::UnbalancedClipPaths)
+    );
+    scene.pop_clip_path();
+    scene.pop_layer();
+    assert_eq!(
+        painter.append(Affine::IDENTITY, &scene),
+        Err(PaintError::UnbalancedLayers)
+    );
+}
+
+/// An image whose width isn't a multiple of the row alignment required when reading back from the GPU.
+fn readback_image() -> Pixmap {
+    let (width, height) = (37_u16, 21_u16);
+    let data = (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| {
+                let a = (x * 7 + y * 3) as u8 | 0x80;
+                PremulRgba8 {
+                    r: (x * 5) as u8 % a,
+                    g: (y * 11) as u8 % a,
+                    b: a / 2,
+                    a,
+                }
+            })
+        })
+        .collect();
+    Pixmap::from_parts(data, width, height)
+}
+
+#[test]
+fn api_texture_readback_cpu() {
+    let image = readback_image();
+    let mut ctx = new_context(0);
+    let id = ctx.register_image(image.clone().into());
+    let texture = TextureId::from_raw(id.as_u32().into());
+
+    let blocking = ctx.read_texture_blocking(texture).unwrap();
+    assert_eq!(blocking.data(), image.data());
+    let awaited = pollster::block_on(ctx.read_texture(texture)).unwrap();
+    assert_eq!(awaited.data(), image.data());
+
+    let invalid = TextureId::from_raw(u64::from(id.as_u32()) + 1);
+    assert_eq!(
+        ctx.read_texture_blocking(invalid).unwrap_err(),
+        PaintError::InvalidTexture(invalid)
+    );
+}
+
+#[test]
+fn api_texture_readback_hybrid() {
+    let instance = wgpu::Instance::default();
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }))
+    .expect("Failed to find an appropriate adapter");
+    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: Some("Device"),
+        required_features: wgpu::Features::empty(),
+        ..Default::default()
+    }))
+    .expect("Failed to create device");
+    let mut renderer = vello_hybrid::Renderer::new(
+        &device,
+        &vello_hybrid::RenderTargetConfig {
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            width: WIDTH.into(),
+            height: HEIGHT.into(),
+        },
+    );
+
+    let image = readback_image();
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    // Upload a second image first, so that the image being read back isn't at the atlas origin.
+    renderer.upload_image(&device, &queue, &mut encoder, &Pixmap::new(10, 10));
+    let id = renderer.upload_image(&device, &queue, &mut encoder, &image);
+    queue.submit([encoder.finish()]);
+    let texture = TextureId::from_raw(id.as_u32().into());
+
+    let reader = HybridTextureReader::new(&renderer, &device, &queue);
+    let blocking = reader.read_texture_blocking(texture).unwrap();
+    assert_eq!(blocking.data(), image.data());
+
+    let readback = reader.read_texture(texture);
+    device.poll(wgpu::PollType::wait_indefinitely()).unwrap();
+    let awaited = pollster::block_on(readback).unwrap();
+    assert_eq!(awaited.data(), image.data());
+
+    let invalid = TextureId::from_raw(u64::from(id.as_u32()) + 1);
+    assert_eq!(
+        reader.read_texture_blocking(invalid).unwrap_err(),
+        PaintError::InvalidTexture(invalid)
+    );
+}
+
+#[test]
+fn api_blurred_rounded_rect_brush_cpu() {
+    let rect = Rect::new(20.0, 25.0, 70.0, 60.0);
+    let transform = Affine::translate((5.0, 10.0));
+
+    let mut scene = vello_api::Scene::new(false);
+    scene.set_blurred_rounded_rect_brush(Affine::IDENTITY, REBECCA_PURPLE, &rect, 10.0, 4.0);
+    scene.fill_path(transform, Fill::NonZero, &rect.inflate(10.0, 10.0));
+    let mut painter = CPUScenePainter::new(new_context(0));
+    painter.append(Affine::IDENTITY, &scene).unwrap();
+
+    let mut ctx = new_context(0);
+    ctx.set_transform(transform);
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.fill_blurred_rounded_rect(&rect, 10.0, 4.0);
+
+    assert!(render(&mut painter.render_context).data() == render(&mut ctx).data());
+}
+
+#[test]
+fn api_blurred_rounded_rect_brush_hybrid() {
+    let rect = Rect::new(20.0, 25.0, 70.0, 60.0);
+    let transform = Affine::translate((5.0, 10.0));
+
+    let mut scene = vello_api::Scene::new(false);
+    scene.set_blurred_rounded_rect_brush(Affine::IDENTITY, REBECCA_PURPLE, &rect, 10.0, 4.0);
+    scene.fill_path(transform, Fill::NonZero, &rect.inflate(10.0, 10.0));
+    let mut painter = HybridScenePainter::new(vello_hybrid::Scene::new(WIDTH, HEIGHT));
+    painter.append(Affine::IDENTITY, &scene).unwrap();
+    let mut hybrid =
+        HybridRenderer::new(WIDTH, HEIGHT, 0, Level::fallback(), RenderMode::default());
+    core::mem::swap(hybrid.scene_mut(), &mut painter.scene);
+
+    let mut ctx = new_context(0);
+    ctx.set_transform(transform);
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.fill_blurred_rounded_rect(&rect, 10.0, 4.0);
+
+    // The shader evaluates the blur on the GPU, so allow for rounding differences, as in the
+    // snapshot tests.
+    let actual = render_pixmap(&hybrid);
+    let expected = render(&mut ctx);
+    let max_difference = actual
+        .data()
+        .iter()
+        .zip(expected.data())
+        .flat_map(|(a, e)| {
+            [
+                a.r.abs_diff(e.r),
+                a.g.abs_diff(e.g),
+                a.b.abs_diff(e.b),
+                a.a.abs_diff(e.a),
+            ]
+        })
+        .max()
+        .unwrap();
+    assert!(
+        max_difference <= 1,
+        "hybrid differs from Vello CPU by {max_difference}"
+    );
+}
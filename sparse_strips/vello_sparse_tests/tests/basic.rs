@@ -465,9 +465,27 @@ fn test_cmd_size(_: &mut impl Renderer) {
 /// 3. Use `composite_to_pixmap_at_offset` to blit it to a specific (x, y) position in a larger spritesheet
 #[test]
 fn composite_to_pixmap_at_offset() {
+    check_composite_to_pixmap_at_offset(0, &[(15, 15), (30, 30), (0, 0)]);
+}
+
+/// Same as `composite_to_pixmap_at_offset`, but with the multi-threaded dispatcher.
+#[test]
+fn composite_to_pixmap_at_offset_multithreaded() {
+    check_composite_to_pixmap_at_offset(3, &[(15, 15), (30, 30), (0, 0)]);
+}
+
+/// Glyphs blitted across the right and bottom edges of the spritesheet are clipped.
+#[test]
+fn composite_to_pixmap_at_offset_clipped() {
+    let positions = [(70, 10), (10, 60), (75, 70), (100, 0), (0, 120)];
+    check_composite_to_pixmap_at_offset(0, &positions);
+    check_composite_to_pixmap_at_offset(3, &positions);
+}
+
+fn check_composite_to_pixmap_at_offset(num_threads: u16, positions: &[(u16, u16)]) {
     let settings = RenderSettings {
         level: Level::try_detect().unwrap_or(Level::baseline()),
-        num_threads: 0,
+        num_threads,
         render_mode: RenderMode::OptimizeQuality,
     };
     let spritesheet_width: u16 = 100;
@@ -497,10 +515,7 @@ fn composite_to_pixmap_at_offset() {
         }));
     glyph_renderer.flush();
 
-    // Positions where we'll blit the glyph
-    let positions: [(u16, u16); 3] = [(15, 15), (30, 30), (0, 0)];
-
-    for (dst_x, dst_y) in positions {
+    for &(dst_x, dst_y) in positions {
         glyph_renderer.composite_to_pixmap_at_offset(&mut spritesheet, dst_x, dst_y);
     }
 
@@ -510,7 +525,7 @@ fn composite_to_pixmap_at_offset() {
         RenderContext::new_with(spritesheet_width, spritesheet_height, settings);
     reference_renderer.set_paint(BLACK);
 
-    for (dst_x, dst_y) in positions {
+    for &(dst_x, dst_y) in positions {
         // The glyph in glyph_renderer was rendered at (0, font_size).
         // When blitted to (dst_x, dst_y), it appears at (dst_x + 0, dst_y + font_size).
         // So we need to render at transform (dst_x, dst_y + font_size) in the reference.
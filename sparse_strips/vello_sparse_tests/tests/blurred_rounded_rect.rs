@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::renderer::Renderer;
-use vello_common::color::palette::css::REBECCA_PURPLE;
-use vello_common::kurbo::{Affine, Point, Rect};
+use vello_common::blurred_rounded_rect::BlurredRoundedRectangle;
+use vello_common::color::palette::css::{BLACK, REBECCA_PURPLE};
+use vello_common::kurbo::{Affine, Circle, Point, Rect, RoundedRect, Shape, Vec2};
+use vello_common::peniko::Fill;
 use vello_dev_macros::vello_test;
 
 fn rect_with(ctx: &mut impl Renderer, radius: f32, std_dev: f32, affine: Affine) {
@@ -62,3 +64,35 @@ fn blurred_rounded_rect_with_transform(ctx: &mut impl Renderer) {
         Affine::rotate_about(45.0_f64.to_radians(), Point::new(50.0, 50.0)),
     );
 }
+
+#[vello_test]
+fn blurred_rounded_rect_paint_outside_shape(ctx: &mut impl Renderer) {
+    let card = RoundedRect::new(25.0, 20.0, 75.0, 70.0, 8.0);
+    // Only paint the shadow outside of the card.
+    let mut outside = Rect::new(0.0, 0.0, 100.0, 100.0).to_path(0.1);
+    outside.extend(card.path_elements(0.1));
+    ctx.set_paint(BlurredRoundedRectangle {
+        rect: card.rect() + Vec2::new(4.0, 6.0),
+        color: BLACK.with_alpha(0.6),
+        radius: 8.0,
+        std_dev: 5.0,
+    });
+    ctx.set_fill_rule(Fill::EvenOdd);
+    ctx.fill_path(&outside);
+}
+
+#[vello_test]
+fn blurred_rounded_rect_paint_with_paint_transform(ctx: &mut impl Renderer) {
+    ctx.set_transform(Affine::translate((10.0, 5.0)));
+    ctx.set_paint_transform(Affine::rotate_about(
+        30.0_f64.to_radians(),
+        Point::new(40.0, 45.0),
+    ));
+    ctx.set_paint(BlurredRoundedRectangle {
+        rect: Rect::new(20.0, 30.0, 60.0, 60.0),
+        color: REBECCA_PURPLE,
+        radius: 5.0,
+        std_dev: 4.0,
+    });
+    ctx.fill_path(&Circle::new((40.0, 45.0), 35.0).to_path(0.1));
+}
@@ -9,8 +9,12 @@ use vello_common::color::AlphaColor;
 use vello_common::color::palette::css::{
     BLACK, PURPLE, REBECCA_PURPLE, ROYAL_BLUE, SEA_GREEN, TOMATO, VIOLET,
 };
-use vello_common::filter_effects::{EdgeMode, Filter, FilterPrimitive};
-use vello_common::kurbo::{Affine, BezPath, Circle, Point, Rect, Shape, Stroke};
+use vello_common::filter_effects::{
+    ColorChannel, CompositeOperator, ConvolutionKernel, EdgeMode, Filter, FilterFunction,
+    FilterGraph, FilterInput, FilterInputs, FilterPrimitive, FilterSource, LightSource,
+    MorphologyOperator, TransferFunction, TurbulenceType, kernels, matrices,
+};
+use vello_common::kurbo::{Affine, BezPath, Circle, Point, Rect, RoundedRect, Shape, Stroke};
 use vello_common::peniko::{BlendMode, Compose, Mix};
 use vello_cpu::color::palette::css::{BLUE, GREEN, RED};
 use vello_cpu::kurbo::Dashes;
@@ -19,7 +23,7 @@ use vello_dev_macros::vello_test;
 // TODO: We are purposefully using multiple of WideTile width/height here, because the implementation
 // currently works incorrectly if it's not the case. Once the issue as been fixed, we should update
 // this test to use normal dimensions.
-#[vello_test(skip_hybrid, skip_multithreaded, width = 256, height = 40)]
+#[vello_test(width = 256, height = 40)]
 fn filter_flood(ctx: &mut impl Renderer) {
     let filter_flood = Filter::from_primitive(FilterPrimitive::Flood { color: TOMATO });
 
@@ -33,7 +37,7 @@ fn filter_flood(ctx: &mut impl Renderer) {
 ///
 /// Note: SVG-compliant flood would use `feComposite` with `operator="in"`, which requires
 /// implementing the composite primitive and filter subregions.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_flood_star(ctx: &mut impl Renderer) {
     let filter_flood = Filter::from_primitive(FilterPrimitive::Flood { color: TOMATO });
     let star_path = circular_star(Point::new(50.0, 50.0), 5, 20.0, 40.0);
@@ -52,7 +56,7 @@ fn filter_flood_star(ctx: &mut impl Renderer) {
 
 /// Test Gaussian blur with small radius (`std_deviation` = 2.0, no decimation).
 /// Uses direct separable convolution at full resolution.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_gaussian_blur_no_decimation(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 2.0,
@@ -68,7 +72,7 @@ fn filter_gaussian_blur_no_decimation(ctx: &mut impl Renderer) {
 
 /// Test Gaussian blur with larger radius (`std_deviation` = 4.0, uses decimation).
 /// Uses multi-scale downsampling for performance.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_gaussian_blur_with_decimation(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 4.0,
@@ -84,7 +88,7 @@ fn filter_gaussian_blur_with_decimation(ctx: &mut impl Renderer) {
 
 /// Test drop shadow filter on text glyph.
 /// Creates a blurred, offset shadow beneath the original graphic.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_drop_shadow(ctx: &mut impl Renderer) {
     let font_size: f32 = 80_f32;
     let (font, glyphs) = layout_glyphs_roboto("A", font_size);
@@ -108,7 +112,7 @@ fn filter_drop_shadow(ctx: &mut impl Renderer) {
 
 /// Test drop shadow on a simple rectangle.
 /// Verifies the offset pixel optimization works correctly with different offsets.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_drop_shadow_corners(ctx: &mut impl Renderer) {
     // Layout parameters
     let margin = 8.0;
@@ -236,7 +240,7 @@ fn filter_drop_shadow_corners(ctx: &mut impl Renderer) {
 
 /// Test `set_filter_effect` and `reset_filter_effect` API.
 /// Applies filters to individual draw calls without creating layers.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_set_effect(ctx: &mut impl Renderer) {
     let filter_drop_shadow = Filter::from_primitive(FilterPrimitive::DropShadow {
         dx: 2.0,
@@ -289,7 +293,7 @@ fn filter_set_effect(ctx: &mut impl Renderer) {
 
 /// Test filter interactions with layers, clips, blend modes, and opacity.
 /// 9 scenarios testing filters at various depths, with clips, opacity, blend modes, etc.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test(hybrid_tolerance = 2)]
 fn filter_varying_depths_clips_and_compositions(ctx: &mut impl Renderer) {
     let filter_drop_shadow = Filter::from_primitive(FilterPrimitive::DropShadow {
         dx: 2.0,
@@ -764,7 +768,7 @@ fn filter_varying_depths_clips_and_compositions(ctx: &mut impl Renderer) {
 /// This verifies that the expansion calculation uses `transform_rect_bbox` to account for
 /// the full transformation matrix (including rotation and shear), rather than just extracting
 /// x/y scales separately. A 45-degree rotation should produce a diamond-shaped blur.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_rotated_blur(ctx: &mut impl Renderer) {
     let filter_gaussian_blur = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 4.0,
@@ -830,7 +834,7 @@ fn filter_rotated_blur(ctx: &mut impl Renderer) {
 }
 
 /// Test that zero blur acts as identity (no-op).
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_gaussian_blur_zero(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 0.0,
@@ -845,7 +849,7 @@ fn filter_gaussian_blur_zero(ctx: &mut impl Renderer) {
 }
 
 /// Test drop shadow with sub-pixel offsets.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_drop_shadow_fractional_offset(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::DropShadow {
         dx: 2.5,
@@ -863,7 +867,7 @@ fn filter_drop_shadow_fractional_offset(ctx: &mut impl Renderer) {
 }
 
 /// Test drop shadow with zero offset (shadow directly behind).
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_drop_shadow_zero_offset(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::DropShadow {
         dx: 0.0,
@@ -883,7 +887,7 @@ fn filter_drop_shadow_zero_offset(ctx: &mut impl Renderer) {
 /// Test offset filter primitive.
 ///
 /// This shifts content within a filter layer and should not clip content to the original bounds.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_offset(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::Offset {
         dx: 18.0,
@@ -918,7 +922,7 @@ fn filter_offset(ctx: &mut impl Renderer) {
 }
 
 /// Test blur with various transforms (translate, rotate, scale, skew).
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_transformed_blur(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 3.0,
@@ -940,7 +944,7 @@ fn filter_transformed_blur(ctx: &mut impl Renderer) {
 }
 
 /// Test filter layer with no content drawn.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_empty_layers(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 4.0,
@@ -957,7 +961,7 @@ fn filter_empty_layers(ctx: &mut impl Renderer) {
 }
 
 /// Test nested filter layers (blur inside drop shadow).
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_nested_layers(ctx: &mut impl Renderer) {
     let blur = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 2.0,
@@ -980,7 +984,7 @@ fn filter_nested_layers(ctx: &mut impl Renderer) {
 }
 
 /// Test blur with very large `std_deviation`.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_extreme_blur(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 20.0,
@@ -995,7 +999,7 @@ fn filter_extreme_blur(ctx: &mut impl Renderer) {
 }
 
 /// Test filter on semi-transparent shapes.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_transparent_shapes(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 3.0,
@@ -1018,7 +1022,7 @@ fn filter_transparent_shapes(ctx: &mut impl Renderer) {
 }
 
 /// Test filter on stroked paths.
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test]
 fn filter_stroked_paths(ctx: &mut impl Renderer) {
     use vello_common::kurbo::{Cap, Join, Stroke};
 
@@ -1051,7 +1055,7 @@ fn filter_stroked_paths(ctx: &mut impl Renderer) {
 /// TODO: This test currently demonstrates a bug where filters render incorrectly
 /// when filtered elements are near or extend beyond viewport boundaries.
 /// See: <https://github.com/linebender/vello/issues/1304>
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test(skip_hybrid)]
 fn issue_filter_canvas_boundaries(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
         std_deviation: 5.0,
@@ -1109,17 +1113,691 @@ pub(crate) fn blur_with_edge_mode(ctx: &mut impl Renderer, edge_mode: EdgeMode)
 // because edge modes currently don't handle other widths/heights correctly. Once that is
 // fixed, we should change the tests back to 100x100 to exercise that path as well.
 
-#[vello_test(skip_hybrid, skip_multithreaded, width = 256, height = 100)]
+#[vello_test(width = 256, height = 100)]
 fn filter_gaussian_blur_edge_mode_duplicate(ctx: &mut impl Renderer) {
     blur_with_edge_mode(ctx, EdgeMode::Duplicate);
 }
 
-#[vello_test(skip_hybrid, skip_multithreaded, width = 256, height = 100)]
+// `vello_cpu` blurs in place, so wrapped samples at the right edge read pixels that have
+// already been overwritten. `vello_hybrid` doesn't replicate this.
+#[vello_test(width = 256, height = 100, skip_hybrid)]
 fn filter_gaussian_blur_edge_mode_wrap(ctx: &mut impl Renderer) {
     blur_with_edge_mode(ctx, EdgeMode::Wrap);
 }
 
-#[vello_test(skip_hybrid, skip_multithreaded, width = 256, height = 100)]
+#[vello_test(width = 256, height = 100)]
 fn filter_gaussian_blur_edge_mode_mirror(ctx: &mut impl Renderer) {
     blur_with_edge_mode(ctx, EdgeMode::Mirror);
 }
+
+/// Draw a few differently colored shapes through the given CSS filter function.
+pub(crate) fn filter_function_scene(ctx: &mut impl Renderer, function: FilterFunction) {
+    color_filter_scene(ctx, Filter::from_function(function));
+}
+
+/// Draw a few differently colored shapes through the given filter.
+pub(crate) fn color_filter_scene(ctx: &mut impl Renderer, filter: Filter) {
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(RED);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 40.0, 50.0));
+    ctx.set_paint(GREEN);
+    ctx.fill_rect(&Rect::new(35.0, 10.0, 65.0, 50.0));
+    ctx.set_paint(BLUE);
+    ctx.fill_rect(&Rect::new(60.0, 10.0, 90.0, 50.0));
+    ctx.set_paint(TOMATO.with_alpha(0.6));
+    ctx.fill_path(&Circle::new((35.0, 65.0), 25.0).to_path(0.1));
+    ctx.set_paint(ROYAL_BLUE.with_alpha(0.8));
+    ctx.fill_path(&Circle::new((65.0, 65.0), 25.0).to_path(0.1));
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 1)]
+fn filter_function_brightness(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Brightness { amount: 1.6 });
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_function_contrast(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Contrast { amount: 0.4 });
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_function_grayscale(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Grayscale { amount: 1.0 });
+}
+
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 1)]
+fn filter_function_hue_rotate(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::HueRotate { angle: 120.0 });
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_function_invert(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Invert { amount: 1.0 });
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_function_opacity(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Opacity { amount: 0.5 });
+}
+
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 3)]
+fn filter_function_saturate(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Saturate { amount: 3.0 });
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_function_sepia(ctx: &mut impl Renderer) {
+    filter_function_scene(ctx, FilterFunction::Sepia { amount: 1.0 });
+}
+
+/// Test CSS filter functions applied to individual draw calls via `set_filter_effect`.
+#[vello_test(skip_hybrid)]
+fn filter_function_set_effect(ctx: &mut impl Renderer) {
+    ctx.set_filter_effect(Filter::from_function(FilterFunction::Grayscale {
+        amount: 0.5,
+    }));
+    ctx.set_paint(TOMATO);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 50.0, 50.0));
+
+    ctx.set_filter_effect(Filter::from_function(FilterFunction::Invert {
+        amount: 0.8,
+    }));
+    ctx.set_paint(SEA_GREEN);
+    ctx.fill_rect(&Rect::new(50.0, 10.0, 90.0, 50.0));
+
+    ctx.set_filter_effect(Filter::from_function(FilterFunction::Opacity {
+        amount: 0.5,
+    }));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.fill_rect(&Rect::new(30.0, 30.0, 70.0, 70.0));
+    ctx.reset_filter_effect();
+
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_rect(&Rect::new(10.0, 70.0, 90.0, 90.0));
+}
+
+/// Test an SVG-style drop shadow built from multiple primitives: the blurred and offset
+/// `SourceAlpha` is flooded with a color and composited below the `SourceGraphic`.
+#[vello_test(skip_hybrid)]
+fn filter_graph_drop_shadow(ctx: &mut impl Renderer) {
+    let mut graph = FilterGraph::new();
+    graph.add(
+        FilterPrimitive::GaussianBlur {
+            std_deviation: 3.0,
+            edge_mode: EdgeMode::None,
+        },
+        Some(FilterInputs::single(FilterInput::Source(
+            FilterSource::SourceAlpha,
+        ))),
+    );
+    let offset = graph.add(FilterPrimitive::Offset { dx: 6.0, dy: 6.0 }, None);
+    let flood = graph.add(
+        FilterPrimitive::Flood {
+            color: REBECCA_PURPLE.with_alpha(0.7),
+        },
+        None,
+    );
+    let shadow = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::In,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Result(flood),
+            FilterInput::Result(offset),
+        )),
+    );
+    let merged = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::Over,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Source(FilterSource::SourceGraphic),
+            FilterInput::Result(shadow),
+        )),
+    );
+    graph.set_output(merged);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(TOMATO);
+    ctx.fill_path(&circular_star(Point::new(45.0, 45.0), 5, 15.0, 35.0));
+    ctx.pop_layer();
+}
+
+/// Test the SVG-compliant version of `filter_flood_star`, which clips the flood to the
+/// `SourceAlpha` with `feComposite`.
+#[vello_test(skip_hybrid)]
+fn filter_graph_flood_in_source_alpha(ctx: &mut impl Renderer) {
+    let mut graph = FilterGraph::new();
+    let flood = graph.add(FilterPrimitive::Flood { color: TOMATO }, None);
+    let composite = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::In,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Result(flood),
+            FilterInput::Source(FilterSource::SourceAlpha),
+        )),
+    );
+    graph.set_output(composite);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(REBECCA_PURPLE.with_alpha(0.5));
+    ctx.fill_path(&circular_star(Point::new(50.0, 50.0), 5, 20.0, 40.0));
+    ctx.pop_layer();
+}
+
+/// Test the Porter-Duff operators of `feComposite`, combining an offset copy of the
+/// source graphic with the source graphic itself.
+fn composite_operator_scene(ctx: &mut impl Renderer, operator: CompositeOperator) {
+    let mut graph = FilterGraph::new();
+    let offset = graph.add(FilterPrimitive::Offset { dx: 20.0, dy: 20.0 }, None);
+    let composite = graph.add(
+        FilterPrimitive::Composite { operator },
+        Some(FilterInputs::dual(
+            FilterInput::Result(offset),
+            FilterInput::Source(FilterSource::SourceGraphic),
+        )),
+    );
+    graph.set_output(composite);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(ROYAL_BLUE.with_alpha(0.8));
+    ctx.fill_rect(&Rect::new(15.0, 15.0, 65.0, 65.0));
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_over(ctx: &mut impl Renderer) {
+    composite_operator_scene(ctx, CompositeOperator::Over);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_in(ctx: &mut impl Renderer) {
+    composite_operator_scene(ctx, CompositeOperator::In);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_out(ctx: &mut impl Renderer) {
+    composite_operator_scene(ctx, CompositeOperator::Out);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_atop(ctx: &mut impl Renderer) {
+    composite_operator_scene(ctx, CompositeOperator::Atop);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_xor(ctx: &mut impl Renderer) {
+    composite_operator_scene(ctx, CompositeOperator::Xor);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_graph_composite_arithmetic(ctx: &mut impl Renderer) {
+    composite_operator_scene(
+        ctx,
+        CompositeOperator::Arithmetic {
+            k1: 0.5,
+            k2: 0.5,
+            k3: 0.5,
+            k4: 0.0,
+        },
+    );
+}
+
+/// Test `feColorMatrix` with a custom matrix that swaps the red and blue channels.
+#[vello_test(skip_hybrid)]
+fn filter_color_matrix_swap_channels(ctx: &mut impl Renderer) {
+    #[rustfmt::skip]
+    let matrix = [
+        0.0, 0.0, 1.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0, 0.0,
+        1.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ];
+    color_filter_scene(
+        ctx,
+        Filter::from_primitive(FilterPrimitive::ColorMatrix { matrix }),
+    );
+}
+
+/// Test `feColorMatrix type="luminanceToAlpha"`.
+///
+/// The shapes are drawn on an opaque background, since the alpha of anti-aliased edges
+/// is replaced by the luminance of their (imprecise) unpremultiplied color otherwise.
+#[vello_test(skip_hybrid)]
+fn filter_color_matrix_luminance_to_alpha(ctx: &mut impl Renderer) {
+    let filter = Filter::from_primitive(FilterPrimitive::ColorMatrix {
+        matrix: matrices::LUMINANCE_TO_ALPHA,
+    });
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(SEA_GREEN);
+    ctx.fill_rect(&Rect::new(0.0, 0.0, 100.0, 100.0));
+    ctx.set_paint(RED);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 40.0, 50.0));
+    ctx.set_paint(GREEN);
+    ctx.fill_rect(&Rect::new(35.0, 10.0, 65.0, 50.0));
+    ctx.set_paint(BLUE);
+    ctx.fill_rect(&Rect::new(60.0, 10.0, 90.0, 50.0));
+    ctx.set_paint(VIOLET);
+    ctx.fill_path(&Circle::new((50.0, 70.0), 20.0).to_path(0.1));
+    ctx.pop_layer();
+}
+
+/// Test `feComponentTransfer` with a different transfer function type for each channel.
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 5)]
+fn filter_component_transfer(ctx: &mut impl Renderer) {
+    color_filter_scene(
+        ctx,
+        Filter::from_primitive(FilterPrimitive::ComponentTransfer {
+            red_function: Some(TransferFunction::Table {
+                values: vec![0.2, 1.0, 0.4],
+            }),
+            green_function: Some(TransferFunction::Gamma {
+                amplitude: 0.8,
+                exponent: 0.5,
+                offset: 0.1,
+            }),
+            blue_function: Some(TransferFunction::Linear {
+                slope: 0.5,
+                intercept: 0.25,
+            }),
+            alpha_function: None,
+        }),
+    );
+}
+
+/// Test `feComponentTransfer` posterizing all channels with discrete functions.
+#[vello_test(skip_hybrid, diff_pixels = 3)]
+fn filter_component_transfer_discrete(ctx: &mut impl Renderer) {
+    let posterize = TransferFunction::Discrete {
+        values: vec![0.0, 0.5, 1.0],
+    };
+    color_filter_scene(
+        ctx,
+        Filter::from_primitive(FilterPrimitive::ComponentTransfer {
+            red_function: Some(posterize.clone()),
+            green_function: Some(posterize.clone()),
+            blue_function: Some(posterize.clone()),
+            alpha_function: Some(posterize),
+        }),
+    );
+}
+
+/// Draw a star and a stroked ring through the given morphology operator.
+fn morphology_scene(ctx: &mut impl Renderer, operator: MorphologyOperator, radius: f32) {
+    let filter = Filter::from_primitive(FilterPrimitive::Morphology { operator, radius });
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_path(&circular_star(Point::new(35.0, 50.0), 5, 12.0, 25.0));
+    ctx.set_paint(TOMATO.with_alpha(0.8));
+    ctx.set_stroke(Stroke::new(6.0));
+    ctx.stroke_path(&Circle::new((72.0, 50.0), 15.0).to_path(0.1));
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_morphology_dilate(ctx: &mut impl Renderer) {
+    morphology_scene(ctx, MorphologyOperator::Dilate, 3.0);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_morphology_erode(ctx: &mut impl Renderer) {
+    morphology_scene(ctx, MorphologyOperator::Erode, 2.0);
+}
+
+/// The radius is specified in user space, so it scales with the transform.
+#[vello_test(skip_hybrid)]
+fn filter_morphology_dilate_scaled(ctx: &mut impl Renderer) {
+    ctx.set_transform(Affine::scale(0.5));
+    morphology_scene(ctx, MorphologyOperator::Dilate, 6.0);
+}
+
+/// Dilating content close to the edge of the layer must not be clipped, because the
+/// layer bounds are expanded by the radius.
+#[vello_test(skip_hybrid)]
+fn filter_morphology_dilate_bounds(ctx: &mut impl Renderer) {
+    let filter = Filter::from_primitive(FilterPrimitive::Morphology {
+        operator: MorphologyOperator::Dilate,
+        radius: 8.0,
+    });
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(SEA_GREEN);
+    ctx.fill_rect(&Rect::new(30.0, 30.0, 70.0, 70.0));
+    ctx.pop_layer();
+}
+
+/// Draw text and shapes through the given convolution kernel.
+///
+/// The content is drawn on an opaque background, since kernels that preserve alpha operate
+/// on unpremultiplied colors, which are very imprecise for anti-aliased edges otherwise.
+fn convolve_matrix_scene(ctx: &mut impl Renderer, kernel: ConvolutionKernel) {
+    let font_size: f32 = 48_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Ag", font_size);
+    let filter = Filter::from_primitive(FilterPrimitive::ConvolveMatrix { kernel });
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(AlphaColor::from_rgb8(240, 240, 220));
+    ctx.fill_rect(&Rect::new(0.0, 0.0, 100.0, 100.0));
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_rect(&Rect::new(10.0, 60.0, 50.0, 90.0));
+    ctx.set_paint(TOMATO);
+    ctx.fill_path(&Circle::new((70.0, 75.0), 15.0).to_path(0.1));
+    ctx.set_transform(Affine::translate((12.0, 48.0)));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .fill_glyphs(glyphs.into_iter());
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_convolve_matrix_gaussian(ctx: &mut impl Renderer) {
+    convolve_matrix_scene(ctx, kernels::gaussian_3x3());
+}
+
+// Differences in anti-aliasing between the u8 and f32 pipelines are amplified by the kernel.
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 4)]
+fn filter_convolve_matrix_sharpen(ctx: &mut impl Renderer) {
+    convolve_matrix_scene(ctx, kernels::sharpen_3x3());
+}
+
+// Differences in anti-aliasing between the u8 and f32 pipelines are amplified by the kernel.
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 7)]
+fn filter_convolve_matrix_edge_detect(ctx: &mut impl Renderer) {
+    convolve_matrix_scene(ctx, kernels::edge_detect_3x3());
+}
+
+// Differences in anti-aliasing between the u8 and f32 pipelines are amplified by the kernel.
+#[vello_test(skip_hybrid, cpu_u8_tolerance = 2)]
+fn filter_convolve_matrix_emboss(ctx: &mut impl Renderer) {
+    convolve_matrix_scene(ctx, kernels::emboss_3x3());
+}
+
+/// Test a non-symmetric 5x5 kernel that smears the input to the bottom right,
+/// with each edge mode.
+fn convolve_matrix_motion_blur(ctx: &mut impl Renderer, edge_mode: EdgeMode) {
+    let mut values = vec![0.0; 25];
+    for i in 0..5 {
+        values[i * 6] = 1.0;
+    }
+    let kernel = ConvolutionKernel {
+        size: 5,
+        values,
+        divisor: 5.0,
+        bias: 0.0,
+        preserve_alpha: false,
+        edge_mode,
+    };
+    let filter = Filter::from_primitive(FilterPrimitive::ConvolveMatrix { kernel });
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_rect(&Rect::new(0.0, 0.0, 30.0, 30.0));
+    ctx.set_paint(TOMATO);
+    ctx.fill_rect(&Rect::new(40.0, 40.0, 60.0, 60.0));
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_convolve_matrix_edge_mode_none(ctx: &mut impl Renderer) {
+    convolve_matrix_motion_blur(ctx, EdgeMode::None);
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_convolve_matrix_edge_mode_duplicate(ctx: &mut impl Renderer) {
+    convolve_matrix_motion_blur(ctx, EdgeMode::Duplicate);
+}
+
+/// Fill the canvas with noise generated by `feTurbulence`.
+fn turbulence_scene(ctx: &mut impl Renderer, primitive: FilterPrimitive) {
+    let filter = Filter::from_primitive(primitive);
+
+    ctx.push_filter_layer(filter);
+    ctx.set_paint(BLACK);
+    ctx.fill_rect(&Rect::new(0.0, 0.0, 100.0, 100.0));
+    ctx.pop_layer();
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_turbulence_fractal_noise(ctx: &mut impl Renderer) {
+    turbulence_scene(
+        ctx,
+        FilterPrimitive::Turbulence {
+            base_frequency: 0.05,
+            num_octaves: 2,
+            seed: 0,
+            turbulence_type: TurbulenceType::FractalNoise,
+        },
+    );
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_turbulence_turbulence(ctx: &mut impl Renderer) {
+    turbulence_scene(
+        ctx,
+        FilterPrimitive::Turbulence {
+            base_frequency: 0.03,
+            num_octaves: 4,
+            seed: 42,
+            turbulence_type: TurbulenceType::Turbulence,
+        },
+    );
+}
+
+/// The noise is defined in user space, so it is scaled and moved along with the content.
+#[vello_test(skip_hybrid)]
+fn filter_turbulence_transformed(ctx: &mut impl Renderer) {
+    ctx.set_transform(Affine::translate((10.0, 10.0)) * Affine::scale(2.0));
+    turbulence_scene(
+        ctx,
+        FilterPrimitive::Turbulence {
+            base_frequency: 0.05,
+            num_octaves: 2,
+            seed: 0,
+            turbulence_type: TurbulenceType::FractalNoise,
+        },
+    );
+}
+
+/// Test `feDisplacementMap`, distorting the source graphic with turbulence as the map.
+#[vello_test(skip_hybrid)]
+fn filter_displacement_map(ctx: &mut impl Renderer) {
+    let mut graph = FilterGraph::new();
+    let noise = graph.add(
+        FilterPrimitive::Turbulence {
+            base_frequency: 0.05,
+            num_octaves: 2,
+            seed: 3,
+            turbulence_type: TurbulenceType::Turbulence,
+        },
+        None,
+    );
+    let displaced = graph.add(
+        FilterPrimitive::DisplacementMap {
+            scale: 20.0,
+            x_channel: ColorChannel::Red,
+            y_channel: ColorChannel::Green,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Source(FilterSource::SourceGraphic),
+            FilterInput::Result(noise),
+        )),
+    );
+    graph.set_output(displaced);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_rect(&Rect::new(20.0, 20.0, 80.0, 80.0));
+    ctx.set_paint(TOMATO);
+    ctx.fill_path(&Circle::new((50.0, 50.0), 20.0).to_path(0.1));
+    ctx.pop_layer();
+}
+
+/// Light the blurred alpha of a few shapes, keeping the result inside of the shapes.
+fn lighting_scene(ctx: &mut impl Renderer, lighting: FilterPrimitive) {
+    let mut graph = FilterGraph::new();
+    graph.add(
+        FilterPrimitive::GaussianBlur {
+            std_deviation: 3.0,
+            edge_mode: EdgeMode::None,
+        },
+        Some(FilterInputs::single(FilterInput::Source(
+            FilterSource::SourceAlpha,
+        ))),
+    );
+    let lit = graph.add(lighting, None);
+    let clipped = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::In,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Result(lit),
+            FilterInput::Source(FilterSource::SourceAlpha),
+        )),
+    );
+    graph.set_output(clipped);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(ROYAL_BLUE);
+    ctx.fill_path(&RoundedRect::new(10.0, 10.0, 90.0, 45.0, 10.0).to_path(0.1));
+    ctx.fill_path(&Circle::new((50.0, 70.0), 22.0).to_path(0.1));
+    ctx.pop_layer();
+}
+
+fn diffuse_lighting(light_source: LightSource) -> FilterPrimitive {
+    FilterPrimitive::DiffuseLighting {
+        surface_scale: 5.0,
+        diffuse_constant: 1.0,
+        kernel_unit_length: 1.0,
+        light_source,
+        lighting_color: AlphaColor::from_rgb8(255, 240, 200),
+    }
+}
+
+fn specular_lighting(light_source: LightSource) -> FilterPrimitive {
+    FilterPrimitive::SpecularLighting {
+        surface_scale: 5.0,
+        specular_constant: 1.0,
+        specular_exponent: 20.0,
+        kernel_unit_length: 1.0,
+        light_source,
+        lighting_color: AlphaColor::from_rgb8(255, 240, 200),
+    }
+}
+
+const DISTANT_LIGHT: LightSource = LightSource::Distant {
+    azimuth: 225.0,
+    elevation: 45.0,
+};
+
+const POINT_LIGHT: LightSource = LightSource::Point {
+    x: 20.0,
+    y: 20.0,
+    z: 40.0,
+};
+
+const SPOT_LIGHT: LightSource = LightSource::Spot {
+    x: 0.0,
+    y: 0.0,
+    z: 60.0,
+    points_at_x: 60.0,
+    points_at_y: 60.0,
+    points_at_z: 0.0,
+    specular_exponent: 2.0,
+    limiting_cone_angle: Some(30.0),
+};
+
+#[vello_test(skip_hybrid)]
+fn filter_diffuse_lighting_distant(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, diffuse_lighting(DISTANT_LIGHT));
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_diffuse_lighting_point(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, diffuse_lighting(POINT_LIGHT));
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_diffuse_lighting_spot(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, diffuse_lighting(SPOT_LIGHT));
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_specular_lighting_distant(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, specular_lighting(DISTANT_LIGHT));
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_specular_lighting_point(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, specular_lighting(POINT_LIGHT));
+}
+
+#[vello_test(skip_hybrid)]
+fn filter_specular_lighting_spot(ctx: &mut impl Renderer) {
+    lighting_scene(ctx, specular_lighting(SPOT_LIGHT));
+}
+
+/// Test a bevelled button as commonly exported by design tools: specular highlights
+/// computed from the blurred alpha are added on top of the source graphic.
+#[vello_test(skip_hybrid)]
+fn filter_lighting_bevel(ctx: &mut impl Renderer) {
+    let mut graph = FilterGraph::new();
+    graph.add(
+        FilterPrimitive::GaussianBlur {
+            std_deviation: 4.0,
+            edge_mode: EdgeMode::None,
+        },
+        Some(FilterInputs::single(FilterInput::Source(
+            FilterSource::SourceAlpha,
+        ))),
+    );
+    let highlight = graph.add(
+        FilterPrimitive::SpecularLighting {
+            surface_scale: 5.0,
+            specular_constant: 0.75,
+            specular_exponent: 20.0,
+            kernel_unit_length: 1.0,
+            light_source: LightSource::Point {
+                x: -50.0,
+                y: -100.0,
+                z: 200.0,
+            },
+            lighting_color: AlphaColor::from_rgb8(187, 187, 187),
+        },
+        None,
+    );
+    let clipped = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::In,
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Result(highlight),
+            FilterInput::Source(FilterSource::SourceAlpha),
+        )),
+    );
+    let lit = graph.add(
+        FilterPrimitive::Composite {
+            operator: CompositeOperator::Arithmetic {
+                k1: 0.0,
+                k2: 1.0,
+                k3: 1.0,
+                k4: 0.0,
+            },
+        },
+        Some(FilterInputs::dual(
+            FilterInput::Source(FilterSource::SourceGraphic),
+            FilterInput::Result(clipped),
+        )),
+    );
+    graph.set_output(lit);
+
+    ctx.push_filter_layer(Filter::from_graph(graph));
+    ctx.set_paint(SEA_GREEN);
+    ctx.fill_path(&RoundedRect::new(15.0, 30.0, 85.0, 70.0, 12.0).to_path(0.1));
+    ctx.pop_layer();
+}
@@ -9,11 +9,16 @@ use crate::util::layout_glyphs_apple_color_emoji;
 use crate::util::{layout_glyphs_noto_cbtf, layout_glyphs_noto_colr, layout_glyphs_roboto};
 use std::iter;
 use std::sync::Arc;
-use vello_common::color::palette::css::{BLACK, BLUE, GREEN, REBECCA_PURPLE};
-use vello_common::glyph::Glyph;
-use vello_common::kurbo::Affine;
+use vello_common::color::palette::css::{BLACK, BLUE, GREEN, REBECCA_PURPLE, WHITE};
+use vello_common::glyph::{
+    Glyph, GlyphCacheBudget, HintingEngine, HintingTarget, SmoothHintingMode,
+};
+use vello_common::kurbo::{Affine, Rect};
 use vello_common::peniko::{Blob, FontData};
+use vello_common::subpixel::SubpixelOrder;
+use vello_cpu::RenderContext;
 use vello_dev_macros::vello_test;
+use vello_hybrid::Scene;
 
 #[vello_test(width = 300, height = 70)]
 fn glyphs_filled(ctx: &mut impl Renderer) {
@@ -243,7 +248,147 @@ fn glyphs_small_unhinted(ctx: &mut impl Renderer) {
         .fill_glyphs(glyphs.into_iter());
 }
 
-#[vello_test(width = 250, height = 70, skip_hybrid)]
+#[vello_test(width = 60, height = 12)]
+fn glyphs_small_hinting_mono(ctx: &mut impl Renderer) {
+    let font_size: f32 = 10_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .hinting_target(HintingTarget::Mono)
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 60, height = 12)]
+fn glyphs_small_hinting_auto(ctx: &mut impl Renderer) {
+    let font_size: f32 = 10_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .hinting_engine(HintingEngine::Auto)
+        .hinting_target(HintingTarget::Smooth {
+            mode: SmoothHintingMode::Normal,
+            symmetric_rendering: true,
+            preserve_linear_metrics: false,
+        })
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 60, height = 24)]
+fn glyphs_small_hinting_mixed(ctx: &mut impl Renderer) {
+    let font_size: f32 = 10_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .fill_glyphs(glyphs.clone().into_iter());
+
+    ctx.set_transform(Affine::translate((0., 2. * f64::from(font_size) + 2.)));
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .hinting_target(HintingTarget::Mono)
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 300, height = 70)]
+fn glyphs_embolden(ctx: &mut impl Renderer) {
+    let font_size: f32 = 50_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(REBECCA_PURPLE.with_alpha(0.5));
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(false)
+        .embolden(0.03)
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 300, height = 70)]
+fn glyphs_skew(ctx: &mut impl Renderer) {
+    let font_size: f32 = 50_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(REBECCA_PURPLE.with_alpha(0.5));
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(false)
+        .skew(14.0)
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 60, height = 36)]
+fn glyphs_small_embolden_skew(ctx: &mut impl Renderer) {
+    let font_size: f32 = 10_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    // Draw the regular glyphs in between, to make sure the synthesized outlines are cached
+    // separately.
+    for (i, (embolden, skew)) in [(0.02, 0.), (0., 0.), (0.02, 14.)].into_iter().enumerate() {
+        ctx.set_transform(Affine::translate((
+            0.,
+            f64::from(font_size) * (i as f64 + 1.) + 1.,
+        )));
+        ctx.set_paint(BLACK);
+        ctx.glyph_run(&font)
+            .font_size(font_size)
+            .hint(true)
+            .embolden(embolden)
+            .skew(skew)
+            .fill_glyphs(glyphs.clone().into_iter());
+    }
+}
+
+fn draw_subpixel_glyphs(ctx: &mut impl Renderer, subpixel_order: SubpixelOrder) {
+    let font_size: f32 = 10_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello, world!", font_size);
+
+    ctx.set_paint(WHITE);
+    ctx.fill_rect(&Rect::new(
+        0.0,
+        0.0,
+        f64::from(ctx.width()),
+        f64::from(ctx.height()),
+    ));
+
+    ctx.set_subpixel_order(Some(subpixel_order));
+    ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+    ctx.set_paint(BLACK);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .hint(true)
+        .fill_glyphs(glyphs.clone().into_iter());
+
+    ctx.set_transform(Affine::translate((0.5, 2. * f64::from(font_size) + 2.)));
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .fill_glyphs(glyphs.into_iter());
+}
+
+#[vello_test(width = 60, height = 24, skip_hybrid)]
+fn glyphs_subpixel_rgb(ctx: &mut impl Renderer) {
+    draw_subpixel_glyphs(ctx, SubpixelOrder::Rgb);
+}
+
+#[vello_test(width = 60, height = 24, skip_hybrid)]
+fn glyphs_subpixel_bgr(ctx: &mut impl Renderer) {
+    draw_subpixel_glyphs(ctx, SubpixelOrder::Bgr);
+}
+
+#[vello_test(width = 250, height = 70, hybrid_tolerance = 1)]
 fn glyphs_bitmap_noto(ctx: &mut impl Renderer) {
     let font_size: f32 = 50_f32;
     let (font, glyphs) = layout_glyphs_noto_cbtf("✅👀🎉🤠", font_size);
@@ -254,22 +399,28 @@ fn glyphs_bitmap_noto(ctx: &mut impl Renderer) {
         .fill_glyphs(glyphs.into_iter());
 }
 
-#[vello_test(width = 250, height = 70, skip_hybrid, cpu_u8_tolerance = 1)]
+#[vello_test(width = 250, height = 70, cpu_u8_tolerance = 1, hybrid_tolerance = 1)]
 fn glyphs_colr_noto(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(ctx, Affine::translate((0., 50.)));
 }
 
-#[vello_test(width = 500, height = 140, skip_hybrid, cpu_u8_tolerance = 1)]
+#[vello_test(width = 500, height = 140, cpu_u8_tolerance = 1, hybrid_tolerance = 1)]
 fn glyphs_colr_noto_scaled_2x(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(ctx, Affine::translate((0., 50.)).then_scale(2.0));
 }
 
-#[vello_test(width = 125, height = 35, skip_hybrid, cpu_u8_tolerance = 1)]
+#[vello_test(width = 125, height = 35, cpu_u8_tolerance = 1, hybrid_tolerance = 1)]
 fn glyphs_colr_noto_scaled_half(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(ctx, Affine::translate((0., 50.)).then_scale(0.5));
 }
 
-#[vello_test(width = 350, height = 350, skip_hybrid, cpu_u8_tolerance = 3)]
+#[vello_test(
+    width = 350,
+    height = 350,
+    cpu_u8_tolerance = 3,
+    hybrid_tolerance = 3,
+    diff_pixels = 100
+)]
 fn glyphs_colr_noto_rotated(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(
         ctx,
@@ -277,7 +428,13 @@ fn glyphs_colr_noto_rotated(ctx: &mut impl Renderer) {
     );
 }
 
-#[vello_test(width = 600, height = 600, skip_hybrid, cpu_u8_tolerance = 2)]
+#[vello_test(
+    width = 600,
+    height = 600,
+    cpu_u8_tolerance = 2,
+    hybrid_tolerance = 3,
+    diff_pixels = 100
+)]
 fn glyphs_colr_noto_rotated_scaled(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(
         ctx,
@@ -287,7 +444,7 @@ fn glyphs_colr_noto_rotated_scaled(ctx: &mut impl Renderer) {
     );
 }
 
-#[vello_test(width = 250, height = 140, skip_hybrid, cpu_u8_tolerance = 1)]
+#[vello_test(width = 250, height = 140, cpu_u8_tolerance = 1, hybrid_tolerance = 1)]
 fn glyphs_colr_noto_scaled_non_uniform(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(
         ctx,
@@ -295,7 +452,13 @@ fn glyphs_colr_noto_scaled_non_uniform(ctx: &mut impl Renderer) {
     );
 }
 
-#[vello_test(width = 300, height = 300, skip_hybrid, cpu_u8_tolerance = 2)]
+#[vello_test(
+    width = 300,
+    height = 300,
+    cpu_u8_tolerance = 2,
+    hybrid_tolerance = 3,
+    diff_pixels = 100
+)]
 fn glyphs_colr_noto_rotated_scaled_non_uniform(ctx: &mut impl Renderer) {
     render_colr_noto_with_transform(
         ctx,
@@ -306,7 +469,7 @@ fn glyphs_colr_noto_rotated_scaled_non_uniform(ctx: &mut impl Renderer) {
 }
 
 #[cfg(target_os = "macos")]
-#[vello_test(width = 200, height = 70, skip_hybrid, cpu_u8_tolerance = 2)]
+#[vello_test(width = 200, height = 70, cpu_u8_tolerance = 2)]
 fn glyphs_bitmap_apple(ctx: &mut impl Renderer) {
     let font_size: f32 = 50_f32;
     let (font, glyphs) = layout_glyphs_apple_color_emoji("✅👀🎉🤠", font_size);
@@ -317,7 +480,7 @@ fn glyphs_bitmap_apple(ctx: &mut impl Renderer) {
         .fill_glyphs(glyphs.into_iter());
 }
 
-#[vello_test(width = 400, height = 960, skip_hybrid, diff_pixels = 50)]
+#[vello_test(width = 400, height = 960, diff_pixels = 50, hybrid_tolerance = 1)]
 fn glyphs_colr_test_glyphs(ctx: &mut impl Renderer) {
     const TEST_FONT: &[u8] =
         include_bytes!("../../../examples/assets/colr_test_glyphs/test_glyphs-glyf_colr_1.ttf");
@@ -394,3 +557,118 @@ fn render_colr_noto_with_transform(ctx: &mut impl Renderer, transform: Affine) {
         .hint(false)
         .fill_glyphs(glyphs.into_iter());
 }
+
+#[test]
+fn glyph_cache_stats() {
+    let font_size: f32 = 20_f32;
+    // "Hello" consists of four distinct glyphs.
+    let (font, glyphs) = layout_glyphs_roboto("Hello", font_size);
+    let mut ctx = RenderContext::new(100, 30);
+
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .fill_glyphs(glyphs.clone().into_iter());
+
+    let stats = ctx.glyph_caches().stats();
+    assert_eq!(stats.outline_misses, 4);
+    assert_eq!(stats.outline_hits, 1);
+    assert_eq!(stats.outline_entries, 4);
+    assert!(stats.outline_bytes > 0);
+    assert_eq!(stats.hint_misses, 1);
+    assert_eq!(stats.hint_instances, 1);
+
+    ctx.glyph_run(&font)
+        .font_size(font_size)
+        .fill_glyphs(glyphs.into_iter());
+
+    let stats = ctx.glyph_caches().stats();
+    assert_eq!(stats.outline_misses, 4);
+    assert_eq!(stats.outline_hits, 6);
+    assert_eq!(stats.hint_hits, 1);
+
+    ctx.glyph_caches_mut().reset_stats();
+    let stats = ctx.glyph_caches().stats();
+    assert_eq!(stats.outline_hits, 0);
+    assert_eq!(stats.outline_entries, 4);
+}
+
+#[test]
+fn glyph_cache_budget() {
+    let font_size: f32 = 20_f32;
+    let (font, glyphs) = layout_glyphs_roboto("Hello", font_size);
+    let mut scene = Scene::new(100, 30);
+    scene.glyph_caches_mut().set_budget(GlyphCacheBudget {
+        max_outlines: Some(2),
+        ..GlyphCacheBudget::default()
+    });
+
+    scene
+        .glyph_run(&font)
+        .font_size(font_size)
+        .fill_glyphs(glyphs.into_iter());
+
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_entries, 2);
+    assert_eq!(stats.outline_evictions, 2);
+
+    // The two most recently used glyphs ("l" and "o") are still cached.
+    let (_, glyphs) = layout_glyphs_roboto("lo", font_size);
+    scene
+        .glyph_run(&font)
+        .font_size(font_size)
+        .fill_glyphs(glyphs.into_iter());
+
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_misses, 4);
+    assert_eq!(stats.outline_hits, 3);
+
+    // Shrinking the budget evicts entries immediately.
+    scene.glyph_caches_mut().set_budget(GlyphCacheBudget {
+        max_outline_bytes: Some(0),
+        ..GlyphCacheBudget::default()
+    });
+
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_entries, 0);
+    assert_eq!(stats.outline_bytes, 0);
+    assert_eq!(stats.outline_evictions, 4);
+}
+
+#[test]
+fn glyph_cache_budget_repeated_runs() {
+    let font_size: f32 = 20_f32;
+    let (font, _) = layout_glyphs_roboto("", font_size);
+    let mut scene = Scene::new(100, 30);
+    scene.glyph_caches_mut().set_budget(GlyphCacheBudget {
+        max_outlines: Some(2),
+        ..GlyphCacheBudget::default()
+    });
+    let fill_text = |scene: &mut Scene, text: &str| {
+        let (_, glyphs) = layout_glyphs_roboto(text, font_size);
+        scene
+            .glyph_run(&font)
+            .font_size(font_size)
+            .fill_glyphs(glyphs.into_iter());
+    };
+
+    // Use the same glyphs many times, so that their earlier uses are no longer relevant
+    // for eviction.
+    for _ in 0..300 {
+        fill_text(&mut scene, "lo");
+    }
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_misses, 2);
+    assert_eq!(stats.outline_hits, 598);
+
+    // "l" is the least recently used glyph, so it is evicted first.
+    fill_text(&mut scene, "H");
+    fill_text(&mut scene, "o");
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_evictions, 1);
+    assert_eq!(stats.outline_hits, 599);
+
+    fill_text(&mut scene, "l");
+    let stats = scene.glyph_caches().stats();
+    assert_eq!(stats.outline_misses, 4);
+    assert_eq!(stats.outline_evictions, 2);
+}
@@ -293,6 +293,52 @@ fn image_with_opacity(ctx: &mut impl Renderer) {
     ctx.pop_layer();
 }
 
+fn sampler_alpha(ctx: &mut impl Renderer, quality: ImageQuality, transform: Affine) {
+    ctx.set_paint(REBECCA_PURPLE);
+    ctx.fill_rect(&Rect::new(0.0, 0.0, 100.0, 100.0));
+
+    let rect = Rect::new(10.0, 10.0, 90.0, 90.0);
+    let image_source = rgb_img_10x10(ctx);
+
+    let image = Image {
+        image: image_source,
+        sampler: ImageSampler {
+            x_extend: Extend::Reflect,
+            y_extend: Extend::Reflect,
+            quality,
+            alpha: 0.5,
+        },
+    };
+
+    ctx.set_paint_transform(transform);
+    ctx.set_paint(image);
+    ctx.fill_rect(&rect);
+}
+
+#[vello_test]
+fn image_sampler_alpha(ctx: &mut impl Renderer) {
+    sampler_alpha(ctx, ImageQuality::Low, Affine::IDENTITY);
+}
+
+#[vello_test]
+fn image_sampler_alpha_bilinear_scale(ctx: &mut impl Renderer) {
+    sampler_alpha(ctx, ImageQuality::Medium, Affine::scale(5.0));
+}
+
+#[vello_test]
+fn image_sampler_alpha_bicubic_scale(ctx: &mut impl Renderer) {
+    sampler_alpha(ctx, ImageQuality::High, Affine::scale(5.0));
+}
+
+#[vello_test]
+fn image_sampler_alpha_with_tint(ctx: &mut impl Renderer) {
+    ctx.set_tint(Some(Tint {
+        color: Color::from_rgba8(0, 128, 255, 255),
+        mode: TintMode::Multiply,
+    }));
+    sampler_alpha(ctx, ImageQuality::Low, Affine::IDENTITY);
+}
+
 fn image_format(ctx: &mut impl Renderer, image_source: ImageSource) {
     let rect = Rect::new(10.0, 10.0, 90.0, 90.0);
 
@@ -465,7 +465,7 @@ fn large_dimensions(ctx: &mut impl Renderer) {
     ctx.fill_rect(&Rect::new(0.0, 0.0, u16::MAX as f64 + 10.0, 8.0));
 }
 
-#[vello_test(skip_multithreaded, skip_hybrid)]
+#[vello_test(skip_hybrid)]
 fn issue_1417(ctx: &mut impl Renderer) {
     let filter_drop_shadow = Filter::from_primitive(FilterPrimitive::Offset { dx: 0.0, dy: 0.0 });
 
@@ -513,7 +513,7 @@ fn issue_1417(ctx: &mut impl Renderer) {
     }
 }
 
-#[vello_test(skip_hybrid, skip_multithreaded)]
+#[vello_test(skip_hybrid)]
 fn issue_1421(ctx: &mut impl Renderer) {
     let filter_flood = Filter::from_primitive(FilterPrimitive::Flood { color: TOMATO });
     let rect = Rect::new(15.0, 15.0, 85.0, 85.0).to_path(0.1);
@@ -595,7 +595,7 @@ fn issue_1468(ctx: &mut impl Renderer) {
     ctx.fill_rect(&Rect::new(0.0, 0.0, 10.0, 10.0));
 }
 
-#[vello_test(width = 768, height = 4, skip_multithreaded, skip_hybrid)]
+#[vello_test(width = 768, height = 4, skip_hybrid)]
 fn issue_1477(ctx: &mut impl Renderer) {
     let filter = Filter::from_primitive(FilterPrimitive::Offset { dx: 0.0, dy: 0.0 });
     let rect = Rect::new(0.0, 0.0, 768.0, 4.0);
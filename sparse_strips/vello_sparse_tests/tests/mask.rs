@@ -1,13 +1,15 @@
 // Copyright 2025 the Vello Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::renderer::Renderer;
+use crate::renderer::{HybridRenderer, Renderer};
+use crate::util::render_pixmap;
 use smallvec::smallvec;
 use vello_common::color::DynamicColor;
-use vello_common::color::palette::css::{BLACK, LIME, RED, YELLOW};
-use vello_common::kurbo::{Point, Rect};
+use vello_common::color::palette::css::{BLACK, BLUE, LIME, RED, YELLOW};
+use vello_common::filter_effects::{EdgeMode, Filter, FilterPrimitive};
+use vello_common::kurbo::{Circle, Point, Rect, Shape};
 use vello_common::mask::Mask;
-use vello_common::peniko::{ColorStop, ColorStops, Gradient};
+use vello_common::peniko::{BlendMode, ColorStop, ColorStops, Compose, Gradient, Mix};
 use vello_cpu::peniko::LinearGradientPosition;
 use vello_cpu::{Level, RenderMode, RenderSettings};
 use vello_cpu::{Pixmap, RenderContext};
@@ -78,6 +80,67 @@ fn mask_luminance(ctx: &mut impl Renderer) {
     mask(ctx, false);
 }
 
+#[vello_test]
+fn mask_nested(ctx: &mut impl Renderer) {
+    ctx.set_paint(BLACK);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 90.0));
+    ctx.push_mask_layer(example_mask(true));
+    ctx.set_paint(RED);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 50.0));
+    ctx.push_mask_layer(example_mask(false));
+    ctx.set_paint(BLUE);
+    ctx.fill_rect(&Rect::new(10.0, 30.0, 90.0, 90.0));
+    ctx.pop_layer();
+    ctx.pop_layer();
+}
+
+#[vello_test]
+fn mask_reused(ctx: &mut impl Renderer) {
+    mask_reused_with(ctx, &example_mask(false));
+}
+
+fn mask_reused_with(ctx: &mut impl Renderer, mask: &Mask) {
+    ctx.push_mask_layer(mask.clone());
+    ctx.set_paint(RED);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 50.0));
+    ctx.pop_layer();
+    ctx.push_mask_layer(mask.clone());
+    ctx.set_paint(BLUE);
+    ctx.fill_rect(&Rect::new(10.0, 50.0, 90.0, 90.0));
+    ctx.pop_layer();
+}
+
+#[vello_test]
+fn mask_with_clip_and_blend(ctx: &mut impl Renderer) {
+    let circle = Circle::new((50.0, 50.0), 35.0).to_path(0.1);
+
+    ctx.set_paint(BLUE);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 90.0));
+    ctx.push_layer(
+        Some(&circle),
+        Some(BlendMode::new(Mix::Multiply, Compose::SrcOver)),
+        Some(0.8),
+        Some(example_mask(false)),
+        None,
+    );
+    ctx.set_paint(YELLOW);
+    ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 90.0));
+    ctx.pop_layer();
+}
+
+#[vello_test]
+fn mask_with_filter(ctx: &mut impl Renderer) {
+    let filter = Filter::from_primitive(FilterPrimitive::GaussianBlur {
+        std_deviation: 2.0,
+        edge_mode: EdgeMode::None,
+    });
+
+    ctx.push_layer(None, None, None, Some(example_mask(true)), Some(filter));
+    ctx.set_paint(BLACK);
+    ctx.fill_rect(&Rect::new(20.0, 20.0, 80.0, 80.0));
+    ctx.pop_layer();
+}
+
 #[vello_test(skip_hybrid)]
 fn mask_non_isolated(ctx: &mut impl Renderer) {
     let mask = example_mask(false);
@@ -88,3 +151,32 @@ fn mask_non_isolated(ctx: &mut impl Renderer) {
     ctx.set_paint(RED);
     ctx.fill_rect(&Rect::new(10.0, 10.0, 90.0, 90.0));
 }
+
+// Masks stay in the image atlas of Vello Hybrid for as long as they are used, so check that
+// frames using cached, replaced and re-uploaded masks match frames rendered on their own.
+#[test]
+fn mask_cached_between_frames_hybrid() {
+    fn draw(ctx: &mut HybridRenderer, mask: &Mask) {
+        ctx.scene_mut().reset();
+        // The render target isn't cleared between frames, so cover the previous frame.
+        ctx.set_paint(BLACK);
+        ctx.fill_rect(&Rect::new(0.0, 0.0, 100.0, 100.0));
+        mask_reused_with(ctx, mask);
+    }
+
+    let alpha = example_mask(true);
+    let luminance = example_mask(false);
+    let mut renderer = HybridRenderer::new(100, 100, 0, Level::fallback(), RenderMode::default());
+
+    for (frame, mask) in [&alpha, &alpha, &luminance, &alpha].into_iter().enumerate() {
+        draw(&mut renderer, mask);
+        let mut expected =
+            HybridRenderer::new(100, 100, 0, Level::fallback(), RenderMode::default());
+        draw(&mut expected, mask);
+
+        assert!(
+            render_pixmap(&renderer).data() == render_pixmap(&expected).data(),
+            "frame {frame} differs"
+        );
+    }
+}
@@ -30,6 +30,7 @@ tests, so there isn't much we can do to prevent it."
 #[cfg(target_arch = "wasm32")]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+mod api;
 mod basic;
 mod blurred_rounded_rect;
 mod clip;
@@ -1,7 +1,7 @@
 // Copyright 2025 the Vello Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::util::layout_glyphs_roboto;
+use crate::util::{layout_glyphs_noto_cbtf, layout_glyphs_noto_colr, layout_glyphs_roboto};
 use vello_common::color::palette::css::{
     DARK_TURQUOISE, FUCHSIA, GOLD, GREEN, LIGHT_SALMON, ORANGE, ORCHID, PALE_VIOLET_RED, PURPLE,
     REBECCA_PURPLE,
@@ -98,6 +98,40 @@ fn recording_glyphs(ctx: &mut impl Renderer) {
     ctx.execute_recording(&recording);
 }
 
+#[vello_test(width = 250, height = 70, hybrid_tolerance = 1)]
+fn recording_bitmap_glyphs(ctx: &mut impl Renderer) {
+    let font_size: f32 = 50_f32;
+    let (font, glyphs) = layout_glyphs_noto_cbtf("✅👀🎉🤠", font_size);
+
+    let mut recording = Recording::new();
+    ctx.record(&mut recording, |ctx| {
+        ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+        ctx.glyph_run(&font)
+            .font_size(font_size)
+            .fill_glyphs(glyphs.into_iter());
+    });
+
+    ctx.prepare_recording(&mut recording);
+    ctx.execute_recording(&recording);
+}
+
+#[vello_test(width = 250, height = 70, cpu_u8_tolerance = 1, hybrid_tolerance = 1)]
+fn recording_colr_glyphs(ctx: &mut impl Renderer) {
+    let font_size: f32 = 50_f32;
+    let (font, glyphs) = layout_glyphs_noto_colr("✅👀🎉🤠", font_size);
+
+    let mut recording = Recording::new();
+    ctx.record(&mut recording, |ctx| {
+        ctx.set_transform(Affine::translate((0., f64::from(font_size))));
+        ctx.glyph_run(&font)
+            .font_size(font_size)
+            .fill_glyphs(glyphs.into_iter());
+    });
+
+    ctx.prepare_recording(&mut recording);
+    ctx.execute_recording(&recording);
+}
+
 #[vello_test(width = 300, height = 70)]
 fn glyph_recording_outside_transform(ctx: &mut impl Renderer) {
     let font_size: f32 = 50_f32;
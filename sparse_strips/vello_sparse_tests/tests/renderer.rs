@@ -12,6 +12,7 @@ use vello_common::paint::{ImageId, ImageSource, PaintType, Tint};
 use vello_common::peniko::{BlendMode, Fill, FontData};
 use vello_common::pixmap::Pixmap;
 use vello_common::recording::{Recordable, Recorder, Recording};
+use vello_common::subpixel::SubpixelOrder;
 use vello_cpu::{Level, RenderContext, RenderMode, RenderSettings};
 use vello_hybrid::Scene;
 #[cfg(all(target_arch = "wasm32", feature = "webgl"))]
@@ -58,6 +59,7 @@ pub(crate) trait Renderer: Sized {
     fn set_fill_rule(&mut self, fill_rule: Fill);
     fn set_transform(&mut self, transform: Affine);
     fn set_aliasing_threshold(&mut self, aliasing_threshold: Option<u8>);
+    fn set_subpixel_order(&mut self, subpixel_order: Option<SubpixelOrder>);
     fn set_blend_mode(&mut self, blend_mode: BlendMode);
     fn set_filter_effect(&mut self, filter: Filter);
     fn reset_filter_effect(&mut self);
@@ -193,6 +195,10 @@ impl Renderer for RenderContext {
         Self::set_aliasing_threshold(self, aliasing_threshold);
     }
 
+    fn set_subpixel_order(&mut self, subpixel_order: Option<SubpixelOrder>) {
+        Self::set_subpixel_order(self, subpixel_order);
+    }
+
     fn set_blend_mode(&mut self, blend_mode: BlendMode) {
         Self::set_blend_mode(self, blend_mode);
     }
@@ -248,6 +254,14 @@ pub(crate) struct HybridRenderer {
     renderer: RefCell<vello_hybrid::Renderer>,
 }
 
+#[cfg(not(all(target_arch = "wasm32", feature = "webgl")))]
+impl HybridRenderer {
+    /// The scene which is rendered by [`Renderer::render_to_pixmap`].
+    pub(crate) fn scene_mut(&mut self) -> &mut Scene {
+        &mut self.scene
+    }
+}
+
 #[cfg(not(all(target_arch = "wasm32", feature = "webgl")))]
 impl Renderer for HybridRenderer {
     type GlyphRenderer = Scene;
@@ -327,8 +341,8 @@ impl Renderer for HybridRenderer {
         self.scene.fill_rect(rect);
     }
 
-    fn fill_blurred_rounded_rect(&mut self, _: &Rect, _: f32, _: f32) {
-        unimplemented!()
+    fn fill_blurred_rounded_rect(&mut self, rect: &Rect, radius: f32, std_dev: f32) {
+        self.scene.fill_blurred_rounded_rect(rect, radius, std_dev);
     }
 
     fn stroke_rect(&mut self, rect: &Rect) {
@@ -370,8 +384,8 @@ impl Renderer for HybridRenderer {
         self.scene.push_layer(None, None, Some(opacity), None, None);
     }
 
-    fn push_mask_layer(&mut self, _: Mask) {
-        unimplemented!()
+    fn push_mask_layer(&mut self, mask: Mask) {
+        self.scene.push_mask_layer(mask);
     }
 
     fn push_filter_layer(&mut self, filter: Filter) {
@@ -422,6 +436,10 @@ impl Renderer for HybridRenderer {
         self.scene.set_aliasing_threshold(aliasing_threshold);
     }
 
+    fn set_subpixel_order(&mut self, _: Option<SubpixelOrder>) {
+        unimplemented!()
+    }
+
     fn set_filter_effect(&mut self, filter: Filter) {
         self.scene.set_filter_effect(filter);
     }
@@ -666,8 +684,8 @@ impl Renderer for HybridRenderer {
         self.scene.fill_rect(rect);
     }
 
-    fn fill_blurred_rounded_rect(&mut self, _: &Rect, _: f32, _: f32) {
-        unimplemented!()
+    fn fill_blurred_rounded_rect(&mut self, rect: &Rect, radius: f32, std_dev: f32) {
+        self.scene.fill_blurred_rounded_rect(rect, radius, std_dev);
     }
 
     fn stroke_rect(&mut self, rect: &Rect) {
@@ -708,8 +726,8 @@ impl Renderer for HybridRenderer {
         self.scene.push_layer(None, None, Some(opacity), None, None);
     }
 
-    fn push_mask_layer(&mut self, _: Mask) {
-        unimplemented!()
+    fn push_mask_layer(&mut self, mask: Mask) {
+        self.scene.push_mask_layer(mask);
     }
 
     fn push_filter_layer(&mut self, filter: Filter) {
@@ -756,6 +774,10 @@ impl Renderer for HybridRenderer {
         self.scene.set_aliasing_threshold(aliasing_threshold);
     }
 
+    fn set_subpixel_order(&mut self, _: Option<SubpixelOrder>) {
+        unimplemented!()
+    }
+
     fn set_filter_effect(&mut self, filter: Filter) {
         self.scene.set_filter_effect(filter);
     }
@@ -503,6 +503,8 @@ impl<'a> DrawGlyphs<'a> {
                 glyph_transform: None,
                 font_size: 16.0,
                 hint: false,
+                embolden: 0.0,
+                skew: 0.0,
                 normalized_coords: coords_start..coords_start,
                 style: Fill::NonZero.into(),
                 glyphs: glyphs_start..glyphs_start,
@@ -552,6 +554,31 @@ impl<'a> DrawGlyphs<'a> {
         self
     }
 
+    /// Sets the strength of synthetic bold, to simulate a bold font face.
+    ///
+    /// The outline of each glyph is expanded by `strength` times the font size on each side.
+    /// Values between 0.01 and 0.03 work well for most fonts.
+    ///
+    /// The default value is 0.0, which disables synthetic bold.
+    #[must_use]
+    pub fn embolden(mut self, strength: f32) -> Self {
+        self.run.embolden = strength;
+        self
+    }
+
+    /// Sets the angle in degrees by which glyphs are skewed horizontally, to simulate an
+    /// oblique font face. Positive angles slant the glyphs to the right.
+    ///
+    /// Unlike a skew in the [glyph transform](Self::glyph_transform), the skew is applied to
+    /// the cached glyph outlines, which also works if hinting is enabled.
+    ///
+    /// The default value is 0.0, which disables synthetic oblique.
+    #[must_use]
+    pub fn skew(mut self, angle: f32) -> Self {
+        self.run.skew = angle;
+        self
+    }
+
     /// Sets the normalized design space coordinates for a variable font instance.
     #[must_use]
     pub fn normalized_coords(mut self, coords: &[NormalizedCoord]) -> Self {
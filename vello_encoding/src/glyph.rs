@@ -31,6 +31,10 @@ pub struct GlyphRun {
     pub font_size: f32,
     /// True if hinting is enabled.
     pub hint: bool,
+    /// Strength of synthetic bold, relative to the font size. Zero disables synthetic bold.
+    pub embolden: f32,
+    /// Angle in degrees of synthetic oblique. Zero disables synthetic oblique.
+    pub skew: f32,
     /// Range of normalized coordinates in the parent encoding.
     pub normalized_coords: Range<usize>,
     /// Fill or stroke style.
@@ -6,9 +6,10 @@ use std::sync::Arc;
 
 use super::{Encoding, StreamOffsets};
 
+use peniko::kurbo::{self, Affine, BezPath, Join, PathEl, Shape, Stroke, StrokeOpts};
 use peniko::{FontData, Style};
 use skrifa::instance::{NormalizedCoord, Size};
-use skrifa::outline::{HintingInstance, HintingOptions, OutlineGlyphFormat};
+use skrifa::outline::{HintingInstance, HintingOptions, OutlineGlyphFormat, OutlinePen};
 use skrifa::{GlyphId, MetadataProvider, OutlineGlyphCollection};
 
 #[derive(Default)]
@@ -29,6 +30,7 @@ impl GlyphCache {
         coords: &'a [NormalizedCoord],
         size: f32,
         hint: bool,
+        synthesis: Synthesis,
         style: &'a Style,
     ) -> Option<GlyphCacheSession<'a>> {
         let font_id = font.data.id();
@@ -78,6 +80,7 @@ impl GlyphCache {
             size_bits: size.ppem().unwrap().to_bits(),
             style,
             style_bits,
+            synthesis,
             outlines,
             hinter,
             serial: self.serial,
@@ -142,6 +145,7 @@ pub(crate) struct GlyphCacheSession<'a> {
     size_bits: u32,
     style: &'a Style,
     style_bits: [u32; 2],
+    synthesis: Synthesis,
     outlines: OutlineGlyphCollection<'a>,
     hinter: Option<&'a HintingInstance>,
     serial: u64,
@@ -160,6 +164,7 @@ impl GlyphCacheSession<'_> {
             font_size_bits: self.size_bits,
             style_bits: self.style_bits,
             hint: self.hinter.is_some(),
+            synthesis_bits: self.synthesis.to_bits(),
         };
         if let Some(entry) = self.map.get_mut(&key) {
             entry.serial = self.serial;
@@ -191,7 +196,14 @@ impl GlyphCacheSession<'_> {
         } else {
             DrawSettings::unhinted(self.size, self.coords)
         };
-        outline.draw(draw_settings, &mut path).ok()?;
+        if self.synthesis.is_none() {
+            outline.draw(draw_settings, &mut path).ok()?;
+        } else {
+            let mut pen = BezPathPen(BezPath::new());
+            outline.draw(draw_settings, &mut pen).ok()?;
+            self.synthesis.apply(&mut pen.0, self.size);
+            path.path_elements(pen.0.elements().iter().copied());
+        }
         if path.finish(false) == 0 {
             encoding_ptr.reset();
         }
@@ -217,6 +229,119 @@ struct GlyphKey {
     font_size_bits: u32,
     style_bits: [u32; 2],
     hint: bool,
+    synthesis_bits: [u32; 2],
+}
+
+/// Settings for synthesizing bold and oblique glyphs from regular outlines.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub(crate) struct Synthesis {
+    /// The amount by which the outline is expanded, relative to the font size.
+    pub(crate) embolden: f32,
+    /// The horizontal skew angle in degrees.
+    pub(crate) skew: f32,
+}
+
+impl Synthesis {
+    fn is_none(self) -> bool {
+        self.embolden == 0.0 && self.skew == 0.0
+    }
+
+    fn to_bits(self) -> [u32; 2] {
+        [self.embolden.to_bits(), self.skew.to_bits()]
+    }
+
+    /// Applies the synthesis to an outline drawn at the given size.
+    fn apply(self, path: &mut BezPath, size: Size) {
+        if self.embolden != 0.0 {
+            let amount = self.embolden * size.ppem().unwrap_or(1.0);
+            *path = embolden_path(path, f64::from(amount));
+        }
+        if self.skew != 0.0 {
+            let skew = f64::from(self.skew).to_radians().tan();
+            // Font outlines are y-up, so a positive skew slants the glyph to the right.
+            path.apply_affine(Affine::skew(skew, 0.0));
+        }
+    }
+}
+
+/// Expands the area covered by a filled outline by `amount` in all directions.
+///
+/// Each contour is stroked with a width of twice the amount, and only the offset curve on the
+/// side facing away from the filled area is kept. This means outer contours grow while counters
+/// shrink, and the result can still be filled with the non-zero fill rule.
+fn embolden_path(path: &BezPath, amount: f64) -> BezPath {
+    // The outline is in pixels at the size it is drawn at, so this is precise enough even if
+    // the glyph is scaled up afterwards.
+    const TOLERANCE: f64 = 0.01;
+    let contours = subpaths(path);
+    let areas = contours.iter().map(Shape::area).collect::<Vec<_>>();
+    // The sign of the total area tells us the orientation of outer contours.
+    let total_area = areas.iter().sum::<f64>();
+    if total_area == 0.0 {
+        return path.clone();
+    }
+    let style = Stroke::new(2.0 * amount).with_join(Join::Miter);
+    let mut emboldened = BezPath::new();
+    for (contour, area) in contours.iter().zip(areas) {
+        let stroked = kurbo::stroke(contour, &style, &StrokeOpts::default(), TOLERANCE);
+        // For closed contours, the stroke consists of one offset curve on each side.
+        let Ok([first, second]) = <[BezPath; 2]>::try_from(subpaths(&stroked)) else {
+            // Keep degenerate or open contours unchanged.
+            emboldened.extend(contour);
+            continue;
+        };
+        let is_outer = area.signum() == total_area.signum();
+        let first_is_larger = first.area().abs() > second.area().abs();
+        let offset = if first_is_larger == is_outer {
+            first
+        } else {
+            second
+        };
+        // Keep the orientation of the original contour, so that the winding is preserved.
+        if offset.area().signum() == area.signum() {
+            emboldened.extend(offset);
+        } else {
+            emboldened.extend(offset.reverse_subpaths());
+        }
+    }
+    emboldened
+}
+
+/// Splits a path into its subpaths.
+fn subpaths(path: &BezPath) -> Vec<BezPath> {
+    let mut subpaths = Vec::new();
+    for el in path.elements() {
+        if matches!(el, PathEl::MoveTo(_)) || subpaths.is_empty() {
+            subpaths.push(BezPath::new());
+        }
+        subpaths.last_mut().unwrap().push(*el);
+    }
+    subpaths
+}
+
+/// Pen that collects an outline into a [`BezPath`].
+struct BezPathPen(BezPath);
+
+impl OutlinePen for BezPathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((x, y));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.0.quad_to((cx0, cy0), (x, y));
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.0.curve_to((cx0, cy0), (cx1, cy1), (x, y));
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
 }
 
 /// Outer level key for variable font caches.
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use super::{DrawTag, Encoding, PathTag, StreamOffsets, Style, Transform};
 
-use crate::glyph_cache::GlyphCache;
+use crate::glyph_cache::{GlyphCache, Synthesis};
 use crate::image_cache::{ImageCache, Images};
 use crate::ramp_cache::{RampCache, Ramps};
 
@@ -438,6 +438,10 @@ impl Resolver {
                         bytemuck::cast_slice(coords),
                         font_size,
                         hint,
+                        Synthesis {
+                            embolden: run.embolden,
+                            skew: run.skew,
+                        },
                         &run.style,
                     ) else {
                         continue;
@@ -620,3 +624,99 @@ fn size_to_words(byte_size: usize) -> u32 {
 fn align_up(len: usize, alignment: u32) -> usize {
     len + (len.wrapping_neg() & (alignment as usize - 1))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use peniko::kurbo::{Point, Rect};
+    use peniko::{Blob, Fill, FontData, color::palette};
+    use skrifa::{FontRef, MetadataProvider};
+
+    use super::{Patch, Resolver};
+    use crate::{Encoding, Glyph, GlyphRun, Transform};
+
+    const ROBOTO_FONT: &[u8] = include_bytes!("../../examples/assets/roboto/Roboto-Regular.ttf");
+
+    /// Resolves a run containing the glyph for `l`, encoded in the same way as by
+    /// `vello::DrawGlyphs`, and returns the bounds of the resulting outline.
+    fn resolved_glyph_bounds(embolden: f32, skew: f32) -> Rect {
+        let glyph_id = FontRef::new(ROBOTO_FONT)
+            .unwrap()
+            .charmap()
+            .map('l')
+            .unwrap();
+        let mut encoding = Encoding::new();
+        encoding.resources.glyphs.push(Glyph {
+            id: glyph_id.to_u32(),
+            x: 0.0,
+            y: 0.0,
+        });
+        encoding.resources.glyph_runs.push(GlyphRun {
+            font: FontData::new(Blob::new(Arc::new(ROBOTO_FONT)), 0),
+            transform: Transform::IDENTITY,
+            glyph_transform: None,
+            font_size: 100.0,
+            hint: false,
+            embolden,
+            skew,
+            normalized_coords: 0..0,
+            style: Fill::NonZero.into(),
+            glyphs: 0..1,
+            stream_offsets: encoding.stream_offsets(),
+        });
+        encoding
+            .resources
+            .patches
+            .push(Patch::GlyphRun { index: 0 });
+        encoding.encode_brush(palette::css::BLACK, 1.0);
+
+        let mut packed = Vec::new();
+        let (layout, _, _) = Resolver::new().resolve(&encoding, &mut packed);
+        let path_data = layout.path_data(&packed);
+        assert!(!path_data.is_empty(), "The glyph should have an outline");
+
+        path_data
+            .chunks_exact(8)
+            .map(|point| {
+                let [x, y] = bytemuck::pod_read_unaligned::<[f32; 2]>(point);
+                Point::new(x.into(), y.into())
+            })
+            .fold(
+                Rect::new(f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+                |rect, point| rect.union_pt(point),
+            )
+    }
+
+    #[test]
+    fn glyph_run_embolden() {
+        let regular = resolved_glyph_bounds(0.0, 0.0);
+        let bold = resolved_glyph_bounds(0.02, 0.0);
+
+        // The outline is expanded by 2 pixels (0.02 of the 100 pixel font size) in all directions.
+        let expanded = regular.inflate(2.0, 2.0);
+        assert!(
+            (bold.x0 - expanded.x0).abs() < 0.1
+                && (bold.y0 - expanded.y0).abs() < 0.1
+                && (bold.x1 - expanded.x1).abs() < 0.1
+                && (bold.y1 - expanded.y1).abs() < 0.1,
+            "{bold:?} should be close to {expanded:?}"
+        );
+    }
+
+    #[test]
+    fn glyph_run_skew() {
+        let regular = resolved_glyph_bounds(0.0, 0.0);
+        let oblique = resolved_glyph_bounds(0.0, 20.0);
+
+        // The outline is y-up and starts at the baseline, so only the top is moved to the right.
+        let shift = regular.y1 * 20_f64.to_radians().tan();
+        assert!((oblique.x0 - regular.x0).abs() < 0.01, "{oblique:?}");
+        assert!(
+            (oblique.x1 - (regular.x1 + shift)).abs() < 0.01,
+            "{oblique:?}"
+        );
+        assert_eq!(oblique.y0, regular.y0);
+        assert_eq!(oblique.y1, regular.y1);
+    }
+}